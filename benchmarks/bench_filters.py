@@ -0,0 +1,61 @@
+"""
+Filter throughput benchmark
+
+Compares the byte-level charset and repeat filters against the
+straightforward per-character implementations they replace.
+
+Usage:
+    python benchmarks/bench_filters.py [token_count]
+"""
+
+import random
+import string
+import sys
+import timeit
+from pathlib import Path
+
+sys.path.insert(0, str(Path(__file__).resolve().parent.parent))
+
+from omniwordlist.config import FilterConfig
+from omniwordlist.filters import CharsetFilter, RepeatFilter
+
+
+def naive_charset(token: str, charset: str) -> bool:
+    allowed = set(charset)
+    return all(c in allowed for c in token)
+
+
+def naive_repeats(token: str, max_repeats: int) -> bool:
+    run = 1
+    for prev, char in zip(token, token[1:]):
+        run = run + 1 if char == prev else 1
+        if run > max_repeats:
+            return False
+    return True
+
+
+def main():
+    count = int(sys.argv[1]) if len(sys.argv) > 1 else 200000
+    rng = random.Random(0)
+    alphabet = string.ascii_letters + string.digits
+    tokens = [''.join(rng.choice(alphabet) for _ in range(rng.randint(6, 16)))
+              for _ in range(count)]
+    
+    config = FilterConfig(charset_filter=string.ascii_lowercase + string.digits, max_repeats=2)
+    charset_filter = CharsetFilter(config)
+    repeat_filter = RepeatFilter(config)
+    
+    cases = [
+        ("charset (naive)", lambda: [naive_charset(t, config.charset_filter) for t in tokens]),
+        ("charset (byte table)", lambda: [charset_filter.should_include(t) for t in tokens]),
+        ("repeats (naive)", lambda: [naive_repeats(t, config.max_repeats) for t in tokens]),
+        ("repeats (regex)", lambda: [repeat_filter.should_include(t) for t in tokens]),
+    ]
+    
+    for name, case in cases:
+        seconds = min(timeit.repeat(case, number=1, repeat=3))
+        print(f"{name:<22} {count / seconds:>14,.0f} tokens/s")
+
+
+if __name__ == "__main__":
+    main()
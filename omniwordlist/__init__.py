@@ -12,10 +12,17 @@ __author__ = "Aaryan Bansal"
 from .config import Config, FilterConfig
 from .generator import Generator
 from .error import OmniError
+from .events import GeneratorObserver
+from .fields import FieldProvider, FieldRegistry
+from .cancel import CancellationToken
 
 __all__ = [
     'Config',
     'FilterConfig', 
     'Generator',
     'OmniError',
+    'GeneratorObserver',
+    'FieldProvider',
+    'FieldRegistry',
+    'CancellationToken',
 ]
@@ -0,0 +1,170 @@
+"""
+Strategy auto-tuning by simulation
+
+Tries variations of a base configuration (transform sets, component
+ordering, which field leads) against a held-out reference list and
+keeps whichever cracks the most of it within a candidate budget. The
+search is greedy: each dimension is tuned in turn on top of the best
+configuration found so far.
+"""
+
+import copy
+from collections import Counter
+from dataclasses import dataclass, field
+from typing import Callable, Dict, Iterator, List, Optional, Sequence, Tuple
+
+from .config import FIELD_ORDERS, Config
+from .error import ConfigError
+from .generator import Generator
+from .simulate import SimulationResult, simulate
+from .transforms import list_transforms
+
+
+# Deterministic transform chains tried in place of the base transforms
+TRANSFORM_SETS: List[List[str]] = [
+    [],
+    ["lowercase"],
+    ["capitalize"],
+    ["uppercase"],
+    ["leet_basic"],
+    ["capitalize", "leet_basic"],
+    ["pluralization"],
+    ["reverse"],
+]
+
+
+@dataclass
+class Trial:
+    """One evaluated variation"""
+    dimension: str
+    description: str
+    config: Config
+    result: SimulationResult
+    score: Tuple[float, float]
+
+
+@dataclass
+class TuneReport:
+    """Every trial and the winner"""
+    budget: int
+    trials: List[Trial] = field(default_factory=list)
+    best: Optional[Trial] = None
+
+
+def discovery_area(result: SimulationResult, budget: int) -> float:
+    """
+    Normalized area under the cumulative hit curve
+
+    Rewards streams that crack the same passwords earlier.
+
+    Args:
+        result: Simulation with a curve point at every hit
+        budget: Candidate budget the curve is measured over
+
+    Returns:
+        Area in [0, 1]
+    """
+    if not result.reference_size or budget < 1:
+        return 0.0
+    area = 0.0
+    for point, following in zip(result.curve, result.curve[1:] + [None]):
+        end = following.depth if following else budget
+        area += point.hits * max(0, min(end, budget) - point.depth)
+    return area / (budget * result.reference_size)
+
+
+def _transform_variations(config: Config, transform_sets: Sequence[List[str]]) -> Iterator[Tuple[str, Dict]]:
+    for transforms in transform_sets:
+        yield f"transforms={','.join(transforms) or '(none)'}", {"transforms": list(transforms)}
+
+
+def _order_variations(config: Config, transform_sets) -> Iterator[Tuple[str, Dict]]:
+    if len(config.enabled_fields) + bool(config.words) < 2:
+        return
+    for field_order in FIELD_ORDERS:
+        yield f"field_order={field_order}", {"field_order": field_order}
+
+
+def _priority_variations(config: Config, transform_sets) -> Iterator[Tuple[str, Dict]]:
+    fields = config.enabled_fields
+    if len(fields) < 2:
+        return
+    for i, lead in enumerate(fields):
+        yield f"lead field={lead}", {"enabled_fields": [lead] + fields[:i] + fields[i + 1:]}
+
+
+# Tuned dimensions, in search order
+DIMENSIONS: List[Tuple[str, Callable]] = [
+    ("transforms", _transform_variations),
+    ("field_order", _order_variations),
+    ("field_priority", _priority_variations),
+]
+
+
+def evaluate(config: Config, reference: Counter, budget: int) -> Tuple[SimulationResult, Tuple[float, float]]:
+    """
+    Simulate a configuration within a candidate budget
+
+    Args:
+        config: Configuration to evaluate
+        reference: Held-out reference (see simulate.load_reference)
+        budget: Candidates to replay
+
+    Returns:
+        (simulation, score) where score orders by cracked fraction, then
+        by discovery area
+    """
+    result = simulate(Generator(config).generate(), reference, limit=budget)
+    return result, (result.fraction, discovery_area(result, budget))
+
+
+def autotune(base: Config, reference: Counter, budget: int,
+             transform_sets: Optional[Sequence[List[str]]] = None,
+             dimensions: Optional[Sequence[str]] = None) -> TuneReport:
+    """
+    Greedily tune a configuration against a reference list
+
+    Args:
+        base: Configuration to start from
+        reference: Held-out reference passwords
+        budget: Candidates each variation may replay
+        transform_sets: Transform chains to try (TRANSFORM_SETS if omitted)
+        dimensions: Names from DIMENSIONS to tune (all if omitted)
+
+    Returns:
+        Report of every trial; best is the winning configuration
+    """
+    if budget < 1:
+        raise ConfigError("budget must be at least 1")
+    transform_sets = TRANSFORM_SETS if transform_sets is None else transform_sets
+    known = list_transforms()
+    for transforms in transform_sets:
+        unknown = [name for name in transforms if name not in known]
+        if unknown:
+            raise ConfigError(f"Unknown transform(s): {', '.join(unknown)}")
+    names = [name for name, _ in DIMENSIONS]
+    for name in dimensions or []:
+        if name not in names:
+            raise ConfigError(f"Unknown tuning dimension: {name} (known: {', '.join(names)})")
+
+    report = TuneReport(budget)
+    result, score = evaluate(base, reference, budget)
+    report.best = Trial("base", "base config", base, result, score)
+    report.trials.append(report.best)
+
+    for name, variations in DIMENSIONS:
+        if dimensions and name not in dimensions:
+            continue
+        leader = report.best
+        for description, changes in variations(leader.config, transform_sets):
+            config = copy.deepcopy(leader.config)
+            for key, value in changes.items():
+                setattr(config, key, value)
+            if config.to_dict() == leader.config.to_dict():
+                continue
+            result, score = evaluate(config, reference, budget)
+            trial = Trial(name, description, config, result, score)
+            report.trials.append(trial)
+            if score > report.best.score:
+                report.best = trial
+    return report
@@ -0,0 +1,78 @@
+"""
+Entropy / quality bucketed output
+
+Routes tokens into low.txt, medium.txt and high.txt by their quality
+score or entropy estimate in a single pass, so the most promising
+bucket can be attacked first without re-filtering the whole list.
+Bucketed output is written by shard.ShardedWriter.
+"""
+
+from typing import Callable, Dict, Optional, Sequence, Tuple
+
+from .error import ConfigError
+
+
+BUCKET_METRICS = ("entropy", "quality")
+BUCKET_NAMES = ("low", "medium", "high")
+
+# Default (low/medium, medium/high) cut points per quality score and entropy mode
+DEFAULT_THRESHOLDS: Dict[str, Tuple[float, float]] = {
+    "quality": (0.4, 0.7),
+    "shannon": (2.0, 3.0),
+    "classes": (28.0, 50.0),
+    "nist": (18.0, 30.0),
+    "guesses": (6.0, 10.0),
+}
+
+
+def bucket_thresholds(metric: str, entropy_mode: str = "shannon",
+                      thresholds: Optional[Sequence[float]] = None) -> Tuple[float, float]:
+    """
+    Cut points between the buckets
+
+    Args:
+        metric: "entropy" or "quality"
+        entropy_mode: Estimator used for the entropy metric
+        thresholds: Explicit (low/medium, medium/high) cut points
+
+    Returns:
+        Ascending pair of cut points
+    """
+    if metric not in BUCKET_METRICS:
+        raise ConfigError(f"Unknown bucket metric: {metric} (known: {', '.join(BUCKET_METRICS)})")
+    if thresholds:
+        if len(thresholds) != 2 or thresholds[0] > thresholds[1]:
+            raise ConfigError("bucket_thresholds must be two ascending cut points")
+        return float(thresholds[0]), float(thresholds[1])
+    if metric == "entropy" and entropy_mode not in DEFAULT_THRESHOLDS:
+        raise ConfigError(f"Unknown entropy mode: {entropy_mode}")
+    return DEFAULT_THRESHOLDS["quality" if metric == "quality" else entropy_mode]
+
+
+def bucket_key(metric: str, entropy_mode: str = "shannon",
+               thresholds: Optional[Sequence[float]] = None) -> Callable[[str], str]:
+    """
+    Function mapping a token to its bucket name
+
+    Args:
+        metric: "entropy" or "quality"
+        entropy_mode: Estimator used for the entropy metric
+        thresholds: Explicit cut points (defaults per metric otherwise)
+
+    Returns:
+        token -> "low" / "medium" / "high"
+    """
+    from .filters import calculate_quality_score, estimate_entropy
+
+    low, high = bucket_thresholds(metric, entropy_mode, thresholds)
+
+    def key(token: str) -> str:
+        if metric == "quality":
+            value = calculate_quality_score(token)
+        else:
+            value = estimate_entropy(token, entropy_mode)
+        if value < low:
+            return "low"
+        return "medium" if value < high else "high"
+
+    return key
@@ -0,0 +1,158 @@
+"""
+Self-contained job bundles
+
+A bundle is a zip archive holding a config plus every local file it
+references (charset file, dictionaries behind field sources, denylist,
+frequency model, input word list, combinator word lists, Markov
+training list or model, chain elements, case statistics, length
+distribution, combo user list).
+URL sources stay URLs.
+Paths inside the bundled config point into the archive, so a job can
+move between machines without breaking.
+"""
+
+import copy
+import hashlib
+import json
+import zipfile
+from pathlib import Path
+from typing import Dict, Optional
+from .config import Config
+from .error import ConfigError
+from .sources import is_remote, is_stream
+
+
+BUNDLE_FORMAT_VERSION = 1
+BUNDLE_CONFIG_NAME = "config.json"
+BUNDLE_MANIFEST_NAME = "manifest.json"
+BUNDLE_FILES_DIR = "files/"
+
+
+def _rewrite_paths(config: Config, rewrite) -> Config:
+    """Apply `rewrite(path)` to every file referenced by a config"""
+    if config.charset_file:
+        config.charset_file = rewrite(config.charset_file)
+    if config.denylist:
+        config.denylist = rewrite(config.denylist)
+    if config.frequency_model:
+        config.frequency_model = rewrite(config.frequency_model)
+    if config.input_wordlist:
+        config.input_wordlist = rewrite(config.input_wordlist)
+    if config.combinator_left:
+        config.combinator_left = rewrite(config.combinator_left)
+    if config.combinator_right:
+        config.combinator_right = rewrite(config.combinator_right)
+    if config.markov_train:
+        config.markov_train = rewrite(config.markov_train)
+    elif config.markov_model:
+        # Only a model that is loaded, not one saved after training
+        config.markov_model = rewrite(config.markov_model)
+    if config.chain_elements:
+        config.chain_elements = rewrite(config.chain_elements)
+    if config.case_stats_file:
+        config.case_stats_file = rewrite(config.case_stats_file)
+    # Inline length distributions and user lists need no file
+    if isinstance(config.length_distribution, str):
+        config.length_distribution = rewrite(config.length_distribution)
+    if isinstance(config.combo_users, str):
+        config.combo_users = rewrite(config.combo_users)
+    # Inline word lists need no file
+    config.field_sources = {
+        field_id: source if isinstance(source, list) else rewrite(source)
+        for field_id, source in config.field_sources.items()
+    }
+    return config
+
+
+def create_bundle(config: Config, output: Path, base_dir: Optional[Path] = None) -> Dict[str, str]:
+    """
+    Write a config and the files it references into a bundle
+    
+    Args:
+        config: Configuration to bundle (left unchanged)
+        output: Bundle path to write
+        base_dir: Directory relative paths in the config are resolved against
+        
+    Returns:
+        Mapping of original paths to their names inside the bundle
+    """
+    base_dir = base_dir or Path.cwd()
+    files: Dict[str, str] = {}
+    
+    def add(path: str) -> str:
+        # Remote sources are fetched by whoever runs the bundle
+        if is_remote(path):
+            return path
+        if is_stream(path):
+            raise ConfigError(f"Stream sources (stdin, named pipes) cannot be bundled: {path}")
+        if path not in files:
+            source = Path(path).expanduser()
+            if not source.is_absolute():
+                source = base_dir / source
+            if not source.is_file():
+                raise ConfigError(f"Referenced file not found: {path}")
+            files[path] = f"{BUNDLE_FILES_DIR}{len(files)}-{source.name}"
+        return files[path]
+    
+    bundled = _rewrite_paths(copy.deepcopy(config), add)
+    # Output locations belong to the machine running the job
+    bundled.output_file = None
+    bundled.checkpoint_dir = None
+    bundled.rejects_output = None
+    if bundled.markov_train:
+        bundled.markov_model = None
+    
+    with zipfile.ZipFile(output, 'w', compression=zipfile.ZIP_DEFLATED) as zf:
+        zf.writestr(BUNDLE_CONFIG_NAME, json.dumps(bundled.to_dict(), indent=2))
+        for original, name in files.items():
+            source = Path(original).expanduser()
+            zf.write(source if source.is_absolute() else base_dir / source, name)
+        zf.writestr(BUNDLE_MANIFEST_NAME, json.dumps({
+            "format_version": BUNDLE_FORMAT_VERSION,
+            "files": {name: original for original, name in files.items()},
+        }, indent=2))
+    
+    return files
+
+
+def default_bundle_dir() -> Path:
+    """Directory bundles are unpacked into"""
+    return Path.home() / ".omniwordlist" / "bundles"
+
+
+def load_bundle(path: Path, extract_dir: Optional[Path] = None) -> Config:
+    """
+    Unpack a bundle and load its config
+    
+    Bundles are unpacked into a directory named after their content hash,
+    so running the same bundle twice reuses the extracted files.
+    
+    Args:
+        path: Bundle file
+        extract_dir: Parent directory for unpacked bundles
+        
+    Returns:
+        Config whose file references point at the unpacked files
+    """
+    data = Path(path).read_bytes()
+    target = (extract_dir or default_bundle_dir()) / hashlib.sha256(data).hexdigest()[:16]
+    
+    try:
+        with zipfile.ZipFile(path) as zf:
+            manifest = json.loads(zf.read(BUNDLE_MANIFEST_NAME))
+            if manifest.get("format_version", 0) > BUNDLE_FORMAT_VERSION:
+                raise ConfigError(f"Bundle format {manifest['format_version']} is newer than supported")
+            config_data = json.loads(zf.read(BUNDLE_CONFIG_NAME))
+            
+            # Only extract files listed in the manifest, under flat names
+            target.mkdir(parents=True, exist_ok=True)
+            for name in manifest.get("files", {}):
+                if not name.startswith(BUNDLE_FILES_DIR) or "/" in name[len(BUNDLE_FILES_DIR):]:
+                    raise ConfigError(f"Invalid bundle entry: {name}")
+                (target / Path(name).name).write_bytes(zf.read(name))
+    except (zipfile.BadZipFile, KeyError) as e:
+        raise ConfigError(f"Invalid bundle {path}: {e}")
+    
+    config = Config.from_dict(config_data)
+    return _rewrite_paths(
+        config, lambda name: name if is_remote(name) else str(target / Path(name).name))
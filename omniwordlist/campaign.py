@@ -0,0 +1,193 @@
+"""
+Cross-run campaign deduplication
+
+A campaign groups the runs of one engagement. Its database keeps a hash
+of every token a run wrote, so later runs of the same campaign drop
+candidates an earlier strategy already produced. Each hash remembers
+the run that first wrote it: a run resumed from a checkpoint keeps its
+run id and is not blocked by its own earlier output.
+
+Written hashes are buffered and committed in batches (at checkpoints,
+on cancellation and when generation finishes), so a process killed
+between commits forgets its last batch. The hashes of earlier runs are
+read in one query on a run's first lookup and held in memory.
+"""
+
+import sqlite3
+import time
+import uuid
+from pathlib import Path
+from typing import Dict, List, Optional, Set
+
+from .dedupe import token_digest
+from .error import StorageError
+from .opsec import require_disk
+
+
+# Hashes buffered before an automatic commit
+DEFAULT_BATCH_SIZE = 10000
+
+
+def default_campaign_db() -> Path:
+    """Database used when a config sets a campaign without campaign_db"""
+    return Path.home() / ".omniwordlist" / "campaigns.db"
+
+
+def new_run_id() -> str:
+    """Identifier of one run within a campaign"""
+    return uuid.uuid4().hex[:12]
+
+
+def _connect(path: Path) -> sqlite3.Connection:
+    """Open (creating if needed) a campaign database"""
+    path = Path(path)
+    require_disk("campaigns", f"the campaign database {path}")
+    path.parent.mkdir(parents=True, exist_ok=True)
+    try:
+        connection = sqlite3.connect(path)
+        with connection:
+            connection.execute("CREATE TABLE IF NOT EXISTS tokens (campaign TEXT NOT NULL, hash BLOB NOT NULL, "
+                               "run TEXT NOT NULL, PRIMARY KEY (campaign, hash)) WITHOUT ROWID")
+            connection.execute("CREATE TABLE IF NOT EXISTS runs (campaign TEXT NOT NULL, run TEXT NOT NULL, "
+                               "job TEXT, started REAL NOT NULL, updated REAL NOT NULL, "
+                               "tokens INTEGER NOT NULL DEFAULT 0, PRIMARY KEY (campaign, run))")
+    except sqlite3.Error as e:
+        raise StorageError(f"Cannot open campaign database {path}: {e}")
+    return connection
+
+
+class CampaignStore:
+    """Token hashes of one campaign, as seen by one run"""
+
+    def __init__(self, path: Path, campaign: str, run_id: Optional[str] = None,
+                 job_id: Optional[str] = None, batch_size: int = DEFAULT_BATCH_SIZE):
+        """
+        Open a campaign
+
+        Args:
+            path: Campaign database
+            campaign: Campaign name
+            run_id: Run writing to the campaign (a new one if omitted)
+            job_id: Generator job of the run, kept for `omni campaign list`
+            batch_size: Hashes buffered before they are committed
+        """
+        self.path = Path(path)
+        self.campaign = campaign
+        self.run_id = run_id or new_run_id()
+        self.job_id = job_id
+        self.batch_size = batch_size
+        self.pending: List[bytes] = []
+        self.recorded = 0
+        # Hashes written by earlier runs, loaded on the first lookup
+        self.earlier: Optional[Set[bytes]] = None
+        self.connection: Optional[sqlite3.Connection] = _connect(self.path)
+
+    def _db(self) -> sqlite3.Connection:
+        """The database connection, reopened if the store was closed"""
+        if self.connection is None:
+            self.connection = _connect(self.path)
+        return self.connection
+
+    def seen(self, token: str) -> bool:
+        """Whether an earlier run of the campaign already wrote a token"""
+        if self.earlier is None:
+            try:
+                rows = self._db().execute("SELECT hash FROM tokens WHERE campaign = ? AND run != ?",
+                                          (self.campaign, self.run_id))
+                self.earlier = {row[0] for row in rows}
+            except sqlite3.Error as e:
+                raise StorageError(f"Cannot read campaign database {self.path}: {e}")
+        return token_digest(token) in self.earlier
+
+    def record(self, token: str):
+        """Remember a written token, committing once a batch is full"""
+        self.pending.append(token_digest(token))
+        if len(self.pending) >= self.batch_size:
+            self.flush()
+
+    def flush(self):
+        """Commit buffered hashes and update this run's entry"""
+        now = time.time()
+        connection = self._db()
+        try:
+            with connection:
+                cursor = connection.executemany(
+                    "INSERT OR IGNORE INTO tokens (campaign, hash, run) VALUES (?, ?, ?)",
+                    ((self.campaign, digest, self.run_id) for digest in self.pending))
+                self.recorded += max(cursor.rowcount, 0)
+                connection.execute(
+                    "INSERT INTO runs (campaign, run, job, started, updated, tokens) VALUES (?, ?, ?, ?, ?, ?) "
+                    "ON CONFLICT (campaign, run) DO UPDATE SET updated = excluded.updated, "
+                    "tokens = runs.tokens + excluded.tokens",
+                    (self.campaign, self.run_id, self.job_id, now, now, max(cursor.rowcount, 0)))
+        except sqlite3.Error as e:
+            raise StorageError(f"Cannot update campaign database {self.path}: {e}")
+        self.pending = []
+
+    def close(self):
+        """Commit and close the database (reopened if the store is used again)"""
+        self.flush()
+        if self.connection is not None:
+            self.connection.close()
+            self.connection = None
+
+
+def list_campaigns(path: Path) -> List[Dict]:
+    """
+    Campaigns of a database
+
+    Args:
+        path: Campaign database
+
+    Returns:
+        One entry per campaign with its token and run counts and last update
+    """
+    if not Path(path).exists():
+        return []
+    connection = _connect(path)
+    rows = connection.execute("SELECT campaign, COUNT(*), SUM(tokens), MAX(updated) FROM runs "
+                              "GROUP BY campaign ORDER BY campaign").fetchall()
+    connection.close()
+    return [{"campaign": name, "runs": runs, "tokens": tokens or 0, "updated": updated}
+            for name, runs, tokens, updated in rows]
+
+
+def campaign_runs(path: Path, campaign: str) -> List[Dict]:
+    """
+    Runs of a campaign, oldest first
+
+    Args:
+        path: Campaign database
+        campaign: Campaign name
+
+    Returns:
+        One entry per run with its job id, start time and new tokens
+    """
+    if not Path(path).exists():
+        return []
+    connection = _connect(path)
+    rows = connection.execute("SELECT run, job, started, tokens FROM runs WHERE campaign = ? "
+                              "ORDER BY started", (campaign,)).fetchall()
+    connection.close()
+    return [{"run": run, "job": job, "started": started, "tokens": tokens} for run, job, started, tokens in rows]
+
+
+def forget_campaign(path: Path, campaign: str) -> int:
+    """
+    Delete a campaign's hashes and runs
+
+    Args:
+        path: Campaign database
+        campaign: Campaign name
+
+    Returns:
+        Number of token hashes deleted
+    """
+    if not Path(path).exists():
+        return 0
+    connection = _connect(path)
+    with connection:
+        deleted = connection.execute("DELETE FROM tokens WHERE campaign = ?", (campaign,)).rowcount
+        connection.execute("DELETE FROM runs WHERE campaign = ?", (campaign,))
+    connection.close()
+    return deleted
@@ -0,0 +1,64 @@
+"""
+Cooperative cancellation for long-running generation
+
+A CancellationToken can be shared with another thread (a GUI event
+loop, a service request handler) to stop a Generator cleanly.
+"""
+
+import signal
+import threading
+from contextlib import contextmanager
+from typing import Callable, Iterator, Optional
+
+
+class CancellationToken:
+    """Thread-safe cancellation flag checked by the generation loop"""
+    
+    def __init__(self):
+        self._event = threading.Event()
+    
+    def cancel(self):
+        """Request cancellation"""
+        self._event.set()
+    
+    def is_cancelled(self) -> bool:
+        """Check whether cancellation was requested"""
+        return self._event.is_set()
+    
+    def reset(self):
+        """Clear a previous cancellation request"""
+        self._event.clear()
+
+
+@contextmanager
+def interrupt_cancels(token: CancellationToken,
+                      on_cancel: Optional[Callable[[], None]] = None) -> Iterator[None]:
+    """
+    Route Ctrl-C (SIGINT) to a cancellation token while the block runs
+    
+    The first Ctrl-C cancels the token, so generation stops at the next
+    candidate and checkpoints; a second one raises KeyboardInterrupt to
+    force-quit. Off the main thread, where signal handlers cannot be
+    installed, the block runs with the default handling.
+    
+    Args:
+        token: Token cancelled by the first Ctrl-C
+        on_cancel: Called after the first Ctrl-C (e.g. to tell the user)
+    """
+    if threading.current_thread() is not threading.main_thread():
+        yield
+        return
+    
+    def handler(signum, frame):
+        if token.is_cancelled():
+            signal.signal(signal.SIGINT, previous)
+            raise KeyboardInterrupt
+        token.cancel()
+        if on_cancel is not None:
+            on_cancel()
+    
+    previous = signal.signal(signal.SIGINT, handler)
+    try:
+        yield
+    finally:
+        signal.signal(signal.SIGINT, previous)
@@ -1,9 +1,15 @@
 """
 Character set definitions and utilities
 
-Implements Crunch-style character patterns and predefined charsets
+Implements Crunch-style character patterns, predefined charsets and
+named character classes for charset filters
 """
 
+import unicodedata
+
+from .error import ConfigError
+
+
 # Predefined character sets
 CHARSET_LOWERCASE = "abcdefghijklmnopqrstuvwxyz"
 CHARSET_UPPERCASE = "ABCDEFGHIJKLMNOPQRSTUVWXYZ"
@@ -15,7 +21,8 @@ CHARSET_ALPHANUMERIC = CHARSET_LOWERCASE + CHARSET_UPPERCASE + CHARSET_DIGITS
 CHARSET_ALPHANUMSYM = CHARSET_ALPHANUMERIC + CHARSET_SYMBOLS
 
 
-def expand_pattern(pattern: str, literal_chars: str = None) -> str:
+def expand_pattern(pattern: str, literal_chars: str = None, custom_charsets: list = None,
+                   literal_mask: str = None) -> str:
     """
     Expand Crunch-style pattern placeholders
     
@@ -24,10 +31,13 @@ def expand_pattern(pattern: str, literal_chars: str = None) -> str:
     - , = uppercase letter (A-Z) 
     - % = digit (0-9)
     - ^ = symbol
+    - 1, 2, 3, 4 = custom charsets, when any is defined
     
     Args:
         pattern: Pattern string with placeholders
-        literal_chars: Characters to treat as literals (don't expand)
+        literal_chars: Characters to treat as literals (see literal_positions)
+        custom_charsets: Custom charsets for the markers 1-4
+        literal_mask: Positional crunch -l mask (see literal_positions)
         
     Returns:
         Expanded charset string
@@ -35,22 +45,14 @@ def expand_pattern(pattern: str, literal_chars: str = None) -> str:
     if not pattern:
         return CHARSET_LOWERCASE
     
-    literal_set = set(literal_chars or "")
     charset = ""
+    markers = marker_charsets(custom_charsets)
     
-    for char in pattern:
-        if char in literal_set:
+    for char, literal in zip(pattern, literal_positions(pattern, literal_chars, custom_charsets, literal_mask)):
+        if literal:
             charset += char
-        elif char == '@':
-            charset += CHARSET_LOWERCASE
-        elif char == ',':
-            charset += CHARSET_UPPERCASE
-        elif char == '%':
-            charset += CHARSET_DIGITS
-        elif char == '^':
-            charset += CHARSET_SYMBOLS
         else:
-            charset += char
+            charset += markers[char] or ""
     
     # Remove duplicates while preserving order
     seen = set()
@@ -63,6 +65,191 @@ def expand_pattern(pattern: str, literal_chars: str = None) -> str:
     return result
 
 
+# Pattern placeholder markers and the charset each one expands to
+PATTERN_MARKERS = {
+    '@': CHARSET_LOWERCASE,
+    ',': CHARSET_UPPERCASE,
+    '%': CHARSET_DIGITS,
+    '^': CHARSET_SYMBOLS,
+}
+
+
+# Markers of the custom charsets (crunch -1 .. -4 style), active once any is defined
+CUSTOM_MARKERS = ("1", "2", "3", "4")
+
+
+def marker_charsets(custom_charsets: list = None) -> dict:
+    """
+    Placeholder -> charset mapping of a pattern
+    
+    With custom charsets, "1" to "4" become placeholders for them (and
+    literal digits need -l); markers of undefined sets map to None.
+    
+    Args:
+        custom_charsets: Up to four custom charsets
+        
+    Returns:
+        Dictionary of placeholder characters
+    """
+    markers = dict(PATTERN_MARKERS)
+    if custom_charsets:
+        for i, marker in enumerate(CUSTOM_MARKERS):
+            markers[marker] = custom_charsets[i] if i < len(custom_charsets) else None
+    return markers
+
+
+def literal_positions(pattern: str, literal_chars: str = None, custom_charsets: list = None,
+                      literal_mask: str = None) -> list:
+    """
+    Which positions of a pattern are fixed literals
+    
+    literal_chars lists characters that are literal wherever they
+    appear. literal_mask follows crunch -l: a string as long as the
+    pattern marks position i literal when its i-th character is a
+    placeholder ("p@ss%" with "a@aaa" keeps the "@").
+    
+    Args:
+        pattern: Pattern string with placeholders
+        literal_chars: Characters to treat as literals
+        custom_charsets: Custom charsets (see marker_charsets)
+        literal_mask: Positional mask of literal placeholders
+        
+    Returns:
+        List with True for every literal position
+        
+    Raises:
+        ConfigError: If the mask is not as long as the pattern
+    """
+    markers = marker_charsets(custom_charsets)
+    if literal_mask and len(literal_mask) != len(pattern):
+        raise ConfigError(f"Literal mask {literal_mask!r} must be as long as the pattern {pattern!r}")
+    mask = literal_mask or [None] * len(pattern)
+    literal_set = set(literal_chars or "")
+    return [char not in markers or char in literal_set or masked in markers
+            for char, masked in zip(pattern, mask)]
+
+
+def expand_pattern_positions(pattern: str, literal_chars: str = None, custom_charsets: list = None,
+                             literal_mask: str = None) -> list:
+    """
+    Expand a Crunch-style pattern position by position
+    
+    Every position is either a placeholder drawing from its own charset
+    or a fixed literal character, so "pass%%" yields
+    ["p", "a", "s", "s", "0123456789", "0123456789"].
+    
+    Args:
+        pattern: Pattern string with placeholders
+        literal_chars: Characters to treat as literals (see literal_positions)
+        custom_charsets: Custom charsets for the markers 1-4
+        literal_mask: Positional crunch -l mask (see literal_positions)
+        
+    Returns:
+        List with one candidate-character string per position
+    """
+    markers = marker_charsets(custom_charsets)
+    positions = []
+    for i, (char, literal) in enumerate(zip(pattern, literal_positions(pattern, literal_chars, custom_charsets, literal_mask))):
+        if literal:
+            positions.append(char)
+        elif markers[char] is None:
+            raise ConfigError(f"Pattern position {i + 1} references undefined custom charset {char}")
+        else:
+            positions.append(markers[char])
+    return positions
+
+
+def pattern_markers(pattern: str, literal_chars: str = None, custom_charsets: list = None,
+                    literal_mask: str = None) -> list:
+    """
+    Marker behind each position of a pattern
+    
+    Args:
+        pattern: Pattern string with placeholders
+        literal_chars: Characters to treat as literals (see literal_positions)
+        custom_charsets: Custom charsets for the markers 1-4
+        literal_mask: Positional crunch -l mask (see literal_positions)
+        
+    Returns:
+        List with the placeholder of every position, None for literals
+    """
+    return [None if literal else char
+            for char, literal in zip(pattern, literal_positions(pattern, literal_chars, custom_charsets, literal_mask))]
+
+
+def parse_duplicate_limit(spec: str) -> dict:
+    """
+    Parse a crunch-style duplicate limit such as "2@" or "2@3%"
+    
+    Each item is a count followed by the marker whose runs it limits;
+    items may be separated by commas ("2@,3%"). A count without a marker
+    limits every marker class and a marker without a count forbids
+    adjacent repeats in its class ("@" is "1@"). Since "," is itself the
+    uppercase marker, a comma directly after a count belongs to that
+    count ("2," limits uppercase runs).
+    
+    Args:
+        spec: Duplicate limit specification
+        
+    Returns:
+        Marker (or "" for every class) -> longest allowed run of one character
+    """
+    limits = {}
+    i = 0
+    while i < len(spec):
+        start = i
+        while i < len(spec) and spec[i].isdigit():
+            i += 1
+        if i == start and spec[i] not in PATTERN_MARKERS:
+            raise ConfigError(f"Invalid duplicate_limit: {spec!r} (expected e.g. 2@ or 2@,3%)")
+        count = int(spec[start:i]) if i > start else 1
+        if count < 1:
+            raise ConfigError("duplicate_limit counts must be at least 1")
+        marker = ""
+        if i < len(spec) and spec[i] in PATTERN_MARKERS:
+            marker = spec[i]
+            i += 1
+            if i < len(spec) and spec[i] == ",":
+                i += 1
+        limits[marker] = count
+    if not limits:
+        raise ConfigError("duplicate_limit is empty")
+    return limits
+
+
+def check_duplicate_limit(token: str, limits: dict, custom_charsets: list = None) -> bool:
+    """
+    Check a finished token against duplicate limits
+    
+    Used where no pattern says which marker produced a character: a run
+    of one repeated character is limited by the markers whose charset
+    contains it (the strictest one if several do), so with "2@" "aab1"
+    and "a111" pass while "aaab1" does not. A count without a marker
+    limits runs of any character.
+    
+    Args:
+        token: Token to check
+        limits: Result of parse_duplicate_limit
+        custom_charsets: Custom charsets for the markers 1-4
+        
+    Returns:
+        True if no run is longer than its limit
+    """
+    classes = [(charset, limits[marker]) for marker, charset in marker_charsets(custom_charsets).items()
+               if marker in limits and charset]
+    i = 0
+    while i < len(token):
+        j = i + 1
+        while j < len(token) and token[j] == token[i]:
+            j += 1
+        matching = [limit for charset, limit in classes if token[i] in charset]
+        limit = min(matching) if matching else limits.get("")
+        if limit is not None and j - i > limit:
+            return False
+        i = j
+    return True
+
+
 def get_charset(name: str) -> str:
     """
     Get predefined charset by name
@@ -107,3 +294,96 @@ def merge_charsets(*charsets: str) -> str:
             result += char
     
     return result
+
+
+def _in_ranges(*ranges):
+    """Build a membership test over inclusive code point intervals"""
+    return lambda char: any(low <= ord(char) <= high for low, high in ranges)
+
+
+def _in_categories(*prefixes):
+    """Build a membership test over Unicode general categories"""
+    return lambda char: unicodedata.category(char).startswith(prefixes)
+
+
+# Named character classes usable in charset allow/deny filters
+CHAR_CLASSES = {
+    "ascii": _in_ranges((0x00, 0x7F)),
+    "ascii_printable": _in_ranges((0x20, 0x7E)),
+    "ascii_letters": _in_ranges((0x41, 0x5A), (0x61, 0x7A)),
+    "ascii_lowercase": _in_ranges((0x61, 0x7A)),
+    "ascii_uppercase": _in_ranges((0x41, 0x5A)),
+    "ascii_digits": _in_ranges((0x30, 0x39)),
+    "ascii_symbols": lambda char: char in CHARSET_SYMBOLS,
+    "letters": _in_categories("L"),
+    "lowercase": _in_categories("Ll"),
+    "uppercase": _in_categories("Lu"),
+    "numbers": _in_categories("N"),
+    "punctuation": _in_categories("P"),
+    "symbols": _in_categories("S"),
+    "marks": _in_categories("M"),
+    "whitespace": lambda char: char.isspace(),
+    "control": _in_categories("Cc", "Cf"),
+    "no_control_chars": lambda char: not unicodedata.category(char).startswith(("Cc", "Cf")),
+}
+
+# Prefix selecting characters by Unicode script, e.g. "script:cyrillic"
+SCRIPT_CLASS_PREFIX = "script:"
+
+
+def char_class_predicate(name: str):
+    """
+    Look up the membership test of a named character class
+    
+    Args:
+        name: Class name from CHAR_CLASSES or "script:<name>"
+        
+    Returns:
+        Function taking a single code point and returning a bool
+    """
+    key = name.lower()
+    if key.startswith(SCRIPT_CLASS_PREFIX):
+        script = key[len(SCRIPT_CLASS_PREFIX):].upper() + " "
+        return lambda char: unicodedata.name(char, "").startswith(script)
+    if key in CHAR_CLASSES:
+        return CHAR_CLASSES[key]
+    raise KeyError(name)
+
+
+def is_char_class(name: str) -> bool:
+    """Check whether a character class name is known"""
+    try:
+        char_class_predicate(name)
+    except KeyError:
+        return False
+    return True
+
+
+class CharMembership:
+    """
+    Code point membership test over literal characters and named classes
+    
+    Tokens are checked per code point, so a grapheme written with
+    combining marks passes only if every code point in it is allowed.
+    Results are memoised per code point.
+    """
+    
+    def __init__(self, chars: str = "", classes=()):
+        self.chars = frozenset(chars or "")
+        self.predicates = [char_class_predicate(name) for name in classes]
+        self._cache = {}
+    
+    def __contains__(self, char: str) -> bool:
+        member = self._cache.get(char)
+        if member is None:
+            member = char in self.chars or any(p(char) for p in self.predicates)
+            self._cache[char] = member
+        return member
+    
+    def all_members(self, token: str) -> bool:
+        """True if every code point of the token is a member"""
+        return all(c in self for c in token)
+    
+    def any_member(self, token: str) -> bool:
+        """True if some code point of the token is a member"""
+        return any(c in self for c in token)
@@ -5,57 +5,267 @@ Main entry point for the omni command
 """
 
 import click
+import json
+import subprocess
 import sys
+import warnings
+import threading
+import time
+from dataclasses import asdict
+from datetime import datetime
 from pathlib import Path
+from typing import Optional
 from rich.console import Console
+from rich.live import Live
 from rich.table import Table
+from rich.markup import escape
+from rich.text import Text
 from rich.progress import track
 
 from . import __version__
-from .config import Config, FilterConfig
+from .config import TOP_PLACEMENTS, Config, ConfigWarning, FilterConfig
 from .generator import Generator
+from .cancel import CancellationToken, interrupt_cancels
+from .error import ConfigError, GeneratorError, GenerationInterrupted, error_payload, exit_code_for
 from .presets import PresetManager, BUILTIN_PRESETS
 from .fields import FieldManager
-from .storage import OutputWriter
+from .storage import OUTPUT_ENCODINGS, CheckpointManager, OutputWriter, SplitWriter, detect_compression, encode_token, open_text, write_combos, write_tokens_to_file, write_split_credentials
 from .transforms import list_transforms
+from .temporal import KINDS as TEMPORAL_KINDS, default_range, parse_date, generate_temporal
+from .structured_ids import STRUCTURED_ID_TEMPLATES, build_patterns
+from .replicate import COMMON_SYMBOLS, describe_structure, infer_structure, parse_year_range, replicate_config
+from .sources import StaleSourceWarning, is_remote, read_word_file, resolve_source
+from .seclists import (SECLISTS_CATALOG, SECLISTS_PREFIX, default_data_dir as seclists_data_dir,
+                       fetch_list, is_seclists_source, list_catalog,
+                       load_manifest as load_seclists_manifest)
+from .directory import USERNAME_FORMATS, employee_config, parse_directory_export, username_candidates
+from .defaults import DEFAULT_CREDENTIALS, get_default_credentials, list_categories, list_vendors
+from .autotune import DIMENSIONS as TUNING_DIMENSIONS, autotune as autotune_config
+from .bundle import create_bundle, load_bundle
+from .preview import PreviewResult, PreviewSampler, annotate_token, sample_tokens
+from .cluster import TOKEN_ENV, Coordinator, chunk_manifest, load_chunk_manifest, make_server, run_worker
+from .explain import DEFAULT_SCAN_LIMIT, describe_chain, explain_token
+from .extract import slice_lines, slice_tokens
+from .campaign import campaign_runs, default_campaign_db, forget_campaign, list_campaigns
+from .dedupe import DEDUPE_MODES, estimated_memory
+from .merge import MERGE_FORMATS, merge_sources, parse_source, tag_counts, write_merged
+from .priority import (DEFAULT_BLOCK, DEFAULT_WINDOW, SCORERS, MergePlan, StreamSpec, make_scorer,
+                       priority_merge, quota_allotments, quota_horizon, quota_shares)
+from .locking import lock_path_for
+from .index import DEFAULT_INDEX_INTERVAL, build_index, index_path_for, lookup
+from .histogram import LengthHistogram, bar, length_coverage
+from .progress import GeneratorStats, throttled
+from .keyspace import chunk_bounds, chunk_count
+from .repair import find_fixes
+from .opsec import DISK_KINDS, enable as enable_opsec, is_enabled as opsec_enabled, shred_path
+from .settings import UserSettings
+from .usage import UsageLedger, default_usage_path, record_run
+from .service import (SERVICE_PLATFORMS, SERVICE_ROLES, ServiceSpec, default_data_dir as service_data_dir,
+                      default_platform, install_service, systemd_unit, windows_script, windows_script_path,
+                      windows_task_command)
+from .simulate import load_reference, simulate as simulate_hits
+from .snapshots import DEFAULT_SNAPSHOT_DIR, SNAPSHOT_SIZE, verify_presets
+from .shard import SHARD_MODES, ShardedWriter, shard_contains
+from .buckets import BUCKET_METRICS, bucket_key, bucket_thresholds as bucket_thresholds_for
+from .spray import SprayPolicy, schedule_spray
+from .tuning import TUNABLE_METRICS, METRIC_FILTER_FIELDS, parse_count, tune_filters, apply_tuning
+from .frequency import FrequencyModel, import_frequency_corpus
+from .toplists import TOPLIST_NAMES, fetch_top_passwords
+from .smartcase import train_case_stats
+from .lengths import measure_lengths
+from .policy import MIN_BAN_SAMPLES, infer_policy, policy_config
+from .hashes import HASH_ALGORITHMS, HASH_FORMATS, hashed_output_bytes
+from .estimate import (HASH_MODE_RATES, parse_hash_rate, rate_for_mode,
+                       attack_seconds, format_duration)
 
 
 console = Console()
 
 
+def fail(error: BaseException, label: str = "Error"):
+    """Report an error and exit with its status (a JSON object on stderr with --json)"""
+    ctx = click.get_current_context(silent=True)
+    if ctx is not None and (ctx.find_root().obj or {}).get('json'):
+        click.echo(json.dumps(error_payload(error)), err=True)
+    else:
+        console.print(f"[red]{label}: {error}[/red]")
+    sys.exit(exit_code_for(error))
+
+
+def load_job_config(config_path: Optional[str], preset: Optional[str]) -> Config:
+    """Config of a --config file, a --preset or the defaults, with the user's default fields applied"""
+    if config_path:
+        config = Config.from_json(Path(config_path))
+    elif preset:
+        config = PresetManager().get_preset_config(preset)
+    else:
+        config = Config()
+    return UserSettings.load().apply_default_fields(config)
+
+
+class WordSource(click.ParamType):
+    """Existing local file, http(s) URL (downloaded into the source cache) or fetched SecLists name"""
+    name = "path|url"
+    
+    def __init__(self, dir_okay: bool = True):
+        self.dir_okay = dir_okay
+    
+    def convert(self, value, param, ctx):
+        if is_remote(value) or is_seclists_source(value):
+            try:
+                return str(resolve_source(value))
+            except Exception as e:
+                self.fail(str(e), param, ctx)
+        path = Path(value).expanduser()
+        if not path.exists():
+            self.fail(f"{value} does not exist", param, ctx)
+        if path.is_dir() and not self.dir_okay:
+            self.fail(f"{value} is a directory", param, ctx)
+        return value
+
+
+_default_showwarning = warnings.showwarning
+
+
+def show_warning(message, category, filename, lineno, file=None, line=None):
+    """Print config warnings (deprecated keys) and stale sources without a source location"""
+    if issubclass(category, (ConfigWarning, StaleSourceWarning)):
+        Console(stderr=True).print(f"[yellow]Warning: {message}[/yellow]")
+    else:
+        _default_showwarning(message, category, filename, lineno, file, line)
+
+
 @click.group()
 @click.version_option(version=__version__)
 @click.option('--verbose', '-v', is_flag=True, help='Verbose output')
+@click.option('--json', 'json_errors', is_flag=True, help='Report errors as JSON on stderr')
+@click.option('--opsec', is_flag=True, help='Write nothing to disk but the requested output (also OMNIWORDLIST_OPSEC=1)')
+@click.option('--allow-disk', multiple=True, help=f'With --opsec, still allow: {", ".join(DISK_KINDS)} (comma-separated)')
 @click.pass_context
-def cli(ctx, verbose):
+def cli(ctx, verbose, json_errors, opsec, allow_disk):
     """OmniWordlist Pro - Enterprise-grade wordlist generation"""
     ctx.ensure_object(dict)
     ctx.obj['verbose'] = verbose
+    ctx.obj['json'] = json_errors
+    warnings.showwarning = show_warning
+    try:
+        if opsec or opsec_enabled():
+            enable_opsec(allow_disk)
+        elif allow_disk:
+            raise ConfigError("--allow-disk only applies with --opsec")
+    except Exception as e:
+        fail(e, "Configuration error")
 
 
 @cli.command()
 @click.option('--min', 'min_length', type=int, help='Minimum length')
 @click.option('--max', 'max_length', type=int, help='Maximum length')
 @click.option('--charset', help='Character set')
+@click.option('--length-distribution', type=click.Path(exists=True, dir_okay=False), help='Length shares from `omni length-stats`: interleave lengths instead of finishing each in turn')
 @click.option('--pattern', help='Pattern (Crunch-style)')
+@click.option('--wordlist', 'input_wordlist', type=click.Path(allow_dash=True, dir_okay=False), help='Mutate the lines of a word list (- for stdin) instead of generating candidates')
+@click.option('--append-pattern', help='Hybrid mode: follow each --wordlist word with every candidate of this pattern, e.g. %%%%')
+@click.option('--prepend-pattern', help='Hybrid mode: precede each --wordlist word with every candidate of this pattern')
+@click.option('--mode', type=click.Choice(['markov']), help='Generation mode other than charset / pattern / fields: markov needs --train or --model')
+@click.option('--train', 'markov_train', type=click.Path(allow_dash=True, dir_okay=False), help='Markov mode: word list to train the model on (saved to --model if given)')
+@click.option('--model', 'markov_model', type=click.Path(dir_okay=False), help='Markov mode: trained model file to load, or to save the --train model to')
+@click.option('--order', 'markov_order', type=click.Choice(['2', '3']), help='Markov mode: characters of context (default 3)')
+@click.option('--chain-max-elements', type=int, help='Chain mode (PRINCE): chains of up to N elements within --min..--max, joined by --separator')
+@click.option('--chain-elements', type=click.Path(allow_dash=True, dir_okay=False), help='Chain mode: file of elements (default: the values of the enabled fields)')
+@click.option('--pipe-through', help='Pipe tokens through an external filter command (tokens on its stdin, output lines from its stdout)')
+@click.option('--pipe-batch-size', type=int, help='Tokens written to the --pipe-through command at a time (default 1000)')
+@click.option('--left', 'combinator_left', type=click.Path(allow_dash=True, dir_okay=False), help='Combinator mode: left word list, streamed (- for stdin)')
+@click.option('--right', 'combinator_right', type=click.Path(allow_dash=True, dir_okay=False), help='Combinator mode: right word list, held in memory (put the smaller list here)')
+@click.option('--swap', 'combinator_swap', is_flag=True, help='Combinator mode: also emit right + left')
+@click.option('--separator', 'separators', multiple=True, help='Separator between the joined words of combinator mode or field components; repeat for one variant per separator (--separator "" for none)')
+@click.option('--field-length-window', is_flag=True, help='Field mode: only build combinations within --min..--max (joined components, before prefix/suffix and transforms)')
+@click.option('--set1', help='Custom charset for the pattern marker 1 (crunch -1)')
+@click.option('--set2', help='Custom charset for the pattern marker 2')
+@click.option('--set3', help='Custom charset for the pattern marker 3')
+@click.option('--set4', help='Custom charset for the pattern marker 4')
+@click.option('--literal', '-l', 'literal_mask', help='Pattern placeholders to keep literally, crunch -l style: a mask as long as the pattern (e.g. a@aaa for p@ss%)')
+@click.option('--duplicate-limit', '-d', help='Longest run of one character per marker class, e.g. 2@, 2@,3% or @ (no adjacent repeats)')
+@click.option('--start', 'start_string', help='First candidate to generate (charset / pattern modes seek to it, like crunch -s)')
+@click.option('--end', 'end_string', help='Last candidate to generate, inclusive (like crunch -e)')
+@click.option('--skip', help='Leave out the first N candidates (from --start if given); charset / pattern modes seek by index')
+@click.option('--take', help='Stop after N candidates, e.g. 1G, to split a keyspace across machines')
+@click.option('--invert', '-i', is_flag=True, default=None, help='Change the leftmost character fastest (crunch -i)')
 @click.option('--output', '-o', type=click.Path(), help='Output file')
 @click.option('--compress', type=click.Choice(['gzip', 'bzip2', 'lz4', 'zstd']), help='Compression format')
 @click.option('--prefix', help='Prefix for each token')
 @click.option('--suffix', help='Suffix for each token')
+@click.option('--as-of', 'reference_date', help='Date relative tokens like {year-1} resolve against (YYYY-MM-DD, default: today)')
 @click.option('--format', type=click.Choice(['txt', 'jsonl', 'csv']), default='txt', help='Output format')
+@click.option('--encoding', 'output_encoding', type=click.Choice(OUTPUT_ENCODINGS), help='Pre-encode tokens, e.g. utf-16le for NTLM tooling (default: utf-8)')
+@click.option('--plain-output', type=click.Path(), help='Also write unencoded tokens to this file when --encoding is set')
 @click.option('--preset', help='Use a preset')
+@click.option('--bundle', 'bundle_path', type=click.Path(exists=True), help='Run a job bundle created with `omni bundle`')
 @click.option('--sample-size', '-s', type=int, help='Limit output to N tokens')
-@click.option('--dedupe', is_flag=True, help='Enable deduplication')
+@click.option('--max-bytes', help='Stop once the uncompressed output reaches this size, e.g. 4G (decimal units)')
+@click.option('--split-bytes', help='Write numbered part files, starting a new one at this uncompressed size, e.g. 100M (crunch -b)')
+@click.option('--split-lines', type=int, help='Write numbered part files of this many lines (crunch -c)')
+@click.option('--dedupe/--no-dedupe', default=None, help='Drop repeated tokens after transforms and filters (default: from the config or preset)')
+@click.option('--dedupe-mode', type=click.Choice(DEDUPE_MODES), help='exact (memory grows with the output) or bloom (fixed memory, drops --bloom-fp-rate of new tokens)')
+@click.option('--bloom-fp-rate', type=float, help='False-positive rate the bloom filter is sized for (default: 0.01)')
+@click.option('--campaign', help='Skip tokens earlier runs of this campaign wrote, and record the ones written')
+@click.option('--campaign-db', type=click.Path(dir_okay=False), help='Campaign database (default: ~/.omniwordlist/campaigns.db)')
 @click.option('--transforms', multiple=True, help='Apply transforms')
+@click.option('--seed', type=int, help='Seed randomized transforms for reproducible output')
+@click.option('--smart-case', type=int, help='Expand each candidate into its N most likely case variants')
+@click.option('--case-stats', 'case_stats_file', type=click.Path(exists=True, dir_okay=False), help='Case statistics from `omni case-stats` for --smart-case')
+@click.option('--include-top', type=int, help='Also emit the N most common passwords')
+@click.option('--exclude-top', type=int, help='Drop the N most common passwords from output')
+@click.option('--top-placement', type=click.Choice(TOP_PLACEMENTS), help='Emit --include-top passwords before, after or alternating with generated tokens')
+@click.option('--region', help='Add themed sports/pop-culture packs for a region (IN, US, EU, ...)')
+@click.option('--rejects-output', type=click.Path(), help='Write a sample of rejected tokens with reasons')
+@click.option('--field-source', 'field_sources', multiple=True,
+              help='Values for a field as NAME=SOURCE (file, URL, named pipe or - for stdin); enables the field')
+@click.option('--frequency-model', type=WordSource(dir_okay=False), help='Order field values by a model from `omni freq import`')
+@click.option('--combo-users', help='Username file/URL; output user:pass combos with the generated passwords')
+@click.option('--combo-mode', type=click.Choice(['product', 'zip', 'per_user']), help='How users are paired with passwords')
+@click.option('--combo-per-user', type=int, help='Passwords per user for --combo-mode per_user')
+@click.option('--combo-separator', help='Separator between username and password (default ":")')
+@click.option('--hash', 'hash_algorithm', type=click.Choice(list(HASH_ALGORITHMS)), help='Write each candidate with its unsalted hash (lookup table)')
+@click.option('--hash-format', type=click.Choice(HASH_FORMATS), help='Hashed line format: potfile (hash:candidate) or csv (default: potfile)')
+@click.option('--workers', type=int, help='Hashing processes for --hash')
+@click.option('--near', 'seed_words', multiple=True, help='Seed word for edit-distance neighborhood mode (repeatable)')
+@click.option('--distance', 'edit_distance', type=int, help='Maximum edit distance around --near words (default: 1)')
+@click.option('--target-size', help='Tune the quality filter to keep about N best tokens (e.g. 500M)')
+@click.option('--max-keyspace', help='Refuse to run if the keyspace is larger than N candidates (e.g. 10G)')
+@click.option('--shard-by', type=click.Choice(SHARD_MODES), help='Write one file per first character / hash bucket into the --output directory')
+@click.option('--shards', 'shard_count', type=int, help='Number of shards for --shard-by hash (default: 16)')
+@click.option('--bucket-by', type=click.Choice(BUCKET_METRICS), help='Write low/medium/high files by entropy or quality score into the --output directory')
+@click.option('--bucket-thresholds', help='Two cut points between buckets, e.g. 2.5,3.5 (default depends on the metric)')
+@click.option('--index', 'write_index', is_flag=True, help='Write a <output>.idx sidecar for `omni lookup` and `omni slice`')
+@click.option('--index-interval', type=int, help=f'Lines between index entries (default: {DEFAULT_INDEX_INTERVAL})')
+@click.option('--tag', 'tags', multiple=True, help='Provenance tag written with each JSONL record (repeatable)')
+@click.option('--token-stats', is_flag=True, default=None, help='Add quality score, language, mask and class-count columns (csv / jsonl)')
+@click.option('--status', 'show_status', is_flag=True, help='Print a length histogram and keyspace coverage periodically')
+@click.option('--status-timer', type=float, default=10.0, help='Seconds between --status / --verbose reports')
+@click.option('--checkpoint-dir', type=click.Path(file_okay=False), help='Checkpoint the run here so `omni resume` can continue it')
 @click.pass_context
-def run(ctx, min_length, max_length, charset, pattern, output, compress, 
-        prefix, suffix, format, preset, sample_size, dedupe, transforms):
+def run(ctx, min_length, max_length, charset, length_distribution, pattern, input_wordlist, append_pattern, prepend_pattern, mode, markov_train, markov_model, markov_order, chain_max_elements, chain_elements, pipe_through, pipe_batch_size, combinator_left, combinator_right, combinator_swap, separators, field_length_window, set1, set2, set3, set4, literal_mask, duplicate_limit, start_string, end_string, skip, take, invert, output, compress, 
+        prefix, suffix, reference_date, format, output_encoding, plain_output, preset, bundle_path, sample_size, max_bytes, split_bytes, split_lines, dedupe, dedupe_mode, bloom_fp_rate, campaign, campaign_db,
+        transforms, seed,
+        smart_case, case_stats_file,
+        include_top, exclude_top, top_placement, region, rejects_output, field_sources,
+        frequency_model, combo_users, combo_mode, combo_per_user, combo_separator,
+        hash_algorithm, hash_format, workers, seed_words, edit_distance, target_size,
+        max_keyspace, shard_by, shard_count, bucket_by, bucket_thresholds, write_index, index_interval, tags, token_stats, show_status, status_timer,
+        checkpoint_dir):
     """Generate a wordlist"""
     
     verbose = ctx.obj.get('verbose', False)
     
-    # Load preset if specified
-    if preset:
+    # Load bundle or preset if specified
+    if bundle_path:
+        try:
+            config = load_bundle(Path(bundle_path))
+        except Exception as e:
+            fail(e, "Bundle error")
+        if verbose:
+            console.print(f"[green]Loaded bundle: {bundle_path}[/green]")
+    elif preset:
         preset_mgr = PresetManager()
         config = preset_mgr.get_preset_config(preset)
         if verbose:
@@ -70,111 +280,707 @@ def run(ctx, min_length, max_length, charset, pattern, output, compress,
         config.max_length = max_length
     if charset:
         config.charset = charset
+    if length_distribution:
+        config.length_distribution = length_distribution
     if pattern:
         config.pattern = pattern
+    if input_wordlist:
+        config.input_wordlist = input_wordlist
+    if (markov_train or markov_model or markov_order) and mode != 'markov':
+        fail(ConfigError("--train, --model and --order need --mode markov"), "Configuration error")
+    if mode == 'markov':
+        if not (markov_train or markov_model or config.markov_train or config.markov_model):
+            fail(ConfigError("--mode markov needs --train or --model"), "Configuration error")
+        if markov_train:
+            config.markov_train = markov_train
+        if markov_model:
+            config.markov_model = markov_model
+        if markov_order:
+            config.markov_order = int(markov_order)
+    if chain_max_elements:
+        config.chain_max_elements = chain_max_elements
+    if chain_elements:
+        config.chain_elements = chain_elements
+    if pipe_through:
+        config.pipe_through = pipe_through
+    if pipe_batch_size:
+        config.pipe_batch_size = pipe_batch_size
+    if append_pattern:
+        config.append_pattern = append_pattern
+    if prepend_pattern:
+        config.prepend_pattern = prepend_pattern
+    if combinator_left:
+        config.combinator_left = combinator_left
+    if combinator_right:
+        config.combinator_right = combinator_right
+    if combinator_swap:
+        config.combinator_swap = True
+    if separators:
+        config.separators = list(separators)
+    if field_length_window:
+        config.field_length_window = True
+    custom_charsets = [set1, set2, set3, set4]
+    if any(custom_charsets):
+        # Sets are positional: a gap before a later set is an error
+        while not custom_charsets[-1]:
+            custom_charsets.pop()
+        if not all(custom_charsets):
+            fail(ConfigError("--set1 .. --set4 must be given in order without gaps"), "Configuration error")
+        config.custom_charsets = custom_charsets
+    if literal_mask:
+        config.literal_mask = literal_mask
+    if duplicate_limit:
+        config.duplicate_limit = duplicate_limit
+    if start_string:
+        config.start_string = start_string
+    if end_string:
+        config.end_string = end_string
+    if invert:
+        config.invert = True
     if prefix:
         config.prefix = prefix
     if suffix:
         config.suffix = suffix
+    if reference_date:
+        config.reference_date = reference_date
     if compress:
         config.compression = compress
     if format:
         config.format = format
+    if output_encoding:
+        config.output_encoding = output_encoding
+    if plain_output:
+        config.plain_output = Path(plain_output)
     if sample_size:
         config.sample_size = sample_size
         config.max_lines = sample_size
-    if dedupe:
+    if dedupe is not None:
         config.dedupe = dedupe
+    if dedupe_mode:
+        config.dedupe_mode = dedupe_mode
+    if bloom_fp_rate is not None:
+        config.bloom_fp_rate = bloom_fp_rate
+    if campaign:
+        config.campaign = campaign
+    if campaign_db:
+        config.campaign_db = Path(campaign_db)
     if transforms:
         config.transforms = list(transforms)
-    
+    if seed is not None:
+        config.seed = seed
+    if smart_case:
+        config.smart_case = smart_case
+    if case_stats_file:
+        config.case_stats_file = case_stats_file
+    if include_top:
+        config.include_top = include_top
+    if exclude_top:
+        config.exclude_top = exclude_top
+    if top_placement:
+        config.top_placement = top_placement
+    if region:
+        config.region = region
+    if rejects_output:
+        config.rejects_output = Path(rejects_output)
+    for field_source in field_sources:
+        name, sep, source = field_source.partition('=')
+        if not sep or not name or not source:
+            raise click.BadParameter(f"expected NAME=SOURCE, got {field_source!r}", param_hint='--field-source')
+        config.field_sources[name] = source
+        if name not in config.enabled_fields:
+            config.enabled_fields.append(name)
+    if frequency_model:
+        config.frequency_model = frequency_model
+    if combo_users:
+        config.combo_users = combo_users
+    if combo_mode:
+        config.combo_mode = combo_mode
+    if combo_per_user:
+        config.combo_per_user = combo_per_user
+    if combo_separator is not None:
+        config.combo_separator = combo_separator
+    if hash_algorithm:
+        config.hash_algorithm = hash_algorithm
+    if hash_format:
+        config.hash_format = hash_format
+    if workers:
+        config.workers = workers
+    if seed_words:
+        config.seed_words = list(seed_words)
+    if edit_distance is not None:
+        config.edit_distance = edit_distance
+    if shard_by:
+        config.shard_by = shard_by
+    if shard_count:
+        config.shard_count = shard_count
+    if bucket_by:
+        config.bucket_by = bucket_by
+    if bucket_thresholds:
+        try:
+            config.bucket_thresholds = [float(value) for value in bucket_thresholds.split(",")]
+        except ValueError:
+            fail(ConfigError(f"Invalid --bucket-thresholds: {bucket_thresholds}"), "Configuration error")
+    if tags:
+        config.tags = list(tags)
+    if token_stats:
+        config.token_stats = True
+    if index_interval:
+        config.index_interval = index_interval
+    elif write_index and not config.index_interval:
+        config.index_interval = DEFAULT_INDEX_INTERVAL
+    if checkpoint_dir:
+        config.checkpoint_dir = Path(checkpoint_dir)
+    if output:
+        config.output_file = Path(output)
     config.verbose = verbose
     
     # Validate configuration
     try:
+        if max_keyspace:
+            config.max_keyspace = parse_count(max_keyspace)
+        if max_bytes:
+            config.max_bytes = parse_count(max_bytes)
+        if split_bytes:
+            config.split_by_bytes = parse_count(split_bytes)
+        if split_lines:
+            config.split_by_lines = split_lines
+        if skip:
+            config.skip = parse_count(skip)
+        if take:
+            config.take = parse_count(take)
         config.validate()
     except Exception as e:
-        console.print(f"[red]Configuration error: {e}[/red]")
-        sys.exit(1)
+        fail(e, "Configuration error")
+    
+    # Two-pass mode: sample first, then generate with the tuned threshold
+    if target_size:
+        try:
+            report = tune_filters(config, parse_count(target_size))
+        except Exception as e:
+            fail(e, "Tuning error")
+        apply_tuning(config, report)
+        if verbose:
+            console.print(f"[cyan]Tuned filters.min_quality to {report.threshold:.3f} "
+                          f"(~{report.expected_output:,} tokens)[/cyan]")
+    
+    if config.shard_by and not output:
+        fail(ConfigError("--shard-by needs an --output directory"), "Configuration error")
+    if config.bucket_by and not output:
+        fail(ConfigError("--bucket-by needs an --output directory"), "Configuration error")
+    if config.index_interval and not output:
+        fail(ConfigError("--index needs an --output file"), "Configuration error")
+    if (config.split_by_bytes or config.split_by_lines) and not output:
+        fail(ConfigError("--split-bytes / --split-lines need an --output file"), "Configuration error")
+    if (config.split_by_bytes or config.split_by_lines) and config.index_interval:
+        fail(ConfigError("--index needs single-file output, not split parts"), "Configuration error")
     
     # Create generator
     try:
-        generator = Generator(config)
+        UserSettings.load().apply_default_fields(config)
+        generator = Generator(config, cancel_token=CancellationToken())
     except Exception as e:
-        console.print(f"[red]Generator error: {e}[/red]")
-        sys.exit(1)
+        fail(e, "Generator error")
+    
+    # Report the effective transform pipeline when steps were dropped
+    if generator.transform_notes:
+        err_console = Console(stderr=True)
+        err_console.print(f"[cyan]Effective transforms: {' -> '.join(generator.ascii_transforms) or '(none)'}[/cyan]")
+        for note in generator.transform_notes:
+            err_console.print(f"  [dim]{note}[/dim]")
+    
+    # Periodic progress on stderr: pipeline counters and the recent rate, plus the
+    # length histogram / coverage report with --status
+    histogram = None
+    if show_status or verbose:
+        if show_status:
+            histogram = LengthHistogram()
+            generator.add_observer(histogram)
+        generator.set_progress_callback(throttled(lambda stats: print_status(generator, histogram, stats),
+                                                  status_timer))
     
     # Show stats
     if verbose:
         estimated = generator.estimate_count()
         console.print(f"[cyan]Estimated tokens: {estimated:,}[/cyan]")
     
-    # Generate and write
-    if output:
-        output_path = Path(output)
-        console.print(f"[green]Generating wordlist to {output_path}...[/green]")
-        
-        try:
-            with OutputWriter(output_path, config.compression, config.format) as writer:
-                for token in track(generator.generate(), 
-                                 description="Generating...",
-                                 total=config.max_lines):
-                    writer.write(token)
-            
-            console.print(f"[green]✓ Generated {generator.tokens_generated:,} tokens[/green]")
-            console.print(f"[cyan]Output: {output_path}[/cyan]")
-        except Exception as e:
-            console.print(f"[red]Error writing output: {e}[/red]")
-            sys.exit(1)
+    # Generate and write; the first Ctrl-C stops cleanly, a second one force-quits
+    try:
+        with interrupt_cancels(generator.cancel_token, announce_stop):
+            if output:
+                output_path = Path(output)
+                console.print(f"[green]Generating wordlist to {output_path}...[/green]")
+                
+                try:
+                    if config.shard_by:
+                        writer = ShardedWriter(output_path, config.shard_by, config.shard_count,
+                                               config.compression, config.format, token_stats=config.token_stats)
+                    elif config.bucket_by:
+                        entropy_mode = config.filters.entropy_mode
+                        thresholds = bucket_thresholds_for(config.bucket_by, entropy_mode, config.bucket_thresholds)
+                        writer = ShardedWriter(output_path, config.bucket_by, compression=config.compression,
+                                               format=config.format,
+                                               key=bucket_key(config.bucket_by, entropy_mode, thresholds),
+                                               manifest={"entropy_mode": entropy_mode, "thresholds": list(thresholds)},
+                                               token_stats=config.token_stats)
+                    else:
+                        writer = generator.open_backend()
+                    if generator.checkpoint_manager is not None:
+                        console.print(f"[cyan]Checkpointing as job {generator.job_id}[/cyan]")
+                    write_with_progress(generator, writer)
+                    if generator.cancelled:
+                        handle_interrupt(generator)
+                    
+                    console.print(f"[green]✓ Generated {generator.tokens_generated:,} tokens[/green]")
+                    if generator.limit_reached:
+                        report_limit(generator, writer.bytes_written)
+                    if isinstance(writer, SplitWriter):
+                        console.print(f"[cyan]Output: {len(writer.parts)} parts, "
+                                      f"{writer.parts[0].path} .. {writer.parts[-1].path}[/cyan]")
+                    else:
+                        console.print(f"[cyan]Output: {output_path}[/cyan]")
+                    if config.index_interval:
+                        index = build_index(output_path, config.index_interval)
+                        console.print(f"[cyan]Index: {index_path_for(output_path)} "
+                                      f"({len(index.entries):,} entries, {'sorted' if index.sorted else 'unsorted'})[/cyan]")
+                except Exception as e:
+                    fail(e, "Error writing output")
+            else:
+                # Write to stdout
+                generator.recording_campaign = True
+                header = generator.output_header()
+                if header is not None:
+                    print(header)
+                lines = generator.output()
+                written_bytes = 0
+                for token in lines:
+                    if config.output_encoding == "utf-16le":
+                        data = (token + "\n").encode("utf-16-le")
+                        sys.stdout.flush()
+                        sys.stdout.buffer.write(data)
+                    else:
+                        line = encode_token(token, config.output_encoding)
+                        data = (line + "\n").encode()
+                        print(line)
+                    generator.tokens_written += 1
+                    written_bytes += len(data)
+                    if config.max_bytes and written_bytes >= config.max_bytes:
+                        generator.finish_early(lines, "max_bytes")
+                        report_limit(generator, written_bytes)
+                        break
+    except KeyboardInterrupt:
+        handle_interrupt(generator, forced=True)
+    
+    if generator.cancelled:
+        handle_interrupt(generator)
+    
+    if show_status or verbose:
+        print_status(generator, histogram, generator.progress_stats())
+    
+    if output or verbose:
+        print_rejection_summary(generator)
+    print_skipped_lines(generator)
+    
+    record_usage(generator, preset)
+
+
+def write_with_progress(generator: Generator, writer):
+    """Write a generator's output lines to a backend under a progress bar"""
+    # Output lines (user:pass pairs with combo_users) still to write, after those of a resumed run
+    total = max(generator.estimate_count() - generator.tokens_written, 0)
+    with writer:
+        for _ in track(generator.write_lines(writer),
+                       description="Generating...",
+                       total=total):
+            pass
+
+
+def record_usage(generator: Generator, preset: Optional[str] = None):
+    """Add a finished run to the local usage ledger when `omni stats --enable` is on"""
+    try:
+        record_run(generator.tokens_written, generator.keyspace_size(), preset)
+    except Exception as e:
+        Console(stderr=True).print(f"[yellow]Usage ledger not updated: {escape(str(e))}[/yellow]")
+
+
+def report_limit(generator: Generator, written_bytes: int):
+    """Tell the user where a run cut short by max_bytes stopped"""
+    message = (f"Stopped at max_bytes after {generator.tokens_written:,} lines "
+               f"({written_bytes:,} bytes before compression)")
+    if generator.last_token is not None:
+        message += f"; last token: {generator.last_token}"
+        # --start seeks raw candidates, and only in charset and pattern modes
+        raw = generator.raw_candidate(generator.last_token) if generator.seeks_string_bounds else None
+        if raw is not None:
+            message += f" (continue with --start {raw}, which repeats it)"
+    Console(stderr=True).print(f"[yellow]{escape(message)}[/yellow]")
+
+
+def announce_stop():
+    """Tell the user the first Ctrl-C is stopping the run"""
+    Console(stderr=True).print("\n[yellow]Stopping after the current token "
+                               "(press Ctrl-C again to quit immediately)...[/yellow]")
+
+
+def handle_interrupt(generator: Generator, forced: bool = False):
+    """
+    Report an interrupted run and exit
+    
+    A run stopped by the first Ctrl-C has flushed its output and, with a
+    checkpoint_dir, saved a paused checkpoint. A forced quit keeps the last
+    periodic checkpoint; resuming cuts the output back to it.
+    """
+    summary = f"Interrupted after writing {generator.tokens_written:,} lines"
+    if generator.last_token is not None:
+        summary += f"; last token: {generator.last_token}"
+    console.print(f"\n[yellow]{escape(summary)}[/yellow]")
+    if generator.checkpoint_manager is not None and not forced:
+        fail(GenerationInterrupted(f"Paused; resume with `omni resume {generator.job_id} "
+                                   f"--checkpoint-dir {generator.checkpoint_manager.checkpoint_dir}`",
+                                   generator.job_id),
+             "Interrupted")
+    if generator.checkpoint_manager is not None:
+        console.print(f"[yellow]Force quit; the last periodic checkpoint of job {generator.job_id} is kept[/yellow]")
     else:
-        # Write to stdout
-        for token in generator.generate():
-            print(token)
+        console.print("[yellow]Interrupted by user[/yellow]")
+    sys.exit(130)
+
+
+def print_status(generator: Generator, histogram: Optional[LengthHistogram], stats: GeneratorStats):
+    """Print pipeline counters and, with a histogram, emitted tokens per length and how far each length band has got"""
+    err_console = Console(stderr=True)
+    keyspace = generator.keyspace_size()
+    seen = stats.generated
+    err_console.print(f"[cyan]Status: {stats.emitted:,} tokens, {seen:,}/{keyspace:,} candidates "
+                      f"({seen / keyspace:.1%}), {stats.rate:,.0f} tokens/s[/cyan]" if keyspace else
+                      f"[cyan]Status: {stats.emitted:,} tokens, {stats.rate:,.0f} tokens/s[/cyan]")
+    err_console.print(f"  generated {stats.generated:,} -> filters {stats.after_filters:,} -> "
+                      f"dedupe {stats.after_dedupe:,} -> written {stats.written:,}", markup=False)
+    config = generator.config
+    if config.start_string or config.end_string or config.skip or config.take:
+        # Absolute indices, so partitions of one job can be checked for overlaps
+        first, last = generator.index_range()
+        err_console.print(f"  range: candidates {first:,} to {last - 1:,} of {keyspace:,}" if last > first else
+                          f"  range: empty (starts at candidate {first:,} of {keyspace:,})", markup=False)
+    if generator.deduper is not None:
+        err_console.print(f"  {describe_dedupe_memory(generator, stats)}", markup=False)
+    if histogram is None:
+        return
+    
+    coverage = length_coverage(generator) or {}
+    lengths = sorted(set(histogram.counts) | set(coverage))
+    for length in lengths:
+        line = f"  len {length:>3}: {histogram.counts.get(length, 0):>12,}"
+        if length in coverage:
+            line += f"  [{bar(coverage[length])}] {coverage[length]:6.1%}"
+        err_console.print(line, markup=False)
+
+
+def describe_dedupe_memory(generator: Generator, stats: GeneratorStats) -> str:
+    """Memory of the dedupe set or filter, with the exact set's projected size for the estimated output"""
+    deduper = generator.deduper
+    if deduper.mode == "bloom":
+        return (f"dedupe bloom: {stats.dedupe_memory / 1e6:,.1f} MB for {deduper.capacity:,} tokens "
+                f"at {deduper.fp_rate:.2%} false positives ({len(deduper):,} held)")
+    projected = estimated_memory("exact", generator.estimate_count(), generator.config.bloom_fp_rate)
+    return (f"dedupe exact: {len(deduper):,} hashes, {stats.dedupe_memory / 1e6:,.1f} MB "
+            f"(~{projected / 1e6:,.1f} MB for the estimated output)")
+
+
+def print_rejection_summary(generator: Generator):
+    """Print which pipeline stages rejected candidates"""
+    report = generator.rejection_report()
+    if not report:
+        return
+    
+    # Keep stdout clean for piped token output
+    err_console = Console(stderr=True)
+    for entry in report:
+        err_console.print(
+            f"[yellow]{entry['stage']} rejected {entry['rate']:.1%} of candidates "
+            f"({entry['rejected']:,})[/yellow]"
+        )
+    if generator.config.rejects_output:
+        err_console.print(f"[cyan]Rejected sample: {generator.config.rejects_output}[/cyan]")
+
+
+def print_skipped_lines(generator: Generator):
+    """Print how many input word list lines mutation mode skipped"""
+    skipped = generator.skipped_lines
+    if not any(skipped.values()):
+        return
+    Console(stderr=True).print(
+        f"[yellow]Skipped {skipped.get('blank', 0):,} blank and "
+        f"{skipped.get('invalid_utf8', 0):,} invalid UTF-8 input lines[/yellow]"
+    )
 
 
 @cli.command()
 @click.option('--preset', help='Preview a preset')
+@click.option('--config', 'config_path', type=click.Path(exists=True), help='Preview a config JSON file')
 @click.option('--sample-size', type=int, default=10, help='Number of samples')
 @click.option('--min', 'min_length', type=int, help='Minimum length')
 @click.option('--max', 'max_length', type=int, help='Maximum length')
 @click.option('--charset', help='Character set')
+@click.option('--watch', is_flag=True, help='Keep a live preview that refreshes when --config changes')
+@click.option('--plain', is_flag=True, help='Print tokens without class colors or rejected candidates')
 @click.pass_context
-def preview(ctx, preset, sample_size, min_length, max_length, charset):
+def preview(ctx, preset, config_path, sample_size, min_length, max_length, charset, watch, plain):
     """Preview wordlist generation"""
     
     verbose = ctx.obj.get('verbose', False)
     
-    # Load preset if specified
+    def load_config() -> Config:
+        # Load preset or config file if specified
+        config = load_job_config(config_path, preset)
+        
+        # Override with command-line options
+        if min_length is not None:
+            config.min_length = min_length
+        if max_length is not None:
+            config.max_length = max_length
+        if charset:
+            config.charset = charset
+        
+        config.verbose = verbose
+        config.sample_size = sample_size
+        config.max_lines = sample_size
+        return config
+    
+    if watch:
+        if not config_path:
+            fail(ConfigError("--watch needs --config"))
+        watch_preview(Path(config_path), load_config, sample_size)
+        return
+    
     if preset:
-        preset_mgr = PresetManager()
-        config = preset_mgr.get_preset_config(preset)
         console.print(f"[green]Previewing preset: {preset}[/green]\n")
+    
+    try:
+        config = load_config()
+        if plain:
+            samples = Generator(config).preview(sample_size)
+            console.print(f"[cyan]Sample output ({len(samples)} tokens):[/cyan]\n")
+            for i, token in enumerate(samples, 1):
+                console.print(f"  {i:3d}. {token}")
+            return
+        
+        # max_lines would stop the trace before rejected candidates show up
+        config.max_lines = None
+        result = sample_tokens(config, limit=sample_size, time_budget=5.0)
+    except Exception as e:
+        fail(e)
+    
+    if result.error:
+        fail(result.exception or GeneratorError(result.error))
+    
+    rejected = len(result.entries) - len(result.tokens)
+    console.print(f"[cyan]Sample output ({len(result.tokens)} tokens, {rejected} rejected):[/cyan]\n")
+    i = 0
+    for token, reason in result.entries:
+        if reason is None:
+            i += 1
+            console.print(Text(f"  {i:3d}. ").append_text(annotate_token(token)))
+        else:
+            console.print(Text("       ").append_text(annotate_token(token, reason)))
+
+
+def render_preview(result: Optional[PreviewResult], source: Path) -> Table:
+    """Render the latest preview sample as a table"""
+    table = Table(title=f"Live preview: {source}")
+    table.add_column("#", justify="right", style="dim")
+    table.add_column("Token", style="green")
+    
+    if result is None:
+        table.caption = "sampling..."
+    elif result.error:
+        table.caption = f"[red]{result.error}[/red]"
     else:
-        config = Config()
+        for i, (token, reason) in enumerate(result.entries, 1):
+            table.add_row(str(i), annotate_token(token, reason))
+        note = " (time budget hit)" if result.truncated else ""
+        table.caption = f"revision {result.revision}, {len(result.tokens)} tokens in {result.elapsed * 1000:.0f} ms{note}"
+    return table
+
+
+def watch_preview(config_path: Path, load_config, limit: int):
+    """Re-sample the preview whenever the config file changes, until Ctrl-C"""
+    sampler = PreviewSampler(limit=limit)
+    last_mtime = None
     
-    # Override with command-line options
-    if min_length is not None:
-        config.min_length = min_length
-    if max_length is not None:
-        config.max_length = max_length
-    if charset:
-        config.charset = charset
+    with Live(render_preview(None, config_path), console=console, auto_refresh=False) as live:
+        sampler.on_result = lambda result: live.update(render_preview(result, config_path), refresh=True)
+        try:
+            while True:
+                mtime = config_path.stat().st_mtime
+                if mtime != last_mtime:
+                    last_mtime = mtime
+                    try:
+                        sampler.update(load_config())
+                    except Exception as e:
+                        live.update(render_preview(PreviewResult(0, [], error=str(e)), config_path),
+                                    refresh=True)
+                time.sleep(0.2)
+        except KeyboardInterrupt:
+            pass
+        finally:
+            sampler.close()
+
+
+@cli.command()
+@click.option('--config', 'config_path', type=click.Path(exists=True), help='Config JSON file')
+@click.option('--preset', help='Estimate a preset')
+@click.option('--hash-rate', help='Measured hash rate per device (e.g. 50GH/s)')
+@click.option('--hash-mode', help='Hash mode for reference speeds (ntlm, md5, bcrypt, ...)')
+@click.option('--devices', type=int, default=1, help='Number of cracking devices')
+@click.option('--hash', 'hash_algorithm', type=click.Choice(list(HASH_ALGORITHMS)), help='Also estimate the size of lookup table output')
+def estimate(config_path, preset, hash_rate, hash_mode, devices, hash_algorithm):
+    """Estimate keyspace size and time to exhaust it"""
     
-    config.verbose = verbose
-    config.sample_size = sample_size
-    config.max_lines = sample_size
+    try:
+        config = load_job_config(config_path, preset)
+        if hash_algorithm:
+            config.hash_algorithm = hash_algorithm
+        
+        keyspace = Generator(config).estimate_count()
+        
+        if hash_rate:
+            rates = [(hash_mode or "custom", parse_hash_rate(hash_rate))]
+        elif hash_mode:
+            rates = [(hash_mode, rate_for_mode(hash_mode))]
+        else:
+            rates = sorted(HASH_MODE_RATES.items(), key=lambda item: -item[1])
+    except Exception as e:
+        fail(e)
+    
+    console.print(f"[cyan]Keyspace: {keyspace:,} candidates[/cyan]\n")
+    
+    if config.hash_algorithm:
+        try:
+            sample = Generator(config).generate_list(limit=1000)
+        except Exception as e:
+            fail(e)
+        average = sum(len(token.encode("utf-8")) for token in sample) / max(1, len(sample))
+        size = hashed_output_bytes(keyspace, average, config.hash_algorithm)
+        console.print(f"[yellow]Warning: {config.hash_algorithm} lookup table output takes about "
+                      f"{size / 1e9:,.2f} GB uncompressed ({average + 2:.0f} bytes of candidate and "
+                      f"separator plus the digest per line)[/yellow]\n")
+    
+    table = Table(title=f"Time to exhaust ({devices} device{'s' if devices != 1 else ''})")
+    table.add_column("Hash mode", style="green")
+    table.add_column("Rate (H/s)", justify="right")
+    table.add_column("Duration", style="cyan")
+    for mode, rate in rates:
+        seconds = attack_seconds(keyspace, rate, devices)
+        table.add_row(mode, f"{rate:,.0f}", format_duration(seconds))
+    console.print(table)
+
+
+@cli.command()
+@click.option('--config', 'config_path', type=click.Path(exists=True), help='Config JSON file')
+@click.option('--preset', help='Tune a preset')
+@click.option('--target-size', required=True, help='Desired number of output tokens (e.g. 500M)')
+@click.option('--metric', type=click.Choice(sorted(TUNABLE_METRICS)), default='quality', help='Metric to threshold on')
+@click.option('--sample', 'sample_size', type=int, default=10000, help='Number of candidates to sample')
+@click.option('--save', type=click.Path(), help='Write the tuned config to this JSON file')
+def tune(config_path, preset, target_size, metric, sample_size, save):
+    """Sample the keyspace and suggest filter thresholds for a target size"""
     
     try:
-        generator = Generator(config)
-        samples = generator.preview(sample_size)
+        config = load_job_config(config_path, preset)
         
-        console.print(f"[cyan]Sample output ({len(samples)} tokens):[/cyan]\n")
-        for i, token in enumerate(samples, 1):
-            console.print(f"  {i:3d}. {token}")
+        report = tune_filters(config, parse_count(target_size), metric, sample_size)
+    except Exception as e:
+        fail(e)
+    
+    console.print(f"[cyan]Keyspace: {report.keyspace:,} candidates[/cyan]")
+    console.print(f"[cyan]Current filters pass {report.pass_rate:.1%} of {report.sample_size:,} samples[/cyan]\n")
+    
+    table = Table(title=f"{metric} distribution of passing candidates")
+    table.add_column("Percentile", justify="right")
+    table.add_column("Value", justify="right", style="green")
+    for percentile, value in report.percentiles.items():
+        table.add_row(f"p{percentile}", f"{value:.3f}")
+    console.print(table)
+    
+    field_name = METRIC_FILTER_FIELDS[metric]
+    console.print(f"\n[green]Suggested filters.{field_name}: {report.threshold:.3f}[/green]")
+    console.print(f"[green]Expected output: ~{report.expected_output:,} tokens "
+                  f"(target {report.target_size:,})[/green]")
+    
+    if save:
+        apply_tuning(config, report).to_json(Path(save))
+        console.print(f"[cyan]Tuned config: {save}[/cyan]")
+
+
+@cli.command()
+@click.option('--config', 'config_path', type=click.Path(exists=True), help='Config JSON file to start from')
+@click.option('--preset', help='Preset to start from')
+@click.option('--reference', required=True, type=WordSource(dir_okay=False), help='Held-out reference list, e.g. leaked passwords')
+@click.option('--budget', default='1M', help='Candidates each variation may try (e.g. 100k)')
+@click.option('--dimension', 'dimensions', multiple=True, type=click.Choice([name for name, _ in TUNING_DIMENSIONS]),
+              help='Only tune these dimensions (repeatable)')
+@click.option('--save', type=click.Path(), help='Write the best config to this JSON file')
+@click.option('--save-preset', help='Save the best config as a custom preset')
+@click.option('--force', is_flag=True, help='Overwrite an existing --save-preset without asking')
+def autotune(config_path, preset, reference, budget, dimensions, save, save_preset, force):
+    """Try variations of a config against a reference list and keep the best"""
+    
+    try:
+        config = load_job_config(config_path, preset)
         
+        report = autotune_config(config, load_reference(Path(reference)), parse_count(budget),
+                                 dimensions=list(dimensions) or None)
     except Exception as e:
-        console.print(f"[red]Error: {e}[/red]")
-        sys.exit(1)
+        fail(e)
+    
+    table = Table(title=f"Variations ({report.budget:,} candidates each)")
+    table.add_column("Dimension", style="cyan")
+    table.add_column("Variation")
+    table.add_column("Cracked", justify="right", style="green")
+    table.add_column("Area", justify="right")
+    for trial in sorted(report.trials, key=lambda t: t.score, reverse=True):
+        marker = " [bold]*[/bold]" if trial is report.best else ""
+        table.add_row(trial.dimension, trial.description + marker,
+                      f"{trial.result.fraction:.2%}", f"{trial.score[1]:.4f}")
+    console.print(table)
+    
+    best = report.best.config
+    console.print(f"\n[green]Best: {report.best.result.fraction:.2%} of the reference "
+                  f"(transforms={','.join(best.transforms) or '(none)'}, field_order={best.field_order})[/green]")
+    
+    try:
+        if save:
+            best.to_json(Path(save))
+            console.print(f"[cyan]Best config: {save}[/cyan]")
+        if save_preset:
+            preset_mgr = PresetManager()
+            description = f"Auto-tuned against {Path(reference).name}"
+            if confirm_preset_save(preset_mgr, save_preset, description, best, force):
+                version = preset_mgr.save_preset(
+                    save_preset, description, best,
+                    f"autotune: {report.best.result.fraction:.2%} within {report.budget:,} candidates")
+                console.print(f"[cyan]Saved preset {save_preset} ({version})[/cyan]")
+    except click.Abort:
+        raise
+    except Exception as e:
+        fail(e)
+
+
+@cli.command()
+@click.option('--config', 'config_path', type=click.Path(exists=True), required=True, help='Config JSON file')
+@click.option('--output', '-o', type=click.Path(), required=True, help='Bundle file to write (e.g. job.omni)')
+def bundle(config_path, output):
+    """Package a config and the files it references into one archive"""
+    
+    try:
+        config = Config.from_json(Path(config_path))
+        files = create_bundle(config, Path(output), base_dir=Path(config_path).resolve().parent)
+    except Exception as e:
+        fail(e)
+    
+    for original, name in files.items():
+        console.print(f"  [dim]{name}[/dim] <- {original}")
+    console.print(f"[green]✓ Bundle written: {output} ({len(files)} files embedded)[/green]")
 
 
 @cli.command('list-presets')
@@ -201,18 +1007,739 @@ def show_preset(preset_name):
         info = preset_mgr.show_preset(preset_name)
         console.print(info)
     except Exception as e:
-        console.print(f"[red]Error: {e}[/red]")
-        sys.exit(1)
+        fail(e)
+
+
+@cli.group()
+def preset():
+    """Manage custom presets and their version history"""
+    pass
+
+
+def confirm_preset_save(preset_mgr: PresetManager, name: str, description: str,
+                        config: Config, force: bool) -> bool:
+    """
+    Show what overwriting a preset changes and ask before doing it
+    
+    Returns:
+        False when an existing preset is identical (nothing to save)
+    """
+    changes = preset_mgr.preset_changes(name, description, config)
+    if changes is None:
+        return True
+    if not changes:
+        console.print(f"[yellow]Preset {name} is unchanged, not saving[/yellow]")
+        return False
+    
+    table = Table(title=f"Changes to preset {name}")
+    table.add_column("Setting", style="cyan")
+    table.add_column("Current", style="red")
+    table.add_column("New", style="green")
+    for key, before, after in changes:
+        table.add_row(key, json.dumps(before), json.dumps(after))
+    console.print(table)
+    if not force:
+        click.confirm(f"Overwrite preset {name}?", abort=True)
+    return True
+
+
+@preset.command('save')
+@click.argument('preset_name')
+@click.option('--config', 'config_path', type=click.Path(exists=True), required=True, help='Config JSON file to save')
+@click.option('--description', default='', help='Preset description')
+@click.option('--message', '-m', help='Changelog message')
+@click.option('--force', is_flag=True, help='Overwrite an existing preset without asking')
+def preset_save(preset_name, config_path, description, message, force):
+    """Save a config as a custom preset, showing the diff before overwriting"""
+    preset_mgr = PresetManager()
+    
+    try:
+        config = Config.from_json(Path(config_path))
+        if not confirm_preset_save(preset_mgr, preset_name, description, config, force):
+            return
+        version = preset_mgr.save_preset(preset_name, description, config, message)
+    except click.Abort:
+        raise
+    except Exception as e:
+        fail(e)
+    
+    console.print(f"[green]✓ Saved preset {preset_name} ({version})[/green]")
+
+
+@preset.command('history')
+@click.argument('preset_name')
+def preset_history(preset_name):
+    """Show the version history of a preset"""
+    preset_mgr = PresetManager()
+    
+    try:
+        history = preset_mgr.preset_history(preset_name)
+    except Exception as e:
+        fail(e)
+    
+    table = Table(title=f"History of {preset_name}")
+    table.add_column("Version", style="green")
+    table.add_column("Saved at", style="cyan")
+    table.add_column("Message")
+    for entry in history:
+        table.add_row(entry['version'], entry['saved_at'], entry['message'])
+    console.print(table)
+
+
+@preset.command('rollback')
+@click.argument('preset_name')
+@click.argument('version')
+def preset_rollback(preset_name, version):
+    """Restore a previous version of a preset"""
+    preset_mgr = PresetManager()
+    
+    try:
+        new_version = preset_mgr.rollback_preset(preset_name, version)
+    except Exception as e:
+        fail(e)
+    
+    console.print(f"[green]✓ Restored {preset_name}@{version} as version {new_version}[/green]")
 
 
 @cli.command()
+@click.option('--from', 'start', help='First day of the range (YYYY-MM-DD, default: a year ago)')
+@click.option('--to', 'end', help='Last day of the range (YYYY-MM-DD, default: today)')
+@click.option('--locale', default='en', help='Comma-separated locales (en, de, fr, es, hi)')
+@click.option('--kinds', default=','.join(TEMPORAL_KINDS), help='Comma-separated kinds (seasons, months, holidays, quarters)')
+@click.option('--output', '-o', type=click.Path(), help='Output file')
+def temporal(start, end, locale, kinds, output):
+    """Generate season, month, holiday and quarter candidates for a date range"""
+    
+    try:
+        range_start, range_end = default_range()
+        if start:
+            range_start = parse_date(start)
+        if end:
+            range_end = parse_date(end)
+        
+        candidates = generate_temporal(
+            range_start, range_end,
+            locales=[l.strip() for l in locale.split(',') if l.strip()],
+            kinds=[k.strip() for k in kinds.split(',') if k.strip()],
+        )
+        
+        if output:
+            count = write_tokens_to_file(candidates, Path(output))
+            console.print(f"[green]✓ Generated {count:,} temporal candidates[/green]")
+            console.print(f"[cyan]Output: {output}[/cyan]")
+        else:
+            for candidate in candidates:
+                print(candidate)
+    except Exception as e:
+        fail(e)
+
+
+@cli.command('ids')
+@click.argument('kind', type=click.Choice(sorted(STRUCTURED_ID_TEMPLATES)))
+@click.option('--state', 'states', multiple=True, help='State code for plate-in (repeatable)')
+@click.option('--prefix', help='Prefix for employee IDs')
+@click.option('--digits', type=int, help='Number of digits')
+@click.option('--letters', type=int, help='Number of letters for initials IDs')
+@click.option('--separator', help='Separator between ID parts')
+@click.option('--output', '-o', type=click.Path(), help='Output file')
+@click.option('--sample-size', '-s', type=int, help='Limit output to N tokens')
+def ids(kind, states, prefix, digits, letters, separator, output, sample_size):
+    """Generate structured IDs (vehicle plates, employee IDs)"""
+    
+    params = {}
+    if states:
+        params['states'] = list(states)
+    if prefix is not None:
+        params['prefix'] = prefix
+    if digits is not None:
+        params['digits'] = digits
+    if letters is not None:
+        params['letters'] = letters
+    if separator is not None:
+        params['separator'] = separator
+    
+    try:
+        patterns = build_patterns(kind, **params)
+        
+        def tokens():
+            emitted = 0
+            for pattern in patterns:
+                config = Config(min_length=len(pattern), max_length=len(pattern), pattern=pattern)
+                for token in Generator(config).generate():
+                    if sample_size and emitted >= sample_size:
+                        return
+                    emitted += 1
+                    yield token
+        
+        if output:
+            count = write_tokens_to_file(tokens(), Path(output))
+            console.print(f"[green]✓ Generated {count:,} IDs from {', '.join(patterns)}[/green]")
+            console.print(f"[cyan]Output: {output}[/cyan]")
+        else:
+            for token in tokens():
+                print(token)
+    except Exception as e:
+        fail(e)
+
+
+@cli.command()
+@click.option('--known', required=True, help='Known password whose structure is replicated')
+@click.option('--word', 'words', multiple=True, help='Base word to use (repeatable)')
+@click.option('--wordlist', type=WordSource(dir_okay=False), help='File of base words, one per line')
+@click.option('--years', help='Year range for year segments (e.g. 2015-2026)')
+@click.option('--symbols', default=COMMON_SYMBOLS, help='Symbols for symbol segments')
+@click.option('--output', '-o', type=click.Path(), help='Output file')
+@click.option('--sample-size', '-s', type=int, help='Limit output to N tokens')
+def replicate(known, words, wordlist, years, symbols, output, sample_size):
+    """Generate candidates with the same structure as a known password"""
+    
+    try:
+        base_words = list(words)
+        if wordlist:
+            base_words.extend(read_word_file(wordlist))
+        
+        config = replicate_config(known, base_words or None,
+                                  parse_year_range(years) if years else None, symbols)
+        if sample_size:
+            config.max_lines = sample_size
+        generator = Generator(config)
+        
+        err_console = Console(stderr=True)
+        err_console.print(f"[cyan]Structure: {describe_structure(infer_structure(known))}[/cyan]")
+        err_console.print(f"[cyan]Candidates: {generator.keyspace_size():,}[/cyan]")
+        
+        if output:
+            count = write_tokens_to_file(generator.generate(), Path(output))
+            console.print(f"[green]✓ Generated {count:,} candidates[/green]")
+            console.print(f"[cyan]Output: {output}[/cyan]")
+        else:
+            for token in generator.generate():
+                print(token)
+    except Exception as e:
+        fail(e)
+
+
+@cli.command()
+@click.option('--vendor', help='Comma-separated vendors (cisco,dlink,...); all if omitted')
+@click.option('--category', type=click.Choice(list_categories()), help='Only vendors of a device category')
+@click.option('--format', 'output_format', type=click.Choice(['combo', 'split', 'passwords', 'users']),
+              default='combo', help='combo: user:pass lines; split: separate user and password files')
+@click.option('--combo-separator', default=':', help='Separator between username and password')
+@click.option('--output', '-o', type=click.Path(), help='Output file (base name for --format split)')
+@click.option('--compress', type=click.Choice(['gzip', 'bzip2', 'lz4', 'zstd']),
+              help='Compression format (default: from the output suffix)')
+@click.option('--list', 'list_vendors_only', is_flag=True, help='List vendors and categories')
+def defaults(vendor, category, output_format, combo_separator, output, compress, list_vendors_only):
+    """Emit default device credentials"""
+    
+    if list_vendors_only:
+        table = Table(title="Default Credential Vendors")
+        table.add_column("Vendor", style="cyan")
+        table.add_column("Category", style="green")
+        table.add_column("Pairs", justify="right")
+        for name in list_vendors():
+            vendor_category, credentials = DEFAULT_CREDENTIALS[name]
+            table.add_row(name, vendor_category, str(len(credentials)))
+        console.print(table)
+        return
+    
+    try:
+        vendors = [v for v in vendor.split(',') if v.strip()] if vendor else None
+        pairs = get_default_credentials(vendors, category)
+        if compress and not output:
+            raise click.UsageError("--compress needs --output")
+        
+        if output_format == 'split':
+            if not output:
+                raise click.UsageError("--format split needs --output")
+            base = Path(output)
+            users_path = base.with_name(base.name + '.users.txt')
+            passwords_path = base.with_name(base.name + '.passwords.txt')
+            users, passwords = write_split_credentials(pairs, users_path, passwords_path, compress)
+            console.print(f"[green]✓ {users} usernames -> {users_path}[/green]")
+            console.print(f"[green]✓ {passwords} passwords -> {passwords_path}[/green]")
+            return
+        
+        if output_format == 'combo':
+            lines = [f"{user}{combo_separator}{password}" for user, password in pairs]
+        elif output_format == 'users':
+            lines = list(dict.fromkeys(user for user, _ in pairs))
+        else:
+            lines = list(dict.fromkeys(password for _, password in pairs))
+        
+        if output:
+            output_path = Path(output)
+            compression = compress or detect_compression(output_path)
+            if output_format == 'combo':
+                count = write_combos(pairs, output_path, combo_separator, compression)
+            else:
+                count = write_tokens_to_file(iter(lines), output_path, compression)
+            console.print(f"[green]✓ Wrote {count:,} entries to {output}[/green]")
+        else:
+            for line in lines:
+                print(line)
+    except Exception as e:
+        fail(e)
+
+
+@cli.command()
+@click.option('--config', 'config_path', type=click.Path(exists=True), help='Config JSON file')
+@click.option('--preset', help='Candidate preset')
+@click.option('--users', help='Username file/URL; batches are written as user:pass combos')
+@click.option('--lockout-threshold', type=int, default=5, help='Failed attempts that lock an account (0 = none)')
+@click.option('--window', 'window_hours', type=float, default=24.0, help='Lockout observation window in hours')
+@click.option('--safety-margin', type=int, default=2, help='Attempts kept in reserve below the threshold')
+@click.option('--per-window', type=int, help='Attempts per user per window (overrides the threshold)')
+@click.option('--start', help='When the first batch may run (ISO date/time, default: now)')
+@click.option('--batches', type=int, help='Number of batches to schedule')
+@click.option('--combo-separator', default=':', help='Separator between username and password')
+@click.option('--output-dir', type=click.Path(), help='Write one file per batch into this directory')
+def spray(config_path, preset, users, lockout_threshold, window_hours, safety_margin, per_window,
+          start, batches, combo_separator, output_dir):
+    """Schedule password-spray batches that stay under a lockout policy"""
+    
+    try:
+        config = load_job_config(config_path, preset)
+        
+        policy = SprayPolicy(lockout_threshold, window_hours, safety_margin, per_window)
+        start_time = datetime.fromisoformat(start) if start else datetime.now().replace(microsecond=0)
+        usernames = read_word_file(users) if users else []
+        generator = Generator(config)
+        
+        scheduled = 0
+        for batch in schedule_spray(generator.generate(), policy, start_time, batches):
+            if usernames:
+                lines = [f"{user}{combo_separator}{password}"
+                         for password in batch.passwords for user in usernames]
+            else:
+                lines = batch.passwords
+            
+            if output_dir:
+                name = f"batch-{batch.index + 1:04d}_{batch.not_before.strftime('%Y%m%dT%H%M')}.txt"
+                write_tokens_to_file(iter(lines), Path(output_dir) / name)
+            else:
+                print(f"# batch {batch.index + 1} not before {batch.not_before.isoformat(sep=' ')}")
+                for line in lines:
+                    print(line)
+            scheduled += 1
+    except Exception as e:
+        fail(e)
+    
+    Console(stderr=True).print(
+        f"[green]✓ Scheduled {scheduled:,} batches of {policy.attempts()} attempts per user "
+        f"every {window_hours:g}h[/green]"
+    )
+
+
+@cli.group()
+def chunk():
+    """Split the keyspace into numbered chunks for distributed work"""
+    pass
+
+
+@chunk.command('info')
+@click.option('--config', 'config_path', type=click.Path(exists=True), help='Config JSON file')
+@click.option('--preset', help='Preset to chunk')
+@click.option('--size', 'chunk_size', required=True, help='Candidates per chunk (e.g. 10M)')
+@click.option('--manifest', 'manifest_path', type=click.Path(dir_okay=False), help='Write a manifest with the alphabet ordering for `chunk generate --manifest`')
+def chunk_info(config_path, preset, chunk_size, manifest_path):
+    """Show how many chunks cover the keyspace"""
+    
+    try:
+        config = load_job_config(config_path, preset)
+        
+        generator = Generator(config)
+        keyspace = generator.rank_count()
+        size = parse_count(chunk_size)
+        console.print(f"[cyan]Keyspace: {keyspace:,} candidates[/cyan]")
+        console.print(f"[cyan]Chunks: {chunk_count(keyspace, size):,} of {size:,} (ids 0-{chunk_count(keyspace, size) - 1})[/cyan]")
+        if generator.keyspace() is None:
+            console.print("[yellow]This mode is not rank-addressable; chunks enumerate up to their start[/yellow]")
+        if manifest_path:
+            Path(manifest_path).write_text(json.dumps(chunk_manifest(config, size), indent=2) + "\n")
+            console.print(f"[cyan]Saved chunk manifest to {manifest_path}[/cyan]")
+    except Exception as e:
+        fail(e)
+
+
+@chunk.command('generate')
+@click.option('--config', 'config_path', type=click.Path(exists=True), help='Config JSON file')
+@click.option('--preset', help='Preset to chunk')
+@click.option('--manifest', 'manifest_path', type=click.Path(exists=True, dir_okay=False), help='Manifest from `chunk info --manifest` (instead of --config / --preset and --size)')
+@click.option('--id', 'chunk_id', type=int, required=True, help='Zero-based chunk number')
+@click.option('--size', 'chunk_size', help='Candidates per chunk (e.g. 10M)')
+@click.option('--output', '-o', type=click.Path(), help='Output file')
+def chunk_generate(config_path, preset, manifest_path, chunk_id, chunk_size, output):
+    """Generate one chunk of the keyspace
+    
+    With --manifest, the chunk is refused if this machine resolves a
+    different alphabet ordering or keyspace than the manifest records.
+    """
+    
+    try:
+        if manifest_path:
+            generator, size = load_chunk_manifest(Path(manifest_path))
+        else:
+            if not chunk_size:
+                raise ConfigError("Give --size or a --manifest")
+            config = load_job_config(config_path, preset)
+            generator, size = Generator(config), parse_count(chunk_size)
+        
+        bounds = chunk_bounds(chunk_id, size, generator.rank_count())
+        tokens = generator.generate_range(bounds.start, bounds.end)
+        
+        if output:
+            count = write_tokens_to_file(tokens, Path(output))
+            console.print(f"[green]✓ Chunk {chunk_id} (ranks {bounds.start:,}-{bounds.end - 1:,}): "
+                          f"{count:,} tokens -> {output}[/green]")
+        else:
+            for token in tokens:
+                print(token)
+    except Exception as e:
+        fail(e)
+
+
+@cli.command()
+@click.option('--config', 'config_path', type=click.Path(exists=True), help='Config JSON file')
+@click.option('--preset', help='Preset whose keyspace to search')
+@click.option('--charset', help='Character set')
+@click.option('--min', 'min_length', type=int, help='Minimum length')
+@click.option('--max', 'max_length', type=int, help='Maximum length')
+@click.option('--index', help='Index to turn into its candidate (e.g. 1500000 or 1.5M)')
+@click.option('--token', help='Raw candidate to find the index of')
+def locate(config_path, preset, charset, min_length, max_length, index, token):
+    """Map between a keyspace index and its candidate, without generating
+    
+    Indices are the length-major ranks that --skip / --take and chunks
+    count; candidates are raw (before prefix, suffix and transforms).
+    """
+    if (index is None) == (token is None):
+        fail(ConfigError("Give exactly one of --index and --token"))
+    try:
+        config = load_job_config(config_path, preset)
+        if charset:
+            config.charset = charset
+        if min_length is not None:
+            config.min_length = min_length
+        if max_length is not None:
+            config.max_length = max_length
+        
+        generator = Generator(config)
+        if token is None:
+            index = parse_count(index)
+            token = generator.token_at(index)
+        else:
+            index = generator.index_of(token)
+        size = generator.rank_count()
+    except Exception as e:
+        fail(e)
+    
+    console.print(f"[cyan]Index {index:,} = {token}[/cyan]")
+    console.print(f"[cyan]Length {len(token)}, {(index + 1) / size:.4%} into a keyspace of {size:,} candidates[/cyan]")
+    if generator.length_shares is not None:
+        console.print("[yellow]length_distribution interleaves lengths; the run emits candidates in another order[/yellow]")
+
+
+@cli.command()
+@click.option('--config', 'config_path', type=click.Path(exists=True), help='Config JSON file')
+@click.option('--preset', help='Preset to distribute')
+@click.option('--size', 'chunk_size', required=True, help='Candidates per chunk (e.g. 10M)')
+@click.option('--host', default='127.0.0.1', help='Bind address (0.0.0.0 to serve other machines)')
+@click.option('--port', type=int, default=8765, help='Bind port')
+@click.option('--timeout', type=float, default=600.0, help='Seconds before an unfinished chunk is re-issued')
+@click.option('--token', required=True, envvar=TOKEN_ENV, help=f'Shared token workers must present (or set {TOKEN_ENV})')
+def coordinate(config_path, preset, chunk_size, host, port, timeout, token):
+    """Serve keyspace chunks to `omni worker` instances over HTTP"""
+    
+    try:
+        config = load_job_config(config_path, preset)
+        
+        coordinator = Coordinator(config, Generator(config).rank_count(), parse_count(chunk_size), timeout)
+        server = make_server(coordinator, token, host, port)
+    except Exception as e:
+        fail(e)
+    
+    console.print(f"[cyan]Serving {coordinator.total_chunks:,} chunks on http://{host}:{server.server_address[1]}[/cyan]")
+    console.print("[dim]Workers: omni worker --url http://<this-host>:<port> --token TOKEN --output-dir DIR[/dim]")
+    
+    thread = threading.Thread(target=server.serve_forever, daemon=True)
+    thread.start()
+    try:
+        while not coordinator.done:
+            time.sleep(1)
+    except KeyboardInterrupt:
+        console.print("[yellow]Coordinator stopped before all chunks completed[/yellow]")
+    finally:
+        server.shutdown()
+    
+    status = coordinator.status()
+    table = Table(title="Workers")
+    table.add_column("Worker", style="cyan")
+    table.add_column("Chunks", justify="right")
+    table.add_column("Tokens", justify="right", style="green")
+    for name, stats in sorted(status['workers'].items()):
+        table.add_row(name, f"{stats['completed']:,}", f"{stats['tokens']:,}")
+    console.print(table)
+    console.print(f"[green]✓ {status['completed']:,}/{status['total_chunks']:,} chunks, "
+                  f"{status['tokens']:,} tokens ({status['reissued']} re-issued)[/green]")
+
+
+@cli.command()
+@click.option('--url', required=True, help='Coordinator URL (http://host:port)')
+@click.option('--output-dir', type=click.Path(), default='.', help='Directory for chunk files')
+@click.option('--name', help='Worker name (default: hostname)')
+@click.option('--max-chunks', type=int, help='Stop after this many chunks')
+@click.option('--token', required=True, envvar=TOKEN_ENV, help=f'Shared token of the coordinator (or set {TOKEN_ENV})')
+def worker(url, output_dir, name, max_chunks, token):
+    """Claim and generate chunks from an `omni coordinate` server"""
+    
+    try:
+        finished = run_worker(url, token, Path(output_dir), name, max_chunks)
+    except Exception as e:
+        fail(e)
+    
+    console.print(f"[green]✓ Completed {finished:,} chunks -> {output_dir}[/green]")
+
+
+@cli.group()
+def daemon():
+    """Run a coordinator or worker as a system service"""
+    pass
+
+
+@daemon.command('install', context_settings={'ignore_unknown_options': True})
+@click.argument('role', type=click.Choice(SERVICE_ROLES))
+@click.argument('args', nargs=-1, type=click.UNPROCESSED)
+@click.option('--name', help='Service name (default: omniwordlist-ROLE)')
+@click.option('--data-dir', type=click.Path(file_okay=False), help='Working and home directory of the service (default: /var/lib/omniwordlist, or ~/.local/share/omniwordlist with --user-service)')
+@click.option('--run-as', help='Account the service runs as (default: root / SYSTEM)')
+@click.option('--user-service', is_flag=True, help='Install a per-user systemd unit (systemctl --user)')
+@click.option('--memory-max', help='Memory limit, e.g. 8G (systemd only)')
+@click.option('--cpu-quota', type=int, help='CPU limit in percent of one core, e.g. 200 (systemd only)')
+@click.option('--nice', type=click.IntRange(-20, 19), help='Scheduling priority (systemd only)')
+@click.option('--platform', type=click.Choice(SERVICE_PLATFORMS), help='Service manager (default: this machine\'s)')
+@click.option('--print', 'print_only', is_flag=True, help='Print the unit / task command instead of installing')
+@click.option('--no-start', is_flag=True, help='Only start the service at the next boot')
+@click.option('--yes', '-y', is_flag=True, help='Do not ask for confirmation')
+def daemon_install(role, args, name, data_dir, run_as, user_service, memory_max, cpu_quota, nice, platform,
+                   print_only, no_start, yes):
+    """
+    Install `omni ROLE ARGS...` as a service that starts at boot
+    
+    Example: omni daemon install worker --memory-max 8G -- --url http://coordinator:8765
+    """
+    platform = platform or default_platform()
+    try:
+        spec = ServiceSpec(role, list(args), name, Path(data_dir) if data_dir else service_data_dir(user_service, platform),
+                           run_as, memory_max, cpu_quota, nice)
+    except Exception as e:
+        fail(e)
+    
+    if platform == "windows" and spec.limits():
+        console.print(f"[yellow]Resource limits are not supported by the task scheduler: "
+                      f"{', '.join(spec.limits())} ignored[/yellow]")
+    if print_only:
+        if platform == "systemd":
+            print(systemd_unit(spec, user_service), end="")
+        else:
+            print(f"{windows_script_path(spec)}:")
+            print(windows_script(spec).replace("\r\n", "\n"), end="")
+            print(subprocess.list2cmdline(windows_task_command(spec)))
+        return
+    
+    if not yes:
+        click.confirm(f"Install service {spec.name} running `{' '.join(spec.command)}`?", abort=True)
+    try:
+        unit_path = install_service(spec, platform, user_service, start=not no_start)
+    except Exception as e:
+        fail(e)
+    
+    state = "installed" if no_start else "installed and started"
+    console.print(f"[green]✓ Service {spec.name} {state} ({unit_path}); data in {spec.data_dir}[/green]")
+
+
+@cli.group()
+def toplist():
+    """Manage built-in common password lists"""
+    pass
+
+
+@toplist.command('fetch')
+@click.argument('size', type=click.Choice([str(size) for size in sorted(TOPLIST_NAMES)]))
+def toplist_fetch(size):
+    """Download a top-N common password list"""
+    try:
+        path = fetch_top_passwords(int(size))
+    except Exception as e:
+        fail(e)
+    
+    console.print(f"[green]✓ Saved top-{size} list to {path}[/green]")
+
+
+@cli.group()
+def campaign():
+    """Cross-run dedupe databases (run --campaign)"""
+    pass
+
+
+@campaign.command('list')
+@click.argument('name', required=False)
+@click.option('--db', 'db_path', type=click.Path(dir_okay=False), help='Campaign database (default: ~/.omniwordlist/campaigns.db)')
+def campaign_list(name, db_path):
+    """Show campaigns, or the runs of one campaign"""
+    path = Path(db_path) if db_path else default_campaign_db()
+    try:
+        if name:
+            table = Table(title=f"Campaign {name}")
+            table.add_column("Run", style="cyan")
+            table.add_column("Job")
+            table.add_column("Started")
+            table.add_column("New tokens", justify="right")
+            for run in campaign_runs(path, name):
+                table.add_row(run["run"], run["job"] or "",
+                              datetime.fromtimestamp(run["started"]).strftime("%Y-%m-%d %H:%M"),
+                              f"{run['tokens']:,}")
+        else:
+            table = Table(title="Campaigns")
+            table.add_column("Campaign", style="cyan")
+            table.add_column("Runs", justify="right")
+            table.add_column("Tokens", justify="right")
+            table.add_column("Last run")
+            for entry in list_campaigns(path):
+                table.add_row(entry["campaign"], str(entry["runs"]), f"{entry['tokens']:,}",
+                              datetime.fromtimestamp(entry["updated"]).strftime("%Y-%m-%d %H:%M"))
+    except Exception as e:
+        fail(e)
+    
+    console.print(table)
+
+
+@campaign.command('forget')
+@click.argument('name')
+@click.option('--db', 'db_path', type=click.Path(dir_okay=False), help='Campaign database (default: ~/.omniwordlist/campaigns.db)')
+@click.option('--yes', '-y', is_flag=True, help='Do not ask for confirmation')
+def campaign_forget(name, db_path, yes):
+    """Delete a campaign's recorded tokens"""
+    if not yes:
+        click.confirm(f"Forget every token recorded for campaign {name}?", abort=True)
+    try:
+        deleted = forget_campaign(Path(db_path) if db_path else default_campaign_db(), name)
+    except Exception as e:
+        fail(e)
+    
+    console.print(f"[green]✓ Forgot {deleted:,} tokens of campaign {name}[/green]")
+
+
+@cli.group()
+def seclists():
+    """Fetch lists from the SecLists repository"""
+    pass
+
+
+@seclists.command('list')
+@click.argument('category', required=False, type=click.Choice(list(SECLISTS_CATALOG)))
+def seclists_list(category):
+    """Show the known lists and whether they were fetched"""
+    try:
+        manifest = load_seclists_manifest()
+    except Exception as e:
+        fail(e)
+    
+    table = Table(title="SecLists")
+    table.add_column("Name", style="cyan")
+    table.add_column("Repository path")
+    table.add_column("Fetched")
+    for name, repo_path in list_catalog(category):
+        entry = manifest.get(repo_path)
+        table.add_row(name, repo_path, f"[green]{entry['sha256'][:12]}[/green]" if entry else "[dim]no[/dim]")
+    console.print(table)
+    console.print(f"[dim]Use as a source: {SECLISTS_PREFIX}<name>[/dim]")
+
+
+@seclists.command('fetch')
+@click.argument('names', nargs=-1, required=True)
+@click.option('--force', is_flag=True, help='Download again even if an intact copy exists')
+def seclists_fetch(names, force):
+    """Download lists (catalog names, whole categories or repository paths)"""
+    try:
+        targets = []
+        for name in names:
+            if name in SECLISTS_CATALOG:
+                targets.extend(entry for entry, _ in list_catalog(name))
+            else:
+                targets.append(name)
+        for name in targets:
+            path, checksum = fetch_list(name, force=force)
+            console.print(f"[green]✓ {name} -> {path}[/green] [dim]sha256 {checksum}[/dim]")
+    except Exception as e:
+        fail(e)
+    
+    console.print(f"[dim]Lists are stored in {seclists_data_dir()}[/dim]")
+
+
+@cli.group()
+def freq():
+    """Frequency models from breach corpora"""
+    pass
+
+
+@freq.command('import')
+@click.argument('corpus', type=WordSource(dir_okay=False))
+@click.argument('model', type=click.Path())
+@click.option('--min-count', type=int, default=1, help='Drop words seen fewer times')
+def freq_import(corpus, model, min_count):
+    """Import a word<TAB>count corpus into a memory-mapped model"""
+    try:
+        entries = import_frequency_corpus(Path(corpus), Path(model), min_count)
+    except Exception as e:
+        fail(e)
+    
+    console.print(f"[green]✓ Imported {entries:,} words into {model}[/green]")
+
+
+@freq.command('top')
+@click.argument('model', type=click.Path(exists=True))
+@click.option('--count', '-n', type=int, default=20, help='Number of words to show')
+def freq_top(model, count):
+    """Show the most frequent words of a model"""
+    try:
+        with FrequencyModel(Path(model)) as loaded:
+            table = Table(title=f"{model} ({len(loaded):,} words, {loaded.total:,} occurrences)")
+            table.add_column("Rank", justify="right")
+            table.add_column("Word", style="cyan")
+            table.add_column("Count", justify="right", style="green")
+            table.add_column("Share", justify="right")
+            for rank in range(min(count, len(loaded))):
+                word, occurrences = loaded[rank]
+                table.add_row(str(rank + 1), word, f"{occurrences:,}", f"{occurrences / loaded.total:.3%}")
+    except Exception as e:
+        fail(e)
+    
+    console.print(table)
+
+
+@cli.group(invoke_without_command=True)
 @click.option('--categories', is_flag=True, help='List field categories')
 @click.option('--category', help='List fields in a category')
 @click.option('--search', help='Search for fields')
-def fields(categories, category, search):
+@click.option('--region', help='List themed packs for a region')
+@click.pass_context
+def fields(ctx, categories, category, search, region):
     """Browse available fields"""
     
-    if categories:
+    if ctx.invoked_subcommand:
+        return
+    if region:
+        # List region packs
+        field_list = FieldManager.get_fields_by_region(region)
+        if not field_list:
+            regions = ", ".join(FieldManager.list_regions())
+            console.print(f"[yellow]No packs for region '{region}' (available: {regions})[/yellow]")
+            return
+        console.print(f"[cyan]Packs for region '{region}':[/cyan]\n")
+        for field in field_list:
+            console.print(f"  - {field['id']:30s} [{field['category']}/{field['group']}]")
+    elif categories:
         # List categories
         cats = FieldManager.list_categories()
         console.print("[cyan]Field Categories:[/cyan]\n")
@@ -240,6 +1767,541 @@ def fields(categories, category, search):
             console.print(f"\n  ... and {len(field_list) - 20} more")
 
 
+@fields.command('defaults')
+@click.option('--enable', 'enable', multiple=True, help='Use the default fields of a category (repeatable)')
+@click.option('--disable', 'disable', multiple=True, help='Stop using the default fields of a category (repeatable)')
+def fields_defaults(enable, disable):
+    """Review and toggle the fields enabled by default, per category
+    
+    Default fields join configs that generate from `words` or `region`
+    without selecting fields; set ignore_default_fields to opt a config out.
+    """
+    try:
+        settings = UserSettings.load()
+        if enable or disable:
+            for category in enable:
+                settings.set_category_defaults(category, True)
+            for category in disable:
+                settings.set_category_defaults(category, False)
+            path = settings.save()
+            console.print(f"[green]✓ Saved {path}[/green]")
+    except Exception as e:
+        fail(e)
+    
+    table = Table(title="Default-enabled fields")
+    table.add_column("Field", style="cyan")
+    table.add_column("Category")
+    table.add_column("Group")
+    table.add_column("Used")
+    for field in FieldManager.get_default_fields():
+        used = settings.default_field_categories.get(field['category'], False)
+        table.add_row(field['id'], field['category'], field['group'],
+                      "[green]on[/green]" if used else "[dim]off[/dim]")
+    console.print(table)
+
+
+@cli.command('index')
+@click.argument('wordlist', type=click.Path(exists=True, dir_okay=False))
+@click.option('--interval', type=int, default=DEFAULT_INDEX_INTERVAL, help='Lines between index entries')
+def index_command(wordlist, interval):
+    """Write a searchable index sidecar for an existing wordlist"""
+    try:
+        index = build_index(Path(wordlist), interval)
+    except Exception as e:
+        fail(e)
+    console.print(f"[green]✓ Indexed {index.lines:,} lines into {index_path_for(Path(wordlist))} "
+                  f"({len(index.entries):,} entries)[/green]")
+    if not index.sorted:
+        console.print("[yellow]The list is not sorted: lookups will scan, line ranges still seek[/yellow]")
+
+
+@cli.command('lookup')
+@click.argument('wordlist', type=WordSource())
+@click.argument('token')
+def lookup_command(wordlist, token):
+    """Check whether a token is in a wordlist or sharded output directory
+    
+    Exits with status 1 when the token is absent.
+    """
+    try:
+        if Path(wordlist).is_dir():
+            found, where = shard_contains(Path(wordlist), token), None
+        else:
+            where = lookup(Path(wordlist), token)
+            found = where is not None
+    except Exception as e:
+        fail(e)
+    
+    if not found:
+        console.print(f"[yellow]{token} not found[/yellow]")
+        sys.exit(1)
+    console.print(f"[green]{token} found[/green]" + (f" at line {where + 1:,}" if where is not None else ""))
+
+
+@cli.command('slice')
+@click.argument('wordlist', type=WordSource(dir_okay=False))
+@click.option('--from', 'start', help='First token of the region (or line number with --lines)')
+@click.option('--to', 'end', help='Last token of the region, inclusive (or line number with --lines)')
+@click.option('--lines', 'by_line', is_flag=True, help='Treat --from/--to as 1-based line numbers')
+@click.option('--sorted', 'sorted_list', is_flag=True, default=None,
+              help='The list is sorted: select tokens between --from and --to even if absent (implied by a sorted index)')
+@click.option('--output', '-o', type=click.Path(), required=True, help='Output file')
+@click.option('--compress', type=click.Choice(['gzip', 'bzip2', 'lz4', 'zstd']),
+              help='Compression format (default: from the output suffix)')
+def slice_command(wordlist, start, end, by_line, sorted_list, output, compress):
+    """Extract a contiguous region of an existing wordlist"""
+    
+    output_path = Path(output)
+    try:
+        if by_line:
+            try:
+                first = int(start) if start else 1
+                last = int(end) if end else None
+            except ValueError:
+                raise ConfigError("--from/--to must be line numbers with --lines")
+            tokens = slice_lines(Path(wordlist), first, last)
+        else:
+            tokens = slice_tokens(Path(wordlist), start, end, sorted_list)
+        count = write_tokens_to_file(tokens, output_path, compress or detect_compression(output_path))
+    except Exception as e:
+        fail(e)
+    
+    console.print(f"[green]✓ Wrote {count:,} tokens to {output_path}[/green]")
+
+
+@cli.command()
+@click.argument('sources', nargs=-1, required=True)
+@click.option('--output', '-o', type=click.Path(), required=True, help='Merged output file')
+@click.option('--format', 'output_format', type=click.Choice(MERGE_FORMATS), default='jsonl', help='Output format')
+@click.option('--compress', type=click.Choice(['gzip', 'bzip2', 'lz4', 'zstd']), help='Compression format (txt/jsonl)')
+@click.option('--token-stats', is_flag=True, help='Add quality score, language, mask and class-count columns (jsonl / sqlite)')
+def merge(sources, output, output_format, compress, token_stats):
+    """Merge wordlists, keeping the tags of every source per token
+    
+    Sources are TAG=PATH (txt or JSONL) or PATH for JSONL outputs that
+    already carry tags (`omni run --format jsonl --tag NAME`). PATH may
+    be an http(s) URL.
+    """
+    try:
+        merged = merge_sources([(tag, str(resolve_source(path)))
+                                for tag, path in map(parse_source, sources)])
+        count = write_merged(merged, Path(output), output_format, compress, token_stats)
+    except Exception as e:
+        fail(e)
+    
+    console.print(f"[green]✓ Merged {count:,} unique tokens into {output}[/green]")
+    table = Table(title="Tokens per tag")
+    table.add_column("Tag", style="cyan")
+    table.add_column("Tokens", justify="right")
+    table.add_column("Only this tag", justify="right")
+    for tag, (total, unique) in tag_counts(merged).items():
+        table.add_row(tag, f"{total:,}", f"{unique:,}")
+    console.print(table)
+
+
+def parse_stream_values(specs, option: str) -> dict:
+    """TAG=NUMBER options (--weight, --quota) as a dict; quotas may be percentages"""
+    values = {}
+    for spec in specs:
+        tag, _, value = spec.partition("=")
+        try:
+            values[tag] = float(value.rstrip("%"))
+        except ValueError:
+            raise ConfigError(f"Expected {option} TAG=NUMBER, got {spec!r}")
+    return values
+
+
+@cli.command('priority-merge')
+@click.argument('streams', nargs=-1)
+@click.option('--plan', 'plan_path', type=click.Path(exists=True, dir_okay=False), help='Merge plan JSON with streams, weights and quotas')
+@click.option('--output', '-o', type=click.Path(), help='Output file (default: stdout)')
+@click.option('--format', 'output_format', type=click.Choice(['txt', 'jsonl']), default='txt',
+              help='Output format (jsonl records carry the score and the source tag)')
+@click.option('--compress', type=click.Choice(['gzip', 'bzip2', 'lz4', 'zstd']), help='Compression format')
+@click.option('--score', 'scorer', type=click.Choice(SCORERS), help='How candidates are scored (default: rank)')
+@click.option('--frequency-model', type=WordSource(dir_okay=False), help='Model from `omni freq import` for --score frequency')
+@click.option('--weight', 'weights', multiple=True, help='Score multiplier of a stream as TAG=WEIGHT (repeatable)')
+@click.option('--quota', 'quotas', multiple=True, help='Share of every block for a stream as TAG=SHARE, e.g. fields=30% (all streams or none)')
+@click.option('--block', type=int, help=f'Lines per quota block (default: {DEFAULT_BLOCK:,})')
+@click.option('--window', type=int, help=f'Tokens of each stream re-sorted by score (default: {DEFAULT_WINDOW})')
+@click.option('--limit', type=int, help='Stop after N unique tokens')
+@click.option('--estimate', 'estimate_only', is_flag=True, help='Estimate stream sizes and quota coverage without merging')
+@click.option('--save-plan', type=click.Path(dir_okay=False), help='Write the streams and options as a merge plan JSON')
+def priority_merge_command(streams, plan_path, output, output_format, compress, scorer, frequency_model,
+                           weights, quotas, block, window, limit, estimate_only, save_plan):
+    """Interleave strategies into one list, best-scored candidates first
+    
+    STREAMS are [TAG=]SOURCE, where SOURCE is a config JSON file, a
+    wordlist (txt or JSONL, local or http(s)) or a preset name. The tag
+    defaults to the file name or preset name. Streams and options add
+    to those of --plan.
+    """
+    try:
+        plan = MergePlan.from_json(Path(plan_path)) if plan_path else MergePlan()
+        plan.streams.extend(StreamSpec.parse(spec) for spec in streams)
+        if not plan.streams:
+            raise ConfigError("Give STREAMS or a --plan")
+        weight_of = parse_stream_values(weights, "--weight")
+        quota_of = parse_stream_values(quotas, "--quota")
+        for spec in plan.streams:
+            spec.weight = weight_of.get(spec.name, spec.weight)
+            spec.quota = quota_of.get(spec.name, spec.quota)
+        plan.score = scorer or plan.score
+        plan.frequency_model = frequency_model or plan.frequency_model
+        plan.block = block or plan.block
+        plan.window = window or plan.window
+        if save_plan:
+            Path(save_plan).write_text(json.dumps(plan.to_dict(), indent=2) + "\n")
+            console.print(f"[cyan]Saved merge plan to {save_plan}[/cyan]")
+        
+        merge_streams = [spec.open() for spec in plan.streams]
+        shares = quota_shares(merge_streams)
+        if estimate_only:
+            print_merge_estimate(plan, shares)
+            return
+        
+        model = FrequencyModel(plan.frequency_model) if plan.frequency_model else None
+        merged = priority_merge(merge_streams, make_scorer(plan.score, model), plan.window, limit, plan.block)
+        counts = {}
+        if output:
+            with OutputWriter(Path(output), compress, output_format) as writer:
+                for entry in merged:
+                    writer.write(entry.token, {"tags": [entry.tag], "score": entry.score})
+                    counts[entry.tag] = counts.get(entry.tag, 0) + 1
+        else:
+            for entry in merged:
+                print(json.dumps({"token": entry.token, "tags": [entry.tag], "score": entry.score})
+                      if output_format == "jsonl" else entry.token)
+        if model is not None:
+            model.close()
+    except Exception as e:
+        fail(e)
+    
+    if output:
+        console.print(f"[green]✓ Merged {sum(counts.values()):,} tokens into {output}[/green]")
+        table = Table(title="Tokens per stream")
+        table.add_column("Stream", style="cyan")
+        table.add_column("Tokens", justify="right")
+        for tag, count in sorted(counts.items(), key=lambda item: -item[1]):
+            table.add_row(tag, f"{count:,}")
+        console.print(table)
+
+
+def print_merge_estimate(plan: MergePlan, shares):
+    """Print each stream's size and share, and how long the quotas hold"""
+    sizes = [spec.size() for spec in plan.streams]
+    table = Table(title="Merge estimate")
+    table.add_column("Stream", style="cyan")
+    table.add_column("Tokens", justify="right")
+    table.add_column("Weight", justify="right")
+    table.add_column(f"Lines per {plan.block:,}", justify="right")
+    allotments = quota_allotments(shares, plan.block) if shares is not None else [None] * len(sizes)
+    for spec, size, lines in zip(plan.streams, sizes, allotments):
+        table.add_row(spec.name, f"{size:,}", f"{spec.weight:g}", f"{lines:,}" if lines is not None else "by score")
+    console.print(table)
+    console.print(f"[cyan]At most {sum(sizes):,} lines (duplicates between streams are dropped)[/cyan]")
+    if shares is not None:
+        lines, index = quota_horizon(sizes, shares)
+        if index is not None:
+            console.print(f"[cyan]Quotas hold for the first ~{lines:,} lines, "
+                          f"until {plan.streams[index].name} runs out[/cyan]")
+
+
+@cli.command()
+@click.option('--candidates', type=WordSource(dir_okay=False), help='Candidate list to replay (default: generate from --config / --preset)')
+@click.option('--config', 'config_path', type=click.Path(exists=True), help='Generate candidates from a config JSON file')
+@click.option('--preset', help='Generate candidates from a preset')
+@click.option('--reference', required=True, type=WordSource(dir_okay=False), help='Reference list, e.g. leaked passwords')
+@click.option('--limit', type=int, help='Stop after N candidates')
+@click.option('--step', type=int, help='Record the curve every N candidates (default: at every hit)')
+@click.option('--output', '-o', type=click.Path(), help='CSV file for the hit curve (default: stdout)')
+def simulate(candidates, config_path, preset, reference, limit, step, output):
+    """Measure how much of a reference list a candidate stream cracks, and how fast"""
+    
+    try:
+        passwords = load_reference(Path(reference))
+        if candidates:
+            with open_text(Path(candidates)) as f:
+                result = simulate_hits((line.rstrip("\r\n") for line in f), passwords, step, limit)
+        else:
+            config = load_job_config(config_path, preset)
+            result = simulate_hits(Generator(config).generate(), passwords, step, limit)
+    except Exception as e:
+        fail(e)
+    
+    if output:
+        with open(output, 'w', newline='') as f:
+            result.write_csv(f)
+    else:
+        result.write_csv(sys.stdout)
+    
+    err_console = Console(stderr=True)
+    err_console.print(f"[green]Cracked {result.hits:,} of {result.reference_size:,} reference entries "
+                      f"({result.fraction:.2%}, {result.unique_hits:,} distinct) "
+                      f"with {result.candidates:,} candidates[/green]")
+    for target in (0.1, 0.25, 0.5):
+        depth = result.depth_for(target)
+        if depth is not None:
+            err_console.print(f"  {target:.0%} of the reference after {depth:,} candidates")
+
+
+@cli.command('explain-token')
+@click.argument('token')
+@click.option('--config', 'config_path', type=click.Path(exists=True), help='Config JSON file')
+@click.option('--preset', help='Explain against a preset')
+@click.option('--scan-limit', type=int, default=DEFAULT_SCAN_LIMIT, help='Candidates to replay before giving up (0 for no limit)')
+def explain_token_cmd(token, config_path, preset, scan_limit):
+    """Explain whether and why a config produces a token"""
+    
+    try:
+        config = load_job_config(config_path, preset)
+        explanation = explain_token(config, token, scan_limit or None)
+    except Exception as e:
+        fail(e)
+    
+    if explanation.produced:
+        console.print(f"[green]✓ Produced as token #{explanation.rank:,} "
+                      f"(after {explanation.candidates_scanned:,} candidates)[/green]")
+    elif explanation.produced is False:
+        reason = f", rejected by {explanation.rejected_by}" if explanation.rejected_by else ""
+        console.print(f"[red]✗ Not produced ({explanation.candidates_scanned:,} candidates replayed{reason})[/red]")
+    else:
+        console.print(f"[yellow]? Not among the first {explanation.candidates_scanned:,} candidates "
+                      f"(raise --scan-limit)[/yellow]")
+    
+    console.print(f"Entropy ({explanation.entropy_mode}): {explanation.entropy:.2f}")
+    console.print(f"Quality: {explanation.quality:.3f}")
+    
+    table = Table(title="Filters")
+    table.add_column("Stage", style="cyan")
+    table.add_column("Result")
+    for check in explanation.filters:
+        table.add_row(check.name, "[green]pass[/green]" if check.passed else "[red]fail[/red]")
+    for stage in explanation.blocked_by:
+        table.add_row(stage, "[red]fail[/red]")
+    console.print(table)
+    
+    if explanation.derivations:
+        console.print("\n[bold]Derivable from:[/bold]")
+        for derivation in explanation.derivations:
+            console.print(f"  {derivation.field_id}: {derivation.value!r} {describe_chain(derivation.transforms)}")
+    elif explanation.components:
+        console.print("\n[bold]Contains field values:[/bold]")
+        for component in explanation.components:
+            console.print(f"  {component.field_id}: {component.value!r}")
+
+
+@cli.command('verify-presets')
+@click.option('--preset', 'presets', multiple=True, help='Preset to check (repeatable, default: every builtin preset)')
+@click.option('--snapshot-dir', type=click.Path(file_okay=False), default=str(DEFAULT_SNAPSHOT_DIR),
+              help='Directory of <preset>.json snapshots')
+@click.option('--size', type=int, default=SNAPSHOT_SIZE, help='Tokens sampled per preset')
+@click.option('--update', is_flag=True, help='Rewrite changed or missing snapshots')
+def verify_presets_cmd(presets, snapshot_dir, size, update):
+    """Compare preset output with committed snapshots"""
+
+    try:
+        results = verify_presets(Path(snapshot_dir), presets or None, size, update)
+    except Exception as e:
+        fail(e)
+
+    for result in results:
+        if result.ok:
+            console.print(f"[green]✓ {result.preset}[/green]" + (f" [dim]({result.detail})[/dim]" if result.detail else ""))
+            continue
+        console.print(f"[red]✗ {result.preset}: {result.detail}[/red]")
+        for line in result.diff:
+            console.print(f"    {line}", markup=False, highlight=False)
+
+    failed = [result for result in results if not result.ok]
+    if failed:
+        console.print(f"\n[red]{len(failed)} of {len(results)} presets differ "
+                      f"(rerun with --update to accept intended changes)[/red]")
+        sys.exit(1)
+
+
+@cli.command('case-stats')
+@click.argument('wordlist', type=WordSource(dir_okay=False))
+@click.option('--output', '-o', type=click.Path(), help='Write the table as JSON for --case-stats')
+def case_stats(wordlist, output):
+    """Measure case pattern shares of a breach list for --smart-case"""
+    try:
+        with open_text(Path(wordlist)) as f:
+            table = train_case_stats((line.rstrip("\r\n") for line in f), source=Path(wordlist).name)
+        if output:
+            Path(output).write_text(json.dumps(table, indent=2) + "\n")
+    except Exception as e:
+        fail(e)
+    
+    table_view = Table(title="Case patterns by length")
+    table_view.add_column("Length", style="cyan")
+    table_view.add_column("Pattern")
+    table_view.add_column("Share", justify="right")
+    for bucket, shares in table["by_length"].items():
+        for name, share in shares.items():
+            table_view.add_row(bucket, name, f"{share:.2%}")
+    console.print(table_view)
+    if output:
+        console.print(f"[green]✓ Wrote case statistics to {output}[/green]")
+
+
+@cli.command('length-stats')
+@click.argument('wordlist', type=WordSource(dir_okay=False))
+@click.option('--output', '-o', type=click.Path(), help='Write the shares as JSON for --length-distribution')
+def length_stats(wordlist, output):
+    """Measure the length distribution of a breach list for --length-distribution"""
+    try:
+        with open_text(Path(wordlist)) as f:
+            table = measure_lengths((line.rstrip("\r\n") for line in f), source=Path(wordlist).name)
+        if output:
+            Path(output).write_text(json.dumps(table, indent=2) + "\n")
+    except Exception as e:
+        fail(e)
+    
+    table_view = Table(title="Share by length")
+    table_view.add_column("Length", style="cyan")
+    table_view.add_column("Share", justify="right")
+    table_view.add_column("")
+    peak = max(table["lengths"].values(), default=1)
+    for length, share in table["lengths"].items():
+        table_view.add_row(length, f"{share:.2%}", bar(share / peak))
+    console.print(table_view)
+    if output:
+        console.print(f"[green]✓ Wrote length distribution to {output}[/green]")
+
+
+@cli.command('infer-policy')
+@click.argument('samples', type=WordSource(dir_okay=False))
+@click.option('--output', '-o', type=click.Path(), help='Write a config JSON restricting generation to the policy')
+def infer_policy_command(samples, output):
+    """Infer the password policy behind known-valid passwords"""
+    try:
+        with open_text(Path(samples)) as f:
+            policy = infer_policy(line.rstrip("\r\n") for line in f)
+        if output:
+            policy_config(policy).to_json(Path(output))
+    except Exception as e:
+        fail(e)
+    
+    table_view = Table(title=f"Policy inferred from {policy.samples:,} samples")
+    table_view.add_column("Rule", style="cyan")
+    table_view.add_column("Value")
+    table_view.add_row("Length", f"{policy.min_length}..{policy.max_length}")
+    table_view.add_row("Required classes", ", ".join(policy.required_classes) or "none")
+    table_view.add_row("Fewest classes", str(policy.min_classes))
+    if policy.max_repeats is not None:
+        table_view.add_row("Longest repeat", str(policy.max_repeats))
+    for name in policy.banned_patterns:
+        table_view.add_row("Banned", name)
+    console.print(table_view)
+    if policy.samples < MIN_BAN_SAMPLES:
+        console.print(f"[yellow]Bans need at least {MIN_BAN_SAMPLES} samples; none were inferred[/yellow]")
+    if output:
+        console.print(f"[green]✓ Wrote policy config to {output}[/green]")
+
+
+@cli.command()
+@click.argument('job_id', required=False)
+@click.option('--checkpoint-dir', type=click.Path(exists=True, file_okay=False),
+              help='Checkpoint directory of the interrupted run')
+@click.option('--from-output', type=click.Path(exists=True, dir_okay=False),
+              help='Without a checkpoint: continue after the last complete line of this output (needs --config)')
+@click.option('--config', 'config_path', type=click.Path(exists=True), help='Config JSON file of the run, for --from-output')
+def resume(job_id, checkpoint_dir, from_output, config_path):
+    """Continue an interrupted run from its checkpoint, or from its output file
+    
+    With --from-output, the last complete line of the (possibly
+    compressed) output is located under --config and generation goes on
+    from the next candidate.
+    """
+    
+    try:
+        if from_output:
+            if job_id or not config_path:
+                raise ConfigError("--from-output takes a --config instead of a JOB_ID")
+            config = UserSettings.load().apply_default_fields(Config.from_json(Path(config_path)))
+            generator = Generator.resume_from_output(config, Path(from_output), CancellationToken())
+            job_id = generator.job_id
+        else:
+            if not job_id or not checkpoint_dir:
+                raise ConfigError("Give a JOB_ID and --checkpoint-dir, or --from-output and --config")
+            generator = Generator.resume(job_id, CheckpointManager(Path(checkpoint_dir)), CancellationToken())
+    except Exception as e:
+        fail(e, "Resume error")
+    
+    output_path = generator.config.output_file
+    console.print(f"[green]Resuming {generator.resumed_from.get('status', 'interrupted')} job {job_id} "
+                  f"after {generator.tokens_written:,} tokens into {output_path}...[/green]")
+    try:
+        with interrupt_cancels(generator.cancel_token, announce_stop):
+            try:
+                backend = generator.open_backend()
+                write_with_progress(generator, backend)
+            except Exception as e:
+                fail(e, "Error writing output")
+    except KeyboardInterrupt:
+        handle_interrupt(generator, forced=True)
+    
+    if generator.cancelled:
+        handle_interrupt(generator)
+    
+    console.print(f"[green]✓ Generated {generator.tokens_generated:,} tokens[/green]")
+    if generator.limit_reached:
+        report_limit(generator, backend.bytes_written)
+    console.print(f"[cyan]Output: {output_path}[/cyan]")
+    record_usage(generator)
+
+
+@cli.command()
+@click.argument('config_path', type=click.Path(exists=True))
+@click.option('--fix', is_flag=True, help='Offer to repair common problems')
+@click.option('--yes', '-y', is_flag=True, help='With --fix, apply every repair without asking')
+@click.option('--output', '-o', type=click.Path(), help='Write the repaired config here instead of in place')
+def validate(config_path, fix, yes, output):
+    """Check a config file, optionally repairing common problems"""
+    
+    try:
+        data = json.loads(Path(config_path).read_text())
+    except (OSError, ValueError) as e:
+        fail(ConfigError(f"Cannot parse {config_path}: {e}"))
+    
+    if fix:
+        # Re-scan after every repair: renaming a key can expose further problems
+        applied, declined = [], set()
+        while True:
+            pending = [f for f in find_fixes(data) if f.description not in declined]
+            if not pending:
+                break
+            repair = pending[0]
+            if yes or click.confirm(repair.description, default=True):
+                repair.apply(data)
+                applied.append(repair.description)
+                if yes:
+                    console.print(f"[green]✓ {repair.description}[/green]")
+            # Never offer the same repair twice
+            declined.add(repair.description)
+        
+        if applied:
+            target = Path(output or config_path)
+            target.write_text(json.dumps(data, indent=2) + "\n")
+            console.print(f"[cyan]Applied {len(applied)} fix(es), wrote {target}[/cyan]")
+        else:
+            console.print("[cyan]Nothing to fix[/cyan]")
+    else:
+        fixable = find_fixes(data)
+        for repair in fixable:
+            console.print(f"[yellow]• {repair.description}[/yellow]")
+        if fixable:
+            console.print(f"[yellow]{len(fixable)} problem(s) can be repaired with --fix[/yellow]")
+    
+    try:
+        Config.from_dict(data).validate()
+    except Exception as e:
+        fail(e, "Invalid config")
+    
+    console.print(f"[green]✓ {output if fix and output else config_path} is valid[/green]")
+
+
 @cli.command()
 def info():
     """Show version and system info"""
@@ -259,11 +2321,138 @@ def info():
     console.print("  txt, jsonl, csv")
 
 
+@cli.command()
+@click.option('--enable/--disable', 'enable', default=None, help='Start / stop keeping the ledger (recorded totals stay)')
+@click.option('--reset', is_flag=True, help='Delete the recorded totals')
+@click.option('--json', 'as_json', is_flag=True, help='Print the ledger as JSON')
+@click.option('--yes', '-y', is_flag=True, help='Do not ask for confirmation')
+def stats(enable, reset, as_json, yes):
+    """
+    Show local usage totals of finished runs
+    
+    The ledger is off until enabled, lives in ~/.omniwordlist/usage.json
+    and is never sent anywhere.
+    """
+    try:
+        settings = UserSettings.load()
+        if enable is not None:
+            settings.usage_stats = enable
+            settings.save()
+            console.print(f"[green]✓ Usage ledger {'enabled' if enable else 'disabled'}[/green]")
+        if reset:
+            if not yes:
+                click.confirm("Delete the recorded usage totals?", abort=True)
+            UsageLedger().save()
+            console.print("[green]✓ Usage totals reset[/green]")
+        ledger = UsageLedger.load()
+    except Exception as e:
+        fail(e)
+    
+    if as_json:
+        print(json.dumps(asdict(ledger), indent=2))
+        return
+    if not settings.usage_stats:
+        console.print("[dim]The usage ledger is off; enable it with `omni stats --enable`[/dim]")
+        if not ledger.runs:
+            return
+    
+    table = Table(title="Usage", caption=str(default_usage_path()))
+    table.add_column("Metric", style="cyan")
+    table.add_column("Value", justify="right")
+    table.add_row("Runs", f"{ledger.runs:,}")
+    table.add_row("Tokens written", f"{ledger.tokens:,}")
+    table.add_row("Largest keyspace", f"{ledger.largest_keyspace:,}")
+    table.add_row("First run", ledger.first_run or "-")
+    table.add_row("Last run", ledger.last_run or "-")
+    console.print(table)
+    if ledger.presets:
+        presets = Table(title="Runs per preset")
+        presets.add_column("Preset", style="cyan")
+        presets.add_column("Runs", justify="right")
+        for name, runs in sorted(ledger.presets.items(), key=lambda item: (-item[1], item[0])):
+            presets.add_row(name, f"{runs:,}")
+        console.print(presets)
+
+
+@cli.command()
+@click.argument('paths', nargs=-1, required=True, type=click.Path(exists=True))
+@click.option('--passes', type=click.IntRange(1), default=1, help='Overwrite passes')
+@click.option('--yes', '-y', is_flag=True, help='Do not ask for confirmation')
+def shred(paths, passes, yes):
+    """
+    Overwrite wordlists, checkpoints or directories with random data, then delete them
+    
+    A file's index sidecar goes with it. On SSDs and copy-on-write or
+    snapshotting file systems old blocks may survive; rely on disk
+    encryption there.
+    """
+    targets = []
+    for path in map(Path, paths):
+        targets.append(path)
+        targets += [sidecar for sidecar in (index_path_for(path), lock_path_for(path)) if sidecar.exists()]
+    if not yes:
+        click.confirm(f"Destroy {', '.join(str(target) for target in targets)}?", abort=True)
+    
+    files = 0
+    try:
+        for target in targets:
+            files += shred_path(target, passes)
+    except Exception as e:
+        fail(e)
+    console.print(f"[green]✓ Shredded {files:,} files[/green]")
+
+
+@cli.command('ad-import')
+@click.argument('export', type=click.Path(exists=True))
+@click.option('--output-dir', '-o', type=click.Path(), required=True, help='Directory for per-employee wordlists')
+@click.option('--config', 'config_path', type=click.Path(exists=True), help='Base config (transforms, filters)')
+@click.option('--preset', help='Base preset (transforms, filters)')
+@click.option('--years', help='Year range combined with employee attributes (e.g. 2020-2026)')
+@click.option('--username-formats', default=','.join(USERNAME_FORMATS),
+              help=f'Comma-separated username formats ({", ".join(USERNAME_FORMATS)})')
+@click.option('--max-components', type=int, default=2, help='Most attributes combined in one candidate')
+def ad_import(export, output_dir, config_path, preset, years, username_formats, max_components):
+    """Build per-employee wordlists and usernames from an AD/LDAP CSV export"""
+    
+    try:
+        if config_path:
+            base = Config.from_json(Path(config_path))
+        elif preset:
+            base = PresetManager().get_preset_config(preset)
+        else:
+            base = Config()
+        
+        parsed = parse_directory_export(Path(export))
+        employees = parsed.employees
+        formats = [f.strip() for f in username_formats.split(',') if f.strip()]
+        year_list = parse_year_range(years) if years else None
+        out_dir = Path(output_dir)
+        
+        console.print("[cyan]Column mapping: " +
+                      ", ".join(f"{column} -> {attribute}" for column, attribute in parsed.columns.items()) + "[/cyan]")
+        
+        usernames = []
+        total = 0
+        for employee in employees:
+            usernames.extend(username_candidates(employee, formats))
+            generator = Generator(employee_config(employee, base, year_list, max_components))
+            total += write_tokens_to_file(generator.generate(), out_dir / f"{employee.key}.txt")
+        
+        usernames = list(dict.fromkeys(usernames))
+        write_tokens_to_file(iter(usernames), out_dir / "usernames.txt")
+    except Exception as e:
+        fail(e)
+    
+    console.print(f"[green]✓ {len(employees):,} employees: {total:,} candidates, "
+                  f"{len(usernames):,} usernames -> {output_dir}[/green]")
+
+
 @cli.command()
 def tui():
     """Launch interactive TUI (Terminal User Interface)"""
     console.print("[yellow]TUI not yet implemented in Python version[/yellow]")
     console.print("Use the CLI commands for now.")
+    console.print("[cyan]For a live preview while editing a config, use: omni preview --config cfg.json --watch[/cyan]")
 
 
 def main():
@@ -274,8 +2463,7 @@ def main():
         console.print("\n[yellow]Interrupted by user[/yellow]")
         sys.exit(130)
     except Exception as e:
-        console.print(f"[red]Unexpected error: {e}[/red]")
-        sys.exit(1)
+        fail(e, "Unexpected error")
 
 
 if __name__ == '__main__':
@@ -0,0 +1,321 @@
+"""
+Cluster coordination
+
+A coordinator hands out keyspace chunks to worker processes over
+plain HTTP/JSON, tracks completion, re-issues chunks whose worker
+went quiet, and aggregates per-worker statistics. Workers write one
+file per chunk; concatenating the files in chunk order gives the
+same wordlist as a single run.
+
+Chunk ranks only line up when every machine enumerates the same
+alphabet ordering (a charset file can differ between hosts), so the
+job and chunk manifests carry the exact orderings and workers refuse
+to generate under different ones.
+
+Every request carries the job's shared token in the X-Omni-Token
+header; requests without it are refused with 401.
+
+Endpoints:
+    GET  /job       configuration and chunk size
+    POST /claim     {"worker": id} -> next chunk, or {"done": true}
+    POST /complete  {"worker": id, "chunk": n, "tokens": count}
+    GET  /status    progress and per-worker statistics
+"""
+
+import hmac
+import json
+import socket
+import threading
+import time
+import urllib.request
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+from pathlib import Path
+from typing import Callable, Dict, Optional, Tuple, Union
+
+from .config import Config
+from .error import ConfigError, GeneratorError, StorageError
+from .generator import Generator
+from .keyspace import chunk_bounds, chunk_count
+from .storage import write_tokens_to_file
+
+
+# Header carrying the shared token, and the environment variable the CLI reads it from
+TOKEN_HEADER = "X-Omni-Token"
+TOKEN_ENV = "OMNI_CLUSTER_TOKEN"
+
+class Coordinator:
+    """Thread-safe chunk bookkeeping for one distributed job"""
+
+    def __init__(self, config: Config, keyspace_size: int, chunk_size: int,
+                 timeout: float = 600.0, clock: Callable[[], float] = time.monotonic):
+        """
+        Initialize coordinator
+
+        Args:
+            config: Job configuration sent to workers
+            keyspace_size: Total number of candidate ranks
+            chunk_size: Candidates per chunk
+            timeout: Seconds after which an unfinished chunk is re-issued
+            clock: Time source (monotonic seconds)
+        """
+        self.config = config
+        self.alphabets = Generator(config).alphabets()
+        self.keyspace_size = keyspace_size
+        self.chunk_size = chunk_size
+        self.total_chunks = chunk_count(keyspace_size, chunk_size)
+        self.timeout = timeout
+        self.clock = clock
+        self.next_chunk = 0
+        self.in_flight: Dict[int, Tuple[str, float]] = {}
+        self.completed: Dict[int, int] = {}
+        self.reissued = 0
+        self.workers: Dict[str, Dict[str, int]] = {}
+        self._lock = threading.Lock()
+
+    def _worker(self, worker: str) -> Dict[str, int]:
+        return self.workers.setdefault(worker, {"claimed": 0, "completed": 0, "tokens": 0})
+
+    def claim(self, worker: str) -> Optional[dict]:
+        """
+        Hand the next chunk to a worker
+
+        Timed-out chunks are re-issued before new ones.
+
+        Args:
+            worker: Worker identifier
+
+        Returns:
+            Chunk assignment, or None when every chunk is done or in flight
+        """
+        with self._lock:
+            now = self.clock()
+            chunk_id = None
+            for candidate, (_, issued) in sorted(self.in_flight.items()):
+                if now - issued >= self.timeout:
+                    chunk_id = candidate
+                    self.reissued += 1
+                    break
+            if chunk_id is None:
+                if self.next_chunk >= self.total_chunks:
+                    return None
+                chunk_id = self.next_chunk
+                self.next_chunk += 1
+
+            self.in_flight[chunk_id] = (worker, now)
+            self._worker(worker)["claimed"] += 1
+            bounds = chunk_bounds(chunk_id, self.chunk_size, self.keyspace_size)
+            return {"chunk": chunk_id, "start": bounds.start, "end": bounds.end}
+
+    def complete(self, worker: str, chunk_id: int, tokens: int) -> bool:
+        """
+        Record a finished chunk
+
+        Args:
+            worker: Worker identifier
+            chunk_id: Chunk number
+            tokens: Tokens the worker wrote for it
+
+        Returns:
+            False if the chunk had already been completed (duplicate work)
+        """
+        with self._lock:
+            self.in_flight.pop(chunk_id, None)
+            if chunk_id in self.completed:
+                return False
+            self.completed[chunk_id] = tokens
+            stats = self._worker(worker)
+            stats["completed"] += 1
+            stats["tokens"] += tokens
+            return True
+
+    @property
+    def done(self) -> bool:
+        return len(self.completed) >= self.total_chunks
+
+    def status(self) -> dict:
+        """Progress and per-worker statistics"""
+        with self._lock:
+            return {
+                "total_chunks": self.total_chunks,
+                "completed": len(self.completed),
+                "in_flight": len(self.in_flight),
+                "reissued": self.reissued,
+                "tokens": sum(self.completed.values()),
+                "done": self.done,
+                "workers": {name: dict(stats) for name, stats in self.workers.items()},
+            }
+
+
+def make_server(coordinator: Coordinator, token: str, host: str = "127.0.0.1",
+                port: int = 8765) -> ThreadingHTTPServer:
+    """
+    Build the HTTP server for a coordinator (call serve_forever to run it)
+
+    Args:
+        coordinator: Job coordinator
+        token: Shared token every request must carry
+        host: Bind address
+        port: Bind port (0 picks a free one)
+
+    Returns:
+        Server instance
+
+    Raises:
+        ConfigError: If the token is empty
+    """
+    if not token:
+        raise ConfigError("The coordinator needs a shared token")
+
+    class Handler(BaseHTTPRequestHandler):
+        def _send(self, payload: dict, status: int = 200):
+            body = json.dumps(payload).encode()
+            self.send_response(status)
+            self.send_header("Content-Type", "application/json")
+            self.send_header("Content-Length", str(len(body)))
+            self.end_headers()
+            self.wfile.write(body)
+
+        def _body(self) -> dict:
+            length = int(self.headers.get("Content-Length", 0))
+            return json.loads(self.rfile.read(length) or b"{}")
+
+        def _authorized(self) -> bool:
+            if hmac.compare_digest(self.headers.get(TOKEN_HEADER, "").encode(), token.encode()):
+                return True
+            self._send({"error": "unauthorized"}, 401)
+            return False
+
+        def do_GET(self):
+            if not self._authorized():
+                return
+            if self.path == "/job":
+                self._send({"config": coordinator.config.to_dict(),
+                            "alphabets": coordinator.alphabets,
+                            "chunk_size": coordinator.chunk_size,
+                            "total_chunks": coordinator.total_chunks})
+            elif self.path == "/status":
+                self._send(coordinator.status())
+            else:
+                self._send({"error": "not found"}, 404)
+
+        def do_POST(self):
+            if not self._authorized():
+                return
+            try:
+                body = self._body()
+                worker = str(body.get("worker", self.client_address[0]))
+                if self.path == "/claim":
+                    assignment = coordinator.claim(worker)
+                    self._send(assignment or {"done": coordinator.done})
+                elif self.path == "/complete":
+                    accepted = coordinator.complete(worker, int(body["chunk"]), int(body.get("tokens", 0)))
+                    self._send({"accepted": accepted})
+                else:
+                    self._send({"error": "not found"}, 404)
+            except (ValueError, KeyError, GeneratorError) as e:
+                self._send({"error": str(e)}, 400)
+
+        def log_message(self, format, *args):
+            pass
+
+    return ThreadingHTTPServer((host, port), Handler)
+
+
+def chunk_manifest(config: Config, chunk_size: int) -> dict:
+    """
+    Describe a chunked job for `omni chunk generate --manifest`
+
+    Args:
+        config: Job configuration
+        chunk_size: Candidates per chunk
+
+    Returns:
+        Manifest with the configuration, chunk layout and alphabet orderings
+    """
+    generator = Generator(config)
+    keyspace = generator.rank_count()
+    return {
+        "config": config.to_dict(),
+        "alphabets": generator.alphabets(),
+        "keyspace": keyspace,
+        "chunk_size": chunk_size,
+        "total_chunks": chunk_count(keyspace, chunk_size),
+    }
+
+
+def load_chunk_manifest(manifest: Union[dict, Path]) -> Tuple[Generator, int]:
+    """
+    Rebuild the generator of a chunk manifest, checking it still splits the same way
+
+    Args:
+        manifest: Manifest from chunk_manifest, or the path of its JSON file
+
+    Returns:
+        (generator, chunk size)
+
+    Raises:
+        GeneratorError: If the alphabet ordering or keyspace size changed
+    """
+    if not isinstance(manifest, dict):
+        try:
+            manifest = json.loads(Path(manifest).read_text())
+        except (OSError, ValueError) as e:
+            raise ConfigError(f"Cannot read chunk manifest {manifest}: {e}")
+    generator = Generator(Config.from_dict(dict(manifest["config"])))
+    generator.check_alphabets(manifest.get("alphabets"), "the chunk manifest")
+    if generator.rank_count() != manifest["keyspace"]:
+        raise GeneratorError(f"Keyspace is {generator.rank_count():,} candidates, "
+                             f"the chunk manifest was made for {manifest['keyspace']:,}")
+    return generator, manifest["chunk_size"]
+
+
+def _request(url: str, token: str, payload: Optional[dict] = None, timeout: float = 30.0) -> dict:
+    """GET (no payload) or POST a JSON request to the coordinator"""
+    data = json.dumps(payload).encode() if payload is not None else None
+    request = urllib.request.Request(url, data=data, headers={"Content-Type": "application/json",
+                                                              TOKEN_HEADER: token})
+    try:
+        with urllib.request.urlopen(request, timeout=timeout) as response:
+            return json.loads(response.read())
+    except OSError as e:
+        raise StorageError(f"Coordinator request failed ({url}): {e}")
+
+
+def run_worker(url: str, token: str, output_dir: Path, worker: Optional[str] = None,
+               max_chunks: Optional[int] = None, poll_interval: float = 5.0) -> int:
+    """
+    Claim and generate chunks until the coordinator reports the job done
+
+    Args:
+        url: Coordinator base URL (http://host:port)
+        token: Shared token of the coordinator
+        output_dir: Directory for chunk files (chunk-<id>.txt)
+        worker: Worker identifier (hostname if omitted)
+        max_chunks: Stop after this many chunks
+        poll_interval: Seconds to wait while remaining chunks are in flight elsewhere
+
+    Returns:
+        Number of chunks this worker completed
+    """
+    url = url.rstrip("/")
+    worker = worker or socket.gethostname()
+    job = _request(f"{url}/job", token)
+    config = Config.from_dict(job["config"])
+    Generator(config).check_alphabets(job.get("alphabets"), f"job {url}")
+    digits = len(str(max(job["total_chunks"] - 1, 0)))
+
+    finished = 0
+    while max_chunks is None or finished < max_chunks:
+        assignment = _request(f"{url}/claim", token, {"worker": worker})
+        if "chunk" not in assignment:
+            if assignment.get("done", True):
+                break
+            time.sleep(poll_interval)
+            continue
+
+        chunk_id = assignment["chunk"]
+        tokens = Generator(config).generate_range(assignment["start"], assignment["end"])
+        count = write_tokens_to_file(tokens, Path(output_dir) / f"chunk-{chunk_id:0{digits}d}.txt")
+        _request(f"{url}/complete", token, {"worker": worker, "chunk": chunk_id, "tokens": count})
+        finished += 1
+    return finished
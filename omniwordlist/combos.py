@@ -0,0 +1,63 @@
+"""
+Combo lists
+
+Pairs a username source with the password stream to produce
+user:pass lines for spraying tools that take combo files.
+
+Modes:
+    product   every user with every password, password-major so each
+              password is tried against all users before the next one
+    zip       the i-th user with the i-th password
+    per_user  like product, limited to the first N passwords
+"""
+
+from typing import Iterable, Iterator, List, Optional, Tuple
+
+
+COMBO_MODES = ("product", "zip", "per_user")
+
+
+def pair_credentials(users: List[str], passwords: Iterable[str], mode: str = "product",
+                     per_user: Optional[int] = None) -> Iterator[Tuple[str, str]]:
+    """
+    Pair usernames with a password stream
+    
+    Args:
+        users: Usernames
+        passwords: Password stream (consumed lazily)
+        mode: product, zip or per_user
+        per_user: Passwords per user for per_user mode
+        
+    Yields:
+        (username, password) pairs
+    """
+    if mode == "zip":
+        yield from zip(users, passwords)
+        return
+    
+    for index, password in enumerate(passwords):
+        if mode == "per_user" and per_user is not None and index >= per_user:
+            return
+        for user in users:
+            yield user, password
+
+
+def combo_count(users: int, passwords: int, mode: str = "product",
+                per_user: Optional[int] = None) -> int:
+    """
+    Number of pairs pair_credentials produces
+    
+    Args:
+        users: Number of usernames
+        passwords: Number of passwords in the stream
+        mode: product, zip or per_user
+        per_user: Passwords per user for per_user mode
+        
+    Returns:
+        Pair count
+    """
+    if mode == "zip":
+        return min(users, passwords)
+    if mode == "per_user" and per_user is not None:
+        passwords = min(passwords, per_user)
+    return users * passwords
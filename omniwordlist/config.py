@@ -1,20 +1,110 @@
 """Configuration validation and management"""
 
-from typing import Optional, List, Dict
-from dataclasses import dataclass, field
+from typing import Optional, List, Dict, Union
+from dataclasses import dataclass, field, fields
 from pathlib import Path
+import difflib
 import json
+import re
+import warnings
 from .error import ConfigError
+from .charset import CUSTOM_MARKERS, expand_pattern_positions, is_char_class, parse_duplicate_limit
+from .neighborhood import MAX_EDIT_DISTANCE
+from .combos import COMBO_MODES
+from .dedupe import DEDUPE_MODES
+from .shard import SHARD_MODES
+from .buckets import bucket_thresholds
+from .storage import OUTPUT_ENCODINGS, STORAGE_BACKENDS
+from .hashes import HASH_ALGORITHMS, HASH_FORMATS
+from .pipe import PIPE_BATCH_SIZE, parse_command
+from .markov import MARKOV_ORDERS
+from .temporal import parse_date, resolve_relative
+
+
+# Field component orderings selectable with Config.field_order
+FIELD_ORDERS = ("fixed", "swap_pairs", "permute")
+
+# Where include_top passwords go: before, after, or alternating with generated candidates
+TOP_PLACEMENTS = ("first", "last", "merged")
+
+# Entropy estimators selectable with FilterConfig.entropy_mode
+ENTROPY_MODES = ("shannon", "classes", "nist", "guesses")
+
+# Character classes of filters.char_class, for FilterConfig.required_classes
+TOKEN_CLASSES = ("lower", "upper", "digit", "symbol", "other")
+
+# Keys renamed or removed as the schema evolves: old key -> new key (None if removed).
+# Old keys keep loading with a ConfigWarning until they are dropped.
+DEPRECATED_KEYS: Dict[str, Optional[str]] = {}
+DEPRECATED_FILTER_KEYS: Dict[str, Optional[str]] = {"min_byte_len": "min_bytes", "max_byte_len": "max_bytes"}
+
+
+class ConfigWarning(UserWarning):
+    """Non-fatal configuration problem, such as a deprecated key"""
+    pass
+
+
+def check_keys(data: Dict, known: List[str], deprecated: Dict[str, Optional[str]],
+               prefix: str = "") -> Dict:
+    """
+    Reject unknown keys and rewrite deprecated ones
+    
+    Args:
+        data: Raw config mapping
+        known: Valid keys
+        deprecated: Deprecated key -> replacement (None if removed)
+        prefix: Shown before key names in messages (e.g. "filters.")
+        
+    Returns:
+        Mapping with deprecated keys renamed or dropped
+    """
+    result = {}
+    for key, value in data.items():
+        if key in deprecated:
+            replacement = deprecated[key]
+            if replacement is None:
+                warnings.warn(f"{prefix}{key} is deprecated and ignored", ConfigWarning, stacklevel=3)
+                continue
+            warnings.warn(f"{prefix}{key} is deprecated, use {prefix}{replacement}", ConfigWarning, stacklevel=3)
+            if replacement not in data:
+                result[replacement] = value
+            continue
+        if key not in known:
+            matches = difflib.get_close_matches(key, known, n=1)
+            hint = f" (did you mean {prefix}{matches[0]}?)" if matches else ""
+            raise ConfigError(f"Unknown config key: {prefix}{key}{hint}")
+        result[key] = value
+    return result
 
 
 @dataclass
 class FilterConfig:
     """Filter configuration"""
+    # Token length in characters (Unicode code points)
     min_len: int = 1
     max_len: int = 100
+    # Token length in UTF-8 bytes, for targets with byte-limited fields
+    min_bytes: Optional[int] = None
+    max_bytes: Optional[int] = None
     charset_filter: Optional[str] = None
+    # Named character classes also allowed (ascii_printable, letters, script:latin, ...)
+    charset_classes: List[str] = field(default_factory=list)
+    # Characters and classes a token must not contain
+    charset_deny: Optional[str] = None
+    charset_deny_classes: List[str] = field(default_factory=list)
+    # Longest allowed run of one repeated character (None = unlimited)
+    max_repeats: Optional[int] = None
+    # Password policy rules (see policy): character classes (TOKEN_CLASSES) every
+    # token must contain, fewest distinct classes per token, and regular
+    # expressions a token must not match
+    required_classes: List[str] = field(default_factory=list)
+    min_classes: Optional[int] = None
+    deny_patterns: List[str] = field(default_factory=list)
     min_entropy: float = 0.0
-    max_entropy: float = 100.0
+    max_entropy: Optional[float] = None
+    # How min_entropy/max_entropy are measured (see ENTROPY_MODES); no upper bound by default
+    entropy_mode: str = "shannon"
+    min_quality: float = 0.0
     allow_duplicates: bool = True
 
 
@@ -25,33 +115,75 @@ class Config:
     # Length constraints
     min_length: int = 1
     max_length: int = 10
+    # Shares per length, or a JSON file from `omni length-stats`: charset mode
+    # interleaves lengths by them instead of finishing each in turn (see lengths)
+    length_distribution: Optional[Union[Dict[str, float], str]] = None
     
     # Character set and pattern
     charset: Optional[str] = None
     pattern: Optional[str] = None
+    # File whose contents is the charset (overrides charset)
+    charset_file: Optional[str] = None
     
-    # Resume and range control
+    # Resume and range control: first / last candidate, inclusive (in charset and
+    # pattern modes raw candidates in generation order, seeked like crunch -s / -e;
+    # otherwise compared with output tokens)
     start_string: Optional[str] = None
     end_string: Optional[str] = None
+    # Index-based partitioning: leave out the first `skip` candidates of that range
+    # and stop after `take` (charset and pattern modes seek to the rank; other
+    # modes enumerate the candidates before it)
+    skip: Optional[int] = None
+    take: Optional[int] = None
     
     # Output configuration
     output_file: Optional[Path] = None
     compression: Optional[str] = None
+    # Registered storage backend receiving the output (see storage.register_storage_backend)
+    storage_backend: str = "file"
+    # Pre-encoding for tools that take UTF-16LE candidates (see storage.OUTPUT_ENCODINGS),
+    # optionally with an unencoded copy of the output kept at plain_output
+    output_encoding: str = "utf-8"
+    plain_output: Optional[Path] = None
     
-    # Limits
+    # Limits: writing stops once the uncompressed output (all shards or split
+    # parts) reaches max_bytes, after at most one line past it
     max_bytes: Optional[int] = None
     max_lines: Optional[int] = None
+    # Refuse to start when the raw keyspace is larger than this
+    max_keyspace: Optional[int] = None
     
-    # Duplicate control
+    # Duplicate control: longest run of one character per pattern marker class,
+    # crunch -d style ("2@", "2@,3%", "@"; see charset.parse_duplicate_limit).
+    # Modes without a pattern check runs by the class of each character
+    # (charset.check_duplicate_limit)
     duplicate_limit: Optional[str] = None
     
     # Generation options
+    # Change the leftmost position fastest (crunch -i; charset and pattern modes)
     invert: bool = False
+    # Pattern placeholders kept literally: characters literal everywhere, and a
+    # crunch -l mask as long as the pattern (see charset.literal_positions)
     literal_chars: Optional[str] = None
+    literal_mask: Optional[str] = None
+    # Up to four custom charsets, used in patterns with the markers 1-4 (crunch -1 .. -4)
+    custom_charsets: List[str] = field(default_factory=list)
     
-    # Splitting options
+    # Split single-file output into numbered parts (storage.SplitWriter): a new
+    # part starts once one has split_by_bytes uncompressed bytes or split_by_lines lines
     split_by_bytes: Optional[int] = None
     split_by_lines: Optional[int] = None
+    # Shard output into one file per first character / token hash bucket
+    # (output is then a directory, see shard.SHARD_MODES)
+    shard_by: Optional[str] = None
+    shard_count: int = 16
+    # Route tokens into low/medium/high files of the output directory by
+    # "entropy" (filters.entropy_mode estimator) or "quality"; thresholds are the
+    # two cut points (defaults per metric, see buckets.DEFAULT_THRESHOLDS)
+    bucket_by: Optional[str] = None
+    bucket_thresholds: List[float] = field(default_factory=list)
+    # Write a `<output>.idx` sidecar indexing every Nth line (uncompressed txt only)
+    index_interval: Optional[int] = None
     
     # Status and display
     show_status: bool = False
@@ -61,29 +193,150 @@ class Config:
     prefix: Optional[str] = None
     suffix: Optional[str] = None
     separator: Optional[str] = None
+    # Separators placed between field components; each one yields its own variant
+    # (overrides separator when set, "" means plain concatenation)
+    separators: List[str] = field(default_factory=list)
     
     # Field-based generation
     enabled_fields: List[str] = field(default_factory=list)
+    # Skip the default-enabled fields of categories switched on in user settings
+    # (`omni fields defaults`), which the CLI otherwise adds to words / region-only configs
+    ignore_default_fields: bool = False
+    
+    # Component ordering: "fixed" (as enabled), "swap_pairs" (also each adjacent
+    # pair swapped) or "permute" (every ordering of min..max_components fields,
+    # 1 to every enabled field by default)
+    field_order: str = "fixed"
+    min_components: Optional[int] = None
+    max_components: Optional[int] = None
+    # Only build field combinations within min_length..max_length (in characters,
+    # before prefix / suffix and transforms); off by default in field mode
+    field_length_window: bool = False
+    
+    # Values for field ids: a dictionary file / URL (one word per line), "-" for
+    # stdin, a named pipe, or an inline list
+    field_sources: Dict[str, Union[str, List[str]]] = field(default_factory=dict)
+    
+    # Inline word list, used as the "words" field (enabled automatically)
+    words: List[str] = field(default_factory=list)
+    
+    # Date relative tokens ({year-1}, {season}, {grad:30}, see temporal) in words,
+    # inline field values, prefix and suffix resolve against (YYYY-MM-DD, default
+    # the run date)
+    reference_date: Optional[str] = None
+    
+    # Edit-distance neighborhood: every string within edit_distance of a seed word,
+    # using the charset for substitutions and insertions
+    seed_words: List[str] = field(default_factory=list)
+    edit_distance: int = 1
+    
+    # Mutation mode: candidates are the lines of this word list ("-" for stdin),
+    # streamed through prefix / suffix, transforms, filters and dedupe
+    input_wordlist: Optional[str] = None
+    # Hybrid mode (hashcat -a 6 / -a 7): each input word followed / preceded by
+    # every candidate of this Crunch-style pattern (markers 1-4 use custom_charsets)
+    append_pattern: Optional[str] = None
+    prepend_pattern: Optional[str] = None
+    
+    # Combinator mode (hashcat -a 1): every left + separator + right joining of
+    # two word lists; the right list is held in memory and the left one streamed,
+    # so put the smaller list on the right
+    combinator_left: Optional[str] = None
+    combinator_right: Optional[str] = None
+    # Also emit right + separator + left after each left + separator + right
+    combinator_swap: bool = False
+    
+    # Markov mode: the likeliest tokens of an order-2 or order-3 character model
+    # (see markov), trained on markov_train or loaded from markov_model; with both,
+    # the model trained on markov_train is saved to markov_model
+    markov_train: Optional[str] = None
+    markov_model: Optional[str] = None
+    markov_order: int = 3
+    
+    # Chain mode (PRINCE): every ordered chain of 1..chain_max_elements elements,
+    # joined by separator, within min_length..max_length (see prince); elements are
+    # the lines of chain_elements, or the values of the enabled fields without it
+    chain_max_elements: Optional[int] = None
+    chain_elements: Optional[str] = None
+    
+    # File of tokens that must never be emitted
+    denylist: Optional[str] = None
+    
+    # Frequency model (`omni freq import`) ordering field values by corpus frequency
+    frequency_model: Optional[str] = None
+    
+    # Region code selecting themed sports/pop-culture packs (IN, US, EU, ...)
+    region: Optional[str] = None
+    
+    # Common-password sources (top-N list emitted alongside / removed from output;
+    # included passwords outside min_length..max_length are dropped)
+    include_top: Optional[int] = None
+    exclude_top: Optional[int] = None
+    top_placement: str = "first"
+    
+    # Combo output: pair usernames (file / URL / inline list) with the generated
+    # passwords as user<sep>pass lines (see combos.COMBO_MODES)
+    combo_users: Optional[Union[str, List[str]]] = None
+    combo_mode: str = "product"
+    combo_per_user: Optional[int] = None
+    combo_separator: str = ":"
+    
+    # Hashed output: each candidate with its unsalted hash, as lookup table lines
+    # (see hashes.HASH_ALGORITHMS and hashes.HASH_FORMATS); hashed on `workers` processes
+    hash_algorithm: Optional[str] = None
+    hash_format: str = "potfile"
+    
+    # External filter: tokens are piped through this command (stdin -> stdout, see
+    # pipe) and its output lines replace them, before hashing or combos
+    pipe_through: Optional[str] = None
+    pipe_batch_size: int = PIPE_BATCH_SIZE
     
     # Transforms
     transforms: List[str] = field(default_factory=list)
+    # Expand every raw candidate into its N most likely case variants, ranked by
+    # breach statistics (see smartcase; case_stats_file overrides the shipped table)
+    smart_case: Optional[int] = None
+    case_stats_file: Optional[str] = None
+    # Drop redundant transform steps (see transforms.optimize_transforms)
+    optimize_transforms: bool = True
     
     # Filters
     filters: FilterConfig = field(default_factory=FilterConfig)
     
+    # Sample of rejected candidates written as "token<TAB>reason" for tuning filters
+    rejects_output: Optional[Path] = None
+    rejects_sample_size: int = 1000
+    
     # Performance
     workers: int = 1
     
-    # Persistence
+    # Persistence: with a checkpoint_dir, write() records a resumable checkpoint every
+    # checkpoint_interval tokens or checkpoint_seconds seconds (0 disables either)
     checkpoint_dir: Optional[Path] = None
+    checkpoint_interval: int = 100000
+    checkpoint_seconds: float = 60.0
     
-    # Deduplication
+    # Deduplication of prepared tokens: candidates are generated, prefixed / suffixed and
+    # transformed, then filtered, then deduplicated, since transforms (e.g. lowercase)
+    # can turn distinct raw candidates into the same token
     dedupe: bool = False
+    # exact keeps a 128-bit hash per unique token (memory grows with the output);
+    # bloom uses a fixed-size filter for dedupe_capacity tokens (default: the
+    # estimated output) that drops a bloom_fp_rate fraction of new tokens (see dedupe)
+    dedupe_mode: str = "exact"
+    dedupe_capacity: Optional[int] = None
     bloom_fp_rate: float = 0.01
+    # Cross-run dedupe: runs sharing a campaign never write a token an earlier run
+    # of it wrote (see campaign; campaign_db defaults to ~/.omniwordlist/campaigns.db)
+    campaign: Optional[str] = None
+    campaign_db: Optional[Path] = None
     
     # Streaming
     buffer_size: int = 8192
     
+    # Progress reporting (emitted tokens between observer progress events)
+    progress_interval: int = 10000
+    
     # Logging
     verbose: bool = False
     colorized: bool = True
@@ -96,6 +349,11 @@ class Config:
     
     # Format
     format: str = "txt"
+    # Provenance tags written with every JSONL record (see `omni merge`)
+    tags: List[str] = field(default_factory=list)
+    # Quality score, language, mask and class-count columns per token in CSV and
+    # JSONL output (see tokenstats)
+    token_stats: bool = False
     
     def validate(self) -> None:
         """Validate configuration"""
@@ -108,27 +366,247 @@ class Config:
         if self.workers < 1:
             raise ConfigError("workers must be at least 1")
         
+        if self.smart_case is not None and self.smart_case < 1:
+            raise ConfigError("smart_case must be at least 1")
+        
+        if self.max_bytes is not None and self.max_bytes < 1:
+            raise ConfigError("max_bytes must be at least 1")
+        
+        for key in ("split_by_bytes", "split_by_lines"):
+            if getattr(self, key) is not None and getattr(self, key) < 1:
+                raise ConfigError(f"{key} must be at least 1")
+        if (self.split_by_bytes or self.split_by_lines) and (self.shard_by or self.bucket_by):
+            raise ConfigError("Split output cannot be sharded or bucketed")
+        
+        if self.token_stats and self.format not in ("csv", "jsonl"):
+            raise ConfigError("token_stats needs csv or jsonl output")
+        
+        if self.skip is not None and self.skip < 0:
+            raise ConfigError("skip must not be negative")
+        
+        if self.take is not None and self.take < 1:
+            raise ConfigError("take must be at least 1")
+        
+        if self.rejects_sample_size < 1:
+            raise ConfigError("rejects_sample_size must be at least 1")
+        
+        if self.progress_interval < 1:
+            raise ConfigError("progress_interval must be at least 1")
+        
+        if self.combo_mode not in COMBO_MODES:
+            raise ConfigError(f"Unsupported combo_mode: {self.combo_mode} "
+                              f"(known: {', '.join(COMBO_MODES)})")
+        
+        if self.combo_mode == "per_user" and (self.combo_per_user is None or self.combo_per_user < 1):
+            raise ConfigError("combo_mode per_user needs combo_per_user >= 1")
+        
+        if self.field_order not in FIELD_ORDERS:
+            raise ConfigError(f"Unsupported field_order: {self.field_order} "
+                              f"(known: {', '.join(FIELD_ORDERS)})")
+        
+        for key in ("min_components", "max_components"):
+            value = getattr(self, key)
+            if value is not None and value < 1:
+                raise ConfigError(f"{key} must be at least 1")
+        if (self.min_components is not None and self.max_components is not None
+                and self.min_components > self.max_components):
+            raise ConfigError("min_components cannot exceed max_components")
+        
+        if self.input_wordlist and (self.pattern or self.seed_words):
+            raise ConfigError("input_wordlist replaces generation; it cannot be combined with pattern or seed_words")
+        
+        if (self.append_pattern or self.prepend_pattern) and not self.input_wordlist:
+            raise ConfigError("append_pattern and prepend_pattern need an input_wordlist")
+        if self.append_pattern and self.prepend_pattern:
+            raise ConfigError("Give one of append_pattern and prepend_pattern")
+        
+        if bool(self.combinator_left) != bool(self.combinator_right):
+            raise ConfigError("Combinator mode needs both combinator_left and combinator_right")
+        if self.combinator_swap and not self.combinator_left:
+            raise ConfigError("combinator_swap needs combinator_left and combinator_right")
+        if self.combinator_left and (self.pattern or self.seed_words or self.input_wordlist):
+            raise ConfigError("Combinator mode cannot be combined with pattern, seed_words or input_wordlist")
+        if self.combinator_left == "-" and self.combinator_right == "-":
+            raise ConfigError("Only one of combinator_left and combinator_right can be read from stdin")
+        
+        if self.chain_max_elements is not None:
+            if self.chain_max_elements < 1:
+                raise ConfigError("chain_max_elements must be at least 1")
+            if self.pattern or self.seed_words or self.input_wordlist or self.combinator_left:
+                raise ConfigError("Chain mode cannot be combined with pattern, seed_words, input_wordlist "
+                                  "or combinators")
+            if len(self.separators) > 1:
+                raise ConfigError("Chain mode joins elements with a single separator")
+        elif self.chain_elements:
+            raise ConfigError("chain_elements needs chain_max_elements")
+        
+        if self.markov_order not in MARKOV_ORDERS:
+            raise ConfigError(f"markov_order must be one of {', '.join(map(str, MARKOV_ORDERS))}")
+        if (self.markov_train or self.markov_model) and (self.pattern or self.seed_words or self.input_wordlist
+                                                         or self.combinator_left):
+            raise ConfigError("Markov mode cannot be combined with pattern, seed_words, input_wordlist or combinators")
+        
+        if self.seed_words and not 0 <= self.edit_distance <= MAX_EDIT_DISTANCE:
+            raise ConfigError(f"edit_distance must be between 0 and {MAX_EDIT_DISTANCE}")
+        
+        if self.reference_date:
+            parse_date(self.reference_date)
+        for key in ("prefix", "suffix"):
+            value = getattr(self, key)
+            if value and len(resolve_relative(value)) > 1:
+                raise ConfigError(f"{key} must resolve to a single value (no ranges or {{grad:...}})")
+        
         if self.bloom_fp_rate < 0 or self.bloom_fp_rate > 1:
             raise ConfigError("bloom_fp_rate must be between 0 and 1")
         
+        if self.dedupe_mode not in DEDUPE_MODES:
+            raise ConfigError(f"Unsupported dedupe_mode: {self.dedupe_mode} "
+                              f"(known: {', '.join(DEDUPE_MODES)})")
+        if self.dedupe_mode == "bloom" and not 0 < self.bloom_fp_rate < 1:
+            raise ConfigError("bloom mode needs a bloom_fp_rate between 0 and 1 (exclusive)")
+        if self.dedupe_capacity is not None and self.dedupe_capacity < 1:
+            raise ConfigError("dedupe_capacity must be at least 1")
+        
         if self.compression and self.compression not in ["gzip", "bzip2", "lz4", "zstd"]:
             raise ConfigError(f"Unsupported compression format: {self.compression}")
         
+        if self.shard_by is not None and self.shard_by not in SHARD_MODES:
+            raise ConfigError(f"Unsupported shard_by: {self.shard_by} (known: {', '.join(SHARD_MODES)})")
+        
+        if self.shard_count < 1:
+            raise ConfigError("shard_count must be at least 1")
+        
+        if self.campaign_db and not self.campaign:
+            raise ConfigError("campaign_db needs a campaign")
+        if self.campaign is not None and not self.campaign.strip():
+            raise ConfigError("campaign must not be empty")
+        
+        if self.checkpoint_interval < 0 or self.checkpoint_seconds < 0:
+            raise ConfigError("checkpoint_interval and checkpoint_seconds must not be negative")
+        if self.checkpoint_dir and (self.hash_algorithm or self.combo_users or self.pipe_through):
+            # Their output lines run ahead of (hashing, pipes) or multiply (combos) the tokens
+            raise ConfigError("checkpoint_dir needs one output line per token "
+                              "(not hash_algorithm, combo_users or pipe_through)")
+        if self.checkpoint_dir and self.dedupe and (self.format != "txt" or self.output_encoding != "utf-8"):
+            # A resumed run refills its dedupe set from the tokens already written
+            raise ConfigError("checkpoint_dir with dedupe needs txt output in utf-8")
+        if self.pipe_through is not None:
+            parse_command(self.pipe_through)
+            if self.pipe_batch_size < 1:
+                raise ConfigError("pipe_batch_size must be at least 1")
+        
+        if self.duplicate_limit:
+            parse_duplicate_limit(self.duplicate_limit)
+        
+        if len(self.custom_charsets) > len(CUSTOM_MARKERS):
+            raise ConfigError(f"At most {len(CUSTOM_MARKERS)} custom_charsets can be defined")
+        if any(not charset for charset in self.custom_charsets):
+            raise ConfigError("custom_charsets must not be empty")
+        if self.pattern:
+            expand_pattern_positions(self.pattern, self.literal_chars, self.custom_charsets, self.literal_mask)
+        for mask in (self.append_pattern, self.prepend_pattern):
+            if mask:
+                expand_pattern_positions(mask, None, self.custom_charsets)
+        
+        if self.storage_backend not in STORAGE_BACKENDS:
+            raise ConfigError(f"Unknown storage backend: {self.storage_backend} "
+                              f"(known: {', '.join(sorted(STORAGE_BACKENDS))})")
+        if self.storage_backend != "file" and (self.shard_by or self.bucket_by or self.index_interval):
+            raise ConfigError("shard_by, bucket_by and index_interval need the file storage backend")
+        
+        if self.output_encoding not in OUTPUT_ENCODINGS:
+            raise ConfigError(f"Unknown output_encoding: {self.output_encoding} "
+                              f"(known: {', '.join(OUTPUT_ENCODINGS)})")
+        if self.output_encoding != "utf-8" or self.plain_output:
+            if self.storage_backend != "file" or self.shard_by or self.bucket_by or self.index_interval:
+                raise ConfigError("output_encoding and plain_output need unsharded, unindexed file output")
+            if self.output_encoding == "utf-16le-hex" and self.format != "txt":
+                raise ConfigError("output_encoding utf-16le-hex needs txt output")
+        
+        if self.bucket_by is not None:
+            if self.shard_by:
+                raise ConfigError("bucket_by and shard_by cannot be combined")
+            bucket_thresholds(self.bucket_by, self.filters.entropy_mode, self.bucket_thresholds)
+        
+        if self.index_interval is not None:
+            if self.index_interval < 1:
+                raise ConfigError("index_interval must be at least 1")
+            if self.compression or self.format != "txt" or self.shard_by or self.bucket_by:
+                raise ConfigError("index_interval needs uncompressed, unsharded, unbucketed txt output")
+        
         if self.format not in ["txt", "jsonl", "csv"]:
             raise ConfigError(f"Unsupported output format: {self.format}")
+        
+        if self.hash_algorithm is not None:
+            if self.hash_algorithm not in HASH_ALGORITHMS:
+                raise ConfigError(f"Unknown hash algorithm: {self.hash_algorithm} "
+                                  f"(known: {', '.join(HASH_ALGORITHMS)})")
+            if self.hash_format not in HASH_FORMATS:
+                raise ConfigError(f"Unknown hash_format: {self.hash_format} (known: {', '.join(HASH_FORMATS)})")
+            if self.format != "txt" or self.combo_users:
+                raise ConfigError("hash_algorithm needs txt output and cannot be combined with combo_users")
+        
+        if self.top_placement not in TOP_PLACEMENTS:
+            raise ConfigError(f"Unsupported top_placement: {self.top_placement} "
+                              f"(known: {', '.join(TOP_PLACEMENTS)})")
+        
+        if (self.filters.min_bytes is not None and self.filters.max_bytes is not None
+                and self.filters.max_bytes < self.filters.min_bytes):
+            raise ConfigError("filters.max_bytes must be >= filters.min_bytes")
+        
+        for field_id, source in self.field_sources.items():
+            if not isinstance(source, (str, list)):
+                raise ConfigError(f"field_sources[{field_id!r}] must be a path, URL or list of words")
+        
+        for name in self.filters.charset_classes + self.filters.charset_deny_classes:
+            if not is_char_class(name):
+                raise ConfigError(f"Unknown character class: {name}")
+        
+        if self.filters.max_repeats is not None and self.filters.max_repeats < 1:
+            raise ConfigError("filters.max_repeats must be at least 1")
+        
+        for name in self.filters.required_classes:
+            if name not in TOKEN_CLASSES:
+                raise ConfigError(f"Unknown required class: {name} (known: {', '.join(TOKEN_CLASSES)})")
+        if self.filters.min_classes is not None and not 1 <= self.filters.min_classes <= len(TOKEN_CLASSES):
+            raise ConfigError(f"filters.min_classes must be between 1 and {len(TOKEN_CLASSES)}")
+        for pattern in self.filters.deny_patterns:
+            try:
+                re.compile(pattern)
+            except re.error as e:
+                raise ConfigError(f"Invalid deny pattern {pattern!r}: {e}")
+        
+        if self.filters.entropy_mode not in ENTROPY_MODES:
+            raise ConfigError(f"Unsupported entropy_mode: {self.filters.entropy_mode} "
+                              f"(known: {', '.join(ENTROPY_MODES)})")
+        
+        for key in ("include_top", "exclude_top"):
+            value = getattr(self, key)
+            if value is not None and value < 1:
+                raise ConfigError(f"{key} must be at least 1")
     
     @classmethod
     def from_dict(cls, data: Dict) -> 'Config':
         """Create Config from dictionary"""
+        data = check_keys(data, [f.name for f in fields(cls)], DEPRECATED_KEYS)
+        
         # Handle nested FilterConfig
         if 'filters' in data and isinstance(data['filters'], dict):
-            data['filters'] = FilterConfig(**data['filters'])
+            filters = check_keys(data['filters'], [f.name for f in fields(FilterConfig)],
+                                 DEPRECATED_FILTER_KEYS, "filters.")
+            data['filters'] = FilterConfig(**filters)
         
         # Convert paths
         if 'output_file' in data and data['output_file']:
             data['output_file'] = Path(data['output_file'])
         if 'checkpoint_dir' in data and data['checkpoint_dir']:
             data['checkpoint_dir'] = Path(data['checkpoint_dir'])
+        if 'campaign_db' in data and data['campaign_db']:
+            data['campaign_db'] = Path(data['campaign_db'])
+        if 'rejects_output' in data and data['rejects_output']:
+            data['rejects_output'] = Path(data['rejects_output'])
+        if 'plain_output' in data and data['plain_output']:
+            data['plain_output'] = Path(data['plain_output'])
         
         return cls(**data)
     
@@ -139,6 +617,17 @@ class Config:
             data = json.load(f)
         return cls.from_dict(data)
     
+    def storage_options(self) -> Dict:
+        """Options for storage.create_storage_backend beyond path, compression and format"""
+        options = {}
+        if self.output_encoding != "utf-8":
+            options["encoding"] = self.output_encoding
+        if self.plain_output:
+            options["plain_path"] = Path(self.plain_output)
+        if self.token_stats:
+            options["token_stats"] = True
+        return options
+    
     def to_dict(self) -> Dict:
         """Convert Config to dictionary"""
         result = {}
@@ -0,0 +1,173 @@
+"""
+In-run deduplication
+
+Two modes trade memory for accuracy:
+
+exact: a set of 128-bit BLAKE2b hashes. A token is only dropped when an
+identical token was emitted before (a hash collision is out of reach
+even for billions of tokens), but memory grows with every unique token,
+about EXACT_ENTRY_BYTES each.
+
+bloom: a Bloom filter of fixed size, allocated up front from the
+expected number of unique tokens (dedupe_capacity, or the keyspace
+estimate capped at MAX_BLOOM_CAPACITY) and bloom_fp_rate. Memory stays
+bounded (about 1.2 bytes per token at 1%, 1.8 bytes at 0.1%), but a
+fraction bloom_fp_rate of new tokens are wrongly dropped as duplicates,
+and more once the filter holds more tokens than it was sized for. It
+never lets a duplicate through.
+"""
+
+import hashlib
+import math
+import sys
+from typing import Optional, Set, Union
+
+from .error import ConfigError
+
+
+DEDUPE_MODES = ("exact", "bloom")
+
+# Size of one stored hash (a 16-byte bytes object)
+HASH_OBJECT_BYTES = sys.getsizeof(bytes(16))
+
+# Approximate memory per hash of the exact set: the hash plus its share
+# of the set's table
+EXACT_ENTRY_BYTES = HASH_OBJECT_BYTES + 24
+
+# Largest capacity a Bloom filter is sized for from a keyspace estimate
+# (about 1.2 GB at a 1% false-positive rate); set dedupe_capacity to go beyond
+MAX_BLOOM_CAPACITY = 1_000_000_000
+
+# Smallest capacity, so tiny estimates still get a usable filter
+MIN_BLOOM_CAPACITY = 1024
+
+
+def token_digest(token: str) -> bytes:
+    """128-bit BLAKE2b hash of a token"""
+    return hashlib.blake2b(token.encode("utf-8", "surrogatepass"), digest_size=16).digest()
+
+
+class ExactDeduper:
+    """Exact deduplication on 128-bit token hashes"""
+
+    mode = "exact"
+
+    def __init__(self):
+        self.hashes: Set[bytes] = set()
+
+    def add(self, token: str) -> bool:
+        """
+        Remember a token
+
+        Returns:
+            True if the token is new, False for a duplicate
+        """
+        digest = token_digest(token)
+        if digest in self.hashes:
+            return False
+        self.hashes.add(digest)
+        return True
+
+    def __len__(self) -> int:
+        return len(self.hashes)
+
+    def memory_bytes(self) -> int:
+        """Estimated memory in use"""
+        return sys.getsizeof(self.hashes) + len(self.hashes) * HASH_OBJECT_BYTES
+
+
+class BloomDeduper:
+    """Memory-bounded deduplication through a Bloom filter"""
+
+    mode = "bloom"
+
+    def __init__(self, capacity: int, fp_rate: float):
+        """
+        Allocate a filter
+
+        Args:
+            capacity: Expected number of unique tokens
+            fp_rate: False-positive rate at capacity (0 < fp_rate < 1)
+        """
+        if capacity < 1:
+            raise ConfigError("Bloom filter capacity must be at least 1")
+        if not 0 < fp_rate < 1:
+            raise ConfigError("bloom_fp_rate must be between 0 and 1 (exclusive) in bloom mode")
+        self.capacity = capacity
+        self.fp_rate = fp_rate
+        self.bits = bloom_bits(capacity, fp_rate)
+        self.hash_count = max(1, round(self.bits / capacity * math.log(2)))
+        self.filter = bytearray((self.bits + 7) // 8)
+        self.count = 0
+
+    def add(self, token: str) -> bool:
+        """
+        Remember a token
+
+        Returns:
+            True if the token is (probably) new, False if it was seen before
+            or is a false positive
+        """
+        digest = token_digest(token)
+        # Double hashing: the k bit positions are h1 + i * h2
+        first = int.from_bytes(digest[:8], "little")
+        second = int.from_bytes(digest[8:], "little") | 1
+        new = False
+        for i in range(self.hash_count):
+            bit = (first + i * second) % self.bits
+            mask = 1 << (bit & 7)
+            if not self.filter[bit >> 3] & mask:
+                self.filter[bit >> 3] |= mask
+                new = True
+        if new:
+            self.count += 1
+        return new
+
+    def __len__(self) -> int:
+        return self.count
+
+    def memory_bytes(self) -> int:
+        """Memory of the bit array"""
+        return len(self.filter)
+
+
+Deduper = Union[ExactDeduper, BloomDeduper]
+
+
+def bloom_bits(capacity: int, fp_rate: float) -> int:
+    """Bits of a Bloom filter holding `capacity` items at a false-positive rate"""
+    return max(8, math.ceil(-capacity * math.log(fp_rate) / math.log(2) ** 2))
+
+
+def bloom_capacity(estimate: int, capacity: Optional[int] = None) -> int:
+    """
+    Capacity a Bloom filter is sized for
+
+    Args:
+        estimate: Estimated output tokens (Generator.estimate_count)
+        capacity: Explicit capacity (Config.dedupe_capacity)
+
+    Returns:
+        The explicit capacity, else the estimate within
+        MIN_BLOOM_CAPACITY..MAX_BLOOM_CAPACITY
+    """
+    if capacity is not None:
+        return capacity
+    return min(max(estimate, MIN_BLOOM_CAPACITY), MAX_BLOOM_CAPACITY)
+
+
+def estimated_memory(mode: str, tokens: int, fp_rate: float) -> int:
+    """
+    Memory a dedupe mode needs for a number of unique tokens
+
+    Args:
+        mode: One of DEDUPE_MODES
+        tokens: Unique tokens (the capacity in bloom mode)
+        fp_rate: bloom_fp_rate
+
+    Returns:
+        Estimated bytes
+    """
+    if mode == "bloom":
+        return (bloom_bits(max(tokens, 1), fp_rate) + 7) // 8
+    return tokens * EXACT_ENTRY_BYTES
@@ -0,0 +1,113 @@
+"""
+Default credentials
+
+Vendor-keyed factory username/password pairs for routers, cameras and
+other network devices, as published in vendor manuals and public
+default-password lists. Used for audits of devices that may still run
+with factory credentials.
+"""
+
+from typing import Dict, List, Optional, Sequence, Tuple
+from .error import ConfigError
+
+
+# vendor -> (category, [(username, password), ...]); "" is an empty password
+DEFAULT_CREDENTIALS: Dict[str, Tuple[str, List[Tuple[str, str]]]] = {
+    "apc": ("ups", [("apc", "apc"), ("device", "apc")]),
+    "asus": ("router", [("admin", "admin")]),
+    "avtech": ("camera", [("admin", "admin")]),
+    "axis": ("camera", [("root", "pass"), ("root", "root")]),
+    "belkin": ("router", [("admin", ""), ("admin", "admin")]),
+    "cisco": ("router", [("cisco", "cisco"), ("admin", "admin"), ("admin", "cisco"),
+                         ("enable", "cisco"), ("root", "Cisco")]),
+    "dahua": ("camera", [("admin", "admin"), ("888888", "888888"), ("666666", "666666")]),
+    "dlink": ("router", [("admin", ""), ("admin", "admin"), ("user", ""), ("admin", "password")]),
+    "foscam": ("camera", [("admin", ""), ("admin", "admin")]),
+    "hikvision": ("camera", [("admin", "12345"), ("admin", "admin")]),
+    "hp": ("printer", [("admin", ""), ("admin", "admin"), ("Admin", "Admin")]),
+    "huawei": ("router", [("admin", "admin"), ("root", "admin"), ("telecomadmin", "admintelecom"),
+                          ("admin", "HuaWei123")]),
+    "juniper": ("router", [("root", ""), ("netscreen", "netscreen"), ("admin", "abc123")]),
+    "linksys": ("router", [("admin", "admin"), ("", "admin"), ("root", "admin")]),
+    "mikrotik": ("router", [("admin", "")]),
+    "netgear": ("router", [("admin", "password"), ("admin", "1234"), ("admin", "admin")]),
+    "raspberrypi": ("iot", [("pi", "raspberry")]),
+    "tenda": ("router", [("admin", "admin"), ("admin", "")]),
+    "tplink": ("router", [("admin", "admin")]),
+    "ubiquiti": ("router", [("ubnt", "ubnt"), ("root", "ubnt")]),
+    "xerox": ("printer", [("admin", "1111"), ("admin", "admin")]),
+    "zyxel": ("router", [("admin", "1234"), ("admin", "admin"), ("root", "1234")]),
+}
+
+VENDOR_ALIASES = {
+    "d-link": "dlink",
+    "tp-link": "tplink",
+    "ubnt": "ubiquiti",
+    "raspberry": "raspberrypi",
+}
+
+
+def normalize_vendor(vendor: str) -> str:
+    """Map a vendor name or alias to its key"""
+    key = vendor.strip().lower().replace(" ", "")
+    return VENDOR_ALIASES.get(key, key)
+
+
+def list_vendors(category: Optional[str] = None) -> List[str]:
+    """
+    List vendors with default credentials
+    
+    Args:
+        category: Only vendors of this device category
+        
+    Returns:
+        Sorted vendor keys
+    """
+    return sorted(vendor for vendor, (vendor_category, _) in DEFAULT_CREDENTIALS.items()
+                  if category is None or vendor_category == category)
+
+
+def list_categories() -> List[str]:
+    """List device categories"""
+    return sorted({category for category, _ in DEFAULT_CREDENTIALS.values()})
+
+
+def get_default_credentials(vendors: Optional[Sequence[str]] = None,
+                            category: Optional[str] = None) -> List[Tuple[str, str]]:
+    """
+    Collect default username/password pairs
+    
+    Args:
+        vendors: Vendor names (all vendors if omitted)
+        category: Only vendors of this device category
+        
+    Returns:
+        Unique (username, password) pairs, in vendor order
+    """
+    if vendors:
+        keys = [normalize_vendor(v) for v in vendors]
+        unknown = [v for v, key in zip(vendors, keys) if key not in DEFAULT_CREDENTIALS]
+        if unknown:
+            raise ConfigError(f"Unknown vendor(s): {', '.join(unknown)} "
+                              f"(known: {', '.join(list_vendors())})")
+    else:
+        keys = list_vendors()
+    
+    pairs = []
+    for key in keys:
+        vendor_category, credentials = DEFAULT_CREDENTIALS[key]
+        if category and vendor_category != category:
+            continue
+        for pair in credentials:
+            if pair not in pairs:
+                pairs.append(pair)
+    return pairs
+
+
+def default_passwords(category: Optional[str] = None) -> List[str]:
+    """Unique non-empty default passwords, optionally for one device category"""
+    passwords = []
+    for _, password in get_default_credentials(category=category):
+        if password and password not in passwords:
+            passwords.append(password)
+    return passwords
@@ -0,0 +1,92 @@
+"""
+Derived-value fields
+
+Computed field components whose value is derived from other fields of
+the same combination, for systems known to build default credentials
+from hashed or encoded user data (e.g. first 8 hex digits of md5(name)).
+
+Field id syntax: derive:<algorithm>:<field>[+<field>...][:<length>]
+
+    derive:md5:first_name:8       md5 hex of the name, first 8 characters
+    derive:base64:name+year       base64 of name and year concatenated
+    derive:crc32:username         crc32 as 8 hex digits
+"""
+
+import base64
+import hashlib
+import zlib
+from dataclasses import dataclass
+from typing import Callable, Dict, List, Optional
+from .error import ConfigError
+
+
+DERIVE_PREFIX = "derive:"
+
+
+def _hexdigest(name: str) -> Callable[[bytes], str]:
+    return lambda data: hashlib.new(name, data).hexdigest()
+
+
+DERIVERS: Dict[str, Callable[[bytes], str]] = {
+    "md5": _hexdigest("md5"),
+    "sha1": _hexdigest("sha1"),
+    "sha256": _hexdigest("sha256"),
+    "crc32": lambda data: f"{zlib.crc32(data) & 0xffffffff:08x}",
+    "base64": lambda data: base64.b64encode(data).decode("ascii"),
+    "base32": lambda data: base64.b32encode(data).decode("ascii"),
+    "hex": lambda data: data.hex(),
+}
+
+
+@dataclass
+class DerivedField:
+    """A parsed derived field id"""
+    algorithm: str
+    sources: List[str]
+    length: Optional[int] = None
+    
+    def compute(self, values: List[str]) -> str:
+        """
+        Derive the value from the source values
+        
+        Args:
+            values: Values of the source fields, in source order
+            
+        Returns:
+            Derived value, truncated to `length` if set
+        """
+        derived = DERIVERS[self.algorithm](''.join(values).encode('utf-8'))
+        return derived[:self.length] if self.length else derived
+
+
+def is_derived_field(field_id: str) -> bool:
+    """Check whether a field id names a derived field"""
+    return field_id.startswith(DERIVE_PREFIX)
+
+
+def parse_derived_field(field_id: str) -> DerivedField:
+    """
+    Parse a derive:<algorithm>:<fields>[:<length>] field id
+    
+    Args:
+        field_id: Field identifier
+        
+    Returns:
+        DerivedField
+    """
+    parts = field_id[len(DERIVE_PREFIX):].split(':')
+    if len(parts) not in (2, 3) or not parts[1]:
+        raise ConfigError(f"Invalid derived field: {field_id} "
+                          f"(expected derive:<algorithm>:<field>[+<field>...][:<length>])")
+    
+    algorithm = parts[0].lower()
+    if algorithm not in DERIVERS:
+        raise ConfigError(f"Unknown derive algorithm: {parts[0]} (known: {', '.join(DERIVERS)})")
+    
+    length = None
+    if len(parts) == 3:
+        if not parts[2].isdigit() or int(parts[2]) < 1:
+            raise ConfigError(f"Invalid derived field length in {field_id}")
+        length = int(parts[2])
+    
+    return DerivedField(algorithm, parts[1].split('+'), length)
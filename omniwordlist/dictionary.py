@@ -0,0 +1,105 @@
+"""
+Memory-mapped dictionaries
+
+Loading a multi-gigabyte wordlist as a Python list costs several times
+its size in string objects. MmapDictionary maps the file read-only and
+keeps only an index of line start and end offsets (16 bytes per word),
+decoding words on access, so several huge dictionaries can be combined
+at once and processes generating from the same file share its page
+cache.
+"""
+
+import mmap
+from array import array
+from pathlib import Path
+from typing import Iterator, List, Sequence, Tuple
+from .error import StorageError
+
+
+# Local files at least this large are memory-mapped instead of read into a list
+MMAP_MIN_BYTES = 16 * 1024 * 1024
+
+
+class MmapDictionary(Sequence[str]):
+    """Read-only word list backed by a memory-mapped file (one word per line)"""
+
+    def __init__(self, path: Path):
+        """
+        Map a dictionary file and index its non-blank lines
+
+        Args:
+            path: Word list path
+        """
+        self.path = Path(path)
+        try:
+            with open(self.path, "rb") as f:
+                size = self.path.stat().st_size
+                # The mapping keeps its own handle on the file
+                self._map = mmap.mmap(f.fileno(), 0, access=mmap.ACCESS_READ) if size else b""
+        except (OSError, ValueError) as e:
+            raise StorageError(f"Cannot map dictionary {path}: {e}")
+
+        # Start and end offsets of every non-blank line (end excludes the newline)
+        self._starts = array("Q")
+        self._ends = array("Q")
+        position = 0
+        while position < size:
+            newline = self._map.find(b"\n", position)
+            end = size if newline == -1 else newline
+            line_end = end - 1 if end > position and self._map[end - 1:end] == b"\r" else end
+            if self._map[position:line_end].strip():
+                self._starts.append(position)
+                self._ends.append(line_end)
+            position = end + 1
+
+    def close(self):
+        """Release the mapping"""
+        if isinstance(self._map, mmap.mmap):
+            self._map.close()
+
+    def __len__(self) -> int:
+        return len(self._starts)
+
+    def __getitem__(self, index):
+        if isinstance(index, slice):
+            return [self[i] for i in range(*index.indices(len(self)))]
+        if index < 0:
+            index += len(self)
+        if not 0 <= index < len(self):
+            raise IndexError(index)
+        return self._map[self._starts[index]:self._ends[index]].decode("utf-8", errors="ignore")
+
+    def __iter__(self) -> Iterator[str]:
+        for start, end in zip(self._starts, self._ends):
+            yield self._map[start:end].decode("utf-8", errors="ignore")
+
+
+def sequence_product(sequences: List[Sequence[str]]) -> Iterator[Tuple[str, ...]]:
+    """
+    Cartesian product of sequences, accessed by index
+
+    Unlike itertools.product this does not copy its inputs into tuples,
+    so memory-mapped dictionaries stay on disk.
+
+    Args:
+        sequences: Sequences to combine
+
+    Yields:
+        Tuples in the same order as itertools.product
+    """
+    if any(len(sequence) == 0 for sequence in sequences):
+        return
+    digits = [0] * len(sequences)
+    current = [sequence[0] for sequence in sequences]
+    while True:
+        yield tuple(current)
+        # Odometer increment, rightmost sequence fastest
+        for i in range(len(sequences) - 1, -1, -1):
+            digits[i] += 1
+            if digits[i] < len(sequences[i]):
+                current[i] = sequences[i][digits[i]]
+                break
+            digits[i] = 0
+            current[i] = sequences[i][0]
+        else:
+            return
@@ -0,0 +1,247 @@
+"""
+Directory (LDAP / Active Directory) export ingestion
+
+Reads CSV exports of user objects (csvde, PowerShell Get-ADUser |
+Export-Csv, LDAP browser dumps), maps their columns onto field ids
+and username formats, and builds one targeted configuration per
+employee.
+"""
+
+import copy
+import csv
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Dict, List, Optional, Sequence
+
+from .config import Config
+from .error import ConfigError
+
+
+# Normalized column header -> employee attribute
+COLUMN_ALIASES: Dict[str, str] = {
+    "displayname": "display_name", "name": "display_name", "cn": "display_name",
+    "fullname": "display_name",
+    "givenname": "first_name", "firstname": "first_name", "first": "first_name",
+    "sn": "last_name", "surname": "last_name", "lastname": "last_name", "last": "last_name",
+    "samaccountname": "username", "username": "username", "uid": "username",
+    "login": "username", "userprincipalname": "upn",
+    "department": "department", "dept": "department",
+    "l": "city", "city": "city", "location": "city",
+    "company": "company", "o": "company",
+    "title": "job_title", "jobtitle": "job_title",
+    "mail": "email", "email": "email", "emailaddress": "email",
+}
+
+# Employee attribute -> field id its values are used for
+ATTRIBUTE_FIELDS: Dict[str, str] = {
+    "first_name": "first_name",
+    "last_name": "last_name_0",
+    "username": "username",
+    "department": "department",
+    "city": "city_name",
+    "company": "company_name",
+    "job_title": "job_title",
+}
+
+# Common corporate username conventions
+USERNAME_FORMATS: Dict[str, str] = {
+    "first.last": "{first}.{last}",
+    "first_last": "{first}_{last}",
+    "firstlast": "{first}{last}",
+    "flast": "{f}{last}",
+    "f.last": "{f}.{last}",
+    "firstl": "{first}{l}",
+    "lastf": "{last}{f}",
+    "last.first": "{last}.{first}",
+    "first": "{first}",
+}
+
+
+@dataclass
+class Employee:
+    """One user object from a directory export"""
+    display_name: str = ""
+    first_name: str = ""
+    last_name: str = ""
+    username: str = ""
+    upn: str = ""
+    department: str = ""
+    city: str = ""
+    company: str = ""
+    job_title: str = ""
+    email: str = ""
+    extra: Dict[str, str] = field(default_factory=dict)
+
+    @property
+    def key(self) -> str:
+        """Stable identifier used for per-employee output files"""
+        for value in (self.username, self.upn.split("@")[0], self.email.split("@")[0],
+                      f"{self.first_name}.{self.last_name}".strip(".")):
+            if value:
+                return re.sub(r"[^\w.-]", "_", value.lower())
+        return "employee"
+
+    def attributes(self) -> Dict[str, str]:
+        """Non-empty attributes that map onto field ids"""
+        return {name: getattr(self, name) for name in ATTRIBUTE_FIELDS if getattr(self, name)}
+
+
+@dataclass
+class DirectoryExport:
+    """Parsed export: recognized columns and the employees found"""
+    columns: Dict[str, str]
+    employees: List[Employee]
+
+
+def normalize_header(header: str) -> str:
+    """Lowercase a column header and drop spaces, dashes and underscores"""
+    return re.sub(r"[\s_\-]", "", header.strip()).lower()
+
+
+def map_columns(headers: Sequence[str]) -> Dict[str, str]:
+    """
+    Map export column headers to employee attributes
+
+    Args:
+        headers: Column headers as they appear in the export
+
+    Returns:
+        Header -> attribute for every recognized column
+    """
+    mapping = {}
+    for header in headers:
+        attribute = COLUMN_ALIASES.get(normalize_header(header))
+        if attribute and attribute not in mapping.values():
+            mapping[header] = attribute
+    return mapping
+
+
+def split_display_name(display_name: str):
+    """Split "First Last" or "Last, First" into (first, last)"""
+    if "," in display_name:
+        last, first = display_name.split(",", 1)
+        return first.strip().split(" ")[0], last.strip()
+    parts = display_name.split()
+    if len(parts) < 2:
+        return display_name.strip(), ""
+    return parts[0], parts[-1]
+
+
+def parse_directory_export(path: Path) -> DirectoryExport:
+    """
+    Parse a CSV directory export
+
+    The delimiter is sniffed (comma, semicolon or tab). Names missing
+    as separate columns are taken from displayName.
+
+    Args:
+        path: Export file
+
+    Returns:
+        Column mapping and employees in file order
+    """
+    try:
+        text = Path(path).read_text(encoding="utf-8-sig", errors="ignore")
+    except OSError as e:
+        raise ConfigError(f"Cannot read directory export {path}: {e.strerror}")
+
+    try:
+        dialect = csv.Sniffer().sniff(text[:4096], delimiters=",;\t")
+    except csv.Error:
+        dialect = csv.excel
+    reader = csv.DictReader(text.splitlines(), dialect=dialect)
+    mapping = map_columns(reader.fieldnames or [])
+    if not mapping:
+        raise ConfigError(f"No recognized directory columns in {path} "
+                          f"(expected e.g. displayName, sAMAccountName, department, city)")
+
+    employees = []
+    for row in reader:
+        employee = Employee()
+        for header, value in row.items():
+            value = (value or "").strip()
+            if header in mapping:
+                setattr(employee, mapping[header], value)
+            elif header and value:
+                employee.extra[header] = value
+        if employee.display_name and not (employee.first_name and employee.last_name):
+            first, last = split_display_name(employee.display_name)
+            employee.first_name = employee.first_name or first
+            employee.last_name = employee.last_name or last
+        if employee.attributes():
+            employees.append(employee)
+    return DirectoryExport(mapping, employees)
+
+
+def username_candidates(employee: Employee, formats: Optional[Sequence[str]] = None) -> List[str]:
+    """
+    Likely usernames for an employee
+
+    Args:
+        employee: Employee
+        formats: USERNAME_FORMATS names (all if omitted)
+
+    Returns:
+        Unique lowercase usernames, the exported account name first
+    """
+    names = []
+    if employee.username:
+        names.append(employee.username.lower())
+    first = re.sub(r"[^\w]", "", employee.first_name).lower()
+    last = re.sub(r"[^\w]", "", employee.last_name).lower()
+    for name in formats or USERNAME_FORMATS:
+        if name not in USERNAME_FORMATS:
+            raise ConfigError(f"Unknown username format: {name} (known: {', '.join(USERNAME_FORMATS)})")
+        template = USERNAME_FORMATS[name]
+        if ("{last}" in template or "{l}" in template) and not last:
+            continue
+        if not first:
+            continue
+        names.append(template.format(first=first, last=last, f=first[:1], l=last[:1]))
+    return list(dict.fromkeys(names))
+
+
+def case_variants(value: str) -> List[str]:
+    """The value as exported, capitalized and lowercase, without spaces"""
+    value = re.sub(r"\s+", "", value)
+    return list(dict.fromkeys([value, value[:1].upper() + value[1:].lower(), value.lower()]))
+
+
+def employee_config(employee: Employee, base: Optional[Config] = None,
+                    years: Optional[Sequence[int]] = None, max_components: int = 2) -> Config:
+    """
+    Targeted configuration for one employee
+
+    Each known attribute becomes an inline field; candidates are every
+    ordering of one to max_components of those fields, optionally with
+    a year field.
+
+    Args:
+        employee: Employee
+        base: Configuration providing transforms, filters and output settings
+        years: Years to combine with the attributes
+        max_components: Largest number of fields in one candidate
+
+    Returns:
+        Configuration in field mode
+    """
+    config = copy.deepcopy(base) if base is not None else Config()
+    config.enabled_fields = []
+    config.field_sources = {}
+    config.words = []
+
+    for attribute, value in employee.attributes().items():
+        field_id = ATTRIBUTE_FIELDS[attribute]
+        config.enabled_fields.append(field_id)
+        config.field_sources[field_id] = case_variants(value)
+    if years:
+        config.enabled_fields.append("year")
+        config.field_sources["year"] = [str(year) for year in years]
+    if not config.enabled_fields:
+        raise ConfigError(f"No usable attributes for employee {employee.key}")
+
+    config.field_order = "permute"
+    config.min_components = 1
+    config.max_components = min(max_components, len(config.enabled_fields))
+    return config
@@ -1,36 +1,102 @@
 """Error types and handling for OmniWordlist Pro"""
 
 
+# Process exit statuses, so wrappers can tell failures apart
+# (2 is left to click's usage errors)
+EXIT_OK = 0
+EXIT_ERROR = 1
+EXIT_IO = 3
+EXIT_INTERRUPTED = 4
+EXIT_KEYSPACE_TOO_LARGE = 5
+EXIT_CONFIG = 6
+
+
 class OmniError(Exception):
     """Base exception class for OmniWordlist Pro"""
-    pass
+    code = "error"
+    exit_code = EXIT_ERROR
 
 
 class ConfigError(OmniError):
     """Configuration validation error"""
-    pass
+    code = "config_error"
+    exit_code = EXIT_CONFIG
 
 
 class GeneratorError(OmniError):
     """Error during wordlist generation"""
-    pass
+    code = "generator_error"
+
+
+class KeyspaceTooLargeError(GeneratorError):
+    """The keyspace exceeds the configured max_keyspace"""
+    code = "keyspace_too_large"
+    exit_code = EXIT_KEYSPACE_TOO_LARGE
+
+
+class GenerationInterrupted(GeneratorError):
+    """Generation was interrupted after writing a checkpoint"""
+    code = "interrupted"
+    exit_code = EXIT_INTERRUPTED
+    
+    def __init__(self, message: str, job_id: str = None):
+        super().__init__(message)
+        self.job_id = job_id
 
 
 class StorageError(OmniError):
     """Error in storage operations"""
-    pass
+    code = "io_error"
+    exit_code = EXIT_IO
 
 
 class TransformError(OmniError):
     """Error applying transforms"""
-    pass
+    code = "transform_error"
+    exit_code = EXIT_CONFIG
 
 
 class FilterError(OmniError):
     """Error applying filters"""
-    pass
+    code = "filter_error"
+    exit_code = EXIT_CONFIG
 
 
 class PresetError(OmniError):
     """Error loading or saving presets"""
-    pass
+    code = "preset_error"
+    exit_code = EXIT_CONFIG
+
+
+class OpsecError(OmniError):
+    """A disk write refused in OPSEC mode"""
+    code = "opsec_refused"
+    exit_code = EXIT_CONFIG
+
+
+def exit_code_for(error: BaseException) -> int:
+    """Exit status for an exception"""
+    if isinstance(error, OmniError):
+        return error.exit_code
+    if isinstance(error, OSError):
+        return EXIT_IO
+    return EXIT_ERROR
+
+
+def error_payload(error: BaseException) -> dict:
+    """Machine-readable description of an error (for --json)"""
+    if isinstance(error, OmniError):
+        code = error.code
+    elif isinstance(error, OSError):
+        code = StorageError.code
+    else:
+        code = OmniError.code
+    payload = {
+        "error": code,
+        "type": type(error).__name__,
+        "message": str(error),
+        "exit_code": exit_code_for(error),
+    }
+    if isinstance(error, GenerationInterrupted) and error.job_id:
+        payload["job_id"] = error.job_id
+    return payload
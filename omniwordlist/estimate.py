@@ -0,0 +1,124 @@
+"""
+Attack-time estimation
+
+Converts a keyspace size into the expected time an offline cracking
+rig needs to exhaust it, so a config can be scoped before generating.
+"""
+
+import re
+from typing import Dict, List, Tuple
+from .error import ConfigError
+
+
+# Approximate hashcat benchmark speeds (hashes/second) of a single
+# RTX 4090. Real rigs vary; use --hash-rate for measured numbers.
+HASH_MODE_RATES: Dict[str, float] = {
+    "ntlm": 288e9,
+    "md5": 164e9,
+    "sha1": 50e9,
+    "sha256": 22e9,
+    "sha512": 7.5e9,
+    "netntlmv2": 9e9,
+    "md5crypt": 68e6,
+    "sha512crypt": 3e6,
+    "wpa2": 2.5e6,
+    "bcrypt": 184e3,
+}
+
+# SI multipliers accepted in hash rates (50GH/s, 1.5 MH/s, 200k)
+RATE_UNITS = {
+    "": 1,
+    "k": 1e3,
+    "m": 1e6,
+    "g": 1e9,
+    "t": 1e12,
+}
+
+_RATE_RE = re.compile(r"^\s*([0-9]*\.?[0-9]+)\s*([kmgt]?)\s*(h(/s)?)?\s*$", re.IGNORECASE)
+
+# Duration units from largest to smallest
+_DURATION_UNITS: List[Tuple[str, int]] = [
+    ("year", 365 * 24 * 3600),
+    ("day", 24 * 3600),
+    ("hour", 3600),
+    ("minute", 60),
+    ("second", 1),
+]
+
+
+def parse_hash_rate(rate: str) -> float:
+    """
+    Parse a human hash rate such as "50GH/s" or "1.5M"
+    
+    Args:
+        rate: Rate string
+        
+    Returns:
+        Hashes per second
+    """
+    match = _RATE_RE.match(rate)
+    if not match:
+        raise ConfigError(f"Invalid hash rate: {rate}")
+    
+    value = float(match.group(1)) * RATE_UNITS[match.group(2).lower()]
+    if value <= 0:
+        raise ConfigError(f"Hash rate must be positive: {rate}")
+    return value
+
+
+def rate_for_mode(mode: str) -> float:
+    """
+    Reference hash rate for a hash mode
+    
+    Args:
+        mode: Hash mode name (ntlm, md5, bcrypt, ...)
+        
+    Returns:
+        Hashes per second
+    """
+    key = mode.lower()
+    if key not in HASH_MODE_RATES:
+        known = ", ".join(sorted(HASH_MODE_RATES))
+        raise ConfigError(f"Unknown hash mode: {mode} (known: {known})")
+    return HASH_MODE_RATES[key]
+
+
+def attack_seconds(keyspace: int, hash_rate: float, devices: int = 1) -> float:
+    """
+    Seconds needed to exhaust a keyspace
+    
+    Args:
+        keyspace: Number of candidates
+        hash_rate: Hashes per second of one device
+        devices: Number of devices working in parallel
+        
+    Returns:
+        Duration in seconds
+    """
+    return keyspace / (hash_rate * max(1, devices))
+
+
+def format_duration(seconds: float) -> str:
+    """
+    Format a duration using its two most significant units
+    
+    The second unit is always the one right below the largest, and is
+    left out when zero ("1 year", never "1 year 5 hours").
+    
+    Args:
+        seconds: Duration in seconds
+        
+    Returns:
+        Human readable duration (e.g. "3 days 4 hours")
+    """
+    if seconds < 1:
+        return "under a second"
+    
+    remaining = int(seconds)
+    largest = next(i for i, (_, size) in enumerate(_DURATION_UNITS) if remaining >= size)
+    parts = []
+    for name, size in _DURATION_UNITS[largest:largest + 2]:
+        count, remaining = divmod(remaining, size)
+        if count:
+            parts.append(f"{count:,} {name}{'s' if count != 1 else ''}")
+    return " ".join(parts)
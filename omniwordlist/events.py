@@ -0,0 +1,68 @@
+"""
+Generation event hooks
+
+Observers let applications embedding the library follow a run
+(progress bars, service dashboards) without scraping log output.
+"""
+
+from typing import List
+
+
+class GeneratorObserver:
+    """
+    Base observer for generator events
+
+    Subclass and override only the hooks you care about; every hook
+    is a no-op by default.
+    """
+
+    def on_token(self, token: str) -> None:
+        """Called for every token emitted by the generator"""
+        pass
+
+    def on_progress(self, tokens_generated: int) -> None:
+        """Called every `progress_interval` emitted tokens"""
+        pass
+
+    def on_stage_change(self, stage: str) -> None:
+        """Called when the generator enters a new stage (mode, length, finished)"""
+        pass
+
+    def on_checkpoint(self, state: dict) -> None:
+        """Called after a checkpoint has been written"""
+        pass
+
+
+class ObserverGroup:
+    """Dispatch events to a list of observers"""
+
+    def __init__(self):
+        self.observers: List[GeneratorObserver] = []
+
+    def add(self, observer: GeneratorObserver):
+        """Register an observer"""
+        self.observers.append(observer)
+
+    def remove(self, observer: GeneratorObserver):
+        """Unregister an observer"""
+        if observer in self.observers:
+            self.observers.remove(observer)
+
+    def token(self, token: str):
+        for observer in self.observers:
+            observer.on_token(token)
+
+    def progress(self, tokens_generated: int):
+        for observer in self.observers:
+            observer.on_progress(tokens_generated)
+
+    def stage_change(self, stage: str):
+        for observer in self.observers:
+            observer.on_stage_change(stage)
+
+    def checkpoint(self, state: dict):
+        for observer in self.observers:
+            observer.on_checkpoint(state)
+
+    def __bool__(self) -> bool:
+        return bool(self.observers)
@@ -0,0 +1,208 @@
+"""
+Single-token explanation
+
+Answers "why isn't X in my list?" for one configuration: whether the
+token is produced and roughly where, which filters it passes or fails,
+its entropy and quality, and which enabled field values and transforms
+could derive it. Nothing is written; the candidate stream is replayed
+up to a scan limit.
+"""
+
+import re
+from dataclasses import dataclass, field
+from typing import Dict, List, Optional, Sequence
+
+from .charset import check_duplicate_limit
+from .config import Config
+from .error import GeneratorError
+from .filters import calculate_quality_score, estimate_entropy
+from .generator import Generator
+from .transforms import RANDOM_TRANSFORMS, apply_transforms, list_transforms
+
+
+DEFAULT_SCAN_LIMIT = 1_000_000
+
+# Random transforms that append a recognisable suffix
+APPENDED_SUFFIXES = {
+    "append_numbers_4": re.compile(r"\d{4}"),
+    "append_numbers_2": re.compile(r"\d{2}"),
+    "append_year": re.compile(r"(19|20)\d{2}"),
+}
+
+# Cap on reported derivations and components
+MAX_MATCHES = 20
+
+
+@dataclass
+class FilterCheck:
+    """Outcome of one filter of the pipeline"""
+    name: str
+    passed: bool
+
+
+@dataclass
+class Derivation:
+    """A field value and transform chain yielding the token"""
+    field_id: str
+    value: str
+    transforms: List[str]
+
+
+@dataclass
+class TokenExplanation:
+    """Everything known about one token under a configuration"""
+    token: str
+    entropy_mode: str
+    entropy: float
+    quality: float
+    filters: List[FilterCheck] = field(default_factory=list)
+    # Later stages that would drop the token (exclude_top, denylist, start_string, end_string)
+    blocked_by: List[str] = field(default_factory=list)
+    # True / False once decided, None if the scan limit was reached first
+    produced: Optional[bool] = None
+    # 1-based position among emitted tokens when produced
+    rank: Optional[int] = None
+    # Stage that rejected the token when it came up as a candidate
+    rejected_by: Optional[str] = None
+    candidates_scanned: int = 0
+    derivations: List[Derivation] = field(default_factory=list)
+    # Enabled field values found inside the token
+    components: List[Derivation] = field(default_factory=list)
+
+    @property
+    def passes_filters(self) -> bool:
+        return all(check.passed for check in self.filters)
+
+
+def _blocking_stages(generator: Generator, token: str) -> List[str]:
+    """Post-filter stages of Generator._process_token that would drop the token"""
+    config = generator.config
+    stages = []
+    if generator.duplicate_limits and not check_duplicate_limit(token, generator.duplicate_limits,
+                                                                config.custom_charsets):
+        stages.append("duplicate_limit")
+    if token in generator.excluded_tokens:
+        stages.append("exclude_top")
+    if token in generator.denied_tokens:
+        stages.append("denylist")
+    if generator.campaign is not None and generator.campaign.seen(token):
+        stages.append("campaign")
+    keyspace = generator.keyspace() if generator.seeks_string_bounds else None
+    if keyspace is not None and (config.start_string or config.end_string or config.skip or config.take):
+        # Seeked modes bound raw candidates by rank
+        try:
+            rank = keyspace.rank_of(token)
+        except GeneratorError:
+            return stages
+        first, last = generator.string_bounds(keyspace)
+        if rank < first:
+            stages.append("start_string")
+        if rank >= last:
+            stages.append("end_string")
+        if first <= rank < last:
+            window_first, window_last = generator.rank_bounds(keyspace)
+            if rank < window_first:
+                stages.append("skip")
+            elif rank >= window_last:
+                stages.append("take")
+        return stages
+    if config.start_string and token < config.start_string:
+        stages.append("start_string")
+    if config.end_string and token > config.end_string:
+        stages.append("end_string")
+    return stages
+
+
+def _transform_chains(config: Config) -> List[List[str]]:
+    """Chains tried on field values: none, each deterministic transform, and the configured chain"""
+    # Locale case variants (uppercase:tr, ...) only differ on a few letters, so
+    # they are tried for the locales the configured chain uses
+    locales = {name.partition(":")[2] for name in config.transforms if ":" in name}
+    chains = [[]] + [[name] for name in list_transforms()
+                     if name not in RANDOM_TRANSFORMS and name.partition(":")[2] in locales | {""}]
+    if config.transforms and list(config.transforms) not in chains:
+        chains.append(list(config.transforms))
+    return chains
+
+
+def _derivations(generator: Generator, fields: Dict[str, Sequence[str]], token: str) -> List[Derivation]:
+    """Single field values that some transform chain turns into the token"""
+    chains = _transform_chains(generator.config)
+    found = []
+    for field_id, values in fields.items():
+        for value in dict.fromkeys(values):
+            base = f"{generator.prefix or ''}{value}{generator.suffix or ''}"
+            for chain in chains:
+                if apply_transforms(base, chain) == token:
+                    found.append(Derivation(field_id, value, chain))
+            if token.startswith(base):
+                for name, suffix in APPENDED_SUFFIXES.items():
+                    if suffix.fullmatch(token[len(base):]):
+                        found.append(Derivation(field_id, value, [name]))
+            if len(found) >= MAX_MATCHES:
+                return found[:MAX_MATCHES]
+    return found
+
+
+def _components(fields: Dict[str, Sequence[str]], token: str) -> List[Derivation]:
+    """Field values (two characters or longer) contained in the token, ignoring case"""
+    lowered = token.lower()
+    found = []
+    for field_id, values in fields.items():
+        for value in dict.fromkeys(values):
+            if len(value) >= 2 and value.lower() in lowered:
+                found.append(Derivation(field_id, value, []))
+                if len(found) >= MAX_MATCHES:
+                    return found
+    return found
+
+
+def explain_token(config: Config, token: str, scan_limit: Optional[int] = DEFAULT_SCAN_LIMIT) -> TokenExplanation:
+    """
+    Explain how a configuration treats a token
+
+    Args:
+        config: Configuration to replay
+        token: Token to look for, as it would appear in the output
+        scan_limit: Candidates to replay before giving up (None for no limit)
+
+    Returns:
+        Explanation; produced is None when the scan limit was hit first
+    """
+    generator = Generator(config)
+    mode = config.filters.entropy_mode
+    explanation = TokenExplanation(token, mode, estimate_entropy(token, mode),
+                                   calculate_quality_score(token))
+    explanation.filters = [FilterCheck(f.name, f.should_include(token))
+                           for f in generator.filter_pipeline.filters]
+    explanation.blocked_by = _blocking_stages(generator, token)
+
+    if generator._enabled_fields() and not config.seed_words and not config.pattern:
+        fields = dict(zip(generator._enabled_fields(), generator._field_values()))
+        explanation.derivations = _derivations(generator, fields, token)
+        explanation.components = _components(fields, token)
+
+    emitted = 0
+    complete = True
+    for candidate, reason in generator.trace():
+        explanation.candidates_scanned += 1
+        if reason is None:
+            emitted += 1
+        if candidate == token:
+            if reason is None:
+                explanation.produced, explanation.rank = True, emitted
+                return explanation
+            # A dedupe rejection means an earlier copy was emitted and found above
+            explanation.rejected_by = explanation.rejected_by or reason
+        if scan_limit is not None and explanation.candidates_scanned >= scan_limit:
+            complete = False
+            break
+
+    if complete:
+        explanation.produced = False
+    return explanation
+
+
+def describe_chain(transforms: Sequence[str]) -> str:
+    """Human-readable transform chain"""
+    return " -> ".join(transforms) if transforms else "(as is)"
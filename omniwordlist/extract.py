@@ -0,0 +1,95 @@
+"""
+Range extraction from existing wordlists
+
+Pulls a contiguous region out of a generated list, by line numbers or
+by boundary tokens, so a slice of the keyspace can be handed to a
+teammate. Compressed lists are decompressed transparently; plain lists
+with an index sidecar are entered with a seek instead of a scan.
+"""
+
+from itertools import islice
+from pathlib import Path
+from typing import Iterator, Optional
+
+from .error import ConfigError
+from .index import iter_lines, load_index
+from .storage import detect_compression, open_text
+
+
+def _lines_from(path: Path, offset: int = 0) -> Iterator[str]:
+    """Tokens of a list from a byte offset (0 for compressed lists)"""
+    if detect_compression(path):
+        with open_text(path) as f:
+            for line in f:
+                yield line.rstrip("\r\n")
+    else:
+        for _, token in iter_lines(path, offset):
+            yield token
+
+
+def slice_lines(path: Path, first: int, last: Optional[int] = None) -> Iterator[str]:
+    """
+    Lines first..last of a wordlist
+
+    Args:
+        path: Wordlist, optionally compressed
+        first: First line (1-based)
+        last: Last line, inclusive (end of file if omitted)
+
+    Yields:
+        Tokens of the range
+    """
+    if first < 1 or (last is not None and last < first):
+        raise ConfigError(f"Invalid line range {first}..{last if last is not None else ''}")
+
+    index = None if detect_compression(path) else load_index(path)
+    offset, line = index.offset_of_line(first - 1) if index else (0, 0)
+    stop = None if last is None else last - line
+    yield from islice(_lines_from(path, offset), first - 1 - line, stop)
+
+
+def slice_tokens(path: Path, first: Optional[str] = None, last: Optional[str] = None,
+                 sorted_list: Optional[bool] = None) -> Iterator[str]:
+    """
+    The region of a wordlist between two tokens, inclusive
+
+    In a sorted list the region is every token with first <= token <= last,
+    whether or not the boundaries occur. Otherwise it runs from the first
+    occurrence of `first` to the next occurrence of `last`.
+
+    Args:
+        path: Wordlist, optionally compressed
+        first: Start token (start of file if omitted)
+        last: End token (end of file if omitted)
+        sorted_list: Whether the list is sorted (taken from the index if omitted)
+
+    Yields:
+        Tokens of the region
+    """
+    index = None if detect_compression(path) else load_index(path)
+    if sorted_list is None:
+        sorted_list = bool(index and index.sorted)
+
+    if sorted_list:
+        offset = 0
+        if index and first is not None:
+            offset, _ = index.offset_of_token(first)
+        for token in _lines_from(path, offset):
+            if first is not None and token < first:
+                continue
+            if last is not None and token > last:
+                return
+            yield token
+        return
+
+    started = first is None
+    for token in _lines_from(path):
+        if not started:
+            if token != first:
+                continue
+            started = True
+        yield token
+        if last is not None and token == last:
+            return
+    if not started:
+        raise ConfigError(f"Start token not found: {first}")
@@ -5,7 +5,7 @@ Provides extensive field definitions across personal, technical,
 cultural, and creative categories.
 """
 
-from typing import Dict, List, Optional
+from typing import Dict, Iterable, Iterator, List, Optional, Sequence
 
 
 # Field definitions with metadata
@@ -18,6 +18,7 @@ FIELDS = {
         "type": "string",
         "examples": ["John", "Michael", "David", "James", "Robert"],
         "cardinality": 1000,
+        "default_enabled": True,
     },
     "first_name_female_0": {
         "id": "first_name_female_0",
@@ -26,6 +27,7 @@ FIELDS = {
         "type": "string",
         "examples": ["Mary", "Sarah", "Jennifer", "Emily", "Jessica"],
         "cardinality": 1000,
+        "default_enabled": True,
     },
     "last_name_0": {
         "id": "last_name_0",
@@ -34,6 +36,7 @@ FIELDS = {
         "type": "string",
         "examples": ["Smith", "Johnson", "Williams", "Brown", "Jones"],
         "cardinality": 5000,
+        "default_enabled": True,
     },
     "birth_year": {
         "id": "birth_year",
@@ -102,7 +105,7 @@ FIELDS = {
         "category": "patterns",
         "group": "suffixes",
         "type": "string",
-        "examples": ["123", "2024", "!", "2023", "!!", "@123"],
+        "examples": ["123", "{year}", "!", "{year-1}", "!!", "@123"],
         "cardinality": 100,
     },
     "common_prefix_0": {
@@ -201,8 +204,349 @@ FIELDS = {
         "examples": ["dog", "cat", "bird", "fish", "hamster"],
         "cardinality": 50,
     },
+    
+    # Region-keyed sports and pop-culture packs
+    "cricket_team_in": {
+        "id": "cricket_team_in",
+        "category": "sports",
+        "group": "cricket",
+        "type": "string",
+        "region": "IN",
+        "examples": ["CSK", "MumbaiIndians", "RCB", "KKR", "SunRisers", "TeamIndia"],
+        "cardinality": 50,
+    },
+    "cricket_player_in": {
+        "id": "cricket_player_in",
+        "category": "sports",
+        "group": "cricket",
+        "type": "string",
+        "region": "IN",
+        "examples": ["Sachin", "Dhoni", "Virat", "Kohli", "Rohit", "Bumrah"],
+        "cardinality": 500,
+    },
+    "bollywood_star_in": {
+        "id": "bollywood_star_in",
+        "category": "pop_culture",
+        "group": "movies",
+        "type": "string",
+        "region": "IN",
+        "examples": ["Shahrukh", "Salman", "Amitabh", "Deepika", "Ranbir", "Katrina"],
+        "cardinality": 300,
+    },
+    "nfl_team_us": {
+        "id": "nfl_team_us",
+        "category": "sports",
+        "group": "football_us",
+        "type": "string",
+        "region": "US",
+        "examples": ["Cowboys", "Patriots", "Packers", "Eagles", "Steelers", "Chiefs"],
+        "cardinality": 32,
+    },
+    "nba_team_us": {
+        "id": "nba_team_us",
+        "category": "sports",
+        "group": "basketball",
+        "type": "string",
+        "region": "US",
+        "examples": ["Lakers", "Celtics", "Bulls", "Warriors", "Knicks", "Heat"],
+        "cardinality": 30,
+    },
+    "pop_culture_us": {
+        "id": "pop_culture_us",
+        "category": "pop_culture",
+        "group": "franchises",
+        "type": "string",
+        "region": "US",
+        "examples": ["Marvel", "StarWars", "Batman", "Pokemon", "Simpsons", "Friends"],
+        "cardinality": 500,
+    },
+    "football_club_eu": {
+        "id": "football_club_eu",
+        "category": "sports",
+        "group": "football",
+        "type": "string",
+        "region": "EU",
+        "examples": ["Barcelona", "RealMadrid", "Liverpool", "Arsenal", "Bayern", "Juventus", "PSG"],
+        "cardinality": 200,
+    },
+    "football_player_eu": {
+        "id": "football_player_eu",
+        "category": "sports",
+        "group": "football",
+        "type": "string",
+        "region": "EU",
+        "examples": ["Messi", "Ronaldo", "Mbappe", "Haaland", "Salah", "Zidane"],
+        "cardinality": 1000,
+    },
+    
+    # Numeric formats (values produced by omniwordlist.formats)
+    "phone_last_4": {
+        "id": "phone_last_4",
+        "category": "numeric",
+        "group": "phone",
+        "type": "generated",
+        "generator": "phone_last_digits",
+        "params": {"length": 4},
+        "examples": ["1234", "0000", "4321", "9876", "5555"],
+        "cardinality": 10000,
+    },
+    "phone_last_6": {
+        "id": "phone_last_6",
+        "category": "numeric",
+        "group": "phone",
+        "type": "generated",
+        "generator": "phone_last_digits",
+        "params": {"length": 6},
+        "examples": ["123456", "654321", "000000", "112233", "987654"],
+        "cardinality": 1000000,
+    },
+    "mobile_prefix_us": {
+        "id": "mobile_prefix_us",
+        "category": "numeric",
+        "group": "phone",
+        "type": "generated",
+        "generator": "mobile_prefixes",
+        "params": {"country": "US"},
+        "examples": ["212", "310", "415", "646", "917"],
+        "cardinality": 16,
+    },
+    "mobile_prefix_in": {
+        "id": "mobile_prefix_in",
+        "category": "numeric",
+        "group": "phone",
+        "type": "generated",
+        "generator": "mobile_prefixes",
+        "params": {"country": "IN"},
+        "examples": ["98", "99", "97", "90", "70"],
+        "cardinality": 29,
+    },
+    "phone_number_us": {
+        "id": "phone_number_us",
+        "category": "numeric",
+        "group": "phone",
+        "type": "generated",
+        "generator": "phone_numbers",
+        "params": {"country": "US"},
+        "examples": ["212-555-0199", "917-555-1234", "310-555-7788"],
+        "cardinality": 160000000,
+    },
+    "phone_number_in": {
+        "id": "phone_number_in",
+        "category": "numeric",
+        "group": "phone",
+        "type": "generated",
+        "generator": "phone_numbers",
+        "params": {"country": "IN"},
+        "examples": ["98765-43210", "99000-12345", "70123-45678"],
+        "cardinality": 2900000000,
+    },
+    "phone_number_uk": {
+        "id": "phone_number_uk",
+        "category": "numeric",
+        "group": "phone",
+        "type": "generated",
+        "generator": "phone_numbers",
+        "params": {"country": "UK"},
+        "examples": ["07700-900123", "07911-123456", "07400-555666"],
+        "cardinality": 700000000,
+    },
+    "phone_number_de": {
+        "id": "phone_number_de",
+        "category": "numeric",
+        "group": "phone",
+        "type": "generated",
+        "generator": "phone_numbers",
+        "params": {"country": "DE"},
+        "examples": ["0151-1234567", "0170-5551234", "0176-9876543"],
+        "cardinality": 100000000,
+    },
+    "phone_number_fr": {
+        "id": "phone_number_fr",
+        "category": "numeric",
+        "group": "phone",
+        "type": "generated",
+        "generator": "phone_numbers",
+        "params": {"country": "FR"},
+        "examples": ["06 12 34 56 78", "07 98 76 54 32", "06 00 11 22 33"],
+        "cardinality": 200000000,
+    },
+    "zip_code_us": {
+        "id": "zip_code_us",
+        "category": "numeric",
+        "group": "postal",
+        "type": "generated",
+        "generator": "postal_codes",
+        "params": {"country": "US"},
+        "examples": ["10001", "90210", "60601", "94105", "02134"],
+        "cardinality": 99450,
+    },
+    "pin_code_in": {
+        "id": "pin_code_in",
+        "category": "numeric",
+        "group": "postal",
+        "type": "generated",
+        "generator": "postal_codes",
+        "params": {"country": "IN"},
+        "examples": ["110001", "400001", "560001", "600001", "700001"],
+        "cardinality": 745126,
+    },
+    "plz_de": {
+        "id": "plz_de",
+        "category": "numeric",
+        "group": "postal",
+        "type": "generated",
+        "generator": "postal_codes",
+        "params": {"country": "DE"},
+        "examples": ["10115", "80331", "20095", "50667", "60311"],
+        "cardinality": 98932,
+    },
+    "postcode_outward_uk": {
+        "id": "postcode_outward_uk",
+        "category": "numeric",
+        "group": "postal",
+        "type": "generated",
+        "generator": "postal_codes",
+        "params": {"country": "UK"},
+        "examples": ["SW1A", "EC1A", "M1", "B1", "LS1"],
+        "cardinality": 26,
+    },
+    "code_postal_fr": {
+        "id": "code_postal_fr",
+        "category": "numeric",
+        "group": "postal",
+        "type": "generated",
+        "generator": "postal_codes",
+        "params": {"country": "FR"},
+        "examples": ["75001", "13001", "69001", "31000", "06000"],
+        "cardinality": 97891,
+    },
+}
+
+# Country codes that resolve to a broader pack region
+REGION_ALIASES = {
+    "UK": "EU", "GB": "EU", "DE": "EU", "FR": "EU", "ES": "EU", "IT": "EU", "NL": "EU",
+    "USA": "US", "IND": "IN",
 }
 
+# Prefix of synthetic field ids that stand for a whole region pack
+REGION_FIELD_PREFIX = "region:"
+
+
+def normalize_region(region: str) -> str:
+    """Resolve a region or country code to its pack region"""
+    code = region.strip().upper()
+    return REGION_ALIASES.get(code, code)
+
+
+def is_lazy_sequence(values) -> bool:
+    """Check for values computed by index (e.g. formats.PhoneNumbers), too many to copy into a list"""
+    return isinstance(values, Sequence) and not isinstance(values, (list, tuple, str))
+
+
+class FieldProvider:
+    """
+    Source of field definitions and values
+    
+    Subclass to plug database- or API-backed fields into generation
+    without touching the static catalog, then register an instance on a
+    FieldRegistry. Field dictionaries need at least "id", "category" and
+    "group" keys.
+    """
+    
+    def fields(self) -> Iterable[Dict]:
+        """Field dictionaries this provider serves"""
+        raise NotImplementedError
+    
+    def values(self, field_id: str) -> Optional[Iterable[str]]:
+        """Values of a field, or None if this provider does not serve it"""
+        raise NotImplementedError
+    
+    def field(self, field_id: str) -> Optional[Dict]:
+        """Field dictionary by ID, or None if this provider does not serve it"""
+        for field in self.fields():
+            if field['id'] == field_id:
+                return field
+        return None
+
+
+class CatalogProvider(FieldProvider):
+    """The built-in FIELDS catalog, including synthetic region pack fields"""
+    
+    def fields(self) -> Iterable[Dict]:
+        return FIELDS.values()
+    
+    def field(self, field_id: str) -> Optional[Dict]:
+        if field_id.startswith(REGION_FIELD_PREFIX):
+            return FieldManager.get_region_field(field_id[len(REGION_FIELD_PREFIX):])
+        return FIELDS.get(field_id)
+    
+    def values(self, field_id: str) -> Optional[Iterable[str]]:
+        # Static fields return their examples; generated fields run their format generator
+        field = self.field(field_id)
+        if field is None:
+            return None
+        if field.get('type') == 'generated':
+            from .formats import run_generator
+            return run_generator(field['generator'], field.get('params', {}))
+        return field['examples']
+
+
+class FieldRegistry:
+    """
+    Ordered set of field providers
+    
+    Providers are consulted in order and the first one serving a field
+    ID wins, so a provider registered with first=True can override
+    catalog fields.
+    """
+    
+    def __init__(self, providers: Optional[List[FieldProvider]] = None):
+        self.providers: List[FieldProvider] = list(providers) if providers is not None else [CatalogProvider()]
+    
+    def register(self, provider: FieldProvider, first: bool = False):
+        """
+        Add a provider
+        
+        Args:
+            provider: Provider to add
+            first: Consult it before the providers already registered
+        """
+        if first:
+            self.providers.insert(0, provider)
+        else:
+            self.providers.append(provider)
+    
+    def unregister(self, provider: FieldProvider):
+        """Remove a previously registered provider"""
+        self.providers.remove(provider)
+    
+    def fields(self) -> Iterator[Dict]:
+        """Every field, each ID once"""
+        seen = set()
+        for provider in self.providers:
+            for field in provider.fields():
+                if field['id'] not in seen:
+                    seen.add(field['id'])
+                    yield field
+    
+    def get_field(self, field_id: str) -> Optional[Dict]:
+        """Field dictionary by ID, or None if no provider serves it"""
+        for provider in self.providers:
+            field = provider.field(field_id)
+            if field is not None:
+                return field
+        return None
+    
+    def values(self, field_id: str) -> Optional[Sequence[str]]:
+        """Value list of a field, or None if no provider serves it"""
+        for provider in self.providers:
+            values = provider.values(field_id)
+            if is_lazy_sequence(values):
+                return values
+            if values is not None:
+                return list(values)
+        return None
+
 
 class FieldManager:
     """Manage field taxonomy and lookups"""
@@ -218,18 +562,87 @@ class FieldManager:
         Returns:
             Field dictionary or None
         """
-        return FIELDS.get(field_id)
+        return default_registry.get_field(field_id)
+    
+    @staticmethod
+    def get_field_values(field_id: str) -> Optional[Sequence[str]]:
+        """
+        Get the full value list of a field
+        
+        Static fields return their examples; generated fields run their
+        format generator (phone numbers stay a lazy sequence);
+        registered providers serve their own fields.
+        
+        Args:
+            field_id: Field identifier
+            
+        Returns:
+            List of values or None if the field is unknown
+        """
+        return default_registry.values(field_id)
+    
+    @staticmethod
+    def list_regions() -> List[str]:
+        """List all regions that have themed packs"""
+        return sorted({field['region'] for field in FIELDS.values() if 'region' in field})
+    
+    @staticmethod
+    def get_fields_by_region(region: str) -> List[Dict]:
+        """
+        Get all themed pack fields for a region
+        
+        Args:
+            region: Region or country code (IN, US, EU, UK, ...)
+            
+        Returns:
+            List of field dictionaries
+        """
+        code = normalize_region(region)
+        return [field for field in FIELDS.values() if field.get('region') == code]
+    
+    @staticmethod
+    def get_region_field(region: str) -> Optional[Dict]:
+        """
+        Build a synthetic field combining every pack of a region
+        
+        Args:
+            region: Region or country code
+            
+        Returns:
+            Field dictionary whose examples are the union of the region packs,
+            or None if the region has no packs
+        """
+        fields = FieldManager.get_fields_by_region(region)
+        if not fields:
+            return None
+        
+        examples = []
+        for field in fields:
+            for value in field['examples']:
+                if value not in examples:
+                    examples.append(value)
+        
+        code = normalize_region(region)
+        return {
+            "id": f"{REGION_FIELD_PREFIX}{code}",
+            "category": "region_pack",
+            "group": code,
+            "type": "string",
+            "region": code,
+            "examples": examples,
+            "cardinality": sum(field['cardinality'] for field in fields),
+        }
     
     @staticmethod
     def list_fields() -> List[str]:
         """List all field IDs"""
-        return list(FIELDS.keys())
+        return [field['id'] for field in default_registry.fields()]
     
     @staticmethod
     def list_categories() -> List[str]:
         """List all field categories"""
         categories = set()
-        for field in FIELDS.values():
+        for field in default_registry.fields():
             categories.add(field['category'])
         return sorted(categories)
     
@@ -245,10 +658,27 @@ class FieldManager:
             List of field dictionaries
         """
         return [
-            field for field in FIELDS.values()
+            field for field in default_registry.fields()
             if field['category'] == category
         ]
     
+    @staticmethod
+    def get_default_fields(categories: Optional[Iterable[str]] = None) -> List[Dict]:
+        """
+        Get fields marked default_enabled
+        
+        Args:
+            categories: Only fields in these categories (all if omitted)
+            
+        Returns:
+            List of field dictionaries
+        """
+        wanted = None if categories is None else set(categories)
+        return [
+            field for field in default_registry.fields()
+            if field.get('default_enabled') and (wanted is None or field['category'] in wanted)
+        ]
+    
     @staticmethod
     def search_fields(query: str) -> List[Dict]:
         """
@@ -263,10 +693,14 @@ class FieldManager:
         query_lower = query.lower()
         results = []
         
-        for field in FIELDS.values():
+        for field in default_registry.fields():
             if (query_lower in field['id'].lower() or
                 query_lower in field['category'].lower() or
                 query_lower in field['group'].lower()):
                 results.append(field)
         
         return results
+
+
+# Registry behind FieldManager and generation; register custom providers here
+default_registry = FieldRegistry()
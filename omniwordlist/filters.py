@@ -7,9 +7,11 @@ and other quality filters.
 
 import math
 import re
-from typing import Optional
+from typing import Dict, Optional
 from .error import FilterError
 from .config import FilterConfig
+from .charset import CharMembership
+from .toplists import BUILTIN_TOP_PASSWORDS
 
 
 def calculate_entropy(token: str) -> float:
@@ -40,6 +42,177 @@ def calculate_entropy(token: str) -> float:
     return entropy
 
 
+# Pool sizes used by the class-based estimators
+CLASS_POOL_SIZES = {
+    "lower": 26,
+    "upper": 26,
+    "digit": 10,
+    "symbol": 33,
+    "other": 100,
+}
+
+_COMMON_WORD_RANKS = {word: rank for rank, word in enumerate(BUILTIN_TOP_PASSWORDS, 1)}
+
+
+def char_class(char: str) -> str:
+    """
+    Classify a character for class-based entropy
+    
+    Args:
+        char: Single character
+        
+    Returns:
+        One of lower, upper, digit, symbol, other
+    """
+    if char.isascii():
+        if char.islower():
+            return "lower"
+        if char.isupper():
+            return "upper"
+        if char.isdigit():
+            return "digit"
+        return "symbol"
+    return "other"
+
+
+def calculate_class_entropy(token: str) -> float:
+    """
+    Calculate character-class entropy in bits
+    
+    Assumes each position is drawn from the union of the classes used
+    (lowercase, uppercase, digits, symbols), as password policies do.
+    
+    Args:
+        token: String to calculate entropy for
+        
+    Returns:
+        length * log2(pool size)
+    """
+    if not token:
+        return 0.0
+    
+    pool = sum(CLASS_POOL_SIZES[c] for c in {char_class(ch) for ch in token})
+    return len(token) * math.log2(pool)
+
+
+def calculate_nist_entropy(token: str) -> float:
+    """
+    Calculate entropy with the NIST SP 800-63 (2004) heuristic
+    
+    4 bits for the first character, 2 bits for characters 2-8, 1.5 bits
+    for characters 9-20 and 1 bit beyond, plus a 6 bit bonus when the
+    token mixes uppercase and non-alphabetic characters.
+    
+    Args:
+        token: String to calculate entropy for
+        
+    Returns:
+        Estimated entropy in bits
+    """
+    entropy = 0.0
+    for position in range(1, len(token) + 1):
+        if position == 1:
+            entropy += 4
+        elif position <= 8:
+            entropy += 2
+        elif position <= 20:
+            entropy += 1.5
+        else:
+            entropy += 1
+    
+    classes = {char_class(ch) for ch in token}
+    if "upper" in classes and classes & {"digit", "symbol", "other"}:
+        entropy += 6
+    return entropy
+
+
+def _run_length(token: str, start: int, step: int) -> int:
+    """Length of the run starting at `start` whose code points advance by `step`"""
+    end = start + 1
+    while end < len(token) and ord(token[end]) - ord(token[end - 1]) == step:
+        end += 1
+    return end - start
+
+
+def calculate_guesses_log10(token: str) -> float:
+    """
+    Estimate log10 of the guesses needed to crack a token
+    
+    A lightweight take on zxcvbn: the token is split greedily into
+    common-password matches, repeated characters, sequences (abc, 321)
+    and brute-forced characters, and the guesses of each segment are
+    multiplied together.
+    
+    Args:
+        token: String to estimate
+        
+    Returns:
+        log10 of the estimated guess count
+    """
+    if not token:
+        return 0.0
+    
+    lowered = token.lower()
+    log_guesses = 0.0
+    i = 0
+    while i < len(token):
+        # Longest common password starting here
+        match = 0
+        for end in range(len(token), i + 2, -1):
+            if lowered[i:end] in _COMMON_WORD_RANKS:
+                match = end - i
+                break
+        if match:
+            rank = _COMMON_WORD_RANKS[lowered[i:i + match]]
+            uppercase_variants = 2 if lowered[i:i + match] != token[i:i + match] else 1
+            log_guesses += math.log10(rank * uppercase_variants)
+            i += match
+            continue
+        
+        pool = CLASS_POOL_SIZES[char_class(token[i])]
+        repeat = _run_length(token, i, 0)
+        sequence = max(_run_length(token, i, 1), _run_length(token, i, -1))
+        if repeat >= 3:
+            log_guesses += math.log10(pool * repeat)
+            i += repeat
+        elif sequence >= 3:
+            log_guesses += math.log10(pool * sequence * 2)
+            i += sequence
+        else:
+            log_guesses += math.log10(pool)
+            i += 1
+    
+    return log_guesses
+
+
+# Entropy estimators by FilterConfig.entropy_mode
+ENTROPY_ESTIMATORS = {
+    "shannon": calculate_entropy,
+    "classes": calculate_class_entropy,
+    "nist": calculate_nist_entropy,
+    "guesses": calculate_guesses_log10,
+}
+
+
+def estimate_entropy(token: str, mode: str = "shannon") -> float:
+    """
+    Estimate entropy with the selected estimator
+    
+    Args:
+        token: String to estimate
+        mode: One of shannon (bits per character), classes (bits),
+              nist (bits) or guesses (log10 guesses)
+        
+    Returns:
+        Entropy value in the estimator's unit
+    """
+    try:
+        estimator = ENTROPY_ESTIMATORS[mode]
+    except KeyError:
+        raise FilterError(f"Unknown entropy mode: {mode}")
+    return estimator(token)
+
+
 def calculate_quality_score(token: str) -> float:
     """
     Calculate quality score (0.0 to 1.0)
@@ -145,6 +318,9 @@ def check_pronounceability(token: str) -> float:
 class TokenFilter:
     """Base token filter"""
     
+    # Name used in rejection statistics
+    name = "filter"
+    
     def __init__(self, config: FilterConfig):
         self.config = config
     
@@ -154,38 +330,129 @@ class TokenFilter:
 
 
 class LengthFilter(TokenFilter):
-    """Filter tokens by length"""
+    """Filter tokens by length in characters (code points, not bytes)"""
+    
+    name = "length"
     
     def should_include(self, token: str) -> bool:
         length = len(token)
         return self.config.min_len <= length <= self.config.max_len
 
 
+class ByteLengthFilter(TokenFilter):
+    """Filter tokens by their UTF-8 encoded length in bytes"""
+    
+    name = "byte_length"
+    
+    def should_include(self, token: str) -> bool:
+        # ASCII tokens are one byte per character, no need to encode
+        length = len(token) if token.isascii() else len(token.encode("utf-8"))
+        if self.config.min_bytes is not None and length < self.config.min_bytes:
+            return False
+        if self.config.max_bytes is not None and length > self.config.max_bytes:
+            return False
+        return True
+
+
 class CharsetFilter(TokenFilter):
-    """Filter tokens by allowed characters"""
+    """
+    Filter tokens by allowed characters and character classes
+    
+    Plain ASCII charsets take a byte-level fast path: the allowed bytes
+    are deleted with bytes.translate (a 256-entry lookup table in C) and
+    the token passes if nothing is left. Unicode charsets and named
+    classes use a memoised code point membership test.
+    """
+    
+    name = "charset"
+    
+    def __init__(self, config: FilterConfig):
+        super().__init__(config)
+        charset = config.charset_filter or ""
+        self.allowed = CharMembership(charset, config.charset_classes)
+        self.ascii_table = None
+        if charset.isascii() and not config.charset_classes:
+            self.ascii_table = charset.encode("ascii")
     
     def should_include(self, token: str) -> bool:
-        if not self.config.charset_filter:
+        if not self.config.charset_filter and not self.config.charset_classes:
             return True
         
-        allowed = set(self.config.charset_filter)
-        return all(c in allowed for c in token)
+        if self.ascii_table is not None:
+            if not token.isascii():
+                return False
+            return not token.encode("ascii").translate(None, self.ascii_table)
+        
+        return self.allowed.all_members(token)
+
+
+class CharsetDenyFilter(TokenFilter):
+    """Filter tokens containing denied characters or character classes"""
+    
+    name = "charset_deny"
+    
+    def __init__(self, config: FilterConfig):
+        super().__init__(config)
+        self.denied = CharMembership(config.charset_deny or "", config.charset_deny_classes)
+    
+    def should_include(self, token: str) -> bool:
+        return not self.denied.any_member(token)
+
+
+class RepeatFilter(TokenFilter):
+    """Filter tokens with too many consecutive repeated characters"""
+    
+    name = "repeats"
+    
+    def __init__(self, config: FilterConfig):
+        super().__init__(config)
+        # A backreference scan runs in the regex engine instead of a Python loop
+        self.pattern = re.compile(r"(.)\1{%d}" % config.max_repeats, re.DOTALL)
+    
+    def should_include(self, token: str) -> bool:
+        return self.pattern.search(token) is None
+
+
+class ClassFilter(TokenFilter):
+    """Filter tokens missing a required character class or mixing too few classes"""
+    
+    name = "classes"
+    
+    def __init__(self, config: FilterConfig):
+        super().__init__(config)
+        self.required = set(config.required_classes)
+        self.min_classes = config.min_classes or 0
+    
+    def should_include(self, token: str) -> bool:
+        classes = {char_class(char) for char in token}
+        return self.required <= classes and len(classes) >= self.min_classes
 
 
 class EntropyFilter(TokenFilter):
     """Filter tokens by entropy"""
     
+    name = "entropy"
+    
+    def __init__(self, config: FilterConfig):
+        super().__init__(config)
+        mode = config.entropy_mode
+        self.estimate = lambda token: estimate_entropy(token, mode)
+    
     def should_include(self, token: str) -> bool:
-        entropy = calculate_entropy(token)
-        return self.config.min_entropy <= entropy <= self.config.max_entropy
+        entropy = self.estimate(token)
+        if self.config.max_entropy is not None and entropy > self.config.max_entropy:
+            return False
+        return entropy >= self.config.min_entropy
 
 
 class QualityFilter(TokenFilter):
     """Filter tokens by quality score"""
     
-    def __init__(self, config: FilterConfig, min_quality: float = 0.3):
+    name = "quality"
+    
+    def __init__(self, config: FilterConfig, min_quality: Optional[float] = None):
         super().__init__(config)
-        self.min_quality = min_quality
+        self.min_quality = config.min_quality if min_quality is None else min_quality
     
     def should_include(self, token: str) -> bool:
         quality = calculate_quality_score(token)
@@ -195,6 +462,8 @@ class QualityFilter(TokenFilter):
 class RegexFilter(TokenFilter):
     """Filter tokens by regex pattern"""
     
+    name = "regex"
+    
     def __init__(self, config: FilterConfig, pattern: str, match: bool = True):
         super().__init__(config)
         self.pattern = re.compile(pattern)
@@ -208,17 +477,52 @@ class RegexFilter(TokenFilter):
 class CompositeFilter(TokenFilter):
     """Combine multiple filters"""
     
+    name = "composite"
+    
     def __init__(self, config: FilterConfig, filters: list = None):
         super().__init__(config)
         self.filters = filters or []
+        self.checked = 0
+        self.rejections: Dict[str, int] = {}
     
     def add_filter(self, filter_obj: TokenFilter):
         """Add a filter to the composite"""
         self.filters.append(filter_obj)
     
+    def rejection_reason(self, token: str) -> Optional[str]:
+        """
+        Find the first filter rejecting a token, without recording statistics
+        
+        Args:
+            token: Token to check
+            
+        Returns:
+            Name of the rejecting filter or None if the token passes
+        """
+        for f in self.filters:
+            if not f.should_include(token):
+                return f.name
+        return None
+    
+    def check(self, token: str) -> Optional[str]:
+        """
+        Check a token and record per-filter rejection counts
+        
+        Args:
+            token: Token to check
+            
+        Returns:
+            Name of the rejecting filter or None if the token passes
+        """
+        self.checked += 1
+        reason = self.rejection_reason(token)
+        if reason is not None:
+            self.rejections[reason] = self.rejections.get(reason, 0) + 1
+        return reason
+    
     def should_include(self, token: str) -> bool:
         """Token must pass all filters"""
-        return all(f.should_include(token) for f in self.filters)
+        return self.check(token) is None
 
 
 def create_filter_pipeline(config: FilterConfig) -> CompositeFilter:
@@ -227,13 +531,31 @@ def create_filter_pipeline(config: FilterConfig) -> CompositeFilter:
     
     # Always add length filter
     composite.add_filter(LengthFilter(config))
+    if config.min_bytes is not None or config.max_bytes is not None:
+        composite.add_filter(ByteLengthFilter(config))
     
     # Add charset filter if specified
-    if config.charset_filter:
+    if config.charset_filter or config.charset_classes:
         composite.add_filter(CharsetFilter(config))
+    if config.charset_deny or config.charset_deny_classes:
+        composite.add_filter(CharsetDenyFilter(config))
+    
+    # Add repeat filter if specified
+    if config.max_repeats is not None:
+        composite.add_filter(RepeatFilter(config))
+    
+    # Add password policy filters if specified
+    if config.required_classes or config.min_classes:
+        composite.add_filter(ClassFilter(config))
+    for pattern in config.deny_patterns:
+        composite.add_filter(RegexFilter(config, pattern, match=False))
     
     # Add entropy filter if specified
-    if config.min_entropy > 0 or config.max_entropy < 100:
+    if config.min_entropy > 0 or config.max_entropy is not None:
         composite.add_filter(EntropyFilter(config))
     
+    # Add quality filter if specified
+    if config.min_quality > 0:
+        composite.add_filter(QualityFilter(config))
+    
     return composite
@@ -0,0 +1,197 @@
+"""
+Locale-aware numeric format generators
+
+Partial phone numbers, PIN codes and postal codes are extremely common
+password components. These generators enumerate them per country so
+they can be enabled as fields and combined with names.
+"""
+
+from typing import Callable, Dict, Iterable, Iterator, List, Sequence
+from .error import ConfigError
+
+
+# Local phone number layouts; '#' is a digit placeholder
+PHONE_LAYOUTS: Dict[str, List[str]] = {
+    "US": ["###-###-####", "(###) ###-####", "##########"],
+    "IN": ["#####-#####", "##########", "+91##########"],
+    "UK": ["#####-######", "###########"],
+    "DE": ["####-#######", "###########"],
+    "FR": ["## ## ## ## ##", "##########"],
+}
+
+# Leading digits of mobile numbers per country
+MOBILE_PREFIXES: Dict[str, List[str]] = {
+    "US": ["201", "212", "213", "310", "312", "415", "512", "617", "646", "702",
+           "713", "718", "786", "805", "818", "917"],
+    "IN": ["70", "72", "73", "74", "75", "76", "77", "78", "79", "80", "81",
+           "82", "83", "84", "85", "86", "87", "88", "89", "90", "91", "92",
+           "93", "94", "95", "96", "97", "98", "99"],
+    "UK": ["071", "073", "074", "075", "077", "078", "079"],
+    "DE": ["0151", "0152", "0157", "0159", "0160", "0162", "0170", "0171",
+           "0172", "0176"],
+    "FR": ["06", "07"],
+}
+
+# Postal code ranges per country: (first, last, width)
+POSTAL_RANGES: Dict[str, tuple] = {
+    "US": (501, 99950, 5),
+    "IN": (110001, 855126, 6),
+    "DE": (1067, 99998, 5),
+    "FR": (1000, 98890, 5),
+}
+
+# UK outward codes (the first half of a postcode) for major areas
+UK_OUTWARD_CODES = [
+    "EC1A", "EC2A", "WC1A", "WC2H", "SW1A", "SW1E", "SE1", "N1", "E1", "W1",
+    "NW1", "M1", "M2", "B1", "B2", "L1", "LS1", "G1", "EH1", "BS1", "CF10",
+    "NE1", "S1", "NG1", "CB1", "OX1",
+]
+
+
+def digits(length: int) -> Iterator[str]:
+    """
+    Enumerate every zero-padded number of a given width
+    
+    Args:
+        length: Number of digits
+        
+    Yields:
+        "000..." through "999..."
+    """
+    for value in range(10 ** length):
+        yield f"{value:0{length}d}"
+
+
+def phone_last_digits(length: int = 4) -> Iterator[str]:
+    """Enumerate the last `length` digits of a phone number"""
+    return digits(length)
+
+
+def mobile_prefixes(country: str) -> Iterator[str]:
+    """Yield the mobile number prefixes of a country"""
+    return iter(_lookup(MOBILE_PREFIXES, country, "mobile prefixes"))
+
+
+class PhoneNumbers(Sequence[str]):
+    """
+    Mobile numbers of a country in one local layout, computed by index
+    
+    Every mobile prefix is followed by every subscriber number that fills
+    the layout, so the sequence runs to hundreds of millions of values;
+    it is never materialized.
+    """
+    
+    def __init__(self, country: str, layout: int = 0):
+        """
+        Initialize phone number sequence
+        
+        Args:
+            country: Country code (US, IN, UK, DE, FR)
+            layout: Index into the country's PHONE_LAYOUTS
+        """
+        self.country = country
+        self.layout = layout
+        layouts = _lookup(PHONE_LAYOUTS, country, "phone layouts")
+        if not 0 <= layout < len(layouts):
+            raise ConfigError(f"No phone layout {layout} for country {country}")
+        width = layouts[layout].count("#")
+        # (prefix, subscriber digits, first index)
+        self._blocks = []
+        self._size = 0
+        for prefix in _lookup(MOBILE_PREFIXES, country, "mobile prefixes"):
+            self._blocks.append((prefix, width - len(prefix), self._size))
+            self._size += 10 ** (width - len(prefix))
+    
+    def __len__(self) -> int:
+        return self._size
+    
+    def __getitem__(self, index):
+        if isinstance(index, slice):
+            return [self[i] for i in range(*index.indices(self._size))]
+        if index < 0:
+            index += self._size
+        if not 0 <= index < self._size:
+            raise IndexError(index)
+        for prefix, width, first in reversed(self._blocks):
+            if index >= first:
+                return format_phone(f"{prefix}{index - first:0{width}d}", self.country, self.layout)
+        raise IndexError(index)
+
+
+def phone_numbers(country: str, layout: int = 0) -> PhoneNumbers:
+    """Mobile numbers of a country formatted in one of its local layouts"""
+    return PhoneNumbers(country, layout)
+
+
+def postal_codes(country: str) -> Iterator[str]:
+    """
+    Enumerate the postal codes of a country
+    
+    Numeric systems enumerate their full assigned range; the UK yields
+    outward codes since full postcodes are not password material.
+    
+    Args:
+        country: Country code (US, IN, DE, FR, UK)
+    """
+    code = country.upper()
+    if code in ("UK", "GB"):
+        return iter(UK_OUTWARD_CODES)
+    
+    first, last, width = _lookup(POSTAL_RANGES, code, "postal codes")
+    return (f"{value:0{width}d}" for value in range(first, last + 1))
+
+
+def format_phone(number: str, country: str, layout: int = 0) -> str:
+    """
+    Render a digit string in a country's local phone layout
+    
+    Args:
+        number: Digits of the phone number
+        country: Country code
+        layout: Index into the country's layouts
+        
+    Returns:
+        Formatted phone number
+    """
+    layouts = _lookup(PHONE_LAYOUTS, country, "phone layouts")
+    template = layouts[layout]
+    if template.count("#") != len(number) or not number.isdigit():
+        raise ConfigError(f"{number!r} does not fit the {country} layout {template!r}")
+    
+    digits_iter = iter(number)
+    return "".join(next(digits_iter) if c == "#" else c for c in template)
+
+
+def _lookup(table: Dict, country: str, what: str):
+    """Look up a per-country table entry with a helpful error"""
+    code = country.upper()
+    if code not in table:
+        known = ", ".join(sorted(table))
+        raise ConfigError(f"No {what} for country {country} (known: {known})")
+    return table[code]
+
+
+# Generators usable by fields of type "generated"
+FORMAT_GENERATORS: Dict[str, Callable[..., Iterable[str]]] = {
+    "digits": digits,
+    "phone_last_digits": phone_last_digits,
+    "phone_numbers": phone_numbers,
+    "mobile_prefixes": mobile_prefixes,
+    "postal_codes": postal_codes,
+}
+
+
+def run_generator(name: str, params: Dict) -> Iterable[str]:
+    """
+    Run a named format generator
+    
+    Args:
+        name: Generator name from FORMAT_GENERATORS
+        params: Keyword arguments for the generator
+        
+    Returns:
+        Generated values (a lazy sequence for phone_numbers)
+    """
+    if name not in FORMAT_GENERATORS:
+        raise ConfigError(f"Unknown format generator: {name}")
+    return FORMAT_GENERATORS[name](**params)
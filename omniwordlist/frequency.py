@@ -0,0 +1,239 @@
+"""
+Frequency models from breach corpora
+
+Imports frequency-annotated word lists (word<TAB>count TSV, or the
+"count word" lines of `uniq -c`) into a compact binary model that is
+memory-mapped when read, so multi-gigabyte models cost no more RAM
+than the pages actually touched.
+
+Model layout (little endian):
+    magic "OMNIFRQ1", u64 entries, u64 total count
+    u64 record offsets in descending count order
+    u64 record offsets in word order (for lookups)
+    records: u64 count, u32 byte length, UTF-8 word
+"""
+
+import mmap
+import re
+import struct
+from pathlib import Path
+from typing import Dict, Iterator, List, Optional, Sequence, Tuple
+from .error import ConfigError, StorageError
+
+
+MODEL_MAGIC = b"OMNIFRQ1"
+HEADER = struct.Struct("<8sQQ")
+OFFSET = struct.Struct("<Q")
+RECORD = struct.Struct("<QI")
+
+_UNIQ_LINE = re.compile(r"^\s*(\d+)\s(.*)$")
+
+
+def parse_frequency_line(line: str) -> Optional[Tuple[str, int]]:
+    """
+    Parse one corpus line
+
+    Args:
+        line: "word<TAB>count", "count<TAB>word" or "   count word"
+
+    Returns:
+        (word, count), or None for blank and malformed lines
+    """
+    line = line.rstrip("\r\n")
+    if not line.strip():
+        return None
+    if "\t" in line:
+        left, right = line.rsplit("\t", 1)
+        if right.strip().isdigit():
+            return left, int(right)
+        if left.strip().isdigit():
+            return right, int(left)
+        return None
+    match = _UNIQ_LINE.match(line)
+    if match:
+        return match.group(2), int(match.group(1))
+    return None
+
+
+def build_frequency_model(counts: Dict[str, int], model_path: Path) -> int:
+    """
+    Write a model from word counts
+
+    Args:
+        counts: Word -> occurrence count
+        model_path: Model file to write
+
+    Returns:
+        Number of entries written
+    """
+    words = sorted(counts, key=lambda w: (-counts[w], w))
+    body = bytearray()
+    offsets: Dict[str, int] = {}
+    base = HEADER.size + 2 * OFFSET.size * len(words)
+    for word in words:
+        encoded = word.encode("utf-8")
+        offsets[word] = base + len(body)
+        body += RECORD.pack(counts[word], len(encoded)) + encoded
+
+    model_path = Path(model_path)
+    model_path.parent.mkdir(parents=True, exist_ok=True)
+    tmp_path = model_path.with_suffix(model_path.suffix + ".part")
+    with open(tmp_path, "wb") as f:
+        f.write(HEADER.pack(MODEL_MAGIC, len(words), sum(counts.values())))
+        for word in words:
+            f.write(OFFSET.pack(offsets[word]))
+        for word in sorted(words, key=lambda w: w.encode("utf-8")):
+            f.write(OFFSET.pack(offsets[word]))
+        f.write(body)
+    tmp_path.replace(model_path)
+    return len(words)
+
+
+def import_frequency_corpus(corpus_path: Path, model_path: Path, min_count: int = 1) -> int:
+    """
+    Import a frequency-annotated corpus into a model file
+
+    Duplicate words have their counts summed.
+
+    Args:
+        corpus_path: TSV / uniq -c corpus
+        model_path: Model file to write
+        min_count: Drop words seen fewer times than this
+
+    Returns:
+        Number of entries in the model
+    """
+    counts: Dict[str, int] = {}
+    try:
+        with open(corpus_path, "r", encoding="utf-8", errors="ignore") as f:
+            for line in f:
+                parsed = parse_frequency_line(line)
+                if parsed is not None:
+                    word, count = parsed
+                    counts[word] = counts.get(word, 0) + count
+    except OSError as e:
+        raise StorageError(f"Cannot read corpus {corpus_path}: {e.strerror}")
+
+    if min_count > 1:
+        counts = {word: count for word, count in counts.items() if count >= min_count}
+    if not counts:
+        raise ConfigError(f"No word/count lines found in {corpus_path}")
+    return build_frequency_model(counts, model_path)
+
+
+def is_frequency_model(path: Path) -> bool:
+    """Check whether a file is a frequency model"""
+    try:
+        with open(path, "rb") as f:
+            return f.read(len(MODEL_MAGIC)) == MODEL_MAGIC
+    except OSError:
+        return False
+
+
+class FrequencyModel:
+    """Read-only, memory-mapped frequency model"""
+
+    def __init__(self, path: Path):
+        """
+        Open a model
+
+        Args:
+            path: Model file written by build_frequency_model
+        """
+        self.path = Path(path)
+        try:
+            self._file = open(self.path, "rb")
+            self._map = mmap.mmap(self._file.fileno(), 0, access=mmap.ACCESS_READ)
+        except (OSError, ValueError) as e:
+            raise StorageError(f"Cannot open frequency model {path}: {e}")
+
+        magic, self.entries, self.total = HEADER.unpack_from(self._map, 0)
+        if magic != MODEL_MAGIC:
+            self.close()
+            raise StorageError(f"Not a frequency model: {path}")
+        self._by_count = HEADER.size
+        self._by_word = HEADER.size + OFFSET.size * self.entries
+
+    def close(self):
+        """Release the mapping"""
+        self._map.close()
+        self._file.close()
+
+    def __enter__(self) -> 'FrequencyModel':
+        return self
+
+    def __exit__(self, *exc):
+        self.close()
+
+    def __len__(self) -> int:
+        return self.entries
+
+    def _record(self, offset: int) -> Tuple[str, int]:
+        count, length = RECORD.unpack_from(self._map, offset)
+        start = offset + RECORD.size
+        return self._map[start:start + length].decode("utf-8"), count
+
+    def __getitem__(self, rank: int) -> Tuple[str, int]:
+        """(word, count) at a rank, most frequent first"""
+        if rank < 0:
+            rank += self.entries
+        if not 0 <= rank < self.entries:
+            raise IndexError(rank)
+        (offset,) = OFFSET.unpack_from(self._map, self._by_count + rank * OFFSET.size)
+        return self._record(offset)
+
+    def __iter__(self) -> Iterator[Tuple[str, int]]:
+        """(word, count) pairs in descending frequency"""
+        for rank in range(self.entries):
+            yield self[rank]
+
+    def words(self, limit: Optional[int] = None) -> List[str]:
+        """The most frequent words"""
+        count = self.entries if limit is None else min(limit, self.entries)
+        return [self[rank][0] for rank in range(count)]
+
+    def count(self, word: str) -> int:
+        """
+        Occurrences of a word (binary search over the word index)
+
+        Args:
+            word: Word to look up
+
+        Returns:
+            Count, or 0 for unknown words
+        """
+        target = word.encode("utf-8")
+        low, high = 0, self.entries
+        while low < high:
+            mid = (low + high) // 2
+            (offset,) = OFFSET.unpack_from(self._map, self._by_word + mid * OFFSET.size)
+            count, length = RECORD.unpack_from(self._map, offset)
+            start = offset + RECORD.size
+            found = self._map[start:start + length]
+            if found == target:
+                return count
+            if found < target:
+                low = mid + 1
+            else:
+                high = mid
+        return 0
+
+    def probability(self, word: str) -> float:
+        """Relative frequency of a word in the corpus"""
+        return self.count(word) / self.total if self.total else 0.0
+
+    def order(self, words: Sequence[str]) -> List[str]:
+        """
+        Sort words by descending corpus frequency
+
+        Unknown words keep their relative order after the known ones.
+
+        Args:
+            words: Words to order
+
+        Returns:
+            Reordered words
+        """
+        counts = [self.count(word) for word in words]
+        ranked = sorted(range(len(words)), key=lambda i: -counts[i])
+        return [words[i] for i in ranked]
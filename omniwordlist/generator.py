@@ -5,38 +5,257 @@ Streaming combinator pipeline with checkpoint support and deduplication
 """
 
 import itertools
+import math
+import os
+import time
+from collections import Counter
+from datetime import date
 import random
-from typing import Iterator, List, Set, Optional
+from typing import Callable, Dict, Iterable, Iterator, List, Sequence, Set, Optional, Tuple
 from pathlib import Path
 import hashlib
+import json
 from .config import Config
-from .charset import expand_pattern, get_charset, CHARSET_LOWERCASE
-from .transforms import apply_transforms
+from .charset import check_duplicate_limit, expand_pattern_positions, get_charset, parse_duplicate_limit, CHARSET_LOWERCASE
+from .transforms import RANDOM_TRANSFORMS, apply_transforms, optimize_transforms, token_rng
 from .filters import create_filter_pipeline
-from .error import GeneratorError
+from .error import ConfigError, GeneratorError, KeyspaceTooLargeError
+from .events import GeneratorObserver, ObserverGroup
+from .dedupe import BloomDeduper, Deduper, ExactDeduper, bloom_capacity
+from .progress import GeneratorStats, ProgressCallback, RateWindow
+from .storage import (CHECKPOINT_PAUSED, CHECKPOINT_RUNNING, CheckpointManager, OutputWriter, SplitWriter,
+                      StorageBackend, create_storage_backend, detect_compression, inspect_output, keep_lines, open_text)
+from .cancel import CancellationToken
+from .toplists import load_top_passwords
+from .sources import count_lines, is_stream, iter_wordlist, load_dictionary, read_word_file, read_charset_file, resolve_source
+from .dictionary import sequence_product
+from .frequency import FrequencyModel, is_frequency_model
+from .combos import combo_count, pair_credentials
+from .hashes import hash_header, hash_lines
+from .markov import MarkovModel
+from .prince import ChainIndex
+from .pipe import pipe_through
+from .derived import DerivedField, is_derived_field, parse_derived_field
+from .keyspace import Keyspace
+from .lengths import interleave_lengths, load_length_distribution
+from .smartcase import case_variants, load_case_stats
+from .fields import FieldRegistry, default_registry, is_lazy_sequence
+from .neighborhood import edit_neighborhood, neighborhood_upper_bound, random_neighbor
+from .temporal import parse_date, resolve_relative, resolve_relative_values
+from .campaign import CampaignStore, default_campaign_db
+
+
+# Field id of the inline `words` list
+INLINE_WORDS_FIELD = "words"
 
 
 class Generator:
     """Main wordlist generator"""
     
-    def __init__(self, config: Config):
+    def __init__(self, config: Config,
+                 cancel_token: Optional[CancellationToken] = None,
+                 job_id: Optional[str] = None,
+                 field_registry: Optional[FieldRegistry] = None,
+                 checkpoint_manager: Optional[CheckpointManager] = None):
         """
         Initialize generator
         
         Args:
             config: Configuration object
+            cancel_token: Optional token used to abort generation from another thread
+            job_id: Job identifier used for checkpoints (derived from config if omitted)
+            field_registry: Field providers resolving enabled fields (fields.default_registry if omitted)
+            checkpoint_manager: Where checkpoints go (the config's checkpoint_dir if omitted)
         """
         config.validate()
         self.config = config
+        self.cancel_token = cancel_token
+        self.field_registry = field_registry
+        # Backend being written by write(), recorded in checkpoints
+        self.backend: Optional[StorageBackend] = None
+        self.job_id = job_id or self._derive_job_id(config)
+        if checkpoint_manager is None and config.checkpoint_dir:
+            checkpoint_manager = CheckpointManager(Path(config.checkpoint_dir))
+        self.checkpoint_manager = checkpoint_manager
         self.tokens_generated = 0
-        self.dedup_hashes: Set[str] = set()
+        self.tokens_written = 0
+        self.candidates_seen = 0
+        # Candidates past the filters, and past deduplication (see progress_stats)
+        self.passed_filters = 0
+        self.passed_dedupe = 0
+        self.started_at: Optional[float] = None
+        # Emitted tokens/s, sampled at progress events
+        self.rate_window = RateWindow()
+        self._progress_callback: Optional[ProgressCallback] = None
+        # Length of the raw candidate being processed
+        self.current_length = 0
+        self.last_token: Optional[str] = None
+        # Position after the last line written by write_lines; checkpoints
+        # record it since generation may be a token ahead of the output
+        self._written_position: Optional[dict] = None
+        # Checkpoint this generator resumes from (see resume)
+        self.resumed_from: Optional[dict] = None
+        self.cancelled = False
+        # Output limit that ended the run early ("max_bytes"; see finish_early)
+        self.limit_reached: Optional[str] = None
+        self._finished = False
+        # Created on first use (see dedupe_filter)
+        self.deduper: Optional[Deduper] = None
+        self.observers = ObserverGroup()
+        
+        # Rejection statistics and a reservoir sample of rejected candidates
+        self.rejections: Dict[str, int] = {}
+        self.rejected_total = 0
+        self.rejected_sample: List[Tuple[str, str]] = []
+        self.last_rejection: Optional[Tuple[str, str]] = None
+        self._rejects_rng = random.Random(0)
+        
+        # Candidates processed before resuming from a checkpoint, and where
+        # the checkpointed run stopped as (base_index, variant_index)
+        self._resume_candidates = 0
+        self._resume_position: Optional[Tuple[int, int]] = None
+        # Position after the last candidate handed out by _expand_candidates:
+        # raw candidates finished before it, and variants of the next one done
+        self.base_index = 0
+        self.variant_index = 0
+        
+        # With a seed, randomized transforms draw from a per-token random
+        # source so the output is reproducible (see transforms.token_rng)
+        self.seeded_transforms = config.seed is not None and bool(RANDOM_TRANSFORMS & set(config.transforms))
+        
+        # Transform chain for ASCII tokens, with redundant steps removed
+        self.ascii_transforms = list(config.transforms)
+        self.transform_notes: List[str] = []
+        if config.optimize_transforms and config.transforms:
+            self.ascii_transforms, self.transform_notes = optimize_transforms(config.transforms)
         
-        # Initialize random seed if specified
-        if config.seed is not None:
-            random.seed(config.seed)
+        # Relative temporal tokens resolve against the run date
+        self.reference_date = parse_date(config.reference_date) if config.reference_date else date.today()
+        self.prefix = self._resolve_affix(config.prefix)
+        self.suffix = self._resolve_affix(config.suffix)
+        
+        # Case statistics ranking the variants of smart_case expansion
+        self.case_table = load_case_stats(config.case_stats_file) if config.smart_case else None
+        
+        # Fields computed from other fields of the same combination
+        self.derived_fields = self._resolve_derived_fields()
+        
+        # Length shares interleaving charset mode, with candidates taken per length
+        self.length_shares: Optional[Dict[int, float]] = None
+        self.length_walked: Optional[Dict[int, int]] = None
+        if config.length_distribution:
+            if config.seed_words or config.pattern or self._list_mode() or self._enabled_fields():
+                raise ConfigError("length_distribution only applies to charset mode")
+            if config.start_string or config.end_string or config.skip or config.take:
+                raise ConfigError("length_distribution cannot be combined with start/end strings or skip/take")
+            self.length_shares = load_length_distribution(config.length_distribution)
+        
+        if config.invert and self.keyspace() is None:
+            raise ConfigError("invert only applies to charset and pattern modes")
+        
+        # Charset and pattern modes seek to start_string / end_string by rank
+        # (crunch -s / -e); other modes compare every token against them
+        self.seeks_string_bounds = (not config.seed_words and not self._list_mode()
+                                    and (bool(config.pattern) or not self._enabled_fields()))
+        # Chain mode chains field values when it has no chain_elements file
+        mode = self._list_mode()
+        if mode and self._enabled_fields() and (mode != "chain" or config.chain_elements):
+            raise ConfigError(f"{mode} mode replaces generation; it cannot be combined with fields, words or region")
+        # Input lines mutation, combinator and Markov modes skipped, by reason ("blank", "invalid_utf8")
+        self.skipped_lines: Dict[str, int] = {}
+        self._wordlist_lines: Optional[int] = None
+        self._markov: Optional[MarkovModel] = None
+        self._chains: Optional[ChainIndex] = None
+        # alphabets(), resolved once (charset files are read again otherwise)
+        self._alphabets: Optional[tuple] = None
+        if self.seeks_string_bounds and (config.start_string or config.end_string):
+            keyspace = self.keyspace()
+            if keyspace is not None:
+                # Fail before writing anything on bounds outside the keyspace
+                self.string_bounds(keyspace)
         
         # Create filter pipeline
         self.filter_pipeline = create_filter_pipeline(config.filters)
+        
+        # Duplicate limits of modes without a pattern (patterns prune them in the keyspace)
+        self.duplicate_limits = None
+        if config.duplicate_limit and not config.pattern:
+            self.duplicate_limits = parse_duplicate_limit(config.duplicate_limit)
+        
+        # Common passwords removed from the output
+        self.excluded_tokens: Set[str] = set()
+        if config.exclude_top:
+            self.excluded_tokens = set(load_top_passwords(config.exclude_top))
+        
+        # Common passwords emitted with the output, within the length window
+        self.top_tokens: List[str] = []
+        if config.include_top:
+            self.top_tokens = [token for token in load_top_passwords(config.include_top)
+                               if config.min_length <= len(token) <= config.max_length]
+        
+        # Tokens from the configured denylist file
+        self.denied_tokens: Set[str] = set()
+        if config.denylist:
+            self.denied_tokens = set(read_word_file(config.denylist))
+        
+        # Tokens written by earlier runs of the campaign; written tokens are
+        # recorded only while writing output (write_lines, or when a caller
+        # sets recording_campaign) so previews leave the campaign untouched
+        self.campaign: Optional[CampaignStore] = None
+        self.recording_campaign = False
+        if config.campaign:
+            self.campaign = CampaignStore(config.campaign_db or default_campaign_db(), config.campaign,
+                                          job_id=self.job_id)
+        
+        if config.max_keyspace is not None:
+            keyspace = self.keyspace_size()
+            if keyspace > config.max_keyspace:
+                raise KeyspaceTooLargeError(
+                    f"Keyspace of {keyspace:,} candidates exceeds max_keyspace ({config.max_keyspace:,})"
+                )
+    
+    def set_progress_callback(self, callback: Optional[Callable[[GeneratorStats], None]]):
+        """
+        Call a function with progress_stats() every `progress_interval` emitted tokens
+        
+        Replaces a previously set callback; None removes it. The call after
+        the last token reports the final counts.
+        
+        Args:
+            callback: Function receiving a GeneratorStats snapshot
+        """
+        if self._progress_callback is not None:
+            self.observers.remove(self._progress_callback)
+            self._progress_callback = None
+        if callback is not None:
+            self._progress_callback = ProgressCallback(self, callback)
+            self.observers.add(self._progress_callback)
+    
+    def progress_stats(self) -> GeneratorStats:
+        """Counters of each pipeline stage, with the recent emission rate"""
+        return GeneratorStats(
+            generated=self.candidates_seen,
+            after_filters=self.passed_filters,
+            after_dedupe=self.passed_dedupe,
+            emitted=self.tokens_generated,
+            written=self.tokens_written,
+            elapsed=time.monotonic() - self.started_at if self.started_at is not None else 0.0,
+            rate=self.rate_window.rate(),
+            dedupe_memory=self.deduper.memory_bytes() if self.deduper is not None else 0,
+        )
+    
+    def add_observer(self, observer: GeneratorObserver):
+        """
+        Register an observer for generation events
+        
+        Args:
+            observer: Observer receiving token, progress, stage and checkpoint events
+        """
+        self.observers.add(observer)
+    
+    def remove_observer(self, observer: GeneratorObserver):
+        """Unregister a previously added observer"""
+        self.observers.remove(observer)
     
     def generate(self) -> Iterator[str]:
         """
@@ -45,82 +264,1143 @@ class Generator:
         Yields:
             Generated tokens
         """
+        stage, source = self._candidate_source()
+        self.observers.stage_change(stage)
+        interval = self.config.progress_interval
+        max_lines = self.config.max_lines
+        self._finished = False
+        if self.started_at is None:
+            self.started_at = time.monotonic()
+        self.rate_window.sample(self.tokens_generated)
+        first_base = skip_variants = 0
+        skip_candidates = self._resume_candidates
+        if self._resume_position is not None:
+            # Skip finished raw candidates without expanding them
+            first_base, skip_variants = self._resume_position
+            source = itertools.islice(source, first_base, None)
+            self.candidates_seen, skip_candidates = self._resume_candidates, 0
+        for base_index, variant_index, candidate in self._expand_candidates(source, first_base, skip_variants):
+            if self.cancel_token is not None and self.cancel_token.is_cancelled():
+                self._handle_cancel()
+                return
+            
+            self.base_index, self.variant_index = base_index, variant_index
+            self.candidates_seen += 1
+            if self.candidates_seen <= skip_candidates:
+                continue
+            
+            self.current_length = len(candidate)
+            token = self._process_token(candidate)
+            if token is None:
+                continue
+            
+            self.last_token = token
+            if self.recording_campaign and self.campaign is not None:
+                self.campaign.record(token)
+            if self.observers:
+                self.observers.token(token)
+                if self.tokens_generated % interval == 0:
+                    self.rate_window.sample(self.tokens_generated)
+                    self.observers.progress(self.tokens_generated)
+            yield token
+            # max_lines counts emitted tokens, after transforms, filters and dedupe
+            if max_lines and self.tokens_generated >= max_lines:
+                break
+        
+        self._finish()
+    
+    def _finish(self):
+        """Report final progress, close the campaign and write rejects once generation ends"""
+        if self._finished:
+            return
+        self._finished = True
+        if self.observers and self.tokens_generated % self.config.progress_interval != 0:
+            self.rate_window.sample(self.tokens_generated)
+            self.observers.progress(self.tokens_generated)
+        if self.campaign is not None:
+            self.campaign.close()
+        if self.config.rejects_output:
+            self.write_rejects(self.config.rejects_output)
+        self.observers.stage_change("finished")
+    
+    def finish_early(self, lines: Iterator[str], reason: str):
+        """
+        End generation before the candidates run out because an output limit was hit
+        
+        Args:
+            lines: The output() iterator being consumed
+            reason: Limit reached (recorded in limit_reached)
+        """
+        self.limit_reached = reason
+        lines.close()
+        self._finish()
+    
+    def _candidate_source(self) -> Tuple[str, Iterator[str]]:
+        """Pick the generation mode and build the raw candidate stream"""
         # Determine generation mode
+        if self.config.input_wordlist:
+            self.skipped_lines = {}
+            stage, source = "wordlist", iter_wordlist(self.config.input_wordlist, self.skipped_lines)
+            if self._hybrid_mask() is not None:
+                stage, source = "hybrid", self._generate_hybrid(source)
+        elif self.config.combinator_left:
+            self.skipped_lines = {}
+            stage, source = "combinator", self.generate_combinator()
+        elif self._list_mode() == "markov":
+            self.skipped_lines = {}
+            stage, source = "markov", self.generate_markov()
+        elif self._list_mode() == "chain":
+            stage, source = "chain", self._generate_chains()
+        elif self.config.seed_words:
+            stage, source = "neighborhood", self._generate_neighborhood()
+        elif self.config.pattern:
+            stage, source = "pattern", self._generate_pattern()
+        elif self._enabled_fields():
+            stage, source = "fields", self._generate_fields()
+        else:
+            stage, source = "charset", self._generate_charset()
+        
+        if self.config.include_top:
+            if self.config.top_placement == "first":
+                source = itertools.chain(self.top_tokens, source)
+            elif self.config.top_placement == "last":
+                source = itertools.chain(source, self.top_tokens)
+            else:
+                source = self._merge_top(source)
+        
+        if (self.config.skip or self.config.take) and self.keyspace() is None:
+            # Without rank addressing the candidates before the window are enumerated
+            first, last = self.window(0, None)
+            source = itertools.islice(source, first, last)
+        
+        return stage, source
+    
+    def _merge_top(self, source: Iterable[str]) -> Iterator[str]:
+        """Alternate top passwords and generated candidates, then emit whatever remains"""
+        top = iter(self.top_tokens)
+        for candidate in source:
+            token = next(top, None)
+            if token is not None:
+                yield token
+            yield candidate
+        yield from top
+    
+    def _expand_candidates(self, source: Iterable[str], first_base: int = 0,
+                           skip_variants: int = 0) -> Iterator[Tuple[int, int, str]]:
+        """
+        Expand raw candidates into their smart_case variants
+        
+        Each variant comes with the position after it, which checkpoints
+        record (see base_index) so a resumed run can skip whole raw
+        candidates and continue mid-expansion.
+        
+        Args:
+            source: Raw candidates
+            first_base: Index of the first raw candidate in the full source
+            skip_variants: Variants of the first raw candidate already processed
+            
+        Yields:
+            (base_index, variant_index, candidate)
+        """
+        if not self.config.smart_case:
+            # A single variant: after it the raw candidate is finished
+            for base_index, candidate in enumerate(source, first_base + 1):
+                yield base_index, 0, candidate
+            return
+        for base_index, candidate in enumerate(source, first_base):
+            variants = case_variants(candidate, self.config.smart_case, self.case_table)
+            for variant_index in range(skip_variants, len(variants)):
+                yield base_index, variant_index + 1, variants[variant_index]
+            skip_variants = 0
+    
+    def trace(self) -> Iterator[Tuple[str, Optional[str]]]:
+        """
+        Run the pipeline and report every candidate, emitted or not
+        
+        Yields:
+            (token, reason) pairs where token is the prepared candidate and
+            reason names the rejecting stage, or None if it was emitted
+        """
+        _, source = self._candidate_source()
+        for _, _, candidate in self._expand_candidates(source):
+            if self.cancel_token is not None and self.cancel_token.is_cancelled():
+                self.cancelled = True
+                return
+            
+            self.candidates_seen += 1
+            rejected_before = self.rejected_total
+            token = self._process_token(candidate)
+            if token is not None:
+                yield token, None
+            elif self.rejected_total != rejected_before:
+                yield self.last_rejection
+            else:
+                # max_lines reached
+                return
+    
+    def keyspace(self) -> Optional[Keyspace]:
+        """
+        Rank-addressable keyspace of the configured mode
+        
+        Returns:
+            Keyspace for charset and pattern modes (including permutations
+            and duplicate limits), or None when candidates can only be
+            reached by enumeration (neighborhood, fields, include_top)
+        """
+        if self.config.seed_words or self._list_mode() or self.config.include_top:
+            return None
         if self.config.pattern:
-            yield from self._generate_pattern()
-        elif self.config.enabled_fields:
-            yield from self._generate_fields()
+            return self._pattern_keyspace()
+        if self._enabled_fields():
+            return None
+        return self._charset_keyspace()
+    
+    def _charset_keyspace(self) -> Keyspace:
+        return Keyspace.for_charset(self._resolve_charset(), self.config.min_length,
+                                    self.config.max_length, self.config.permutations_only,
+                                    self.config.invert)
+    
+    def _pattern_keyspace(self) -> Keyspace:
+        return Keyspace.for_pattern(self.config.pattern, self.config.literal_chars,
+                                    self.config.duplicate_limit, self.config.custom_charsets,
+                                    self.config.invert, self.config.literal_mask)
+    
+    def alphabets(self) -> Optional[List[str]]:
+        """
+        Exact character orderings the candidate order is built on
+        
+        Ranks, checkpoints and chunks only mean the same candidates under
+        the same orderings: a reordered charset (or a charset file edited
+        between runs) shifts every rank.
+        
+        Returns:
+            The resolved charset for charset and seed word modes, the
+            distinct position sets of a pattern, or None for modes without
+            an alphabet (fields, input wordlist)
+        """
+        if self._alphabets is None:
+            alphabets = None
+            if self.config.pattern:
+                alphabets = self._pattern_keyspace().alphabets()
+            elif self.config.seed_words or not (self._list_mode() or self._enabled_fields()):
+                alphabets = [self._resolve_charset()]
+            self._alphabets = (alphabets,)
+        return self._alphabets[0]
+    
+    def check_alphabets(self, recorded: Optional[List[str]], what: str):
+        """
+        Refuse to continue work recorded under other alphabet orderings
+        
+        Args:
+            recorded: alphabets() saved with the work (None: not recorded)
+            what: Description of the work for the error message
+            
+        Raises:
+            GeneratorError: If the orderings differ
+        """
+        if recorded is None:
+            return
+        current = self.alphabets()
+        if current != list(recorded):
+            raise GeneratorError(
+                f"Alphabet ordering differs from the one {what} was made with "
+                f"(recorded {recorded!r}, now {current!r}); continuing would overlap or leave gaps"
+            )
+    
+    def string_bounds(self, keyspace: Keyspace) -> Tuple[int, int]:
+        """
+        Rank range selected by start_string and end_string
+        
+        Both are raw candidates of the keyspace (before prefix, suffix and
+        transforms) and bound the range inclusively, in generation order.
+        
+        Args:
+            keyspace: Keyspace of the configured mode
+            
+        Returns:
+            (first rank, rank after the last one)
+        """
+        bounds = [0, keyspace.size]
+        for i, name in enumerate(("start_string", "end_string")):
+            value = getattr(self.config, name)
+            if not value:
+                continue
+            try:
+                bounds[i] = keyspace.rank_of(value) + i
+            except GeneratorError as e:
+                raise ConfigError(f"{name} {e}")
+        if bounds[0] >= bounds[1]:
+            raise ConfigError("end_string comes before start_string in generation order")
+        return bounds[0], bounds[1]
+    
+    def window(self, first: int, last: Optional[int]) -> Tuple[int, Optional[int]]:
+        """
+        Narrow a candidate range by skip and take
+        
+        Args:
+            first: First index of the range
+            last: Index after the last one (None for an unbounded stream)
+            
+        Returns:
+            (first index, index after the last one); empty when skip runs past the end
+        """
+        if self.config.skip:
+            first += self.config.skip
+            if last is not None:
+                first = min(first, last)
+        if self.config.take:
+            last = first + self.config.take if last is None else min(last, first + self.config.take)
+        return first, last
+    
+    def rank_bounds(self, keyspace: Keyspace) -> Tuple[int, int]:
+        """
+        Rank range generated: string_bounds narrowed by skip and take
+        
+        skip and take count ranks, so in constrained keyspaces
+        (permutations, duplicate limits) they include pruned candidates,
+        like the chunks of distributed generation.
+        
+        Args:
+            keyspace: Keyspace of the configured mode
+            
+        Returns:
+            (first rank, rank after the last one)
+        """
+        first, last = self.string_bounds(keyspace)
+        if self.config.include_top:
+            # The window covers the stream with top passwords chained in (see _candidate_source)
+            return first, last
+        return self.window(first, last)
+    
+    def index_range(self) -> Tuple[int, int]:
+        """
+        Absolute candidate indices this configuration generates
+        
+        Ranks of the keyspace in charset and pattern modes, positions in the
+        raw candidate stream otherwise. Partitions of one job must not overlap.
+        
+        Returns:
+            (first index, index after the last one)
+        """
+        keyspace = self.keyspace()
+        if keyspace is not None:
+            return self.rank_bounds(keyspace)
+        return self.window(0, self.keyspace_size())
+    
+    def _addressable_keyspace(self) -> Keyspace:
+        keyspace = self.keyspace()
+        if keyspace is None:
+            raise GeneratorError("Index lookup needs charset or pattern mode (without include_top)")
+        return keyspace
+    
+    def token_at(self, index: int) -> str:
+        """
+        Raw candidate at an index, computed without generation
+        
+        Indices count the charset or pattern keyspace in length-major
+        order from min_length (the ranks chunks, skip and take count),
+        whatever start/end strings, skip, take or length_distribution
+        select. Candidates are raw: before prefix, suffix and transforms.
+        
+        Args:
+            index: Zero-based index
+            
+        Returns:
+            Candidate string
+            
+        Raises:
+            GeneratorError: If the mode is not rank-addressable, the index is
+                out of range, or permutations / duplicate limits prune it
+        """
+        keyspace = self._addressable_keyspace()
+        token = keyspace.token_at(index)
+        if not keyspace.accepts(index):
+            raise GeneratorError(f"Index {index} ({token!r}) is pruned by permutations or the duplicate limit")
+        return token
+    
+    def index_of(self, token: str) -> int:
+        """
+        Index of a raw candidate (inverse of token_at), computed without generation
+        
+        Args:
+            token: Raw candidate
+            
+        Returns:
+            Zero-based index
+            
+        Raises:
+            GeneratorError: If the mode is not rank-addressable, the token has
+                a length or character outside the keyspace, or it is pruned
+        """
+        keyspace = self._addressable_keyspace()
+        index = keyspace.rank_of(token)
+        if not keyspace.accepts(index):
+            raise GeneratorError(f"{token!r} is pruned by permutations or the duplicate limit")
+        return index
+    
+    def rank_count(self) -> int:
+        """
+        Number of ranks generate_range addresses
+        
+        Equals keyspace_size, except that constrained keyspaces
+        (permutations, duplicate limits) also count their pruned ranks.
+        """
+        keyspace = self.keyspace()
+        return keyspace.size if keyspace is not None else self.keyspace_size()
+    
+    def generate_range(self, start: int, end: int) -> Iterator[str]:
+        """
+        Generate the tokens whose raw candidates have ranks in [start, end)
+        
+        Ranks count raw candidates in generation order, before filters, so
+        the same range always yields the same tokens. Addressable modes seek
+        straight to start (constrained ones keep gaps at pruned ranks, see
+        rank_count); the others enumerate up to it.
+        
+        Args:
+            start: First candidate rank
+            end: Rank after the last candidate
+            
+        Yields:
+            Tokens that pass the pipeline
+        """
+        keyspace = self.keyspace()
+        if keyspace is not None:
+            first, last = self.rank_bounds(keyspace)
+            source = keyspace.iter_range(max(start, first), min(end, last))
+        else:
+            source = itertools.islice(self._candidate_source()[1], start, end)
+        
+        for _, _, candidate in self._expand_candidates(source):
+            if self.cancel_token is not None and self.cancel_token.is_cancelled():
+                self.cancelled = True
+                return
+            self.candidates_seen += 1
+            token = self._process_token(candidate)
+            if token is not None:
+                self.last_token = token
+                yield token
+                if self.config.max_lines and self.tokens_generated >= self.config.max_lines:
+                    return
+    
+    def combo_users(self) -> List[str]:
+        """Usernames paired with passwords in combo output (empty if not configured)"""
+        users = self.config.combo_users
+        if not users:
+            return []
+        if isinstance(users, list):
+            return list(users)
+        return read_word_file(users)
+    
+    def output(self) -> Iterator[str]:
+        """
+        Output lines: generated tokens, user:pass combos when combo_users is set,
+        or candidate / hash pairs when hash_algorithm is set; with pipe_through,
+        the lines its command prints take the place of the generated tokens
+        
+        Yields:
+            Lines to write
+        """
+        tokens = self.generate()
+        if self.config.pipe_through:
+            # max_lines counts the tokens sent to the command
+            tokens = pipe_through(tokens, self.config.pipe_through, self.config.pipe_batch_size)
+        
+        if self.config.hash_algorithm:
+            yield from hash_lines(tokens, self.config.hash_algorithm,
+                                  self.config.hash_format, self.config.workers)
+            return
+        
+        users = self.combo_users()
+        if not users:
+            yield from tokens
+            return
+        
+        separator = self.config.combo_separator
+        max_lines = self.config.max_lines
+        passwords = tokens
+        for lines, (user, password) in enumerate(pair_credentials(users, passwords, self.config.combo_mode,
+                                                                  self.config.combo_per_user), 1):
+            yield f"{user}{separator}{password}"
+            # max_lines counts pairs here; passwords are capped at max_lines too, which covers them
+            if max_lines and lines >= max_lines:
+                passwords.close()
+                self._finish()
+                return
+    
+    def output_header(self) -> Optional[str]:
+        """Line written ahead of output() and not counted as an output line, if any"""
+        if self.config.hash_algorithm:
+            return hash_header(self.config.hash_format)
+        return None
+    
+    def write(self, backend: Optional[StorageBackend] = None) -> int:
+        """
+        Generate straight into a storage backend
+        
+        Args:
+            backend: Destination (the config's storage_backend at output_file if omitted)
+            
+        Returns:
+            Number of lines written
+        """
+        if backend is None:
+            backend = self.open_backend()
+        written = 0
+        with backend:
+            for _ in self.write_lines(backend):
+                written += 1
+        return written
+    
+    def open_backend(self) -> StorageBackend:
+        """
+        Storage backend of the configured output, not yet opened
+        
+        A resumed generator gets its output file back to the state of the
+        checkpoint: plain files are cut after the last checkpointed line
+        (dropping lines written after it) and then appended to.
+        """
+        options = self.config.storage_options()
+        state = self.resumed_from
+        if state is not None and self.config.storage_backend == "file":
+            options["append"] = True
+            if not self.config.compression:
+                for key, path in (("output_bytes", self.config.output_file),
+                                  ("plain_bytes", self.config.plain_output)):
+                    if state.get(key) is not None and path and Path(path).exists():
+                        if Path(path).stat().st_size > state[key]:
+                            os.truncate(path, state[key])
+        if self.config.split_by_bytes or self.config.split_by_lines:
+            if self.config.storage_backend != "file":
+                raise ConfigError("Split output needs the file storage backend")
+            return SplitWriter(self.config.output_file, self.config.compression, self.config.format,
+                               self.config.split_by_bytes, self.config.split_by_lines, **options)
+        backend = create_storage_backend(self.config.storage_backend, self.config.output_file,
+                                         self.config.compression, self.config.format, **options)
+        if state is not None and self.config.compression and isinstance(backend, OutputWriter):
+            # Appended compressed streams cannot be measured; keep counting uncompressed bytes
+            backend.bytes_written = state.get('output_bytes') or 0
+            if backend.plain is not None:
+                backend.plain.bytes_written = state.get('plain_bytes') or 0
+        return backend
+    
+    def write_lines(self, backend: StorageBackend) -> Iterator[str]:
+        """
+        Write output lines to an opened backend, checkpointing as configured
+        
+        Checkpoints are written every checkpoint_interval lines or
+        checkpoint_seconds seconds when a checkpoint manager is set; the
+        checkpoint of a run that completes is removed. A cancelled run
+        flushes the backend and leaves a paused checkpoint.
+        
+        With max_bytes, writing stops after the line that brings the
+        uncompressed output (all shards or split parts) to the limit,
+        so the output exceeds it by less than one line.
+        
+        Args:
+            backend: Opened destination
+            
+        Yields:
+            Each line once it has been written
+        """
+        metadata = {"tags": self.config.tags} if self.config.tags else None
+        max_bytes = self.config.max_bytes
+        if max_bytes and getattr(backend, 'bytes_written', None) is None:
+            raise ConfigError("max_bytes needs a storage backend that counts the bytes it writes")
+        self.backend = backend
+        self._written_position = self._position(backend)
+        manager = self.checkpoint_manager
+        interval = self.config.checkpoint_interval
+        seconds = self.config.checkpoint_seconds
+        saved_at = time.monotonic()
+        self.recording_campaign = True
+        
+        header = self.output_header()
+        if header is not None:
+            backend.write_token(header)
+        lines = self.output()
+        # Uncompressed bytes across all parts, counted as written
+        if max_bytes and backend.bytes_written >= max_bytes:
+            self.finish_early(lines, "max_bytes")
+        for line in lines:
+            backend.write_token(line, metadata)
+            self.tokens_written += 1
+            self._written_position = self._position(backend)
+            if manager is not None:
+                due = bool(interval) and self.tokens_written % interval == 0
+                # Clock reads are batched; the interval is a lower bound anyway
+                if not due and seconds and self.tokens_written % 1000 == 0:
+                    due = time.monotonic() - saved_at >= seconds
+                if due:
+                    backend.flush()
+                    self.save_checkpoint(manager, self.job_id)
+                    saved_at = time.monotonic()
+            yield line
+            if max_bytes and backend.bytes_written >= max_bytes:
+                backend.flush()
+                self.finish_early(lines, "max_bytes")
+                break
+        
+        self.backend = None
+        if manager is not None and not self.cancelled:
+            manager.delete_checkpoint(self.job_id)
+    
+    def cancel(self):
+        """Request cancellation of a running generation"""
+        if self.cancel_token is None:
+            self.cancel_token = CancellationToken()
+        self.cancel_token.cancel()
+    
+    def _handle_cancel(self):
+        """Stop generation, flushing output and leaving a paused checkpoint if configured"""
+        self.cancelled = True
+        self.observers.stage_change("cancelled")
+        if self.backend is not None:
+            self.backend.flush()
+        if self.checkpoint_manager is not None:
+            self.save_checkpoint(self.checkpoint_manager, self.job_id)
+        if self.campaign is not None:
+            self.campaign.close()
+    
+    def commit_campaign(self):
+        """Commit the tokens recorded into the campaign so far"""
+        if self.campaign is not None:
+            self.campaign.flush()
+    
+    @staticmethod
+    def _derive_job_id(config: Config) -> str:
+        """Stable job identifier derived from the configuration"""
+        encoded = json.dumps(config.to_dict(), sort_keys=True, default=str)
+        return hashlib.sha1(encoded.encode()).hexdigest()[:12]
+    
+    @classmethod
+    def from_checkpoint(cls, state: dict,
+                        cancel_token: Optional[CancellationToken] = None) -> 'Generator':
+        """
+        Recreate a generator positioned after a saved checkpoint
+        
+        Args:
+            state: Checkpoint state produced by save_checkpoint
+            cancel_token: Optional cancellation token for the resumed run
+            
+        Returns:
+            Generator that continues where the checkpointed run stopped
+        """
+        config = Config.from_dict(dict(state['config']))
+        generator = cls(config, cancel_token=cancel_token, job_id=state.get('job_id'))
+        generator.check_alphabets(state.get('alphabets'), f"checkpoint {state.get('job_id')}")
+        generator._resume_candidates = state.get('candidates_seen', 0)
+        if 'base_index' in state:
+            generator._resume_position = (state['base_index'], state['variant_index'])
+        generator.tokens_generated = state.get('tokens_generated', 0)
+        generator.tokens_written = state.get('tokens_written', 0)
+        generator.passed_filters = state.get('passed_filters', 0)
+        generator.passed_dedupe = state.get('passed_dedupe', 0)
+        generator.last_token = state.get('last_token')
+        if generator.campaign is not None and state.get('campaign_run'):
+            # Tokens the checkpointed run already recorded are its own
+            generator.campaign.run_id = state['campaign_run']
+        return generator
+    
+    @classmethod
+    def resume(cls, job_id: str, manager: CheckpointManager,
+               cancel_token: Optional[CancellationToken] = None) -> 'Generator':
+        """
+        Continue a checkpointed job into its output file
+        
+        Write with write() (or open_backend and write_lines): the output
+        file is reopened in append mode after the checkpointed lines, so
+        the finished file equals that of an uninterrupted run.
+        
+        Args:
+            job_id: Job identifier of the checkpoint
+            manager: Checkpoint manager holding it
+            cancel_token: Optional cancellation token for the resumed run
+            
+        Returns:
+            Generator positioned after the checkpoint
+        """
+        state = manager.load_checkpoint(job_id)
+        if state is None:
+            raise GeneratorError(f"No checkpoint for job {job_id} in {manager.checkpoint_dir}")
+        config = Config.from_dict(dict(state['config']))
+        if not config.output_file:
+            raise ConfigError(f"Job {job_id} has no output_file to resume into")
+        if config.shard_by or config.bucket_by or config.split_by_bytes or config.split_by_lines:
+            raise ConfigError("Only single-file output can be resumed")
+        
+        generator = cls.from_checkpoint(state, cancel_token)
+        generator.checkpoint_manager = manager
+        generator.resumed_from = state
+        generator.current_length = state.get('current_length', 0)
+        if config.dedupe:
+            generator._restore_dedupe(state.get('tokens_written', 0))
+        return generator
+    
+    @classmethod
+    def resume_from_output(cls, config: Config, output: Path,
+                           cancel_token: Optional[CancellationToken] = None) -> 'Generator':
+        """
+        Continue a run without a checkpoint, from the output it left
+        
+        The last complete line of the output is located in the config's
+        candidate order: by its rank in charset and pattern modes (when
+        it is a raw candidate with at most the prefix and suffix added),
+        otherwise by generating, without writing, as many tokens as the
+        file has lines and checking the last one matches. A line cut
+        short is dropped; compressed output is recompressed up to the
+        last complete line.
+        
+        Args:
+            config: Configuration of the interrupted run
+            output: Its output file (possibly compressed)
+            cancel_token: Optional cancellation token for the resumed run
+            
+        Returns:
+            Generator positioned after the last complete line, writing to output
+            
+        Raises:
+            GeneratorError: If the output was not generated by this config
+        """
+        config.output_file = Path(output)
+        if (config.format != "txt" or config.output_encoding != "utf-8" or config.hash_algorithm
+                or config.combo_users or config.pipe_through or config.plain_output
+                or config.shard_by or config.bucket_by or config.split_by_bytes or config.split_by_lines
+                or config.storage_backend != "file"):
+            raise ConfigError("Resuming from an output file needs plain token lines: one UTF-8 txt file "
+                              "without hashes, combos, pipe_through, plain_output, shards or buckets")
+        if not config.output_file.exists():
+            raise GeneratorError(f"No output file {config.output_file} to resume from")
+        # The run may have written list.txt.zst with --compress; keep appending the same way
+        config.compression = compression = config.compression or detect_compression(config.output_file)
+        lines, last, complete = inspect_output(config.output_file, compression)
+        
+        probe = cls(config)
+        if last is None:
+            position = probe._position()
         else:
-            yield from self._generate_charset()
+            position = probe._rank_position(last, lines) or probe._scan_position(last, lines)
+        state = {**position, 'job_id': probe.job_id, 'config': config.to_dict(),
+                 'tokens_written': lines, 'output_bytes': complete, 'status': 'recovered'}
+        if compression:
+            keep_lines(config.output_file, lines, compression)
+        
+        generator = cls.from_checkpoint(state, cancel_token)
+        generator.resumed_from = state
+        if config.dedupe:
+            generator._restore_dedupe(lines)
+        return generator
+    
+    def raw_candidate(self, token: str) -> Optional[str]:
+        """
+        Raw candidate behind an emitted token, when only the prefix and
+        suffix were added to it (no transforms or smart_case)
+        """
+        if self.config.transforms or self.config.smart_case:
+            return None
+        raw = token
+        if self.prefix:
+            if not raw.startswith(self.prefix):
+                return None
+            raw = raw[len(self.prefix):]
+        if self.suffix:
+            if not raw.endswith(self.suffix):
+                return None
+            raw = raw[:-len(self.suffix)]
+        return raw
+    
+    def _rank_position(self, last: str, lines: int) -> Optional[dict]:
+        """Position after the output line `last` from its rank, if it maps to a raw candidate"""
+        keyspace = self.keyspace()
+        raw = self.raw_candidate(last)
+        if keyspace is None or raw is None:
+            return None
+        try:
+            rank = keyspace.rank_of(raw)
+        except GeneratorError:
+            return None
+        if not keyspace.accepts(rank):
+            return None
+        first, end = self.rank_bounds(keyspace)
+        if not first <= rank < end:
+            raise GeneratorError(f"Last output line {last!r} lies outside the configured range")
+        base = rank + 1 - first
+        return {'tokens_generated': lines, 'candidates_seen': base, 'passed_filters': lines,
+                'passed_dedupe': lines, 'current_length': len(raw), 'base_index': base,
+                'variant_index': 0, 'last_token': last}
+    
+    def _scan_position(self, last: str, lines: int) -> dict:
+        """Position after the output line `last` by generating the tokens before it"""
+        produced = None
+        for count, token in enumerate(self.generate(), 1):
+            produced = token
+            if count == lines:
+                break
+        if self.tokens_generated < lines or produced != last:
+            raise GeneratorError(
+                f"Output does not match the config: line {lines:,} is {last!r}, "
+                f"the config generates {produced!r} after {self.tokens_generated:,} tokens"
+            )
+        return self._position()
+    
+    def _restore_dedupe(self, lines: int):
+        """Refill the dedupe set from the lines already in the output file"""
+        path = self.config.output_file
+        if not Path(path).exists():
+            return
+        with open_text(Path(path), self.config.compression) as f:
+            for line in itertools.islice(f, lines):
+                self.dedupe_filter().add(line.rstrip("\n"))
     
     def _generate_charset(self) -> Iterator[str]:
         """Generate tokens from charset"""
-        charset = self._resolve_charset()
+        keyspace = self._charset_keyspace()
+        if self.length_shares is not None:
+            starts = itertools.accumulate(keyspace.sizes, initial=0)
+            streams = {len(segment): keyspace.iter_range(start, start + size)
+                       for segment, size, start in zip(keyspace.segments, keyspace.sizes, starts)}
+            self.length_walked = {}
+            yield from interleave_lengths(streams, self.length_shares, self.length_walked)
+            return
+        first, last = self.rank_bounds(keyspace)
+        start = 0
+        for segment, size in zip(keyspace.segments, keyspace.sizes):
+            if start + size > first and start < last:
+                self.observers.stage_change(f"length:{len(segment)}")
+                yield from keyspace.iter_range(max(start, first), min(start + size, last))
+            start += size
+    
+    def _list_mode(self) -> Optional[str]:
+        """
+        Mode taking candidates from word lists, a trained model or chained
+        elements ("wordlist", "combinator", "markov", "chain"), if any
+        """
+        if self.config.input_wordlist:
+            return "wordlist"
+        if self.config.combinator_left:
+            return "combinator"
+        if self.config.markov_train or self.config.markov_model:
+            return "markov"
+        if self.config.chain_max_elements:
+            return "chain"
+        return None
+    
+    def markov_model(self) -> MarkovModel:
+        """
+        Model of Markov mode: trained on markov_train (and saved to
+        markov_model when both are set) or loaded from markov_model
+        """
+        if self._markov is None:
+            if self.config.markov_train:
+                train = self.config.markov_train
+                if not is_stream(train) and is_frequency_model(Path(train).expanduser()):
+                    # Breach corpus counts weight the transitions
+                    with FrequencyModel(Path(train).expanduser()) as model:
+                        self._markov = MarkovModel.train_counts(model, self.config.markov_order)
+                else:
+                    words = iter_wordlist(train, self.skipped_lines)
+                    self._markov = MarkovModel.train(words, self.config.markov_order)
+                if self.config.markov_model:
+                    self._markov.save(Path(self.config.markov_model))
+            else:
+                self._markov = MarkovModel.load(Path(self.config.markov_model))
+        return self._markov
+    
+    def generate_markov(self) -> Iterator[str]:
+        """
+        Tokens of the Markov model within min_length..max_length, likeliest first
         
-        for length in range(self.config.min_length, self.config.max_length + 1):
-            if self.config.permutations_only:
-                # Generate permutations (no repeating characters)
-                for combo in itertools.permutations(charset, length):
-                    token = ''.join(combo)
-                    processed_token = self._process_token(token)
-                    if processed_token is not None:
-                        yield processed_token
+        Yields:
+            Raw candidates in descending probability (see markov)
+        """
+        model = self.markov_model()
+        for token, _ in model.enumerate(self.config.min_length, self.config.max_length):
+            yield token
+    
+    def chain_index(self) -> ChainIndex:
+        """Elements of chain mode: the lines of chain_elements, or every value of the enabled fields"""
+        if self._chains is None:
+            if self.config.chain_elements:
+                elements = read_word_file(self.config.chain_elements)
             else:
-                # Generate combinations with replacement
-                for combo in itertools.product(charset, repeat=length):
-                    token = ''.join(combo)
-                    processed_token = self._process_token(token)
-                    if processed_token is not None:
-                        yield processed_token
+                elements = [value for values in self._field_values() for value in values]
+            self._chains = ChainIndex(elements)
+        return self._chains
+    
+    def _generate_chains(self) -> Iterator[str]:
+        """Generate PRINCE-style element chains (see prince)"""
+        yield from self.chain_index().chains(self.config.min_length, self.config.max_length,
+                                             self.config.chain_max_elements, self._separators()[0])
+    
+    def _hybrid_mask(self) -> Optional[Keyspace]:
+        """Keyspace of the append / prepend pattern of hybrid mode, if one is set"""
+        mask = self.config.append_pattern or self.config.prepend_pattern
+        if not mask:
+            return None
+        return Keyspace.for_pattern(mask, custom_charsets=self.config.custom_charsets)
+    
+    def _generate_hybrid(self, words: Iterable[str]) -> Iterator[str]:
+        """
+        Attach every mask candidate to each word (hashcat -a 6 / -a 7)
+        
+        Args:
+            words: Input words
+            
+        Yields:
+            word + mask candidate (prepend_pattern: mask candidate + word),
+            the whole mask walked for each word in turn
+        """
+        mask = self._hybrid_mask()
+        append = bool(self.config.append_pattern)
+        for word in words:
+            for tail in mask.iter_range(0, mask.size):
+                yield word + tail if append else tail + word
+    
+    def generate_combinator(self) -> Iterator[str]:
+        """
+        Join every left word to every right word (hashcat -a 1)
+        
+        The right list is read once into memory and the left one streamed,
+        so memory is bounded by the right list. Each separator (see
+        _separators) yields its own joining; with combinator_swap every
+        left + right is followed by right + left.
+        
+        Yields:
+            Raw candidates, left words in file order, right words within each
+        """
+        right = list(iter_wordlist(self.config.combinator_right, self.skipped_lines))
+        separators = self._separators()
+        swap = self.config.combinator_swap
+        for left in iter_wordlist(self.config.combinator_left, self.skipped_lines):
+            for word in right:
+                for separator in separators:
+                    yield left + separator + word
+                    if swap:
+                        yield word + separator + left
     
     def _generate_pattern(self) -> Iterator[str]:
         """Generate tokens using pattern matching (Crunch-style)"""
-        pattern = self.config.pattern
-        if not pattern:
+        if not self.config.pattern:
             raise GeneratorError("No pattern specified")
         
-        # Expand pattern to charset
-        charset = expand_pattern(pattern, self.config.literal_chars)
-        
-        # Generate combinations based on pattern length
-        length = len(pattern)
-        for combo in itertools.product(charset, repeat=length):
-            token = ''.join(combo)
-            processed_token = self._process_token(token)
-            if processed_token is not None:
-                yield processed_token
+        # Each position draws from its own charset (or is a fixed literal)
+        keyspace = self._pattern_keyspace()
+        yield from keyspace.iter_range(*self.rank_bounds(keyspace))
+    
+    def _generate_neighborhood(self) -> Iterator[str]:
+        """Generate every string within edit_distance of the seed words"""
+        charset = self._resolve_charset()
+        return edit_neighborhood(self.config.seed_words, self.config.edit_distance, charset)
     
     def _generate_fields(self) -> Iterator[str]:
         """Generate tokens using field-based approach"""
-        from .fields import FieldManager
-        
-        if not self.config.enabled_fields:
+        if not self._enabled_fields():
             raise GeneratorError("No fields enabled")
         
-        # Get field values
+        # Generate combinations of field values per component order,
+        # one variant per separator (within the length window if enabled)
+        low, high = self._field_window()
+        field_values = self._field_values()
+        for order in self._field_orders(len(field_values)):
+            separators = self._separators() if len(order) > 1 else ['']
+            joins = [len(separator) * (len(order) - 1) for separator in separators]
+            for combo in self._field_combos(field_values, order, min(joins), max(joins)):
+                length = sum(len(component) for component in combo)
+                for separator, join in zip(separators, joins):
+                    if low <= length + join <= high:
+                        yield separator.join(combo)
+    
+    def _resolve_derived_fields(self) -> Dict[int, Tuple[DerivedField, List[int]]]:
+        """Derived fields by position, with the positions of their source fields"""
+        fields = self._enabled_fields()
+        derived = {}
+        for i, field_id in enumerate(fields):
+            if not is_derived_field(field_id):
+                continue
+            spec = parse_derived_field(field_id)
+            sources = []
+            for source in spec.sources:
+                if source not in fields or is_derived_field(source):
+                    raise ConfigError(f"{field_id}: source field {source} must be an enabled, "
+                                      f"non-derived field")
+                sources.append(fields.index(source))
+            derived[i] = (spec, sources)
+        return derived
+    
+    def _needed_fields(self, order: Tuple[int, ...]) -> List[int]:
+        """Positions of the non-derived fields an order draws values from"""
+        needed = set()
+        for i in order:
+            if i in self.derived_fields:
+                needed.update(self.derived_fields[i][1])
+            else:
+                needed.add(i)
+        return sorted(needed)
+    
+    def _field_components(self, order: Tuple[int, ...], values: Dict[int, str]) -> List[str]:
+        """Assemble the components of one combination, computing derived fields"""
+        components = []
+        for i in order:
+            if i in self.derived_fields:
+                spec, sources = self.derived_fields[i]
+                components.append(spec.compute([values[s] for s in sources]))
+            else:
+                components.append(values[i])
+        return components
+    
+    def _field_window(self) -> Tuple[float, float]:
+        """Length window for joined field combinations (unbounded unless field_length_window)"""
+        if self.config.field_length_window:
+            return self.config.min_length, self.config.max_length
+        return 0, math.inf
+    
+    def _field_combos(self, field_values: List[Sequence[str]], order: Tuple[int, ...],
+                      shortest_join: int = 0, longest_join: int = 0) -> Iterator[List[str]]:
+        """
+        Every combination of components for one order that can fit the length window
+        
+        With field_length_window, partial products whose shortest possible
+        completion is already longer than max_length (or whose longest is
+        shorter than min_length) are skipped whole; callers still check each joined
+        token, since the separators differ in length.
+        
+        Args:
+            field_values: Values of every enabled field
+            order: Field positions, in component order
+            shortest_join: Combined length of the separators in the shortest variant
+            longest_join: Combined length of the separators in the longest variant
+            
+        Yields:
+            Component lists, in the same order as the full cartesian product
+        """
+        needed = self._needed_fields(order)
+        sequences = [field_values[i] for i in needed]
+        if (not self.config.field_length_window or any(i in self.derived_fields for i in order)
+                or not all(sequences)):
+            # No window to prune by, or derived components only known once computed
+            for combo in sequence_product(sequences):
+                yield self._field_components(order, dict(zip(needed, combo)))
+            return
+        
+        low, high = self._field_window()
+        low, high = low - longest_join, high - shortest_join
+        # Shortest and longest completions of the positions from k on
+        rest_min, rest_max = [0] * (len(sequences) + 1), [0] * (len(sequences) + 1)
+        for k in range(len(sequences) - 1, -1, -1):
+            lengths = [len(value) for value in sequences[k]]
+            rest_min[k] = rest_min[k + 1] + min(lengths)
+            rest_max[k] = rest_max[k + 1] + max(lengths)
+        if rest_min[0] >= low and rest_max[0] <= high:
+            # Every combination fits
+            for combo in sequence_product(sequences):
+                yield self._field_components(order, dict(zip(needed, combo)))
+            return
+        
+        chosen: List[str] = []
+        
+        def walk(k: int, length: int) -> Iterator[List[str]]:
+            if k == len(sequences):
+                yield self._field_components(order, dict(zip(needed, chosen)))
+                return
+            for value in sequences[k]:
+                total = length + len(value)
+                if total + rest_min[k + 1] > high or total + rest_max[k + 1] < low:
+                    continue
+                chosen.append(value)
+                yield from walk(k + 1, total)
+                chosen.pop()
+        
+        yield from walk(0, 0)
+    
+    def _field_orders(self, count: int) -> List[Tuple[int, ...]]:
+        """
+        Orders in which field components are combined
+        
+        Args:
+            count: Number of enabled fields
+            
+        Returns:
+            Unique tuples of field indexes, the enabled order first
+        """
+        identity = tuple(range(count))
+        if self.config.field_order == "swap_pairs":
+            orders = [identity]
+            for i in range(count - 1):
+                swapped = list(identity)
+                swapped[i], swapped[i + 1] = swapped[i + 1], swapped[i]
+                orders.append(tuple(swapped))
+            return list(dict.fromkeys(orders))
+        
+        if self.config.field_order == "permute":
+            low = min(self.config.min_components or 1, count)
+            high = min(self.config.max_components or count, count)
+            return [order for r in range(low, high + 1)
+                    for order in itertools.permutations(identity, r)]
+        
+        return [identity]
+    
+    def _separators(self) -> List[str]:
+        """Separators joining field components (plain concatenation if none set)"""
+        if self.config.separators:
+            return list(dict.fromkeys(self.config.separators))
+        return [self.config.separator or '']
+    
+    def _enabled_fields(self) -> List[str]:
+        """Enabled field ids, including inline `words` and the region pack selected by `region`"""
+        from .fields import REGION_FIELD_PREFIX, normalize_region
+        
+        fields = list(self.config.enabled_fields)
+        if self.config.words and INLINE_WORDS_FIELD not in fields:
+            fields.insert(0, INLINE_WORDS_FIELD)
+        if self.config.region:
+            region_field = REGION_FIELD_PREFIX + normalize_region(self.config.region)
+            if region_field not in fields:
+                fields.append(region_field)
+        return fields
+    
+    def _field_values(self) -> List[Sequence[str]]:
+        """Resolve the value list of every enabled field"""
+        registry = self.field_registry or default_registry
         field_values = []
-        for field_id in self.config.enabled_fields:
-            field = FieldManager.get_field(field_id)
-            if field:
-                field_values.append(field['examples'])
+        for field_id in self._enabled_fields():
+            if is_derived_field(field_id):
+                # Computed per combination, see _field_components
+                field_values.append([])
+                continue
+            if field_id == INLINE_WORDS_FIELD and self.config.words:
+                field_values.append(resolve_relative_values(self.config.words, self.reference_date))
+                continue
+            source = self.config.field_sources.get(field_id)
+            if isinstance(source, list):
+                field_values.append(resolve_relative_values(source, self.reference_date))
+                continue
+            if source is not None:
+                field_values.append(load_dictionary(source))
+                continue
+            values = registry.values(field_id)
+            if is_lazy_sequence(values):
+                field_values.append(values)
+            elif values is not None:
+                field_values.append(resolve_relative_values(values, self.reference_date))
             else:
                 # Use field_id as fallback
                 field_values.append([field_id])
         
-        # Generate combinations of field values
-        for combo in itertools.product(*field_values):
-            # Join with separator if specified, otherwise concatenate
-            if self.config.separator:
-                token = self.config.separator.join(combo)
-            else:
-                token = ''.join(combo)
-            
-            processed_token = self._process_token(token)
-            if processed_token is not None:
-                yield processed_token
+        if self.config.frequency_model:
+            # Most frequent values first, so likelier combinations come early
+            with FrequencyModel(resolve_source(self.config.frequency_model)) as model:
+                field_values = [model.order(values) for values in field_values]
+        return field_values
+    
+    def _resolve_affix(self, value: Optional[str]) -> Optional[str]:
+        """Prefix or suffix with its relative temporal tokens resolved (single-valued, see Config.validate)"""
+        return resolve_relative(value, self.reference_date)[0] if value else value
     
     def _resolve_charset(self) -> str:
         """Resolve charset from configuration"""
+        if self.config.charset_file:
+            return read_charset_file(self.config.charset_file)
+        
         if self.config.charset:
             # Check if it's a named charset (specific known names only)
             named_charsets = ['lowercase', 'uppercase', 'digits', 'symbols', 
@@ -133,6 +1413,97 @@ class Generator:
         # Default to lowercase
         return CHARSET_LOWERCASE
     
+    def prepare_token(self, token: str) -> str:
+        """
+        Apply prefix, suffix and transforms to a raw candidate
+        
+        Args:
+            token: Raw candidate
+            
+        Returns:
+            Candidate as it would be checked by the filters
+        """
+        if self.prefix:
+            token = self.prefix + token
+        if self.suffix:
+            token = token + self.suffix
+        
+        if self.config.transforms:
+            chain = self.ascii_transforms if token.isascii() else self.config.transforms
+            rng = token_rng(self.config.seed, token) if self.seeded_transforms else None
+            token = apply_transforms(token, chain, rng)
+        return token
+    
+    def sample_candidates(self, count: int, rng: Optional[random.Random] = None) -> List[str]:
+        """
+        Draw raw candidates uniformly at random from the keyspace
+        
+        Args:
+            count: Number of candidates to draw
+            rng: Random source (a fresh seeded one if omitted)
+            
+        Returns:
+            List of raw candidates (may repeat)
+        """
+        rng = rng or random.Random(self.config.seed)
+        
+        if self.config.input_wordlist:
+            # Streams cannot be sampled without reading them whole: take the first lines
+            words = iter_wordlist(self.config.input_wordlist)
+            if self._hybrid_mask() is not None:
+                words = self._generate_hybrid(words)
+            return list(itertools.islice(words, count))
+        if self.config.combinator_left:
+            return list(itertools.islice(self.generate_combinator(), count))
+        if self._list_mode() == "markov":
+            # The likeliest tokens rather than a uniform draw
+            return list(itertools.islice(self.generate_markov(), count))
+        if self._list_mode() == "chain":
+            # The shortest chains rather than a uniform draw
+            return list(itertools.islice(self._generate_chains(), count))
+        
+        if self.config.seed_words:
+            # Random edit walks: close to, but not exactly, uniform
+            charset = self._resolve_charset()
+            return [random_neighbor(self.config.seed_words, self.config.edit_distance, charset, rng)
+                    for _ in range(count)]
+        
+        if self.config.pattern:
+            positions = expand_pattern_positions(self.config.pattern, self.config.literal_chars,
+                                                 self.config.custom_charsets, self.config.literal_mask)
+            return [''.join(rng.choice(p) for p in positions) for _ in range(count)]
+        
+        if self._enabled_fields():
+            field_values = self._field_values()
+            separators = self._separators()
+            orders = self._field_orders(len(field_values))
+            weights = [math.prod(len(field_values[i]) for i in self._needed_fields(order))
+                       for order in orders]
+            low, high = self._field_window()
+            samples = []
+            # Draws outside the length window are retried, a bounded number of times
+            for _ in range(count * 100):
+                if len(samples) >= count:
+                    break
+                order = rng.choices(orders, weights)[0]
+                values = {i: rng.choice(field_values[i]) for i in self._needed_fields(order)}
+                token = rng.choice(separators).join(self._field_components(order, values))
+                if low <= len(token) <= high:
+                    samples.append(token)
+            return samples
+        
+        charset = self._resolve_charset()
+        lengths = list(range(self.config.min_length, self.config.max_length + 1))
+        if self.config.permutations_only:
+            lengths = [l for l in lengths if l <= len(charset)]
+            weights = [math.perm(len(charset), l) for l in lengths]
+            return [''.join(rng.sample(charset, rng.choices(lengths, weights)[0]))
+                    for _ in range(count)]
+        
+        weights = [len(charset) ** l for l in lengths]
+        return [''.join(rng.choice(charset) for _ in range(rng.choices(lengths, weights)[0]))
+                for _ in range(count)]
+    
     def _process_token(self, token: str) -> Optional[str]:
         """
         Process and validate token
@@ -143,32 +1514,40 @@ class Generator:
         Returns:
             Processed token or None if should be filtered
         """
-        # Apply prefix/suffix
-        if self.config.prefix:
-            token = self.config.prefix + token
-        if self.config.suffix:
-            token = token + self.config.suffix
-        
-        # Apply transforms
-        if self.config.transforms:
-            token = apply_transforms(token, self.config.transforms)
+        token = self.prepare_token(token)
         
         # Check filters
-        if not self.filter_pipeline.should_include(token):
-            return None
+        reason = self.filter_pipeline.check(token)
+        if reason is not None:
+            return self._reject(token, reason)
         
-        # Check start/end boundaries
-        if self.config.start_string and token < self.config.start_string:
-            return None
-        if self.config.end_string and token > self.config.end_string:
-            return None
+        if self.duplicate_limits and not check_duplicate_limit(token, self.duplicate_limits,
+                                                              self.config.custom_charsets):
+            return self._reject(token, "duplicate_limit")
+        
+        if token in self.excluded_tokens:
+            return self._reject(token, "exclude_top")
+        
+        if token in self.denied_tokens:
+            return self._reject(token, "denylist")
+        
+        if self.campaign is not None and self.campaign.seen(token):
+            return self._reject(token, "campaign")
+        
+        # Check start/end boundaries (seeked instead in charset and pattern modes)
+        if not self.seeks_string_bounds:
+            if self.config.start_string and token < self.config.start_string:
+                return self._reject(token, "start_string")
+            if self.config.end_string and token > self.config.end_string:
+                return self._reject(token, "end_string")
+        
+        self.passed_filters += 1
         
         # Deduplication
         if self.config.dedupe:
-            token_hash = hashlib.blake2b(token.encode()).hexdigest()
-            if token_hash in self.dedup_hashes:
-                return None
-            self.dedup_hashes.add(token_hash)
+            if not self.dedupe_filter().add(token):
+                return self._reject(token, "dedupe")
+        self.passed_dedupe += 1
         
         # Check limits
         if self.config.max_lines and self.tokens_generated >= self.config.max_lines:
@@ -177,10 +1556,77 @@ class Generator:
         self.tokens_generated += 1
         return token
     
+    def dedupe_filter(self) -> Deduper:
+        """
+        Deduplication set of the run, created on first use
+        
+        In bloom mode the filter is sized for dedupe_capacity, or else the
+        estimated output (see dedupe.bloom_capacity).
+        
+        Returns:
+            ExactDeduper or BloomDeduper, per dedupe_mode
+        """
+        if self.deduper is None:
+            if self.config.dedupe_mode == "bloom":
+                capacity = bloom_capacity(self.estimate_count(), self.config.dedupe_capacity)
+                self.deduper = BloomDeduper(capacity, self.config.bloom_fp_rate)
+            else:
+                self.deduper = ExactDeduper()
+        return self.deduper
+    
+    def _reject(self, token: str, reason: str) -> None:
+        """Record a rejected candidate and keep a reservoir sample of rejects"""
+        self.rejections[reason] = self.rejections.get(reason, 0) + 1
+        self.rejected_total += 1
+        self.last_rejection = (token, reason)
+        
+        sample_size = self.config.rejects_sample_size
+        if len(self.rejected_sample) < sample_size:
+            self.rejected_sample.append((token, reason))
+        else:
+            slot = self._rejects_rng.randrange(self.rejected_total)
+            if slot < sample_size:
+                self.rejected_sample[slot] = (token, reason)
+        return None
+    
+    def rejection_report(self) -> List[dict]:
+        """
+        Per-stage rejection statistics
+        
+        Returns:
+            One entry per rejecting stage with its count and the share of
+            processed candidates it rejected, largest first
+        """
+        processed = max(1, self.candidates_seen - self._resume_candidates)
+        report = [
+            {'stage': stage, 'rejected': count, 'rate': count / processed}
+            for stage, count in self.rejections.items()
+        ]
+        return sorted(report, key=lambda entry: -entry['rejected'])
+    
+    def write_rejects(self, path: Path) -> int:
+        """
+        Write the sampled rejected candidates as "token<TAB>reason" lines
+        
+        Args:
+            path: Output file
+            
+        Returns:
+            Number of lines written
+        """
+        path.parent.mkdir(parents=True, exist_ok=True)
+        with open(path, 'w', encoding='utf-8') as f:
+            for token, reason in self.rejected_sample:
+                f.write(f"{token}\t{reason}\n")
+        return len(self.rejected_sample)
+    
     def generate_list(self, limit: Optional[int] = None) -> List[str]:
         """
         Generate tokens as a list
         
+        Collects everything in memory, so only meant for small keyspaces
+        and previews; generate() and output() stream lazily.
+        
         Args:
             limit: Optional limit on number of tokens
             
@@ -208,13 +1654,96 @@ class Generator:
     
     def estimate_count(self) -> int:
         """
-        Estimate total number of tokens to be generated
+        Estimate total number of output lines
         
         Returns:
-            Estimated token count
+            Estimated token count (user:pass pairs when combo_users is set)
         """
+        total = self.keyspace_size()
+        if self.config.skip or self.config.take:
+            first, last = self.index_range()
+            total = min(total, last - first)
+        total *= self.config.smart_case or 1
+        users = self.combo_users()
+        if users:
+            total = combo_count(len(users), total, self.config.combo_mode, self.config.combo_per_user)
         if self.config.max_lines:
-            return self.config.max_lines
+            total = min(total, self.config.max_lines)
+        return total
+    
+    def keyspace_size(self) -> int:
+        """
+        Size of the raw candidate keyspace for the configured mode
+        
+        Ignores output limits and filters, so this is an upper bound on
+        the number of tokens the configuration can produce.
+        
+        Returns:
+            Number of raw candidates
+        """
+        top = len(self.top_tokens)
+        
+        if self.config.input_wordlist:
+            # Lines of the file, blank and invalid ones included (0 for stdin and pipes)
+            if self._wordlist_lines is None:
+                self._wordlist_lines = count_lines(self.config.input_wordlist) or 0
+            mask = self._hybrid_mask()
+            return self._wordlist_lines * (mask.size if mask is not None else 1) + top
+        
+        if self.config.combinator_left:
+            # Left lines (0 for stdin and pipes) times right lines, blank and invalid ones included
+            if self._wordlist_lines is None:
+                self._wordlist_lines = ((count_lines(self.config.combinator_left) or 0)
+                                        * (count_lines(self.config.combinator_right) or 0))
+            pairs = self._wordlist_lines * len(self._separators())
+            return pairs * (2 if self.config.combinator_swap else 1) + top
+        
+        if self._list_mode() == "markov":
+            # Unknown without enumerating the model; max_lines bounds the run
+            return top
+        
+        if self._list_mode() == "chain":
+            return self.chain_index().count(self.config.min_length, self.config.max_length,
+                                            self.config.chain_max_elements, self._separators()[0]) + top
+        
+        if self.config.seed_words:
+            charset_size = len(set(self._resolve_charset()))
+            return neighborhood_upper_bound(self.config.seed_words, self.config.edit_distance,
+                                            charset_size) + top
+        
+        if self.config.pattern:
+            total = 1
+            for position in expand_pattern_positions(self.config.pattern, self.config.literal_chars,
+                                                     self.config.custom_charsets, self.config.literal_mask):
+                total *= len(position)
+            return total + top
+        
+        if self._enabled_fields():
+            field_values = self._field_values()
+            separators = self._separators()
+            low, high = self._field_window()
+            # Field position -> value length -> values of that length
+            lengths: Dict[int, Counter] = {}
+            total = 0
+            for order in self._field_orders(len(field_values)):
+                needed = self._needed_fields(order)
+                joins = [len(separator) * (len(order) - 1) for separator in separators] if len(order) > 1 else [0]
+                if not self.config.field_length_window or any(i in self.derived_fields for i in order):
+                    # Every combination counts (derived components are only known once computed)
+                    total += len(joins) * math.prod(len(field_values[i]) for i in needed)
+                    continue
+                combined = Counter({0: 1})
+                for i in needed:
+                    if i not in lengths:
+                        lengths[i] = Counter(len(value) for value in field_values[i])
+                    following: Counter = Counter()
+                    for length, combos in combined.items():
+                        for value_length, values in lengths[i].items():
+                            following[length + value_length] += combos * values
+                    combined = following
+                total += sum(combos for length, combos in combined.items() for join in joins
+                             if low <= length + join <= high)
+            return total + top
         
         charset = self._resolve_charset()
         charset_size = len(set(charset))
@@ -229,13 +1758,60 @@ class Generator:
                     for i in range(length):
                         perm *= (charset_size - i)
                     total += perm
-            return total
+            return total + top
         else:
             # Combinations with replacement: n^r
             total = 0
             for length in range(self.config.min_length, self.config.max_length + 1):
                 total += charset_size ** length
-            return total
+            return total + top
+    
+    def save_checkpoint(self, manager: CheckpointManager, job_id: str) -> dict:
+        """
+        Write a checkpoint of the current generation state
+        
+        Args:
+            manager: Checkpoint manager to write through
+            job_id: Job identifier
+            
+        Returns:
+            The saved state dictionary
+        """
+        position = self._written_position if self.backend is not None and self._written_position else self._position()
+        state = {
+            'job_id': job_id,
+            **position,
+            'cancelled': self.cancelled,
+            'status': CHECKPOINT_PAUSED if self.cancelled else CHECKPOINT_RUNNING,
+            'config': self.config.to_dict(),
+            'alphabets': self.alphabets(),
+        }
+        if self.campaign is not None:
+            state['campaign_run'] = self.campaign.run_id
+            self.commit_campaign()
+        manager.save_checkpoint(job_id, state)
+        self.observers.checkpoint(state)
+        return state
+    
+    def _position(self, backend: Optional[StorageBackend] = None) -> dict:
+        """Iteration position recorded in checkpoints, with the output size when writing"""
+        position = {
+            'tokens_generated': self.tokens_generated,
+            'tokens_written': self.tokens_written,
+            'candidates_seen': self.candidates_seen,
+            'passed_filters': self.passed_filters,
+            'passed_dedupe': self.passed_dedupe,
+            'current_length': self.current_length,
+            'base_index': self.base_index,
+            'variant_index': self.variant_index,
+            'last_token': self.last_token,
+        }
+        if backend is not None:
+            # Byte sizes let a resumed run cut lines written after the checkpoint
+            position['output_bytes'] = getattr(backend, 'bytes_written', None)
+            plain = getattr(backend, 'plain', None)
+            position['plain_bytes'] = plain.bytes_written if plain is not None else None
+        return position
     
     def get_stats(self) -> dict:
         """
@@ -246,7 +1822,16 @@ class Generator:
         """
         return {
             'tokens_generated': self.tokens_generated,
+            'candidates_seen': self.candidates_seen,
+            'after_filters': self.passed_filters,
+            'after_dedupe': self.passed_dedupe,
+            'tokens_written': self.tokens_written,
+            'skipped_lines': dict(self.skipped_lines),
+            'last_token': self.last_token,
+            'cancelled': self.cancelled,
             'estimated_total': self.estimate_count(),
-            'dedup_cache_size': len(self.dedup_hashes),
+            'dedup_cache_size': len(self.deduper) if self.deduper is not None else 0,
+            'dedupe_memory': self.deduper.memory_bytes() if self.deduper is not None else 0,
+            'rejections': self.rejection_report(),
             'config': self.config.to_dict(),
         }
@@ -0,0 +1,167 @@
+"""
+Hashed output for lookup tables
+
+Pairs every candidate with its unsalted hash (MD5, SHA1 or NTLM) so a
+generated list doubles as a precomputed lookup table for fast hashes.
+Hashing runs on a process pool in batches (threads would share one
+interpreter lock) while the order of the candidate stream is kept.
+
+Lines are written potfile-style ("<hash>:<candidate>", as hashcat and
+John store cracked hashes) or as CSV ("candidate,hash" below a header).
+"""
+
+import collections
+import hashlib
+import itertools
+import struct
+from concurrent.futures import ProcessPoolExecutor
+from functools import partial
+from typing import Callable, Dict, Iterable, Iterator, List, Optional
+
+from .error import ConfigError
+
+
+HASH_FORMATS = ("potfile", "csv")
+
+# First line of CSV output, written ahead of the candidate lines
+HASH_CSV_HEADER = "candidate,hash"
+
+# Candidates hashed per task of the process pool
+HASH_BATCH_SIZE = 4096
+
+
+def _md4(data: bytes) -> bytes:
+    """MD4 digest (RFC 1320), for OpenSSL builds without the legacy provider"""
+    def rotl(x, n):
+        x &= 0xFFFFFFFF
+        return ((x << n) | (x >> (32 - n))) & 0xFFFFFFFF
+
+    message = data + b"\x80" + b"\x00" * ((55 - len(data)) % 64) + struct.pack("<Q", len(data) * 8)
+    state = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476]
+    for block in range(0, len(message), 64):
+        x = struct.unpack("<16I", message[block:block + 64])
+        a, b, c, d = state
+        for i in range(16):
+            k, s = i, (3, 7, 11, 19)[i % 4]
+            a, b, c, d = d, rotl(a + ((b & c) | (~b & d)) + x[k], s), b, c
+        for i in range(16):
+            k, s = (i % 4) * 4 + i // 4, (3, 5, 9, 13)[i % 4]
+            a, b, c, d = d, rotl(a + ((b & c) | (b & d) | (c & d)) + x[k] + 0x5A827999, s), b, c
+        for i in range(16):
+            k, s = (0, 8, 4, 12, 2, 10, 6, 14, 1, 9, 5, 13, 3, 11, 7, 15)[i], (3, 9, 11, 15)[i % 4]
+            a, b, c, d = d, rotl(a + (b ^ c ^ d) + x[k] + 0x6ED9EBA1, s), b, c
+        state = [(v + w) & 0xFFFFFFFF for v, w in zip(state, (a, b, c, d))]
+    return struct.pack("<4I", *state)
+
+
+def _ntlm(token: str) -> str:
+    data = token.encode("utf-16-le")
+    try:
+        return hashlib.new("md4", data).hexdigest()
+    except ValueError:
+        return _md4(data).hex()
+
+
+HASH_ALGORITHMS: Dict[str, Callable[[str], str]] = {
+    "md5": lambda token: hashlib.md5(token.encode("utf-8")).hexdigest(),
+    "sha1": lambda token: hashlib.sha1(token.encode("utf-8")).hexdigest(),
+    "ntlm": _ntlm,
+}
+
+# Hex digest length per algorithm
+DIGEST_LENGTHS = {"md5": 32, "sha1": 40, "ntlm": 32}
+
+
+def hash_token(token: str, algorithm: str) -> str:
+    """
+    Hex digest of a candidate
+
+    Args:
+        token: Candidate
+        algorithm: One of HASH_ALGORITHMS
+
+    Returns:
+        Lowercase hex digest
+    """
+    if algorithm not in HASH_ALGORITHMS:
+        raise ConfigError(f"Unknown hash algorithm: {algorithm} (known: {', '.join(HASH_ALGORITHMS)})")
+    return HASH_ALGORITHMS[algorithm](token)
+
+
+def _csv_field(value: str) -> str:
+    if any(char in value for char in ',"\r\n'):
+        return '"' + value.replace('"', '""') + '"'
+    return value
+
+
+def format_hash_line(token: str, digest: str, format: str = "potfile") -> str:
+    """Output line of a candidate and its digest"""
+    if format == "potfile":
+        return f"{digest}:{token}"
+    if format == "csv":
+        return f"{_csv_field(token)},{digest}"
+    raise ConfigError(f"Unknown hash output format: {format} (known: {', '.join(HASH_FORMATS)})")
+
+
+def hash_header(format: str = "potfile") -> Optional[str]:
+    """Line written ahead of hashed output in a format (None for potfiles)"""
+    return HASH_CSV_HEADER if format == "csv" else None
+
+
+def _render_batch(batch: List[str], algorithm: str, format: str) -> List[str]:
+    """Output lines of a batch of candidates (run in pool processes)"""
+    hasher = HASH_ALGORITHMS[algorithm]
+    return [format_hash_line(token, hasher(token), format) for token in batch]
+
+
+def hash_lines(tokens: Iterable[str], algorithm: str, format: str = "potfile",
+               processes: int = 1, batch_size: int = HASH_BATCH_SIZE) -> Iterator[str]:
+    """
+    Hash a candidate stream into output lines
+
+    Args:
+        tokens: Candidates
+        algorithm: One of HASH_ALGORITHMS
+        format: One of HASH_FORMATS
+        processes: Hashing processes
+        batch_size: Candidates per process pool task
+
+    Yields:
+        Lines in candidate order (without the header, see hash_header)
+    """
+    if algorithm not in HASH_ALGORITHMS:
+        raise ConfigError(f"Unknown hash algorithm: {algorithm} (known: {', '.join(HASH_ALGORITHMS)})")
+    format_hash_line("", "", format)
+
+    render = partial(_render_batch, algorithm=algorithm, format=format)
+    tokens = iter(tokens)
+    batches = iter(lambda: list(itertools.islice(tokens, batch_size)), [])
+    if processes <= 1:
+        for batch in batches:
+            yield from render(batch)
+        return
+
+    with ProcessPoolExecutor(max_workers=processes) as pool:
+        # A bounded window of batches in flight keeps memory flat
+        pending = collections.deque()
+        for batch in batches:
+            pending.append(pool.submit(render, batch))
+            if len(pending) >= processes * 2:
+                yield from pending.popleft().result()
+        while pending:
+            yield from pending.popleft().result()
+
+
+def hashed_output_bytes(candidates: int, average_token_bytes: float, algorithm: str) -> int:
+    """
+    Approximate size of hashed output
+
+    Args:
+        candidates: Number of lines
+        average_token_bytes: Mean encoded candidate length
+        algorithm: One of HASH_ALGORITHMS
+
+    Returns:
+        Bytes (candidate, separator, digest and newline per line)
+    """
+    return int(candidates * (average_token_bytes + DIGEST_LENGTHS[algorithm] + 2))
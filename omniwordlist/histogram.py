@@ -0,0 +1,71 @@
+"""
+Token length histogram and keyspace coverage
+
+LengthHistogram observes a run and counts emitted tokens per length;
+length_coverage reports how much of each length band of the keyspace
+the generator has walked so far. Together they back the periodic
+`omni run --status` report.
+"""
+
+from typing import Dict, List, Optional, Tuple
+from .events import GeneratorObserver
+
+
+class LengthHistogram(GeneratorObserver):
+    """Counts emitted tokens by length"""
+
+    def __init__(self):
+        self.counts: Dict[int, int] = {}
+
+    def on_token(self, token: str) -> None:
+        length = len(token)
+        self.counts[length] = self.counts.get(length, 0) + 1
+
+    @property
+    def total(self) -> int:
+        return sum(self.counts.values())
+
+    def items(self) -> List[Tuple[int, int]]:
+        """(length, count) pairs, shortest first"""
+        return sorted(self.counts.items())
+
+
+def length_coverage(generator) -> Optional[Dict[int, float]]:
+    """
+    Fraction of each length band walked so far
+
+    Only rank-addressable modes (charset, pattern) enumerate length by
+    length, so only they have per-length coverage.
+
+    Args:
+        generator: Running generator
+
+    Returns:
+        Length -> coverage in [0, 1], or None for other modes
+    """
+    keyspace = generator.keyspace()
+    # Constrained keyspaces do not count their pruned candidates as seen
+    if keyspace is None or keyspace.constraint is not None:
+        return None
+
+    if generator.length_walked is not None:
+        # Interleaved lengths (length_distribution) are walked side by side
+        return {len(segment): generator.length_walked.get(len(segment), 0) / size if size else 1.0
+                for segment, size in zip(keyspace.segments, keyspace.sizes)}
+
+    # Partitioned runs (start_string, skip) begin part way into the keyspace
+    remaining = generator.rank_bounds(keyspace)[0] + generator.candidates_seen
+    coverage = {}
+    for segment, size in zip(keyspace.segments, keyspace.sizes):
+        walked = min(remaining, size)
+        remaining -= walked
+        length = len(segment)
+        # Patterns have one band; charset mode one band per length
+        coverage[length] = walked / size if size else 1.0
+    return coverage
+
+
+def bar(fraction: float, width: int = 20) -> str:
+    """Text progress bar for a fraction in [0, 1]"""
+    filled = int(round(fraction * width))
+    return "#" * filled + "." * (width - filled)
@@ -0,0 +1,204 @@
+"""
+Searchable index sidecar for generated lists
+
+An index records the token, byte offset and line number of every Nth
+line of an uncompressed txt wordlist, in `<file>.idx`. Looking up a
+token in a sorted list then binary-searches the index and reads at
+most N lines; extracting by line number seeks straight to the nearest
+indexed line in any list, sorted or not.
+"""
+
+import bisect
+import json
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Iterator, List, Optional, Tuple
+
+from .error import StorageError
+from .storage import detect_compression
+
+
+INDEX_SUFFIX = ".idx"
+INDEX_VERSION = 1
+DEFAULT_INDEX_INTERVAL = 1000
+
+
+def index_path_for(path: Path) -> Path:
+    """Sidecar index path of a wordlist"""
+    path = Path(path)
+    return path.with_name(path.name + INDEX_SUFFIX)
+
+
+@dataclass
+class WordlistIndex:
+    """Sparse line index of a wordlist"""
+    interval: int
+    lines: int = 0
+    size: int = 0
+    # Whether every line is >= the previous one (code point order)
+    sorted: bool = True
+    # (token, byte offset, line number) of lines 0, interval, 2 * interval, ...
+    entries: List[Tuple[str, int, int]] = field(default_factory=list)
+
+    def save(self, path: Path) -> None:
+        """Write the index as JSON"""
+        data = {"version": INDEX_VERSION, "interval": self.interval, "lines": self.lines,
+                "size": self.size, "sorted": self.sorted, "entries": self.entries}
+        try:
+            Path(path).write_text(json.dumps(data, separators=(",", ":")) + "\n")
+        except OSError as e:
+            raise StorageError(f"Cannot write index {path}: {e.strerror}")
+
+    @classmethod
+    def load(cls, path: Path) -> 'WordlistIndex':
+        """Read an index written by save()"""
+        try:
+            data = json.loads(Path(path).read_text())
+        except (OSError, ValueError) as e:
+            raise StorageError(f"Cannot read index {path}: {e}")
+        if data.get("version") != INDEX_VERSION:
+            raise StorageError(f"Unsupported index version in {path}: {data.get('version')}")
+        return cls(data["interval"], data["lines"], data["size"], data["sorted"],
+                   [tuple(entry) for entry in data["entries"]])
+
+    def offset_of_line(self, line: int) -> Tuple[int, int]:
+        """
+        Nearest indexed position at or before a line
+
+        Args:
+            line: Line number (0-based)
+
+        Returns:
+            (byte offset, line number) to start reading from
+        """
+        if not self.entries:
+            return 0, 0
+        _, offset, start = self.entries[min(line // self.interval, len(self.entries) - 1)]
+        return offset, start
+
+    def offset_of_token(self, token: str) -> Tuple[int, int]:
+        """
+        Position to scan from for a token of a sorted list
+
+        Args:
+            token: Token to look for
+
+        Returns:
+            (byte offset, line number) of the last indexed line < token, so
+            the first of several equal lines is found
+        """
+        if not self.sorted:
+            raise StorageError("Token search needs a sorted wordlist")
+        keys = [entry[0] for entry in self.entries]
+        position = bisect.bisect_left(keys, token) - 1
+        if position < 0:
+            return 0, 0
+        _, offset, line = self.entries[position]
+        return offset, line
+
+
+def _check_plain(path: Path) -> None:
+    if detect_compression(path):
+        raise StorageError(f"Indexes need an uncompressed wordlist: {path}")
+
+
+def iter_lines(path: Path, offset: int = 0) -> Iterator[Tuple[int, str]]:
+    """
+    Lines of a wordlist from a byte offset
+
+    Args:
+        path: Uncompressed wordlist
+        offset: Byte offset of a line start
+
+    Yields:
+        (byte offset, token) pairs
+    """
+    try:
+        with open(path, "rb") as f:
+            f.seek(offset)
+            for raw in f:
+                yield offset, raw.rstrip(b"\r\n").decode("utf-8", errors="ignore")
+                offset += len(raw)
+    except OSError as e:
+        raise StorageError(f"Cannot read {path}: {e.strerror}")
+
+
+def build_index(path: Path, interval: int = DEFAULT_INDEX_INTERVAL,
+                index_path: Optional[Path] = None) -> WordlistIndex:
+    """
+    Index a wordlist and write the sidecar
+
+    Args:
+        path: Uncompressed txt wordlist
+        interval: Lines between index entries
+        index_path: Sidecar path (index_path_for(path) if omitted)
+
+    Returns:
+        The index written
+    """
+    if interval < 1:
+        raise StorageError("Index interval must be at least 1")
+    _check_plain(path)
+    index = WordlistIndex(interval)
+    previous = None
+    for offset, token in iter_lines(path):
+        if index.lines % interval == 0:
+            index.entries.append((token, offset, index.lines))
+        if previous is not None and token < previous:
+            index.sorted = False
+        previous = token
+        index.lines += 1
+    index.size = Path(path).stat().st_size
+    index.save(index_path or index_path_for(path))
+    return index
+
+
+def load_index(path: Path) -> Optional[WordlistIndex]:
+    """
+    Sidecar index of a wordlist, if present and up to date
+
+    Args:
+        path: Wordlist
+
+    Returns:
+        Index, or None when there is none or the list changed since
+    """
+    sidecar = index_path_for(path)
+    if not sidecar.exists():
+        return None
+    index = WordlistIndex.load(sidecar)
+    if index.size != Path(path).stat().st_size:
+        return None
+    return index
+
+
+def lookup(path: Path, token: str, index: Optional[WordlistIndex] = None) -> Optional[int]:
+    """
+    Line number of a token in a wordlist
+
+    Sorted, indexed lists are binary-searched; others are scanned.
+
+    Args:
+        path: Uncompressed wordlist
+        token: Token to find
+        index: Index to use (the sidecar if omitted)
+
+    Returns:
+        0-based line number, or None if absent
+    """
+    _check_plain(path)
+    index = index or load_index(path)
+    if index is None or not index.sorted:
+        for line, (_, found) in enumerate(iter_lines(path)):
+            if found == token:
+                return line
+        return None
+
+    offset, line = index.offset_of_token(token)
+    for _, found in iter_lines(path, offset):
+        if found == token:
+            return line
+        if found > token:
+            return None
+        line += 1
+    return None
@@ -0,0 +1,383 @@
+"""
+Rank addressing for the candidate keyspace
+
+Charset and pattern modes enumerate a mixed-radix space: every
+candidate has a rank equal to its position in the generation order,
+and any rank can be turned back into its candidate without walking
+the ones before it. Chunked and distributed generation build on this.
+
+The same odometer drives constrained modes (permutations, duplicate
+limits): a constraint hook prunes prefixes while counting, so pruned
+candidates leave gaps in the rank space but every rank keeps its
+meaning.
+
+Inverted keyspaces (crunch -i) change the leftmost position fastest:
+"ab" at length 2 runs aa, ba, ab, bb. They walk the positions in
+reverse order and reverse every candidate on the way out, so seeking,
+ranges and constraints work unchanged.
+
+Whole segments are enumerated by itertools.product. Ranges starting or
+ending inside a segment (resumed runs, skip / take, chunks, start
+strings) need the odometer, several times slower, except in charset
+keyspaces of exactly the ASCII digits or lowercase letters (in that
+order, not inverted, no permutations): a digit candidate is its offset
+within the length zero-padded by integer formatting, and lowercase
+candidates join a head string onto slices of a table of every
+three-letter tail, both at about the speed of a whole segment.
+"""
+
+import functools
+import itertools
+import math
+import string
+from dataclasses import dataclass
+from typing import Callable, Dict, Iterator, List, Optional, Sequence
+
+from .charset import expand_pattern_positions, parse_duplicate_limit, pattern_markers
+from .error import GeneratorError
+
+
+# Constraint hook: (chars, i) -> whether chars[:i + 1] is an acceptable prefix
+Constraint = Callable[[List[str], int], bool]
+
+# Charsets with a fast path, by name
+FAST_CHARSETS = {string.digits: "digits", string.ascii_lowercase: "lowercase"}
+
+# Length of the lowercase tails joined onto each head (a table of 26 ** 3 strings)
+LOWERCASE_TAIL = 3
+
+
+def distinct_chars(chars: List[str], i: int) -> bool:
+    """Constraint of permutation mode: no character repeats"""
+    return chars[i] not in chars[:i]
+
+
+def duplicate_limit_constraint(markers: Sequence[Optional[str]], limits: Dict[str, int]) -> Constraint:
+    """
+    Constraint limiting runs of one character per pattern marker class
+    
+    A run is a sequence of adjacent positions from the same marker
+    holding the same character; each marker class is limited on its own
+    and literal positions are exempt (they neither count nor continue a
+    run).
+    
+    Args:
+        markers: Result of charset.pattern_markers
+        limits: Result of charset.parse_duplicate_limit
+    
+    Returns:
+        Constraint hook
+    """
+    limit_at = [limits.get(marker, limits.get("")) if marker else None for marker in markers]
+    
+    def check(chars: List[str], i: int) -> bool:
+        limit = limit_at[i]
+        if limit is None:
+            return True
+        run, j = 1, i - 1
+        while j >= 0 and markers[j] == markers[i] and chars[j] == chars[i]:
+            run += 1
+            j -= 1
+        return run <= limit
+    
+    return check
+
+
+class Keyspace:
+    """
+    Ordered keyspace made of consecutive mixed-radix segments
+    
+    Each segment is a list of positions (one candidate-character
+    sequence per position). Charset mode has one segment per length,
+    pattern mode a single segment.
+    """
+    
+    def __init__(self, segments: Sequence[Sequence[Sequence[str]]],
+                 constraint: Optional[Constraint] = None, inverted: bool = False):
+        """
+        Initialize keyspace
+        
+        Args:
+            segments: Segments in generation order
+            constraint: Optional prefix-pruning hook; ranks of rejected
+                candidates are skipped rather than renumbered. When
+                inverted it sees the positions in reverse order.
+            inverted: Change the leftmost position fastest (crunch -i)
+        """
+        # Positions are kept in walking order, i.e. reversed when inverted
+        self.segments = [[list(position) for position in (reversed(segment) if inverted else segment)]
+                         for segment in segments]
+        self.constraint = constraint
+        self.inverted = inverted
+        # Name of the fast path every segment takes (see FAST_CHARSETS), set by for_charset
+        self.fast: Optional[str] = None
+        self.sizes = []
+        for segment in self.segments:
+            size = 1
+            for position in segment:
+                size *= len(position)
+            self.sizes.append(size)
+    
+    @classmethod
+    def for_charset(cls, charset: str, min_length: int, max_length: int,
+                    permutations: bool = False, inverted: bool = False) -> 'Keyspace':
+        """Keyspace of every charset string from min_length to max_length (without repeats for permutations)"""
+        if permutations:
+            charset = ''.join(dict.fromkeys(charset))
+            max_length = min(max_length, len(charset))
+        keyspace = cls([[charset] * length for length in range(min_length, max_length + 1)],
+                       distinct_chars if permutations else None, inverted)
+        if not permutations and not inverted:
+            keyspace.fast = FAST_CHARSETS.get(charset)
+        return keyspace
+    
+    @classmethod
+    def for_pattern(cls, pattern: str, literal_chars: Optional[str] = None,
+                    duplicate_limit: Optional[str] = None,
+                    custom_charsets: Optional[List[str]] = None, inverted: bool = False,
+                    literal_mask: Optional[str] = None) -> 'Keyspace':
+        """Keyspace of a Crunch-style pattern, optionally with crunch -d style duplicate limits"""
+        constraint = None
+        if duplicate_limit:
+            markers = pattern_markers(pattern, literal_chars, custom_charsets, literal_mask)
+            constraint = duplicate_limit_constraint(markers[::-1] if inverted else markers,
+                                                    parse_duplicate_limit(duplicate_limit))
+        return cls([expand_pattern_positions(pattern, literal_chars, custom_charsets, literal_mask)], constraint, inverted)
+    
+    def alphabets(self) -> List[str]:
+        """Distinct position alphabets in the order their characters are counted, in walking order"""
+        return list(dict.fromkeys("".join(position) for segment in self.segments for position in segment))
+    
+    @property
+    def size(self) -> int:
+        """Total number of candidates"""
+        return sum(self.sizes)
+    
+    def _locate(self, rank: int):
+        """Segment index and offset within it for a rank"""
+        if rank < 0 or rank >= self.size:
+            raise GeneratorError(f"Rank {rank} outside keyspace of {self.size} candidates")
+        for index, size in enumerate(self.sizes):
+            if rank < size:
+                return index, rank
+            rank -= size
+    
+    def _digits(self, segment: List[List[str]], offset: int) -> List[int]:
+        """Mixed-radix digits of an offset, most significant first"""
+        digits = [0] * len(segment)
+        for i in range(len(segment) - 1, -1, -1):
+            offset, digits[i] = divmod(offset, len(segment[i]))
+        return digits
+    
+    def token_at(self, rank: int) -> str:
+        """
+        Candidate at a rank
+        
+        Args:
+            rank: Zero-based position in generation order
+        
+        Returns:
+            Candidate string (possibly one the constraint prunes, see accepts)
+        """
+        index, offset = self._locate(rank)
+        segment = self.segments[index]
+        token = ''.join(segment[i][d] for i, d in enumerate(self._digits(segment, offset)))
+        return token[::-1] if self.inverted else token
+    
+    def rank_of(self, token: str) -> int:
+        """
+        Rank of a candidate (inverse of token_at), computed without enumeration
+        
+        Args:
+            token: Candidate string
+        
+        Returns:
+            Zero-based position in generation order
+        
+        Raises:
+            GeneratorError: If no segment has the token's length or a
+                character is not available at its position
+        """
+        base = 0
+        walked = token[::-1] if self.inverted else token
+        for segment, size in zip(self.segments, self.sizes):
+            if len(segment) != len(token):
+                base += size
+                continue
+            offset = 0
+            for i, (position, char) in enumerate(zip(segment, walked)):
+                if char not in position:
+                    position_number = len(token) - i if self.inverted else i + 1
+                    raise GeneratorError(f"{token!r}: character {char!r} at position {position_number} "
+                                         f"is not in the keyspace")
+                offset = offset * len(position) + position.index(char)
+            return base + offset
+        lengths = sorted({len(segment) for segment in self.segments})
+        covered = f"{lengths[0]}-{lengths[-1]}" if len(lengths) > 1 else str(lengths[0]) if lengths else "none"
+        raise GeneratorError(f"{token!r}: length {len(token)} is outside the keyspace (lengths {covered})")
+    
+    def accepts(self, rank: int) -> bool:
+        """Whether the candidate at a rank survives the constraint"""
+        if self.constraint is None:
+            return True
+        token = self.token_at(rank)
+        chars = list(token[::-1] if self.inverted else token)
+        return all(self.constraint(chars, i) for i in range(len(chars)))
+    
+    def iter_range(self, start: int, end: int) -> Iterator[str]:
+        """
+        Candidates with ranks in [start, end)
+        
+        Args:
+            start: First rank
+            end: Rank after the last one (clamped to the keyspace size)
+        
+        Yields:
+            Candidates in generation order, skipping pruned ranks
+        """
+        end = min(end, self.size)
+        if start >= end:
+            return
+        if self.inverted:
+            yield from (token[::-1] for token in self._iter_walked(start, end))
+        else:
+            yield from self._iter_walked(start, end)
+    
+    def _iter_walked(self, start: int, end: int) -> Iterator[str]:
+        """Candidates of a non-empty rank range in walking order"""
+        index, offset = self._locate(start)
+        remaining = end - start
+        while remaining > 0:
+            count = min(remaining, self.sizes[index] - offset)
+            if self.constraint is None:
+                yield from self._walk(self.segments[index], offset, count)
+            else:
+                yield from self._walk_constrained(self.segments[index], offset, count)
+            remaining -= count
+            index, offset = index + 1, 0
+    
+    def _walk(self, segment: List[List[str]], offset: int, count: int) -> Iterator[str]:
+        """Odometer over count candidates of a segment from offset"""
+        if offset == 0 and count == math.prod(len(position) for position in segment):
+            # Whole segment: same order as the odometer, enumerated in C
+            yield from map(''.join, itertools.product(*segment))
+            return
+        if self.fast == "digits":
+            yield from map(f"%0{len(segment)}d".__mod__, range(offset, offset + count))
+            return
+        if self.fast == "lowercase":
+            yield from _walk_lowercase(len(segment), offset, count)
+            return
+        digits = self._digits(segment, offset)
+        chars = [segment[i][d] for i, d in enumerate(digits)]
+        for _ in range(count):
+            yield ''.join(chars)
+            # Odometer increment, least significant position last
+            for i in range(len(segment) - 1, -1, -1):
+                digits[i] += 1
+                if digits[i] < len(segment[i]):
+                    chars[i] = segment[i][digits[i]]
+                    break
+                digits[i] = 0
+                chars[i] = segment[i][0]
+    
+    def _walk_constrained(self, segment: List[List[str]], offset: int, count: int) -> Iterator[str]:
+        """Odometer over a segment that skips every subtree whose prefix the constraint rejects"""
+        end = offset + count
+        radices = [len(position) for position in segment]
+        weights = [1] * len(segment)
+        for i in range(len(segment) - 2, -1, -1):
+            weights[i] = weights[i + 1] * radices[i + 1]
+        digits = self._digits(segment, offset)
+        chars = [segment[i][d] for i, d in enumerate(digits)]
+        
+        def bump(i: int) -> int:
+            # Advance position i, reset the positions after it; returns the
+            # position that changed or -1 once the segment is exhausted
+            for j in range(i + 1, len(segment)):
+                digits[j] = 0
+                chars[j] = segment[j][0]
+            while i >= 0:
+                digits[i] += 1
+                if digits[i] < radices[i]:
+                    chars[i] = segment[i][digits[i]]
+                    return i
+                digits[i] = 0
+                chars[i] = segment[i][0]
+                i -= 1
+            return -1
+        
+        i = 0
+        while True:
+            # Validate positions from the first changed one onwards
+            while 0 <= i < len(segment):
+                i = i + 1 if self.constraint(chars, i) else bump(i)
+            if i < 0 or sum(d * w for d, w in zip(digits, weights)) >= end:
+                return
+            yield ''.join(chars)
+            i = bump(len(segment) - 1)
+            if i < 0:
+                return
+
+
+@functools.lru_cache(maxsize=None)
+def _lowercase_tails(length: int) -> List[str]:
+    """Every lowercase string of a length, in generation order"""
+    return list(map(''.join, itertools.product(string.ascii_lowercase, repeat=length)))
+
+
+def _walk_lowercase(length: int, offset: int, count: int) -> Iterator[str]:
+    """count lowercase candidates of a length from offset: heads joined onto slices of the tail table"""
+    tails = _lowercase_tails(min(length, LOWERCASE_TAIL))
+    head_length = length - min(length, LOWERCASE_TAIL)
+    head, start = divmod(offset, len(tails))
+    end = offset + count
+    while head * len(tails) < end:
+        stop = min(end - head * len(tails), len(tails))
+        if head_length:
+            prefix, value = [], head
+            for _ in range(head_length):
+                value, digit = divmod(value, 26)
+                prefix.append(string.ascii_lowercase[digit])
+            yield from map(''.join(reversed(prefix)).__add__, itertools.islice(tails, start, stop))
+        else:
+            yield from itertools.islice(tails, start, stop)
+        head, start = head + 1, 0
+
+
+@dataclass
+class Chunk:
+    """A numbered, fixed-size slice of the keyspace"""
+    id: int
+    start: int
+    end: int
+    
+    @property
+    def size(self) -> int:
+        return self.end - self.start
+
+
+def chunk_count(keyspace_size: int, chunk_size: int) -> int:
+    """Number of chunks of chunk_size covering a keyspace"""
+    if chunk_size < 1:
+        raise GeneratorError("Chunk size must be at least 1")
+    return -(-keyspace_size // chunk_size)
+
+
+def chunk_bounds(chunk_id: int, chunk_size: int, keyspace_size: int) -> Chunk:
+    """
+    Rank range covered by a chunk
+    
+    Args:
+        chunk_id: Zero-based chunk number
+        chunk_size: Candidates per chunk
+        keyspace_size: Total keyspace size
+    
+    Returns:
+        Chunk with start (inclusive) and end (exclusive) ranks
+    """
+    total = chunk_count(keyspace_size, chunk_size)
+    if chunk_id < 0 or chunk_id >= total:
+        raise GeneratorError(f"Chunk {chunk_id} out of range (0-{total - 1})")
+    start = chunk_id * chunk_size
+    return Chunk(chunk_id, start, min(start + chunk_size, keyspace_size))
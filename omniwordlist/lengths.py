@@ -0,0 +1,111 @@
+"""
+Target length distribution
+
+Charset mode normally finishes every length before starting the next,
+so the head of a list is all short candidates. With a length
+distribution (shares per length, e.g. measured from a breach corpus
+with `omni length-stats`) the lengths are interleaved instead: at any
+point of the output, each length has contributed its share of the
+candidates so far, so the head of the list mirrors real-world length
+frequencies. A length that runs out leaves the others to continue in
+proportion; lengths without a share follow once the weighted ones are
+exhausted, shortest first.
+"""
+
+import heapq
+import json
+from collections import Counter
+from pathlib import Path
+from typing import Dict, Iterable, Iterator, Optional, Union
+
+from .error import ConfigError
+
+
+def measure_lengths(words: Iterable[str], source: str = "") -> Dict:
+    """
+    Share of each length in a word list
+
+    Args:
+        words: Breach list or other sample of real passwords
+        source: Description stored with the table
+
+    Returns:
+        Table for load_length_distribution
+    """
+    counts = Counter(len(word) for word in words if word)
+    total = sum(counts.values())
+    lengths = {str(length): round(count / total, 6) for length, count in sorted(counts.items())}
+    return {"source": source, "lengths": lengths}
+
+
+def load_length_distribution(value: Union[Dict, str, Path]) -> Dict[int, float]:
+    """
+    Normalise a length distribution
+
+    Args:
+        value: Length -> weight mapping, a table from measure_lengths, or
+            the path of a JSON file holding either
+
+    Returns:
+        Length -> share, shares summing to 1
+    """
+    if isinstance(value, (str, Path)):
+        try:
+            value = json.loads(Path(value).expanduser().read_text())
+        except (OSError, ValueError) as e:
+            raise ConfigError(f"Cannot read length distribution {value}: {e}")
+    if not isinstance(value, dict):
+        raise ConfigError("length_distribution must map lengths to weights")
+    if isinstance(value.get("lengths"), dict):
+        value = value["lengths"]
+
+    weights = {}
+    for length, weight in value.items():
+        try:
+            length, weight = int(length), float(weight)
+        except (TypeError, ValueError):
+            raise ConfigError(f"length_distribution: expected length: weight, got {length!r}: {weight!r}")
+        if length < 1 or weight < 0:
+            raise ConfigError(f"length_distribution: invalid length {length} or weight {weight}")
+        if weight:
+            weights[length] = weight
+    total = sum(weights.values())
+    if not total:
+        raise ConfigError("length_distribution needs at least one positive weight")
+    return {length: weight / total for length, weight in sorted(weights.items())}
+
+
+def interleave_lengths(streams: Dict[int, Iterator[str]], shares: Dict[int, float],
+                       counts: Optional[Dict[int, int]] = None) -> Iterator[str]:
+    """
+    Interleave per-length candidate streams by their shares
+
+    Each stream's n-th candidate is due at n / share; the earliest due
+    candidate goes next (ties to the shorter length), so every prefix of
+    the output holds each length in proportion to its share.
+
+    Args:
+        streams: Length -> candidates of that length
+        shares: Length -> share (see load_length_distribution)
+        counts: Updated with candidates taken per length
+
+    Yields:
+        Candidates
+    """
+    counts = counts if counts is not None else {}
+    heap = [(1 / shares[length], length) for length in streams if shares.get(length)]
+    heapq.heapify(heap)
+    while heap:
+        _, length = heapq.heappop(heap)
+        token = next(streams[length], None)
+        if token is None:
+            continue
+        counts[length] = counts.get(length, 0) + 1
+        yield token
+        heapq.heappush(heap, ((counts[length] + 1) / shares[length], length))
+
+    for length in sorted(streams):
+        if not shares.get(length):
+            for token in streams[length]:
+                counts[length] = counts.get(length, 0) + 1
+                yield token
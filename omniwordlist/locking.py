@@ -0,0 +1,104 @@
+"""
+Advisory file locking and atomic writes
+
+Presets and job files can be written by several processes at once
+(a long-running daemon and an interactive CLI). Writers take an
+exclusive advisory lock and replace files atomically so readers never
+observe a half-written JSON document.
+"""
+
+import json
+import os
+import tempfile
+import threading
+from contextlib import contextmanager
+from pathlib import Path
+from typing import Iterator
+
+try:
+    import fcntl
+except ImportError:  # pragma: no cover - Windows
+    fcntl = None
+
+try:
+    import msvcrt
+except ImportError:
+    msvcrt = None
+
+from .error import StorageError
+
+
+# Serializes lock acquisition between threads of the same process; flock
+# locks are per open file description so threads would not block each other
+_thread_lock = threading.RLock()
+
+
+def lock_path_for(path: Path) -> Path:
+    """Return the sidecar lock file used for `path`"""
+    return path.parent / f".{path.name}.lock"
+
+
+@contextmanager
+def file_lock(path: Path, shared: bool = False) -> Iterator[None]:
+    """
+    Hold an advisory lock on `path` for the duration of the block
+    
+    Args:
+        path: File being protected (the lock lives in a sidecar file)
+        shared: Take a shared (reader) lock instead of an exclusive one
+    """
+    lock_path = lock_path_for(path)
+    lock_path.parent.mkdir(parents=True, exist_ok=True)
+    
+    with _thread_lock:
+        handle = open(lock_path, 'a+')
+        try:
+            if fcntl is not None:
+                fcntl.flock(handle.fileno(), fcntl.LOCK_SH if shared else fcntl.LOCK_EX)
+            elif msvcrt is not None:
+                handle.seek(0)
+                msvcrt.locking(handle.fileno(), msvcrt.LK_LOCK, 1)
+            yield
+        finally:
+            if fcntl is not None:
+                fcntl.flock(handle.fileno(), fcntl.LOCK_UN)
+            elif msvcrt is not None:
+                handle.seek(0)
+                msvcrt.locking(handle.fileno(), msvcrt.LK_UNLCK, 1)
+            handle.close()
+
+
+def atomic_write_json(path: Path, data, indent: int = 2):
+    """
+    Write JSON to `path` via a temporary file and atomic rename
+    
+    Args:
+        path: Destination file
+        data: JSON-serializable data
+        indent: JSON indentation
+    """
+    path.parent.mkdir(parents=True, exist_ok=True)
+    fd, tmp_name = tempfile.mkstemp(dir=path.parent, prefix=f".{path.name}.", suffix=".tmp")
+    try:
+        with os.fdopen(fd, 'w') as f:
+            json.dump(data, f, indent=indent)
+            f.flush()
+            os.fsync(f.fileno())
+        os.replace(tmp_name, path)
+    except OSError as e:
+        if os.path.exists(tmp_name):
+            os.unlink(tmp_name)
+        raise StorageError(f"Failed to write {path}: {e}")
+
+
+def locked_write_json(path: Path, data, indent: int = 2):
+    """Atomically write JSON to `path` while holding its exclusive lock"""
+    with file_lock(path):
+        atomic_write_json(path, data, indent)
+
+
+def locked_read_json(path: Path):
+    """Read JSON from `path` while holding a shared lock"""
+    with file_lock(path, shared=True):
+        with open(path, 'r') as f:
+            return json.load(f)
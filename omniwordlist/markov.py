@@ -0,0 +1,159 @@
+"""
+Character Markov model generation
+
+A model trained on a word list (`omni run --train rockyou.txt`) counts,
+for every context of `order` characters, which character follows it;
+word starts are padded with a start marker and word ends count as an
+end marker, so the model also learns how words begin and how long
+they run. A token's probability is the product of its transitions,
+the end included.
+
+Generation is a deterministic best-first search rather than sampling:
+partial tokens wait in a priority queue by probability, and since
+extending a token can only make it less likely, complete tokens come
+out in descending probability (ties by token). The same model and
+length range always give the same list, and the most likely
+candidates come first. Only transitions seen in training are
+followed. The queue grows with the number of tokens taken, so bound
+long runs with max_lines.
+
+Frequency models (see frequency) train with each word weighted by its
+count. Models are saved as JSON (`--train words.txt --model model.json`) and
+reused with `--model model.json`.
+"""
+
+import heapq
+import json
+import math
+from pathlib import Path
+from typing import Dict, Iterable, Iterator, Tuple
+
+from .error import ConfigError
+
+
+MARKOV_ORDERS = (2, 3)
+MODEL_FORMAT_VERSION = 1
+
+# Padding before the first character and the marker counted after the last
+START = "\x02"
+END = "\x03"
+
+
+class MarkovModel:
+    """Transition counts of an order-N character Markov chain"""
+
+    def __init__(self, order: int, transitions: Dict[str, Dict[str, int]]):
+        """
+        Initialize model
+
+        Args:
+            order: Characters of context per transition
+            transitions: Context -> next character (or END) -> count
+        """
+        if order not in MARKOV_ORDERS:
+            raise ConfigError(f"Markov order must be one of {', '.join(map(str, MARKOV_ORDERS))}")
+        self.order = order
+        self.transitions = transitions
+        # Context -> [(cost, next character)], cost = -log P(next | context), likeliest first
+        self._costs: Dict[str, list] = {}
+
+    @classmethod
+    def train(cls, words: Iterable[str], order: int = 3) -> 'MarkovModel':
+        """
+        Count the transitions of a word list
+
+        Args:
+            words: Training words
+            order: Characters of context (2 or 3)
+
+        Returns:
+            Trained model
+        """
+        return cls.train_counts(((word, 1) for word in words), order)
+
+    @classmethod
+    def train_counts(cls, counts: Iterable[Tuple[str, int]], order: int = 3) -> 'MarkovModel':
+        """
+        Count the transitions of a word list with occurrence counts
+
+        Args:
+            counts: (word, count) pairs, e.g. a frequency model
+            order: Characters of context (2 or 3)
+
+        Returns:
+            Trained model, each word's transitions weighted by its count
+        """
+        transitions: Dict[str, Dict[str, int]] = {}
+        for word, count in counts:
+            if not word:
+                continue
+            padded = START * order + word + END
+            for i in range(order, len(padded)):
+                following = transitions.setdefault(padded[i - order:i], {})
+                following[padded[i]] = following.get(padded[i], 0) + count
+        return cls(order, transitions)
+
+    @classmethod
+    def load(cls, path: Path) -> 'MarkovModel':
+        """Read a model saved with save"""
+        try:
+            data = json.loads(Path(path).expanduser().read_text(encoding="utf-8"))
+        except (OSError, ValueError) as e:
+            raise ConfigError(f"Cannot read Markov model {path}: {e}")
+        if data.get("format_version", 0) > MODEL_FORMAT_VERSION:
+            raise ConfigError(f"Markov model format {data['format_version']} is newer than supported")
+        return cls(data["order"], data["transitions"])
+
+    def save(self, path: Path) -> Path:
+        """Write the model as JSON"""
+        path = Path(path).expanduser()
+        data = {"format_version": MODEL_FORMAT_VERSION, "order": self.order, "transitions": self.transitions}
+        path.write_text(json.dumps(data, ensure_ascii=False, sort_keys=True) + "\n", encoding="utf-8")
+        return path
+
+    def _next(self, context: str) -> list:
+        """Costs of the characters that can follow a context"""
+        costs = self._costs.get(context)
+        if costs is None:
+            following = self.transitions.get(context, {})
+            total = sum(following.values())
+            costs = sorted((-math.log(count / total), char) for char, count in following.items())
+            self._costs[context] = costs
+        return costs
+
+    def probability(self, token: str) -> float:
+        """Probability of a complete token under the model (0 if it has an unseen transition)"""
+        padded = START * self.order + token + END
+        probability = 1.0
+        for i in range(self.order, len(padded)):
+            following = self.transitions.get(padded[i - self.order:i], {})
+            if not following.get(padded[i]):
+                return 0.0
+            probability *= following[padded[i]] / sum(following.values())
+        return probability
+
+    def enumerate(self, min_length: int, max_length: int) -> Iterator[Tuple[str, float]]:
+        """
+        Tokens within a length range in descending probability
+
+        Args:
+            min_length: Shortest token
+            max_length: Longest token
+
+        Yields:
+            (token, probability)
+        """
+        # (cost, token, complete): complete entries are finished tokens
+        queue = [(0.0, "", False)]
+        while queue:
+            cost, token, complete = heapq.heappop(queue)
+            if complete:
+                yield token, math.exp(-cost)
+                continue
+            context = (START * self.order + token)[-self.order:]
+            for step, char in self._next(context):
+                if char == END:
+                    if min_length <= len(token) <= max_length:
+                        heapq.heappush(queue, (cost + step, token, True))
+                elif len(token) < max_length:
+                    heapq.heappush(queue, (cost + step, token + char, False))
@@ -0,0 +1,183 @@
+"""
+Tagged multi-source merge
+
+Merges outputs of several strategies into one deduplicated list while
+remembering which sources produced each token. Every source carries a
+tag; a token found in several sources keeps the union of their tags,
+so hits from the merged list can still be attributed to strategies.
+
+Sources are txt lists (tagged from the command line) or JSONL outputs
+whose records already carry "tags". Merged output is txt, JSONL or an
+SQLite database with a token_tags table for attribution queries.
+"""
+
+import json
+import sqlite3
+from pathlib import Path
+from typing import Dict, Iterator, List, Optional, Sequence, Tuple
+
+from .error import ConfigError, StorageError
+from .storage import OutputWriter, detect_compression, open_text
+from .tokenstats import STATS_COLUMNS, STATS_SQL_TYPES, token_stats as describe_token
+
+
+MERGE_FORMATS = ("txt", "jsonl", "sqlite")
+
+
+def parse_source(spec: str) -> Tuple[Optional[str], str]:
+    """
+    Parse a TAG=PATH source (a bare PATH has no tag of its own)
+
+    Args:
+        spec: Source specification
+
+    Returns:
+        (tag or None, path)
+    """
+    tag, sep, path = spec.partition("=")
+    if not sep:
+        return None, spec
+    if not tag or not path:
+        raise ConfigError(f"Expected TAG=PATH, got {spec!r}")
+    return tag, path
+
+
+def _is_jsonl(path: Path) -> bool:
+    name = Path(path).name
+    if detect_compression(path):
+        name = name.rsplit(".", 1)[0]
+    return name.endswith(".jsonl")
+
+
+def read_tagged(path: Path, tag: Optional[str] = None) -> Iterator[Tuple[str, List[str]]]:
+    """
+    Tokens of one source with their tags
+
+    Args:
+        path: txt or JSONL list, optionally compressed
+        tag: Tag added to every token of the source
+
+    Yields:
+        (token, tags) pairs
+    """
+    jsonl = _is_jsonl(path)
+    with open_text(Path(path)) as f:
+        for number, line in enumerate(f, 1):
+            line = line.rstrip("\r\n")
+            if not line:
+                continue
+            if not jsonl:
+                yield line, [tag] if tag else []
+                continue
+            try:
+                record = json.loads(line)
+                token, tags = record["token"], list(record.get("tags", []))
+            except (ValueError, KeyError, TypeError):
+                raise StorageError(f"{path}:{number}: not a JSONL token record")
+            if tag and tag not in tags:
+                tags.append(tag)
+            yield token, tags
+
+
+def merge_sources(sources: Sequence[Tuple[Optional[str], Path]]) -> Dict[str, List[str]]:
+    """
+    Merge sources, deduplicating tokens and uniting their tags
+
+    Args:
+        sources: (tag, path) pairs, in priority order
+
+    Returns:
+        Token -> tags, tokens in first-seen order, tags in source order
+    """
+    merged: Dict[str, List[str]] = {}
+    for tag, path in sources:
+        for token, tags in read_tagged(Path(path), tag):
+            known = merged.setdefault(token, [])
+            for name in tags:
+                if name not in known:
+                    known.append(name)
+    return merged
+
+
+def write_merged(merged: Dict[str, List[str]], output_path: Path, format: str = "txt",
+                 compression: Optional[str] = None, token_stats: bool = False) -> int:
+    """
+    Write a merged list
+
+    Args:
+        merged: Result of merge_sources
+        output_path: Output file
+        format: "txt", "jsonl" or "sqlite"
+        compression: Compression for txt / JSONL output
+        token_stats: Add the tokenstats columns to JSONL records and the tokens table
+
+    Returns:
+        Number of tokens written
+    """
+    if format not in MERGE_FORMATS:
+        raise ConfigError(f"Unsupported merge format: {format} (known: {', '.join(MERGE_FORMATS)})")
+    output_path = Path(output_path)
+
+    if format == "sqlite":
+        if compression:
+            raise ConfigError("SQLite output cannot be compressed")
+        return _write_sqlite(merged, output_path, token_stats)
+
+    with OutputWriter(output_path, compression, "txt") as writer:
+        for token, tags in merged.items():
+            if format == "jsonl":
+                record = {"token": token, "tags": tags}
+                if token_stats:
+                    record.update(describe_token(token))
+                writer.write(json.dumps(record))
+            else:
+                writer.write(token)
+    return len(merged)
+
+
+def _write_sqlite(merged: Dict[str, List[str]], output_path: Path, token_stats: bool = False) -> int:
+    """Write tokens(position, token, tags[, stats columns]) and token_tags(token, tag) tables"""
+    stats_columns = STATS_COLUMNS if token_stats else ()
+    columns = ("position", "token", "tags") + stats_columns
+    output_path.parent.mkdir(parents=True, exist_ok=True)
+    if output_path.exists():
+        output_path.unlink()
+    try:
+        connection = sqlite3.connect(output_path)
+        with connection:
+            connection.execute("CREATE TABLE tokens (position INTEGER PRIMARY KEY, "
+                               "token TEXT NOT NULL UNIQUE, tags TEXT NOT NULL"
+                               + "".join(f", {column} {STATS_SQL_TYPES[column]} NOT NULL" for column in stats_columns)
+                               + ")")
+            connection.execute("CREATE TABLE token_tags (token TEXT NOT NULL, tag TEXT NOT NULL, "
+                               "PRIMARY KEY (token, tag))")
+            connection.executemany(f"INSERT INTO tokens ({', '.join(columns)}) VALUES ({', '.join('?' * len(columns))})",
+                                   ((i, token, ",".join(tags), *(describe_token(token).values() if token_stats else ()))
+                                    for i, (token, tags) in enumerate(merged.items())))
+            connection.executemany("INSERT INTO token_tags (token, tag) VALUES (?, ?)",
+                                   ((token, tag) for token, tags in merged.items() for tag in tags))
+            connection.execute("CREATE INDEX token_tags_by_tag ON token_tags (tag)")
+        connection.close()
+    except sqlite3.Error as e:
+        raise StorageError(f"Cannot write {output_path}: {e}")
+    return len(merged)
+
+
+def tag_counts(merged: Dict[str, List[str]]) -> Dict[str, Tuple[int, int]]:
+    """
+    Per-tag totals of a merge
+
+    Args:
+        merged: Result of merge_sources
+
+    Returns:
+        Tag -> (tokens carrying it, tokens carrying only it)
+    """
+    counts: Dict[str, List[int]] = {}
+    for tags in merged.values():
+        for tag in tags:
+            entry = counts.setdefault(tag, [0, 0])
+            entry[0] += 1
+            if len(tags) == 1:
+                entry[1] += 1
+    return {tag: (total, unique) for tag, (total, unique) in counts.items()}
@@ -0,0 +1,116 @@
+"""
+Edit-distance neighborhoods
+
+Enumerates every string within Levenshtein distance K of seed words,
+for passwords that are a typo or a per-site tweak away from a known
+base ("Summer2024" -> "Sumer2024", "Summer2024x").
+"""
+
+import random
+from typing import Iterator, List, Sequence, Set
+
+
+# Neighborhoods grow roughly as (len * charset)^K; keep K small
+MAX_EDIT_DISTANCE = 3
+
+
+def single_edits(word: str, charset: Sequence[str]) -> Iterator[str]:
+    """
+    Yield every string one edit away from a word
+    
+    Deletions come first, then substitutions, then insertions, each
+    left to right. Results may repeat.
+    
+    Args:
+        word: Base string
+        charset: Characters used for substitutions and insertions
+    """
+    for i in range(len(word)):
+        yield word[:i] + word[i + 1:]
+    for i, current in enumerate(word):
+        for char in charset:
+            if char != current:
+                yield word[:i] + char + word[i + 1:]
+    for i in range(len(word) + 1):
+        for char in charset:
+            yield word[:i] + char + word[i:]
+
+
+def edit_neighborhood(seeds: Sequence[str], distance: int, charset: Sequence[str]) -> Iterator[str]:
+    """
+    Enumerate all strings within an edit distance of the seeds
+    
+    Candidates are emitted nearest first (the seeds, then distance 1,
+    then 2, ...) and never repeat, even across seeds.
+    
+    Args:
+        seeds: Base words
+        distance: Maximum Levenshtein distance
+        charset: Characters used for substitutions and insertions
+        
+    Yields:
+        Unique candidates
+    """
+    seen: Set[str] = set()
+    frontier: List[str] = []
+    for seed in seeds:
+        if seed not in seen:
+            seen.add(seed)
+            frontier.append(seed)
+            yield seed
+    
+    for _ in range(distance):
+        next_frontier = []
+        for word in frontier:
+            for candidate in single_edits(word, charset):
+                if candidate not in seen:
+                    seen.add(candidate)
+                    next_frontier.append(candidate)
+                    yield candidate
+        frontier = next_frontier
+
+
+def neighborhood_upper_bound(seeds: Sequence[str], distance: int, charset_size: int) -> int:
+    """
+    Upper bound on the neighborhood size (edits are counted, not deduped)
+    
+    Args:
+        seeds: Base words
+        distance: Maximum Levenshtein distance
+        charset_size: Number of distinct charset characters
+        
+    Returns:
+        Upper bound on the number of candidates
+    """
+    total = 0
+    for seed in set(seeds):
+        level, length = 1, len(seed)
+        total += 1
+        for _ in range(distance):
+            # deletions + substitutions + insertions of the longest string at this level
+            level *= length + length * charset_size + (length + 1) * charset_size
+            length += 1
+            total += level
+    return total
+
+
+def random_neighbor(seeds: Sequence[str], distance: int, charset: Sequence[str],
+                    rng: random.Random) -> str:
+    """
+    Draw a random string within the edit distance of a random seed
+    
+    Args:
+        seeds: Base words
+        distance: Maximum Levenshtein distance
+        charset: Characters used for substitutions and insertions
+        rng: Random source
+        
+    Returns:
+        Candidate
+    """
+    word = rng.choice(seeds)
+    for _ in range(rng.randint(0, distance)):
+        edits = list(single_edits(word, charset))
+        if edits:
+            word = rng.choice(edits)
+    return word
@@ -0,0 +1,155 @@
+"""
+OPSEC mode for restricted environments
+
+With OPSEC mode on (`omni --opsec`, or OMNIWORDLIST_OPSEC=1), nothing
+but the output a command was asked for is written to disk: checkpoints,
+saved presets and their history, settings, campaign databases and
+downloads into the source cache are refused, and the usage ledger is
+skipped. A kind can be allowed explicitly (`--allow-disk checkpoints`,
+or OMNIWORDLIST_OPSEC_ALLOW=checkpoints,presets). `omni run` without
+-o keeps generation in memory and streams to stdout.
+
+shred_path overwrites files with random bytes before unlinking them.
+On SSDs (wear levelling), journaling or copy-on-write file systems and
+snapshots, old blocks can survive an overwrite; shred is a best effort
+there, and full-disk encryption is the reliable control.
+"""
+
+import os
+import secrets
+from pathlib import Path
+from typing import Iterable, Optional, Set
+
+from .error import ConfigError, OpsecError
+
+
+ENV_OPSEC = "OMNIWORDLIST_OPSEC"
+ENV_ALLOW = "OMNIWORDLIST_OPSEC_ALLOW"
+
+# What OPSEC mode keeps off the disk unless allowed
+DISK_KINDS = ("checkpoints", "presets", "settings", "usage", "campaigns", "cache")
+
+_enabled: Optional[bool] = None
+_allowed: Set[str] = set()
+
+
+def parse_kinds(values: Iterable[str]) -> Set[str]:
+    """
+    Parse --allow-disk values (comma-separated, repeatable)
+
+    Args:
+        values: Option values
+
+    Returns:
+        Allowed kinds
+    """
+    kinds = {kind.strip() for value in values for kind in value.split(",") if kind.strip()}
+    unknown = kinds - set(DISK_KINDS)
+    if unknown:
+        raise ConfigError(f"Unknown disk kind: {', '.join(sorted(unknown))} (known: {', '.join(DISK_KINDS)})")
+    return kinds
+
+
+def enable(allowed: Iterable[str] = ()):
+    """Turn OPSEC mode on for this process, allowing some kinds of disk writes"""
+    global _enabled, _allowed
+    _enabled = True
+    _allowed = parse_kinds(allowed)
+
+
+def disable():
+    """Turn OPSEC mode off for this process (overrides the environment)"""
+    global _enabled, _allowed
+    _enabled = False
+    _allowed = set()
+
+
+def is_enabled() -> bool:
+    """Whether OPSEC mode is on"""
+    if _enabled is not None:
+        return _enabled
+    return os.environ.get(ENV_OPSEC, "").lower() in ("1", "true", "yes", "on")
+
+
+def disk_allowed(kind: str) -> bool:
+    """Whether a kind of disk write (one of DISK_KINDS) may happen"""
+    if not is_enabled():
+        return True
+    return kind in _allowed or kind in parse_kinds([os.environ.get(ENV_ALLOW, "")])
+
+
+def require_disk(kind: str, what: str):
+    """
+    Refuse a disk write OPSEC mode does not allow
+
+    Args:
+        kind: One of DISK_KINDS
+        what: Description of the write for the error message
+    """
+    if not disk_allowed(kind):
+        raise OpsecError(f"OPSEC mode: refusing to write {what} (allow with --allow-disk {kind})")
+
+
+def shred_file(path: Path, passes: int = 1, chunk_size: int = 1 << 20):
+    """
+    Overwrite a file with random bytes, rename it and delete it
+
+    Symlinks are removed without touching their target.
+
+    Args:
+        path: File to destroy
+        passes: Overwrite passes
+        chunk_size: Bytes written per write call
+    """
+    path = Path(path)
+    if path.is_symlink():
+        path.unlink()
+        return
+    size = path.stat().st_size
+    with open(path, "r+b", buffering=0) as f:
+        for _ in range(passes):
+            f.seek(0)
+            remaining = size
+            while remaining:
+                chunk = min(chunk_size, remaining)
+                f.write(secrets.token_bytes(chunk))
+                remaining -= chunk
+            os.fsync(f.fileno())
+        f.truncate(0)
+        os.fsync(f.fileno())
+    # Drop the original name from the directory before unlinking
+    hidden = path.with_name(secrets.token_hex(8))
+    path.rename(hidden)
+    hidden.unlink()
+
+
+def shred_path(path: Path, passes: int = 1) -> int:
+    """
+    Shred a file, or every file under a directory and then the directory
+
+    Args:
+        path: File or directory
+        passes: Overwrite passes
+
+    Returns:
+        Number of files shredded
+    """
+    path = Path(path)
+    if passes < 1:
+        raise ConfigError("passes must be at least 1")
+    if not path.is_dir() or path.is_symlink():
+        shred_file(path, passes)
+        return 1
+    count = 0
+    for root, dirs, files in os.walk(path, topdown=False):
+        for name in files:
+            shred_file(Path(root) / name, passes)
+            count += 1
+        for name in dirs:
+            directory = Path(root) / name
+            if directory.is_symlink():
+                directory.unlink()
+            else:
+                directory.rmdir()
+    path.rmdir()
+    return count
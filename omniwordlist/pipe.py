@@ -0,0 +1,117 @@
+"""
+External command filter
+
+Pipes generated tokens through a command (`omni run --pipe-through
+./filter.py`) so filter and mangling scripts that cannot be ported yet
+stay usable: the command reads tokens from stdin, one per line, and
+whatever lines it prints become the output, so it can drop, rewrite
+or multiply tokens.
+
+The command runs once for the whole run. Tokens are written to it in
+batches from a feeder thread while its output is read as it comes;
+the feeder blocks once the pipe buffer is full, so generation never
+runs more than a batch and a pipe buffer ahead of the command. A
+command that reads all of its input before printing still works, but
+its output only arrives once every token has been generated.
+"""
+
+import shlex
+import subprocess
+import threading
+from typing import Iterable, Iterator, List
+
+from .error import ConfigError, GeneratorError
+
+
+# Tokens written to the command per write call
+PIPE_BATCH_SIZE = 1000
+
+
+def parse_command(command: str) -> List[str]:
+    """
+    Split a --pipe-through command line into arguments (no shell is involved)
+
+    Args:
+        command: Command line, quoted as in a POSIX shell
+
+    Returns:
+        Program and arguments
+    """
+    try:
+        args = shlex.split(command)
+    except ValueError as e:
+        raise ConfigError(f"Invalid pipe_through command {command!r}: {e}")
+    if not args:
+        raise ConfigError("pipe_through command must not be empty")
+    return args
+
+
+def pipe_through(tokens: Iterable[str], command: str, batch_size: int = PIPE_BATCH_SIZE) -> Iterator[str]:
+    """
+    Stream tokens through an external command
+
+    Args:
+        tokens: Tokens to send (consumed on a feeder thread)
+        command: Command line (see parse_command)
+        batch_size: Tokens per write to the command
+
+    Yields:
+        Lines the command prints, without line endings
+
+    Raises:
+        GeneratorError: If the command cannot be started or exits with an error
+    """
+    try:
+        process = subprocess.Popen(parse_command(command), stdin=subprocess.PIPE, stdout=subprocess.PIPE)
+    except OSError as e:
+        raise GeneratorError(f"Cannot run pipe_through command {command!r}: {e}")
+
+    stop = threading.Event()
+    failure: List[BaseException] = []
+
+    def feed():
+        try:
+            batch = []
+            for token in tokens:
+                batch.append(token)
+                if len(batch) >= batch_size:
+                    process.stdin.write(("\n".join(batch) + "\n").encode("utf-8"))
+                    process.stdin.flush()
+                    batch = []
+                    if stop.is_set():
+                        return
+            if batch:
+                process.stdin.write(("\n".join(batch) + "\n").encode("utf-8"))
+        except (BrokenPipeError, ValueError):
+            # The command exited (or was stopped) before reading everything
+            pass
+        except BaseException as e:
+            failure.append(e)
+        finally:
+            try:
+                process.stdin.close()
+            except OSError:
+                pass
+
+    feeder = threading.Thread(target=feed, name="pipe-through", daemon=True)
+    feeder.start()
+    finished = False
+    try:
+        for line in process.stdout:
+            yield line.rstrip(b"\r\n").decode("utf-8", errors="replace")
+        finished = True
+    finally:
+        if not finished:
+            # The consumer stopped early: let the feeder run into a closed pipe
+            stop.set()
+            process.kill()
+        feeder.join()
+        process.stdout.close()
+        code = process.wait()
+        close = getattr(tokens, "close", None)
+        if close is not None:
+            close()
+    if failure:
+        raise failure[0]
+    if code != 0:
+        raise GeneratorError(f"pipe_through command {command!r} exited with status {code}")
@@ -0,0 +1,136 @@
+"""
+Password policy inference
+
+Known-valid passwords from recon (`omni infer-policy samples.txt`)
+reveal the policy they were created under: the shortest and longest
+sample bound the length, a character class found in every sample is
+taken as required, and the fewest classes any sample mixes gives a
+"N of 4 classes" rule. Bans are inferred from a catalogue of rules
+policies commonly enforce (no whitespace, no runs like 123 or qwe, no
+character repeated three times): a rule counts as banned when no
+sample breaks it, but only once there are enough samples for that
+absence to mean something.
+
+The inferred policy becomes a config whose length range and filters
+(required_classes, min_classes, max_repeats, deny_patterns) keep only
+candidates the target would accept.
+"""
+
+import re
+import string
+from dataclasses import dataclass, field
+from typing import Dict, Iterable, List, Optional
+
+from .config import Config, TOKEN_CLASSES
+from .error import ConfigError
+from .filters import char_class
+
+
+# Bans are only inferred from at least this many samples
+MIN_BAN_SAMPLES = 20
+
+# Longest run of one character a policy is assumed to allow at most, when
+# no sample repeats a character more often
+MAX_BANNED_REPEATS = 2
+
+
+def _runs(sequence: str, length: int = 3) -> str:
+    """Alternation of every ascending and descending run of a sequence"""
+    forward = [sequence[i:i + length] for i in range(len(sequence) - length + 1)]
+    return "|".join(forward + [run[::-1] for run in forward])
+
+
+# Commonly banned patterns: name -> regular expression (matched case-insensitively where relevant)
+BAN_CATALOG: Dict[str, str] = {
+    "whitespace": r"\s",
+    "sequential_digits": _runs(string.digits),
+    "sequential_letters": "(?i)" + _runs(string.ascii_lowercase),
+    "keyboard_runs": "(?i)" + "|".join(_runs(row) for row in ("qwertyuiop", "asdfghjkl", "zxcvbnm")),
+}
+
+
+@dataclass
+class PasswordPolicy:
+    """Policy rules inferred from sample passwords"""
+    samples: int
+    min_length: int
+    max_length: int
+    # Classes of filters.char_class present in every sample
+    required_classes: List[str] = field(default_factory=list)
+    # Fewest distinct classes in one sample
+    min_classes: int = 1
+    # Longest run of one character allowed, when inferred
+    max_repeats: Optional[int] = None
+    # Banned BAN_CATALOG entries: name -> regular expression
+    banned_patterns: Dict[str, str] = field(default_factory=dict)
+
+
+def infer_policy(passwords: Iterable[str]) -> PasswordPolicy:
+    """
+    Infer the policy a set of known-valid passwords satisfies
+
+    Args:
+        passwords: Sample passwords (empty lines are ignored)
+
+    Returns:
+        Inferred policy
+
+    Raises:
+        ConfigError: If there are no samples
+    """
+    samples = 0
+    min_length = max_length = 0
+    required = set(TOKEN_CLASSES)
+    min_classes = len(TOKEN_CLASSES)
+    longest_run = 0
+    unbroken = dict(BAN_CATALOG)
+    compiled = {name: re.compile(pattern) for name, pattern in BAN_CATALOG.items()}
+
+    for password in passwords:
+        if not password:
+            continue
+        samples += 1
+        length = len(password)
+        min_length = length if samples == 1 else min(min_length, length)
+        max_length = max(max_length, length)
+        classes = {char_class(char) for char in password}
+        required &= classes
+        min_classes = min(min_classes, len(classes))
+        longest_run = max(longest_run, max(len(m.group()) for m in re.finditer(r"(.)\1*", password, re.DOTALL)))
+        for name in [name for name in unbroken if compiled[name].search(password)]:
+            del unbroken[name]
+
+    if not samples:
+        raise ConfigError("No sample passwords to infer a policy from")
+
+    policy = PasswordPolicy(samples, min_length, max_length,
+                            [name for name in TOKEN_CLASSES if name in required], min_classes)
+    if samples >= MIN_BAN_SAMPLES:
+        policy.banned_patterns = unbroken
+        if longest_run <= MAX_BANNED_REPEATS:
+            policy.max_repeats = longest_run
+    return policy
+
+
+def policy_config(policy: PasswordPolicy, config: Optional[Config] = None) -> Config:
+    """
+    Restrict a config to candidates the policy accepts
+
+    Args:
+        policy: Inferred policy
+        config: Config to restrict (default: a new one)
+
+    Returns:
+        The config, with the policy's length range and filters
+    """
+    config = config or Config()
+    config.min_length = config.filters.min_len = policy.min_length
+    config.max_length = config.filters.max_len = policy.max_length
+    config.filters.required_classes = list(policy.required_classes)
+    # A class count the required classes already imply is left out
+    if policy.min_classes > max(len(policy.required_classes), 1):
+        config.filters.min_classes = policy.min_classes
+    if policy.max_repeats is not None:
+        config.filters.max_repeats = policy.max_repeats
+    config.filters.deny_patterns = list(policy.banned_patterns.values())
+    return config
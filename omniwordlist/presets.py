@@ -5,10 +5,17 @@ Save and load wordlist generation presets
 """
 
 import json
+import re
+import shutil
+import threading
+from datetime import datetime
 from pathlib import Path
-from typing import Dict, List, Optional
+from typing import Any, Dict, List, Optional, Tuple
 from .config import Config
 from .error import PresetError
+from .locking import file_lock, locked_read_json, atomic_write_json
+from .opsec import disk_allowed, require_disk
+from .defaults import default_passwords
 
 
 # Built-in presets
@@ -85,9 +92,102 @@ BUILTIN_PRESETS = {
             },
         }
     },
+    "vehicle_plate_in": {
+        "name": "vehicle_plate_in",
+        "description": "Indian vehicle plates (MH12AB1234)",
+        "config": {
+            "min_length": 10,
+            "max_length": 10,
+            "pattern": "MH%%,,%%%%",  # state + district + series + number
+        }
+    },
+    "router_defaults": {
+        "name": "router_defaults",
+        "description": "Factory default passwords of routers",
+        "config": {
+            "words": default_passwords("router"),
+            "dedupe": True,
+        }
+    },
+    "camera_defaults": {
+        "name": "camera_defaults",
+        "description": "Factory default passwords of IP cameras",
+        "config": {
+            "words": default_passwords("camera"),
+            "dedupe": True,
+        }
+    },
+    "employee_id_emp": {
+        "name": "employee_id_emp",
+        "description": "Prefixed employee IDs (EMP0001)",
+        "config": {
+            "min_length": 7,
+            "max_length": 7,
+            "pattern": "EMP%%%%",
+        }
+    },
+    "employee_id_initials": {
+        "name": "employee_id_initials",
+        "description": "Initials-based employee IDs (ab-12345)",
+        "config": {
+            "min_length": 8,
+            "max_length": 8,
+            "pattern": "@@-%%%%%",
+        }
+    },
 }
 
 
+# Version assigned to the first save of a custom preset
+INITIAL_PRESET_VERSION = "1.0"
+# Saved versions are major.minor
+PRESET_VERSION_RE = re.compile(r"^\d+\.\d+$")
+
+
+def next_preset_version(version: str) -> str:
+    """
+    Bump the minor component of a preset version (1.0 -> 1.1 -> ... -> 1.10)
+    
+    Args:
+        version: Current version string
+        
+    Returns:
+        Next version string
+    """
+    try:
+        major, minor = version.split(".", 1)
+        return f"{int(major)}.{int(minor) + 1}"
+    except ValueError:
+        raise PresetError(f"Invalid preset version: {version}")
+
+
+def diff_configs(old: Dict, new: Dict, prefix: str = "") -> List[Tuple[str, Any, Any]]:
+    """
+    Settings that differ between two config dictionaries
+    
+    Nested dictionaries (filters, field_sources) are compared key by
+    key, so a change shows up as "filters.min_len" rather than the
+    whole section.
+    
+    Args:
+        old: Current config dictionary
+        new: Config dictionary replacing it
+        prefix: Prepended to keys (used for nested sections)
+        
+    Returns:
+        (key, old value, new value) per changed setting, sorted by key;
+        a setting missing on one side has None there
+    """
+    changes = []
+    for key in sorted(set(old) | set(new)):
+        before, after = old.get(key), new.get(key)
+        if isinstance(before, dict) and isinstance(after, dict):
+            changes.extend(diff_configs(before, after, f"{prefix}{key}."))
+        elif before != after:
+            changes.append((f"{prefix}{key}", before, after))
+    return changes
+
+
 class PresetManager:
     """Manage presets"""
     
@@ -99,7 +199,47 @@ class PresetManager:
             preset_dir: Directory for custom presets
         """
         self.preset_dir = preset_dir or Path.home() / ".omniwordlist" / "presets"
-        self.preset_dir.mkdir(parents=True, exist_ok=True)
+        if disk_allowed("presets"):
+            self.preset_dir.mkdir(parents=True, exist_ok=True)
+        
+        # Custom presets cached by name with the file mtime they were read at,
+        # so edits made by other processes are picked up on the next access
+        self._cache: Dict[str, Tuple[float, Dict]] = {}
+        self._lock = threading.RLock()
+    
+    def _preset_path(self, name: str) -> Path:
+        """Path of a custom preset file"""
+        return self.preset_dir / f"{name}.json"
+    
+    def _history_dir(self, name: str) -> Path:
+        """Directory holding prior versions and the changelog of a preset"""
+        return self.preset_dir / ".history" / name
+    
+    def _read_changelog(self, name: str) -> List[Dict]:
+        """Read the changelog of a preset (oldest entry first)"""
+        changelog_path = self._history_dir(name) / "changelog.json"
+        if not changelog_path.exists():
+            return []
+        with open(changelog_path, 'r') as f:
+            return json.load(f)
+    
+    def _load_custom(self, name: str) -> Optional[Dict]:
+        """Load a custom preset, reloading it if the file changed on disk"""
+        preset_path = self._preset_path(name)
+        with self._lock:
+            try:
+                mtime = preset_path.stat().st_mtime_ns
+            except FileNotFoundError:
+                self._cache.pop(name, None)
+                return None
+            
+            cached = self._cache.get(name)
+            if cached and cached[0] == mtime:
+                return cached[1]
+            
+            preset = locked_read_json(preset_path)
+            self._cache[name] = (mtime, preset)
+            return preset
     
     def list_presets(self) -> List[str]:
         """List all available presets (built-in and custom)"""
@@ -128,11 +268,15 @@ class PresetManager:
         if name in BUILTIN_PRESETS:
             return BUILTIN_PRESETS[name]
         
+        # name@version addresses a specific saved version
+        if "@" in name:
+            base_name, version = name.split("@", 1)
+            return self.get_preset_version(base_name, version)
+        
         # Check custom presets
-        preset_path = self.preset_dir / f"{name}.json"
-        if preset_path.exists():
-            with open(preset_path, 'r') as f:
-                return json.load(f)
+        preset = self._load_custom(name)
+        if preset is not None:
+            return preset
         
         raise PresetError(f"Preset not found: {name}")
     
@@ -147,30 +291,162 @@ class PresetManager:
             Config object
         """
         preset = self.get_preset(name)
-        return Config.from_dict(preset['config'])
+        return Config.from_dict(dict(preset['config']))
+    
+    def get_preset_version(self, name: str, version: str) -> Dict:
+        """
+        Get a specific saved version of a custom preset
+        
+        Args:
+            name: Preset name
+            version: Version string (e.g. "1.0")
+            
+        Returns:
+            Preset dictionary as it was saved at that version
+        """
+        if not PRESET_VERSION_RE.match(version):
+            raise PresetError(f"Invalid preset version: {version}")
+        current = self._load_custom(name)
+        if current is not None and current.get('version', INITIAL_PRESET_VERSION) == version:
+            return current
+        
+        version_path = self._history_dir(name) / f"{version}.json"
+        if not version_path.exists():
+            raise PresetError(f"Preset version not found: {name}@{version}")
+        with open(version_path, 'r') as f:
+            return json.load(f)
+    
+    def preset_changes(self, name: str, description: str,
+                       config: Config) -> Optional[List[Tuple[str, Any, Any]]]:
+        """
+        What saving a config under a name would change
+        
+        Args:
+            name: Preset name
+            description: Description to save
+            config: Configuration to save
+            
+        Returns:
+            None if no custom preset of that name exists yet, otherwise
+            the diff_configs changes (a description change included)
+        """
+        current = self._load_custom(name)
+        if current is None:
+            return None
+        changes = diff_configs(current.get('config', {}), config.to_dict())
+        if current.get('description', '') != description:
+            changes.insert(0, ("description", current.get('description', ''), description))
+        return changes
     
-    def save_preset(self, name: str, description: str, config: Config):
+    def save_preset(self, name: str, description: str, config: Config,
+                    message: Optional[str] = None) -> str:
         """
         Save a custom preset
         
+        Saving over an existing preset archives the previous version and
+        records a changelog entry, so the change can be rolled back.
+        
         Args:
             name: Preset name
             description: Preset description
             config: Configuration to save
+            message: Optional changelog message
+            
+        Returns:
+            Version assigned to the saved preset
         """
-        preset_data = {
-            "name": name,
-            "description": description,
-            "config": config.to_dict(),
-        }
+        if "@" in name:
+            raise PresetError(f"Preset names cannot contain '@': {name}")
+        require_disk("presets", f"preset {name} and its history")
+        
+        preset_path = self._preset_path(name)
+        history_dir = self._history_dir(name)
         
-        preset_path = self.preset_dir / f"{name}.json"
-        with open(preset_path, 'w') as f:
-            json.dump(preset_data, f, indent=2)
+        with self._lock, file_lock(preset_path):
+            previous = None
+            if preset_path.exists():
+                with open(preset_path, 'r') as f:
+                    previous = json.load(f)
+            
+            if previous is None:
+                version = INITIAL_PRESET_VERSION
+                default_message = "Initial version"
+            else:
+                previous_version = previous.get('version', INITIAL_PRESET_VERSION)
+                atomic_write_json(history_dir / f"{previous_version}.json", previous)
+                version = next_preset_version(previous_version)
+                default_message = f"Updated from {previous_version}"
+            
+            preset_data = {
+                "name": name,
+                "description": description,
+                "version": version,
+                "config": config.to_dict(),
+            }
+            atomic_write_json(preset_path, preset_data)
+            
+            changelog = self._read_changelog(name)
+            changelog.append({
+                "version": version,
+                "saved_at": datetime.now().isoformat(timespec="seconds"),
+                "description": description,
+                "message": message or default_message,
+            })
+            atomic_write_json(history_dir / "changelog.json", changelog)
+            self._cache.pop(name, None)
+        
+        return version
+    
+    def preset_history(self, name: str) -> List[Dict]:
+        """
+        Get the changelog of a custom preset
+        
+        Args:
+            name: Preset name
+            
+        Returns:
+            Changelog entries, oldest first
+        """
+        if name in BUILTIN_PRESETS:
+            raise PresetError(f"Built-in presets have no history: {name}")
+        
+        with file_lock(self._preset_path(name), shared=True):
+            changelog = self._read_changelog(name)
+        
+        if not changelog and self._load_custom(name) is None:
+            raise PresetError(f"Preset not found: {name}")
+        return changelog
+    
+    def rollback_preset(self, name: str, version: str) -> str:
+        """
+        Restore a prior version of a preset
+        
+        The restored content is saved as a new version, so the rollback
+        itself can be undone.
+        
+        Args:
+            name: Preset name
+            version: Version to restore
+            
+        Returns:
+            New version created by the rollback
+        """
+        if name in BUILTIN_PRESETS:
+            raise PresetError(f"Cannot roll back built-in preset: {name}")
+        
+        old = self.get_preset_version(name, version)
+        return self.save_preset(
+            name,
+            old.get('description', ''),
+            Config.from_dict(dict(old['config'])),
+            message=f"Rolled back to {version}",
+        )
     
     def delete_preset(self, name: str):
         """
-        Delete a custom preset
+        Delete a custom preset and its history
+        
+        A preset saved later under the same name starts a fresh history.
         
         Args:
             name: Preset name
@@ -178,11 +454,13 @@ class PresetManager:
         if name in BUILTIN_PRESETS:
             raise PresetError(f"Cannot delete built-in preset: {name}")
         
-        preset_path = self.preset_dir / f"{name}.json"
-        if preset_path.exists():
+        preset_path = self._preset_path(name)
+        with self._lock, file_lock(preset_path):
+            self._cache.pop(name, None)
+            if not preset_path.exists():
+                raise PresetError(f"Preset not found: {name}")
             preset_path.unlink()
-        else:
-            raise PresetError(f"Preset not found: {name}")
+            shutil.rmtree(self._history_dir(name), ignore_errors=True)
     
     def show_preset(self, name: str) -> str:
         """
@@ -199,6 +477,10 @@ class PresetManager:
         lines = [
             f"Preset: {preset['name']}",
             f"Description: {preset['description']}",
+        ]
+        if 'version' in preset:
+            lines.append(f"Version: {preset['version']}")
+        lines += [
             "",
             "Configuration:",
         ]
@@ -0,0 +1,210 @@
+"""
+Live preview sampling
+
+Samples a bounded number of tokens from the real generator in the
+background whenever the configuration changes. Updates are debounced
+so rapid edits (toggling transforms, flipping fields) trigger a single
+run, and every run is capped by a token limit and a time budget.
+
+Sampled tokens can be rendered with their character classes colored
+and with the reason for candidates the active filters reject.
+"""
+
+import copy
+import threading
+import time
+from typing import Callable, List, Optional, Tuple
+from rich.text import Text
+from .config import Config
+from .cancel import CancellationToken
+from .filters import char_class
+
+
+# Styles of character classes in annotated tokens (lowercase stays plain)
+CLASS_STYLES = {
+    "upper": "bold",
+    "digit": "cyan",
+    "symbol": "magenta",
+    "other": "bold yellow",
+}
+
+
+def annotate_token(token: str, reason: Optional[str] = None) -> Text:
+    """
+    Color-code a token by character class
+    
+    Digits, symbols, uppercase letters and non-ASCII characters (e.g.
+    unicode substitutions) get distinct styles. Rejected tokens are
+    struck through and followed by the rejecting stage.
+    
+    Args:
+        token: Token to render
+        reason: Rejecting filter or stage, None if the token passed
+        
+    Returns:
+        Rich Text ready to print
+    """
+    text = Text()
+    for char in token:
+        style = CLASS_STYLES.get(char_class(char), "")
+        if reason is not None:
+            style = f"{style} dim strike".strip()
+        text.append(char, style=style)
+    if reason is not None:
+        text.append(f"  ✗ {reason}", style="red")
+    return text
+
+
+class PreviewResult:
+    """Tokens sampled for one configuration revision"""
+    
+    def __init__(self, revision: int, tokens: List[str], error: Optional[str] = None,
+                 elapsed: float = 0.0, truncated: bool = False,
+                 entries: Optional[List[Tuple[str, Optional[str]]]] = None,
+                 exception: Optional[BaseException] = None):
+        self.revision = revision
+        self.tokens = tokens
+        # Candidates in generation order with their rejection reason (None = emitted)
+        self.entries = entries if entries is not None else [(t, None) for t in tokens]
+        self.error = error
+        # The exception behind error, so callers can report it by type
+        self.exception = exception
+        self.elapsed = elapsed
+        self.truncated = truncated
+
+
+def sample_tokens(config: Config, limit: int = 20, time_budget: float = 0.5,
+                  cancel_token: Optional[CancellationToken] = None) -> PreviewResult:
+    """
+    Sample the first tokens of a configuration within strict bounds
+    
+    Rejected candidates met on the way are kept in the result entries;
+    sampling stops after `limit` emitted or `limit` rejected candidates.
+    
+    Args:
+        config: Configuration to sample
+        limit: Maximum number of tokens
+        time_budget: Seconds after which sampling stops
+        cancel_token: Token used to abort the sample early
+        
+    Returns:
+        PreviewResult with revision 0
+    """
+    from .generator import Generator
+    
+    cancel_token = cancel_token or CancellationToken()
+    timer = threading.Timer(time_budget, cancel_token.cancel)
+    timer.daemon = True
+    started = time.monotonic()
+    timer.start()
+    try:
+        generator = Generator(copy.deepcopy(config), cancel_token=cancel_token)
+        entries = []
+        tokens = []
+        rejected = 0
+        for token, reason in generator.trace():
+            entries.append((token, reason))
+            if reason is None:
+                tokens.append(token)
+            else:
+                rejected += 1
+            if len(tokens) >= limit or rejected >= limit:
+                break
+    except Exception as e:
+        return PreviewResult(0, [], error=str(e), elapsed=time.monotonic() - started, exception=e)
+    finally:
+        timer.cancel()
+    
+    return PreviewResult(0, tokens, elapsed=time.monotonic() - started,
+                         truncated=generator.cancelled, entries=entries)
+
+
+class PreviewSampler:
+    """
+    Debounced background sampler feeding a preview pane
+    
+    Call update() on every configuration change; once no change has
+    arrived for `debounce` seconds a background thread samples the
+    latest configuration and `on_result` is called with the result.
+    A newer update cancels a sample that is still running.
+    """
+    
+    def __init__(self, limit: int = 20, debounce: float = 0.3, time_budget: float = 0.5,
+                 on_result: Optional[Callable[[PreviewResult], None]] = None):
+        self.limit = limit
+        self.debounce = debounce
+        self.time_budget = time_budget
+        self.on_result = on_result
+        self.result: Optional[PreviewResult] = None
+        self.runs = 0
+        self._revision = 0
+        self._lock = threading.Lock()
+        self._timer: Optional[threading.Timer] = None
+        self._worker: Optional[threading.Thread] = None
+        self._running_cancel: Optional[CancellationToken] = None
+    
+    def update(self, config: Config):
+        """
+        Schedule a preview of a new configuration
+        
+        Args:
+            config: Configuration to preview (copied immediately)
+        """
+        snapshot = copy.deepcopy(config)
+        with self._lock:
+            self._revision += 1
+            revision = self._revision
+            if self._timer is not None:
+                self._timer.cancel()
+            if self._running_cancel is not None:
+                self._running_cancel.cancel()
+            self._timer = threading.Timer(self.debounce, self._start, (snapshot, revision))
+            self._timer.daemon = True
+            self._timer.start()
+    
+    def _start(self, config: Config, revision: int):
+        cancel_token = CancellationToken()
+        with self._lock:
+            if revision != self._revision:
+                return
+            self._running_cancel = cancel_token
+            self._worker = threading.Thread(target=self._run, args=(config, revision, cancel_token),
+                                            daemon=True)
+            self._worker.start()
+    
+    def _run(self, config: Config, revision: int, cancel_token: CancellationToken):
+        result = sample_tokens(config, self.limit, self.time_budget, cancel_token)
+        result.revision = revision
+        with self._lock:
+            self.runs += 1
+            # Drop results superseded by a newer update
+            if revision != self._revision:
+                return
+            self.result = result
+        if self.on_result is not None:
+            self.on_result(result)
+    
+    def wait(self, timeout: Optional[float] = None):
+        """Block until pending sampling has finished (mainly for tests)"""
+        deadline = None if timeout is None else time.monotonic() + timeout
+        while True:
+            with self._lock:
+                timer, worker = self._timer, self._worker
+            if timer is not None:
+                timer.join(None if deadline is None else max(0.0, deadline - time.monotonic()))
+            with self._lock:
+                worker = self._worker
+            if worker is not None:
+                worker.join(None if deadline is None else max(0.0, deadline - time.monotonic()))
+            with self._lock:
+                if self._timer is timer and self._worker is worker:
+                    return
+    
+    def close(self):
+        """Cancel pending and running samples"""
+        with self._lock:
+            self._revision += 1
+            if self._timer is not None:
+                self._timer.cancel()
+            if self._running_cancel is not None:
+                self._running_cancel.cancel()
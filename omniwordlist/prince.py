@@ -0,0 +1,119 @@
+"""
+PRINCE-style element chains
+
+Chain mode (after the PRINCE attack) takes a set of elements, such as
+the lines of a file or the values of the enabled fields, and emits
+every ordered chain of 1..K elements, repeats allowed, whose joined
+length (separators included) falls within min..max length.
+
+Elements are indexed by length, and chains are built per target length
+from compositions of that length into element lengths that exist, so
+chains that can never fit the window are never enumerated. The order
+is deterministic: by chain length, then element count, then the
+element lengths from left to right, then the elements in input order
+(the rightmost changing fastest).
+"""
+
+import itertools
+from typing import Dict, Iterator, List, Sequence, Tuple
+
+
+class ChainIndex:
+    """Elements grouped by length, for enumerating and counting chains"""
+
+    def __init__(self, elements: Sequence[str]):
+        """
+        Initialize index
+
+        Args:
+            elements: Chain elements; duplicates and empty ones are dropped
+        """
+        self.by_length: Dict[int, List[str]] = {}
+        for element in dict.fromkeys(elements):
+            if element:
+                self.by_length.setdefault(len(element), []).append(element)
+        self.lengths = sorted(self.by_length)
+
+    def compositions(self, total: int, count: int) -> Iterator[Tuple[int, ...]]:
+        """
+        Element lengths of `count` elements adding up to `total`
+
+        Args:
+            total: Combined element length
+            count: Number of elements
+
+        Yields:
+            Tuples of element lengths, in ascending lexicographic order
+        """
+        if not self.lengths:
+            return
+        shortest, longest = self.lengths[0], self.lengths[-1]
+
+        def walk(remaining: int, left: int) -> Iterator[Tuple[int, ...]]:
+            if left == 0:
+                if remaining == 0:
+                    yield ()
+                return
+            for length in self.lengths:
+                rest = remaining - length
+                # Prune lengths the other elements cannot make up for
+                if rest < shortest * (left - 1):
+                    break
+                if rest > longest * (left - 1):
+                    continue
+                for tail in walk(rest, left - 1):
+                    yield (length,) + tail
+
+        yield from walk(total, count)
+
+    def chains(self, min_length: int, max_length: int, max_elements: int,
+               separator: str = "") -> Iterator[str]:
+        """
+        Every chain within a length window
+
+        Args:
+            min_length: Shortest chain, separators included
+            max_length: Longest chain, separators included
+            max_elements: Most elements per chain
+            separator: Placed between elements
+
+        Yields:
+            Chains in the order described in the module docstring
+        """
+        for length in range(min_length, max_length + 1):
+            for count in range(1, max_elements + 1):
+                total = length - len(separator) * (count - 1)
+                if total < count:
+                    break
+                for lengths in self.compositions(total, count):
+                    for elements in itertools.product(*(self.by_length[n] for n in lengths)):
+                        yield separator.join(elements)
+
+    def count(self, min_length: int, max_length: int, max_elements: int, separator: str = "") -> int:
+        """
+        Number of chains chains() yields, computed without enumerating them
+
+        Args:
+            min_length: Shortest chain, separators included
+            max_length: Longest chain, separators included
+            max_elements: Most elements per chain
+            separator: Placed between elements
+
+        Returns:
+            Chain count
+        """
+        sizes = {length: len(elements) for length, elements in self.by_length.items()}
+        # ways[total] = chains of the current element count with that combined element length
+        ways = {0: 1}
+        count = 0
+        for elements in range(1, max_elements + 1):
+            following: Dict[int, int] = {}
+            for total, chains in ways.items():
+                for length, size in sizes.items():
+                    if total + length <= max_length:
+                        following[total + length] = following.get(total + length, 0) + chains * size
+            ways = following
+            joined = len(separator) * (elements - 1)
+            count += sum(chains for total, chains in ways.items()
+                         if min_length <= total + joined <= max_length)
+        return count
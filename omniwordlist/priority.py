@@ -0,0 +1,325 @@
+"""
+Priority merge of candidate streams
+
+Interleaves several strategies (field combinations, masks, top lists,
+existing wordlists) into one list ordered by score, so the best
+candidates of every strategy come first instead of one strategy's
+whole output after another's.
+
+Each stream scores its tokens and is merged k-way on a heap, always
+emitting the highest-scored head. A k-way merge is exact for streams
+that are already best-first; other streams are re-sorted through a
+lookahead window of their next tokens. A token produced by several
+streams is kept once, at its best position, tagged with the stream
+that scored it highest.
+
+Quotas keep the head of the list diverse: with quotas, every block of
+`block` lines holds each stream's share (e.g. 50% / 30% / 20% per
+million lines) and the score only orders candidates within them. Once
+a stream runs out, the others fill its share.
+
+A merge plan (MergePlan, a JSON file) records the streams of a
+campaign with their weights and quotas.
+"""
+
+import heapq
+import json
+from dataclasses import asdict, dataclass, field
+from pathlib import Path
+from typing import Callable, Iterable, Iterator, List, Optional, Sequence, Set, Tuple
+
+from .config import Config
+from .error import ConfigError
+from .filters import calculate_quality_score
+from .frequency import FrequencyModel
+from .generator import Generator
+from .merge import parse_source, read_tagged
+from .presets import PresetManager
+from .sources import is_remote, resolve_source
+from .storage import open_text
+
+
+# rank: 1 / (position + 1) in the stream's own order (a Zipf prior for
+# streams that are generated most likely first)
+# quality: filters.calculate_quality_score
+# frequency: relative frequency in a breach corpus model (`omni freq import`)
+SCORERS = ("rank", "quality", "frequency")
+
+DEFAULT_WINDOW = 10000
+
+# Lines per quota block
+DEFAULT_BLOCK = 1000000
+
+
+@dataclass
+class Stream:
+    """One strategy's candidates"""
+    tag: str
+    tokens: Iterable[str]
+    # Multiplies every score of the stream
+    weight: float = 1.0
+    # Share of every quota block (normalized over the streams)
+    quota: Optional[float] = None
+
+
+@dataclass
+class MergedToken:
+    """A merged candidate with its score and the stream it was taken from"""
+    token: str
+    score: float
+    tag: str
+
+
+def make_scorer(name: str, model: Optional[FrequencyModel] = None) -> Callable[[str, int], float]:
+    """
+    Score function of a scorer
+
+    Args:
+        name: One of SCORERS
+        model: Frequency model for the frequency scorer
+
+    Returns:
+        Function of (token, position in its stream) to a score, higher first
+    """
+    if name == "rank":
+        return lambda token, position: 1.0 / (position + 1)
+    if name == "quality":
+        return lambda token, position: calculate_quality_score(token)
+    if name == "frequency":
+        if model is None:
+            raise ConfigError("The frequency scorer needs a frequency model")
+        return lambda token, position: model.probability(token)
+    raise ConfigError(f"Unknown scorer: {name} (known: {', '.join(SCORERS)})")
+
+
+def best_first(stream: Stream, score: Callable[[str, int], float],
+               window: int = DEFAULT_WINDOW) -> Iterator[Tuple[float, str]]:
+    """
+    Scored tokens of a stream, re-sorted within a lookahead window
+
+    Args:
+        stream: Stream to score
+        score: Function from make_scorer
+        window: Tokens compared per pick (1 keeps the stream's order)
+
+    Yields:
+        (weighted score, token), best first within the window
+    """
+    if window < 1:
+        raise ConfigError("window must be at least 1")
+    heap: List[Tuple[float, int, str]] = []
+    for position, token in enumerate(stream.tokens):
+        # Ties keep stream order through the position
+        heapq.heappush(heap, (-score(token, position) * stream.weight, position, token))
+        if len(heap) >= window:
+            negated, _, best = heapq.heappop(heap)
+            yield -negated, best
+    while heap:
+        negated, _, best = heapq.heappop(heap)
+        yield -negated, best
+
+
+def quota_shares(streams: Sequence[Stream]) -> Optional[List[float]]:
+    """
+    Normalized quotas of the streams
+
+    Args:
+        streams: Streams to merge
+
+    Returns:
+        Share of each stream (summing to 1), or None without quotas
+    """
+    quotas = [stream.quota for stream in streams]
+    if all(quota is None for quota in quotas):
+        return None
+    if any(quota is None for quota in quotas):
+        missing = ", ".join(stream.tag for stream in streams if stream.quota is None)
+        raise ConfigError(f"Quotas must be set for every stream or none (missing: {missing})")
+    if any(quota < 0 for quota in quotas) or not sum(quotas):
+        raise ConfigError("Quotas must not be negative and must not all be 0")
+    total = sum(quotas)
+    return [quota / total for quota in quotas]
+
+
+def quota_allotments(shares: Sequence[float], block: int) -> List[int]:
+    """
+    Lines of each stream per block, rounded by largest remainder to fill it exactly
+
+    Args:
+        shares: Result of quota_shares
+        block: Lines per block
+
+    Returns:
+        Lines per stream, summing to block
+    """
+    if block < 1:
+        raise ConfigError("block must be at least 1")
+    exact = [share * block for share in shares]
+    lines = [int(value) for value in exact]
+    by_remainder = sorted(range(len(shares)), key=lambda i: -(exact[i] - lines[i]))
+    for i in by_remainder[:block - sum(lines)]:
+        lines[i] += 1
+    return lines
+
+
+def quota_horizon(sizes: Sequence[int], shares: Sequence[float]) -> Tuple[int, Optional[int]]:
+    """
+    How long the quotas hold before a stream runs out
+
+    Args:
+        sizes: Tokens of each stream
+        shares: Result of quota_shares
+
+    Returns:
+        (output lines, index of the stream that runs out first), with
+        None as the index when no stream has a share
+    """
+    horizons = [(size / share, i) for i, (size, share) in enumerate(zip(sizes, shares)) if share > 0]
+    if not horizons:
+        return 0, None
+    lines, index = min(horizons)
+    return int(lines), index
+
+
+def priority_merge(streams: List[Stream], score: Callable[[str, int], float],
+                   window: int = DEFAULT_WINDOW,
+                   limit: Optional[int] = None,
+                   block: int = DEFAULT_BLOCK) -> Iterator[MergedToken]:
+    """
+    Merge streams into one best-first list
+
+    Args:
+        streams: Streams to merge; on equal scores earlier streams go first
+        score: Function from make_scorer
+        window: Lookahead window of each stream (see best_first)
+        limit: Stop after this many unique tokens
+        block: Lines per quota block (with stream quotas)
+
+    Yields:
+        Unique tokens in descending score order, within the quotas
+    """
+    if not streams:
+        raise ConfigError("priority merge needs at least one stream")
+    shares = quota_shares(streams)
+    allotments = quota_allotments(shares, block) if shares is not None else None
+    heads = [_tagged(stream, score, window) for stream in streams]
+    # One (negated score, stream index, entry) per stream with tokens left;
+    # streams over their quota wait in `parked` for the next block
+    heap: List[Tuple[float, int, MergedToken]] = []
+    parked: List[Tuple[float, int, MergedToken]] = []
+    for index in range(len(heads)):
+        _advance(heap, heads, index)
+    used = [0] * len(streams)
+    emitted: Set[str] = set()
+
+    while heap or parked:
+        if not heap:
+            # Only streams over their quota are left: the others ran out,
+            # so their share goes to the next block
+            heap, parked, used = parked, [], [0] * len(streams)
+            heapq.heapify(heap)
+        item = heapq.heappop(heap)
+        _, index, entry = item
+        if allotments is not None and used[index] >= allotments[index]:
+            parked.append(item)
+            continue
+        _advance(heap, heads, index)
+        if entry.token in emitted:
+            continue
+        if limit is not None and len(emitted) >= limit:
+            return
+        emitted.add(entry.token)
+        yield entry
+        if allotments is not None:
+            used[index] += 1
+            if sum(used) >= block:
+                for parked_item in parked:
+                    heapq.heappush(heap, parked_item)
+                parked, used = [], [0] * len(streams)
+
+
+def _advance(heap: list, heads: List[Iterator[MergedToken]], index: int):
+    entry = next(heads[index], None)
+    if entry is not None:
+        heapq.heappush(heap, (-entry.score, index, entry))
+
+
+def _tagged(stream: Stream, score: Callable[[str, int], float], window: int) -> Iterator[MergedToken]:
+    for value, token in best_first(stream, score, window):
+        yield MergedToken(token, value, stream.tag)
+
+
+@dataclass
+class StreamSpec:
+    """A stream of a merge plan"""
+    # Config JSON file, wordlist (txt / JSONL, local or http(s)) or preset name
+    source: str
+    # Defaults to the file name or preset name
+    tag: Optional[str] = None
+    weight: float = 1.0
+    quota: Optional[float] = None
+
+    @classmethod
+    def parse(cls, spec: str) -> 'StreamSpec':
+        """Parse a [TAG=]SOURCE command-line stream"""
+        tag, source = parse_source(spec)
+        return cls(source, tag)
+
+    def _config(self) -> Optional[Config]:
+        """Config of a config-file or preset stream (None for wordlists)"""
+        path = Path(self.source)
+        if path.suffix == ".json" and path.exists():
+            return Config.from_json(path)
+        if path.exists() or is_remote(self.source):
+            return None
+        return PresetManager().get_preset_config(self.source)
+
+    @property
+    def name(self) -> str:
+        path = Path(self.source)
+        return self.tag or (path.stem if path.exists() else self.source)
+
+    def open(self) -> Stream:
+        """Stream generating or reading the source"""
+        config = self._config()
+        if config is not None:
+            tokens = Generator(config).generate()
+        else:
+            tokens = (token for token, _ in read_tagged(resolve_source(self.source)))
+        return Stream(self.name, tokens, self.weight, self.quota)
+
+    def size(self) -> int:
+        """Estimated tokens (keyspace estimate for configs, lines for wordlists)"""
+        config = self._config()
+        if config is not None:
+            return Generator(config).estimate_count()
+        with open_text(resolve_source(self.source)) as f:
+            return sum(1 for line in f if line.strip())
+
+
+@dataclass
+class MergePlan:
+    """Streams of a campaign and how they are merged"""
+    streams: List[StreamSpec] = field(default_factory=list)
+    score: str = "rank"
+    window: int = DEFAULT_WINDOW
+    block: int = DEFAULT_BLOCK
+    frequency_model: Optional[str] = None
+
+    @classmethod
+    def from_dict(cls, data: dict) -> 'MergePlan':
+        data = dict(data)
+        try:
+            data['streams'] = [StreamSpec(**stream) for stream in data.get('streams', [])]
+            return cls(**data)
+        except TypeError as e:
+            raise ConfigError(f"Invalid merge plan: {e}")
+
+    @classmethod
+    def from_json(cls, path: Path) -> 'MergePlan':
+        """Load a plan from a JSON file"""
+        with open(path) as f:
+            return cls.from_dict(json.load(f))
+
+    def to_dict(self) -> dict:
+        return asdict(self)
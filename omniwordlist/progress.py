@@ -0,0 +1,108 @@
+"""
+Generation progress statistics
+
+GeneratorStats is a snapshot of how many candidates made it through
+each pipeline stage, with the throughput measured over a moving time
+window rather than since the start (which hides slowdowns late in long
+runs). Generator.set_progress_callback delivers one every
+`progress_interval` emitted tokens.
+"""
+
+import time
+from collections import deque
+from dataclasses import dataclass
+from typing import Callable, Deque, Tuple
+
+from .events import GeneratorObserver
+
+
+# Seconds of progress samples the tokens/s rate is computed over
+DEFAULT_RATE_WINDOW = 10.0
+
+
+@dataclass
+class GeneratorStats:
+    """Pipeline counters of a run"""
+    # Raw candidates processed
+    generated: int = 0
+    # Candidates accepted by filters, limits and exclusion lists
+    after_filters: int = 0
+    # ... and not dropped as duplicates
+    after_dedupe: int = 0
+    # Tokens emitted (generation stops once max_lines are out)
+    emitted: int = 0
+    # Output lines written by write_lines
+    written: int = 0
+    # Seconds since generation started
+    elapsed: float = 0.0
+    # Emitted tokens per second over the rate window
+    rate: float = 0.0
+    # Estimated bytes held by the dedupe set or filter
+    dedupe_memory: int = 0
+
+
+class RateWindow:
+    """Throughput of a growing counter over the last `seconds` seconds"""
+
+    def __init__(self, seconds: float = DEFAULT_RATE_WINDOW, clock=time.monotonic):
+        self.seconds = seconds
+        self.clock = clock
+        self.samples: Deque[Tuple[float, int]] = deque()
+
+    def sample(self, count: int) -> float:
+        """
+        Record the counter and return its current rate
+
+        Args:
+            count: Counter value now
+
+        Returns:
+            Increase per second since the oldest sample inside the window
+        """
+        now = self.clock()
+        self.samples.append((now, count))
+        # Keep one sample at or before the window start as the baseline
+        while len(self.samples) > 2 and now - self.samples[1][0] >= self.seconds:
+            self.samples.popleft()
+        return self.rate()
+
+    def rate(self) -> float:
+        """Rate between the oldest and newest samples"""
+        if len(self.samples) < 2:
+            return 0.0
+        (start, first), (end, last) = self.samples[0], self.samples[-1]
+        return (last - first) / (end - start) if end > start else 0.0
+
+
+class ProgressCallback(GeneratorObserver):
+    """Calls a function with the generator's stats on every progress event"""
+
+    def __init__(self, generator, callback: Callable[[GeneratorStats], None]):
+        self.generator = generator
+        self.callback = callback
+
+    def on_progress(self, tokens_generated: int) -> None:
+        self.callback(self.generator.progress_stats())
+
+
+def throttled(callback: Callable[[GeneratorStats], None], interval: float,
+              clock=time.monotonic) -> Callable[[GeneratorStats], None]:
+    """
+    Wrap a progress callback so it runs at most every `interval` seconds
+
+    Args:
+        callback: Progress callback
+        interval: Minimum seconds between calls
+        clock: Time source
+
+    Returns:
+        Callback for Generator.set_progress_callback
+    """
+    last = [clock()]
+
+    def call(stats: GeneratorStats):
+        now = clock()
+        if now - last[0] >= interval:
+            last[0] = now
+            callback(stats)
+    return call
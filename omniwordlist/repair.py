@@ -0,0 +1,169 @@
+"""
+Config repair for `omni validate --fix`
+
+Works on the raw JSON mapping, before it becomes a Config, so that
+files too broken to load (misspelled keys) can still be repaired.
+Each detected problem is a Fix the caller may accept or skip.
+"""
+
+import difflib
+from dataclasses import dataclass, fields
+from typing import Callable, Dict, List
+
+from .config import Config, FilterConfig
+from .derived import is_derived_field, parse_derived_field
+from .error import ConfigError
+from .transforms import list_transforms
+
+
+COMPRESSION_FORMATS = ("gzip", "bzip2", "lz4", "zstd")
+
+# Common spellings of the supported compression formats
+COMPRESSION_ALIASES = {
+    "gz": "gzip", "gzip": "gzip",
+    "bz2": "bzip2", "bzip": "bzip2", "bzip2": "bzip2",
+    "lz4": "lz4",
+    "zst": "zstd", "zstd": "zstd", "zstandard": "zstd",
+}
+
+# (section, lower key, upper key) pairs that must be ordered
+RANGE_KEYS = [
+    (None, "min_length", "max_length"),
+    ("filters", "min_len", "max_len"),
+    ("filters", "min_bytes", "max_bytes"),
+    ("filters", "min_entropy", "max_entropy"),
+    (None, "min_components", "max_components"),
+]
+
+
+@dataclass
+class Fix:
+    """One repairable problem"""
+    description: str
+    apply: Callable[[Dict], None]
+
+
+def _key_fixes(data: Dict, known: List[str], section: str = None) -> List[Fix]:
+    """Rename misspelled keys, drop unknown ones"""
+    prefix = f"{section}." if section else ""
+    found = []
+    for key in list(data):
+        if key in known:
+            continue
+        matches = difflib.get_close_matches(key, known, n=1)
+        if matches and matches[0] not in data:
+            def rename(d, key=key, new=matches[0]):
+                target = d[section] if section else d
+                target[new] = target.pop(key)
+            found.append(Fix(f"Rename unknown key {prefix}{key} to {prefix}{matches[0]}", rename))
+        else:
+            def drop(d, key=key):
+                target = d[section] if section else d
+                target.pop(key, None)
+            found.append(Fix(f"Remove unknown key {prefix}{key}", drop))
+    return found
+
+
+def _range_fixes(data: Dict) -> List[Fix]:
+    """Swap lower/upper bounds given in the wrong order"""
+    found = []
+    for section, low_key, high_key in RANGE_KEYS:
+        values = data.get(section) if section else data
+        if not isinstance(values, dict):
+            continue
+        low, high = values.get(low_key), values.get(high_key)
+        if isinstance(low, (int, float)) and isinstance(high, (int, float)) and low > high:
+            def swap(d, section=section, low_key=low_key, high_key=high_key):
+                target = d[section] if section else d
+                target[low_key], target[high_key] = target[high_key], target[low_key]
+            prefix = f"{section}." if section else ""
+            found.append(Fix(f"Swap reversed {prefix}{low_key}/{prefix}{high_key} ({low} > {high})", swap))
+    return found
+
+
+def _compression_fixes(data: Dict) -> List[Fix]:
+    """Normalize compression spellings"""
+    compression = data.get("compression")
+    if not isinstance(compression, str) or compression in COMPRESSION_FORMATS:
+        return []
+
+    normalized = COMPRESSION_ALIASES.get(compression.strip().lower())
+    if normalized:
+        def normalize(d):
+            d["compression"] = normalized
+        return [Fix(f"Normalize compression {compression!r} to {normalized!r}", normalize)]
+
+    def uncompressed(d):
+        d["compression"] = None
+    return [Fix(f"Remove unsupported compression {compression!r} (write uncompressed)", uncompressed)]
+
+
+def _transform_fixes(data: Dict) -> List[Fix]:
+    """Remove transforms that do not exist"""
+    transforms = data.get("transforms")
+    if not isinstance(transforms, list):
+        return []
+
+    known = list_transforms()
+    found = []
+    for name in [t for t in transforms if t not in known]:
+        matches = difflib.get_close_matches(str(name), known, n=1)
+        note = f" (did you mean {matches[0]}?)" if matches else ""
+        found.append(Fix(f"Remove unknown transform {name!r}{note}",
+                         lambda d, name=name: d["transforms"].remove(name)))
+    return found
+
+
+def _field_fixes(data: Dict) -> List[Fix]:
+    """Enable fields other settings depend on"""
+    enabled = data.get("enabled_fields")
+    if not isinstance(enabled, list):
+        return []
+
+    # Source fields of derived fields, mapped to the first derived field needing them
+    inline_words = "words" if data.get("words") else None
+    missing: Dict[str, str] = {}
+    for field_id in enabled:
+        if not isinstance(field_id, str) or not is_derived_field(field_id):
+            continue
+        try:
+            sources = parse_derived_field(field_id).sources
+        except ConfigError:
+            continue
+        for source in sources:
+            if source not in enabled and source != inline_words:
+                missing.setdefault(source, field_id)
+
+    found = []
+    for source, needed_by in missing.items():
+        def enable(d, source=source, needed_by=needed_by):
+            d["enabled_fields"].insert(d["enabled_fields"].index(needed_by), source)
+        found.append(Fix(f"Enable field {source!r} needed by {needed_by!r}", enable))
+
+    field_sources = data.get("field_sources")
+    if isinstance(field_sources, dict):
+        for field_id in field_sources:
+            if field_id not in enabled and field_id not in missing:
+                found.append(Fix(f"Enable field {field_id!r} (it has a field source but is not enabled)",
+                                 lambda d, field_id=field_id: d["enabled_fields"].append(field_id)))
+    return found
+
+
+def find_fixes(data: Dict) -> List[Fix]:
+    """
+    Detect repairable problems in a raw config mapping
+
+    Args:
+        data: Config mapping as loaded from JSON
+
+    Returns:
+        Fixes, in the order they should be offered
+    """
+    found = _key_fixes(data, [f.name for f in fields(Config)])
+    if isinstance(data.get("filters"), dict):
+        found += _key_fixes(data["filters"], [f.name for f in fields(FilterConfig)], "filters")
+    found += _range_fixes(data)
+    found += _compression_fixes(data)
+    found += _transform_fixes(data)
+    found += _field_fixes(data)
+    return found
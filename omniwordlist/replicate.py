@@ -0,0 +1,206 @@
+"""
+Password structure replication
+
+Infers the structure ("pattern DNA") of one known password, e.g.
+Summer2019! -> Capitalized word + year + symbol, and builds a config
+producing every candidate with that same structure from other words,
+years and symbols.
+"""
+
+import itertools
+from dataclasses import dataclass
+from datetime import date
+from typing import List, Optional, Sequence
+from .config import Config
+from .error import ConfigError
+from .temporal import MONTHS, SEASONS
+from .toplists import BUILTIN_TOP_PASSWORDS
+
+
+# Symbols people actually put in passwords, most common first
+COMMON_SYMBOLS = "!@#$%&*?.-_+="
+
+# Digit runs this long or shorter are enumerated exhaustively
+MAX_ENUMERATED_DIGITS = 4
+
+
+@dataclass
+class Segment:
+    """One run of a password structure"""
+    kind: str           # word, year, digits or symbols
+    text: str
+    case: str = ""      # for words: capitalized, lower, upper or mixed
+
+
+def _char_kind(char: str) -> str:
+    if char.isalpha():
+        return "word"
+    if char.isdigit():
+        return "digits"
+    return "symbols"
+
+
+def _word_case(word: str) -> str:
+    if word.islower():
+        return "lower"
+    if word.isupper() and len(word) > 1:
+        return "upper"
+    if word[0].isupper() and (len(word) == 1 or word[1:].islower()):
+        return "capitalized"
+    return "mixed"
+
+
+def infer_structure(password: str) -> List[Segment]:
+    """
+    Split a password into word, year, digit and symbol runs
+    
+    Args:
+        password: Known password
+        
+    Returns:
+        Segments in order
+    """
+    if not password:
+        raise ConfigError("Known password must not be empty")
+    
+    segments = []
+    for kind, chars in itertools.groupby(password, key=_char_kind):
+        text = ''.join(chars)
+        if kind == "word":
+            segments.append(Segment("word", text, _word_case(text)))
+        elif kind == "digits" and len(text) == 4 and text[:2] in ("19", "20"):
+            segments.append(Segment("year", text))
+        else:
+            segments.append(Segment(kind, text))
+    return segments
+
+
+def describe_structure(segments: Sequence[Segment]) -> str:
+    """
+    Describe a structure in words, e.g. "Capitalized word + year + symbol"
+    
+    Args:
+        segments: Segments from infer_structure
+        
+    Returns:
+        Human readable description
+    """
+    parts = []
+    for segment in segments:
+        if segment.kind == "word":
+            parts.append(f"{segment.case.capitalize()} word")
+        elif segment.kind == "year":
+            parts.append("year")
+        elif segment.kind == "digits":
+            parts.append(f"{len(segment.text)} digit{'s' if len(segment.text) > 1 else ''}")
+        else:
+            parts.append("symbol" if len(segment.text) == 1 else f"{len(segment.text)} symbols")
+    return " + ".join(parts)
+
+
+def default_base_words() -> List[str]:
+    """Seasons, months and alphabetic common passwords, deduplicated"""
+    words = []
+    for season_words in SEASONS["en"].values():
+        words.extend(season_words)
+    words.extend(MONTHS["en"])
+    words.extend(w for w in BUILTIN_TOP_PASSWORDS if w.isalpha())
+    
+    seen = set()
+    unique = []
+    for word in words:
+        if word.lower() not in seen:
+            seen.add(word.lower())
+            unique.append(word)
+    return unique
+
+
+def _apply_case(word: str, case: str) -> str:
+    if case == "capitalized":
+        return word.capitalize()
+    if case == "lower":
+        return word.lower()
+    if case == "upper":
+        return word.upper()
+    return word
+
+
+def segment_values(segment: Segment, words: Sequence[str], years: Sequence[int],
+                   symbols: str) -> List[str]:
+    """
+    Candidate values for one segment
+    
+    Args:
+        segment: Segment to fill
+        words: Base words (case is adapted to the segment)
+        years: Years for year segments
+        symbols: Symbols for symbol segments
+        
+    Returns:
+        Values, the known one included
+    """
+    if segment.kind == "word":
+        values = [_apply_case(w, segment.case) for w in words]
+    elif segment.kind == "year":
+        values = [str(y) for y in years]
+    elif segment.kind == "digits":
+        width = len(segment.text)
+        if width > MAX_ENUMERATED_DIGITS:
+            values = []
+        else:
+            values = [str(n).zfill(width) for n in range(10 ** width)]
+    else:
+        values = [''.join(p) for p in itertools.product(symbols, repeat=len(segment.text))]
+    
+    if segment.text not in values:
+        values.insert(0, segment.text)
+    return list(dict.fromkeys(values))
+
+
+def replicate_config(password: str, words: Optional[Sequence[str]] = None,
+                     years: Optional[Sequence[int]] = None,
+                     symbols: str = COMMON_SYMBOLS) -> Config:
+    """
+    Build a field config generating the known password's structure
+    
+    Args:
+        password: Known password
+        words: Base words (default: seasons, months and common words)
+        years: Years (default: ten years back to next year)
+        symbols: Symbols for symbol runs
+        
+    Returns:
+        Config with one inline field source per segment
+    """
+    segments = infer_structure(password)
+    words = list(words) if words else default_base_words()
+    if years is None:
+        this_year = date.today().year
+        years = range(this_year - 10, this_year + 2)
+    
+    config = Config()
+    for i, segment in enumerate(segments):
+        field_id = f"replicate_{i}_{segment.kind}"
+        config.enabled_fields.append(field_id)
+        config.field_sources[field_id] = segment_values(segment, words, list(years), symbols)
+    config.dedupe = True
+    return config
+
+
+def parse_year_range(value: str) -> List[int]:
+    """
+    Parse "2015-2026" or a single year
+    
+    Args:
+        value: Year range
+        
+    Returns:
+        Years in the range
+    """
+    try:
+        if '-' in value:
+            start, end = value.split('-', 1)
+            return list(range(int(start), int(end) + 1))
+        return [int(value)]
+    except ValueError:
+        raise ConfigError(f"Invalid year range: {value}")
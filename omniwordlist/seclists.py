@@ -0,0 +1,190 @@
+"""
+SecLists integration
+
+Knows where well-known lists live in the SecLists repository, fetches
+them into the local data directory (mirroring the repository layout)
+and records a SHA-256 checksum of every fetched file. Fetched lists are
+usable wherever a word list path is accepted through a named source:
+
+    "field_sources": {"dictionary": "seclists:Passwords/xato-net-10-million"}
+
+Names not in the catalog may spell out a repository path instead
+("seclists:Passwords/Common-Credentials/best110.txt").
+"""
+
+import hashlib
+import json
+import shutil
+import time
+from pathlib import Path
+from typing import Dict, List, Optional, Tuple
+
+from .error import ConfigError, StorageError
+from .locking import atomic_write_json
+
+
+SECLISTS_PREFIX = "seclists:"
+
+SECLISTS_RAW_BASE = "https://raw.githubusercontent.com/danielmiessler/SecLists/master/"
+
+# Named lists per category, mapped to their path in the repository
+SECLISTS_CATALOG: Dict[str, Dict[str, str]] = {
+    "Passwords": {
+        "xato-net-10-million": "Passwords/Common-Credentials/xato-net-10-million-passwords-1000000.txt",
+        "top-1000": "Passwords/Common-Credentials/10-million-password-list-top-1000.txt",
+        "top-10000": "Passwords/Common-Credentials/10-million-password-list-top-10000.txt",
+        "top-100000": "Passwords/Common-Credentials/10-million-password-list-top-100000.txt",
+        "best1050": "Passwords/Common-Credentials/best1050.txt",
+        "darkweb2017-top10000": "Passwords/darkweb2017-top10000.txt",
+        "default-passwords": "Passwords/Default-Credentials/default-passwords.txt",
+    },
+    "Usernames": {
+        "top-usernames-shortlist": "Usernames/top-usernames-shortlist.txt",
+        "xato-net-10-million": "Usernames/xato-net-10-million-usernames.txt",
+        "names": "Usernames/Names/names.txt",
+    },
+    "Discovery": {
+        "common": "Discovery/Web-Content/common.txt",
+        "raft-medium-directories": "Discovery/Web-Content/raft-medium-directories.txt",
+        "subdomains-top1million-5000": "Discovery/DNS/subdomains-top1million-5000.txt",
+    },
+}
+
+MANIFEST_NAME = "manifest.json"
+
+
+def default_data_dir() -> Path:
+    """Directory fetched SecLists files are stored in"""
+    return Path.home() / ".omniwordlist" / "data" / "seclists"
+
+
+def is_seclists_source(path: str) -> bool:
+    """Check whether a source reference names a SecLists list"""
+    return str(path).startswith(SECLISTS_PREFIX)
+
+
+def list_catalog(category: Optional[str] = None) -> List[Tuple[str, str]]:
+    """
+    Named lists of the catalog
+
+    Args:
+        category: Only lists of this category
+
+    Returns:
+        (name, repository path) pairs, names as "<category>/<list>"
+    """
+    if category is not None and category not in SECLISTS_CATALOG:
+        raise ConfigError(f"Unknown SecLists category: {category} "
+                          f"(known: {', '.join(SECLISTS_CATALOG)})")
+    return [(f"{cat}/{name}", repo_path)
+            for cat, entries in SECLISTS_CATALOG.items() if category in (None, cat)
+            for name, repo_path in entries.items()]
+
+
+def repository_path(name: str) -> str:
+    """
+    Repository path of a named list
+
+    Args:
+        name: "<category>/<list>" from the catalog, or a repository path of a .txt file
+
+    Returns:
+        Path relative to the repository root
+    """
+    name = name[len(SECLISTS_PREFIX):] if is_seclists_source(name) else name
+    category, _, entry = name.partition("/")
+    if entry in SECLISTS_CATALOG.get(category, {}):
+        return SECLISTS_CATALOG[category][entry]
+    parts = Path(name).parts
+    if name.endswith(".txt") and parts and ".." not in parts and not Path(name).is_absolute():
+        return Path(name).as_posix()
+    raise ConfigError(f"Unknown SecLists list: {name} (see `omni seclists list`)")
+
+
+def load_manifest(data_dir: Optional[Path] = None) -> Dict[str, Dict]:
+    """Checksums and origins of fetched lists, keyed by repository path"""
+    path = (data_dir or default_data_dir()) / MANIFEST_NAME
+    if not path.exists():
+        return {}
+    try:
+        return json.loads(path.read_text())
+    except ValueError as e:
+        raise StorageError(f"Corrupt SecLists manifest {path}: {e}")
+
+
+def file_checksum(path: Path) -> str:
+    """SHA-256 of a file"""
+    digest = hashlib.sha256()
+    with open(path, "rb") as f:
+        for chunk in iter(lambda: f.read(1 << 20), b""):
+            digest.update(chunk)
+    return digest.hexdigest()
+
+
+def fetch_list(name: str, data_dir: Optional[Path] = None, force: bool = False) -> Tuple[Path, str]:
+    """
+    Download a list into the data directory and record its checksum
+
+    A list already fetched is kept when its checksum still matches.
+
+    Args:
+        name: Catalog name or repository path
+        data_dir: Override for the data directory
+        force: Download again even if an intact copy exists
+
+    Returns:
+        (local path, SHA-256)
+    """
+    from .sources import fetch_remote
+
+    data_dir = data_dir or default_data_dir()
+    repo_path = repository_path(name)
+    path = data_dir / repo_path
+    manifest = load_manifest(data_dir)
+    entry = manifest.get(repo_path)
+    if not force and entry and path.exists() and file_checksum(path) == entry["sha256"]:
+        return path, entry["sha256"]
+
+    url = SECLISTS_RAW_BASE + repo_path
+    # Download through the source cache so dropped transfers resume; nothing
+    # of it (a .part file of a failed download included) is kept afterwards
+    downloads = data_dir / ".downloads"
+    try:
+        downloaded = fetch_remote(url, cache_dir=downloads)
+        path.parent.mkdir(parents=True, exist_ok=True)
+        shutil.move(str(downloaded), str(path))
+    finally:
+        shutil.rmtree(downloads, ignore_errors=True)
+
+    checksum = file_checksum(path)
+    manifest[repo_path] = {
+        "url": url,
+        "sha256": checksum,
+        "size": path.stat().st_size,
+        "fetched": time.strftime("%Y-%m-%dT%H:%M:%SZ", time.gmtime()),
+    }
+    atomic_write_json(data_dir / MANIFEST_NAME, manifest)
+    return path, checksum
+
+
+def resolve_list(name: str, data_dir: Optional[Path] = None) -> Path:
+    """
+    Local file of a fetched list, checked against its recorded checksum
+
+    Args:
+        name: "seclists:<name>", catalog name or repository path
+        data_dir: Override for the data directory
+
+    Returns:
+        Local path
+    """
+    data_dir = data_dir or default_data_dir()
+    repo_path = repository_path(name)
+    path = data_dir / repo_path
+    entry = load_manifest(data_dir).get(repo_path)
+    if entry is None or not path.exists():
+        bare = name[len(SECLISTS_PREFIX):] if is_seclists_source(name) else name
+        raise ConfigError(f"SecLists list {bare} not fetched; run `omni seclists fetch {bare}` first")
+    if file_checksum(path) != entry["sha256"]:
+        raise StorageError(f"Checksum mismatch for {path}; fetch it again with --force")
+    return path
@@ -0,0 +1,232 @@
+"""
+Service packaging for long-running build servers
+
+Installs `omni coordinate` or `omni worker` so it starts with the
+machine: a systemd unit on Linux, or a startup task on Windows (the
+service control manager only runs programs written as services, so a
+console program is registered with the task scheduler instead, through
+a .cmd script in the data directory since task commands are limited to
+261 characters).
+
+Every path the CLI keeps state under (presets, campaigns, caches) is
+relative to the home directory, so the service runs with its data
+directory both as working directory and as home; its state lives under
+DATA_DIR/.omniwordlist and worker chunk files default to DATA_DIR.
+Resource limits (memory, CPU, nice level) are applied by systemd; the
+task scheduler has no equivalent.
+"""
+
+import os
+import re
+import shutil
+import subprocess
+import sys
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Callable, List, Optional
+
+from .error import ConfigError, StorageError
+
+
+# `omni` commands that can run as a service
+SERVICE_ROLES = ("worker", "coordinate")
+
+SERVICE_PLATFORMS = ("systemd", "windows")
+
+SYSTEM_UNIT_DIR = Path("/etc/systemd/system")
+
+_SAFE_ARGUMENT = re.compile(r"^[A-Za-z0-9_./:=@+,-]+$")
+
+
+def default_platform() -> str:
+    """Service platform of this machine"""
+    return "windows" if os.name == "nt" else "systemd"
+
+
+def user_unit_dir() -> Path:
+    """Directory of per-user systemd units"""
+    return Path.home() / ".config" / "systemd" / "user"
+
+
+def default_data_dir(user_service: bool = False, platform: Optional[str] = None) -> Path:
+    """Data directory of a system-wide or per-user service"""
+    if user_service or (platform or default_platform()) == "windows":
+        return Path.home() / ".local" / "share" / "omniwordlist"
+    return Path("/var/lib/omniwordlist")
+
+
+def omni_command() -> List[str]:
+    """Absolute command line that starts the CLI"""
+    omni = shutil.which("omni")
+    if omni:
+        return [str(Path(omni).resolve())]
+    return [str(Path(sys.executable).resolve()), "-m", "omniwordlist.cli"]
+
+
+@dataclass
+class ServiceSpec:
+    """A service running one `omni` command"""
+    # One of SERVICE_ROLES
+    role: str
+    # Arguments after the role, e.g. ["--url", "http://coordinator:8765"]
+    args: List[str] = field(default_factory=list)
+    # Defaults to omniwordlist-<role>
+    name: Optional[str] = None
+    data_dir: Path = field(default_factory=default_data_dir)
+    # Account the service runs as (system services only)
+    user: Optional[str] = None
+    # systemd MemoryMax, e.g. 8G
+    memory_max: Optional[str] = None
+    # Percent of one CPU (200 = two cores)
+    cpu_quota: Optional[int] = None
+    nice: Optional[int] = None
+    # Command starting the CLI (omni_command() if omitted)
+    executable: Optional[List[str]] = None
+
+    def __post_init__(self):
+        if self.role not in SERVICE_ROLES:
+            raise ConfigError(f"Unknown service role: {self.role} (known: {', '.join(SERVICE_ROLES)})")
+        if self.cpu_quota is not None and self.cpu_quota < 1:
+            raise ConfigError("cpu_quota must be at least 1 (percent of one CPU)")
+        if self.nice is not None and not -20 <= self.nice <= 19:
+            raise ConfigError("nice must be between -20 and 19")
+        self.name = self.name or f"omniwordlist-{self.role}"
+        self.data_dir = Path(self.data_dir).expanduser().absolute()
+
+    @property
+    def command(self) -> List[str]:
+        """Full command line of the service"""
+        return (self.executable or omni_command()) + [self.role] + list(self.args)
+
+    def limits(self) -> List[str]:
+        """Names of the configured resource limits"""
+        return [name for name, value in (("memory_max", self.memory_max), ("cpu_quota", self.cpu_quota),
+                                         ("nice", self.nice)) if value is not None]
+
+
+def _systemd_quote(argument: str) -> str:
+    """Quote an ExecStart argument (systemd expands % and $ itself)"""
+    if not _SAFE_ARGUMENT.match(argument):
+        argument = '"' + argument.replace("\\", "\\\\").replace('"', '\\"') + '"'
+    return argument.replace("%", "%%").replace("$", "$$")
+
+
+def systemd_unit(spec: ServiceSpec, user_service: bool = False) -> str:
+    """
+    Render a systemd unit
+
+    Args:
+        spec: Service to run
+        user_service: Unit for `systemctl --user` (no User=, started with the login session)
+
+    Returns:
+        Unit file contents
+    """
+    lines = [
+        "[Unit]",
+        f"Description=OmniWordlist {spec.role}",
+        "After=network-online.target",
+        "Wants=network-online.target",
+        "",
+        "[Service]",
+        "Type=simple",
+        f"ExecStart={' '.join(_systemd_quote(argument) for argument in spec.command)}",
+        f"WorkingDirectory={_systemd_quote(str(spec.data_dir))}",
+        f"Environment={_systemd_quote('HOME=' + str(spec.data_dir))}",
+        "Environment=PYTHONUNBUFFERED=1",
+        # Workers and coordinators exit once their job is done
+        "Restart=on-failure",
+        "RestartSec=10",
+    ]
+    if spec.user and not user_service:
+        lines.append(f"User={spec.user}")
+    if spec.memory_max:
+        lines.append(f"MemoryMax={spec.memory_max}")
+    if spec.cpu_quota is not None:
+        lines.append(f"CPUQuota={spec.cpu_quota}%")
+    if spec.nice is not None:
+        lines.append(f"Nice={spec.nice}")
+    lines += ["", "[Install]", f"WantedBy={'default.target' if user_service else 'multi-user.target'}", ""]
+    return "\n".join(lines)
+
+
+def windows_script_path(spec: ServiceSpec) -> Path:
+    """Script the startup task runs"""
+    return spec.data_dir / f"{spec.name}.cmd"
+
+
+def windows_script(spec: ServiceSpec) -> str:
+    """
+    Render the .cmd script of a startup task
+
+    Args:
+        spec: Service to run
+
+    Returns:
+        Script contents (CRLF line endings)
+    """
+    data_dir = str(spec.data_dir)
+    # cmd expands %VAR% in scripts; %% is a literal percent sign
+    command = subprocess.list2cmdline(spec.command).replace("%", "%%")
+    lines = ["@echo off", f'cd /d "{data_dir}"', f'set "USERPROFILE={data_dir}"', f'set "HOME={data_dir}"',
+             "set PYTHONUNBUFFERED=1", command, ""]
+    return "\r\n".join(lines)
+
+
+def windows_task_command(spec: ServiceSpec) -> List[str]:
+    """
+    schtasks command registering the startup task
+
+    Args:
+        spec: Service to run
+
+    Returns:
+        Command line for subprocess
+    """
+    return ["schtasks", "/Create", "/F", "/TN", spec.name, "/SC", "ONSTART",
+            "/RU", spec.user or "SYSTEM", "/TR", f'"{windows_script_path(spec)}"']
+
+
+def install_service(spec: ServiceSpec, platform: Optional[str] = None, user_service: bool = False,
+                    unit_dir: Optional[Path] = None, start: bool = True,
+                    runner: Optional[Callable[[List[str]], object]] = None) -> Path:
+    """
+    Install (and by default start) a service
+
+    Args:
+        spec: Service to install
+        platform: One of SERVICE_PLATFORMS (default_platform() if omitted)
+        user_service: Per-user systemd unit
+        unit_dir: Directory for the unit file (default: system or user unit directory)
+        start: Enable and start the service now, not only at the next boot
+        runner: Runs a command, raising on failure (subprocess.run with check=True if omitted)
+
+    Returns:
+        Path of the unit file or task script
+    """
+    platform = platform or default_platform()
+    if platform not in SERVICE_PLATFORMS:
+        raise ConfigError(f"Unknown service platform: {platform} (known: {', '.join(SERVICE_PLATFORMS)})")
+    runner = runner or (lambda command: subprocess.run(command, check=True))
+
+    try:
+        spec.data_dir.mkdir(parents=True, exist_ok=True)
+        if platform == "windows":
+            windows_script_path(spec).write_bytes(windows_script(spec).encode())
+            runner(windows_task_command(spec))
+            if start:
+                runner(["schtasks", "/Run", "/TN", spec.name])
+            return windows_script_path(spec)
+
+        if spec.user and not user_service:
+            shutil.chown(spec.data_dir, spec.user)
+        unit_dir = Path(unit_dir or (user_unit_dir() if user_service else SYSTEM_UNIT_DIR))
+        unit_dir.mkdir(parents=True, exist_ok=True)
+        unit_path = unit_dir / f"{spec.name}.service"
+        unit_path.write_text(systemd_unit(spec, user_service))
+        systemctl = ["systemctl", "--user"] if user_service else ["systemctl"]
+        runner(systemctl + ["daemon-reload"])
+        runner(systemctl + ["enable"] + (["--now"] if start else []) + [spec.name])
+        return unit_path
+    except (OSError, LookupError, subprocess.CalledProcessError) as e:
+        raise StorageError(f"Cannot install service {spec.name}: {e}")
@@ -0,0 +1,112 @@
+"""
+User settings
+
+Per-user preferences persisted in ~/.omniwordlist/settings.json, as
+opposed to job configuration: the per-category toggles for
+default-enabled fields and whether the local usage ledger is kept.
+The CLI loads them and applies them to the configs it runs; the
+generator itself never reads them.
+"""
+
+import json
+from dataclasses import asdict, dataclass, field
+from pathlib import Path
+from typing import Dict, List, Optional
+
+from .config import Config
+from .error import ConfigError, StorageError
+from .fields import FieldManager
+from .opsec import require_disk
+
+
+def default_settings_path() -> Path:
+    """File user settings are stored in"""
+    return Path.home() / ".omniwordlist" / "settings.json"
+
+
+@dataclass
+class UserSettings:
+    """Persistent user preferences"""
+
+    # Field category -> whether its default-enabled fields are used when a
+    # field-mode config (words / region only) selects no fields itself.
+    # Categories not listed are off.
+    default_field_categories: Dict[str, bool] = field(default_factory=dict)
+    # Keep a local ledger of finished runs (see usage; `omni stats`)
+    usage_stats: bool = False
+
+    @classmethod
+    def load(cls, path: Optional[Path] = None) -> 'UserSettings':
+        """
+        Load settings, returning defaults when the file does not exist
+
+        Args:
+            path: Settings file (default_settings_path() if omitted)
+
+        Returns:
+            UserSettings instance
+        """
+        path = Path(path or default_settings_path())
+        if not path.exists():
+            return cls()
+        try:
+            data = json.loads(path.read_text())
+        except (OSError, ValueError) as e:
+            raise ConfigError(f"Cannot read settings {path}: {e}")
+        categories = data.get("default_field_categories", {})
+        if not isinstance(categories, dict):
+            raise ConfigError(f"{path}: default_field_categories must be an object")
+        return cls(default_field_categories={str(k): bool(v) for k, v in categories.items()},
+                   usage_stats=bool(data.get("usage_stats", False)))
+
+    def save(self, path: Optional[Path] = None) -> Path:
+        """
+        Write settings
+
+        Args:
+            path: Settings file (default_settings_path() if omitted)
+
+        Returns:
+            Path written
+        """
+        path = Path(path or default_settings_path())
+        require_disk("settings", f"settings to {path}")
+        try:
+            path.parent.mkdir(parents=True, exist_ok=True)
+            path.write_text(json.dumps(asdict(self), indent=2) + "\n")
+        except OSError as e:
+            raise StorageError(f"Cannot write settings {path}: {e.strerror}")
+        return path
+
+    def set_category_defaults(self, category: str, enabled: bool) -> None:
+        """
+        Toggle the default-enabled fields of a category
+
+        Args:
+            category: Field category
+            enabled: Whether its default fields are used
+        """
+        if category not in FieldManager.list_categories():
+            raise ConfigError(f"Unknown field category: {category} "
+                              f"(known: {', '.join(FieldManager.list_categories())})")
+        self.default_field_categories[category] = enabled
+
+    def default_fields(self) -> List[str]:
+        """Ids of the default-enabled fields in categories switched on"""
+        enabled = [category for category, on in self.default_field_categories.items() if on]
+        return [f['id'] for f in FieldManager.get_default_fields(enabled)]
+
+    def apply_default_fields(self, config: Config) -> Config:
+        """
+        Enable the default fields in a field-mode config (words / region
+        only) that selects none itself and does not ignore them
+
+        Args:
+            config: Config to update
+
+        Returns:
+            The config
+        """
+        if not config.enabled_fields and not config.ignore_default_fields and (config.words or config.region):
+            config.enabled_fields = self.default_fields()
+        return config
@@ -0,0 +1,190 @@
+"""
+Sharded output
+
+Routes tokens into one file per shard inside an output directory,
+keyed by first character (a.txt, b.txt, ..., 0.txt, other.txt), by a
+hash of the token (00.txt .. ff.txt) or by entropy / quality bucket
+(low.txt, medium.txt, high.txt, see buckets). The shard of a candidate
+can be computed directly, so checking whether it was generated reads a
+single shard, and downstream crackers can consume shards in parallel.
+
+A shards.json manifest records how the directory was sharded.
+"""
+
+import hashlib
+import json
+from pathlib import Path
+from typing import Callable, Dict, Optional
+
+from .buckets import BUCKET_METRICS, bucket_key
+from .error import ConfigError, StorageError
+from .storage import COMPRESSION_SUFFIXES, OutputWriter, StorageBackend, open_text
+
+
+SHARD_MODES = ("first_char", "hash")
+
+MANIFEST_NAME = "shards.json"
+
+# Shard of tokens whose first character is not a letter or digit
+OTHER_SHARD = "other"
+
+
+def shard_key(token: str, mode: str, count: int = 16) -> str:
+    """
+    Shard a token belongs to
+
+    Letters are folded to lowercase so shard names never collide on
+    case-insensitive filesystems.
+
+    Args:
+        token: Token
+        mode: "first_char" or "hash"
+        count: Number of shards in hash mode
+
+    Returns:
+        Shard name (file name without suffix)
+    """
+    if mode == "first_char":
+        first = token[:1].lower()
+        return first if first.isascii() and first.isalnum() else OTHER_SHARD
+    if mode == "hash":
+        digest = int.from_bytes(hashlib.md5(token.encode("utf-8")).digest()[:4], "big")
+        width = len(f"{count - 1:x}")
+        return f"{digest % count:0{width}x}"
+    raise ConfigError(f"Unknown shard mode: {mode} (known: {', '.join(SHARD_MODES)})")
+
+
+def shard_file_name(key: str, format: str = "txt", compression: Optional[str] = None) -> str:
+    """File name of a shard"""
+    return f"{key}.{format}{COMPRESSION_SUFFIXES.get(compression, '')}"
+
+
+class ShardedWriter(StorageBackend):
+    """Writes tokens into per-shard files of a directory"""
+
+    def __init__(self, directory: Path, mode: str, count: int = 16,
+                 compression: Optional[str] = None, format: str = "txt",
+                 key: Optional[Callable[[str], str]] = None, manifest: Optional[Dict] = None,
+                 token_stats: bool = False):
+        """
+        Initialize a sharded writer
+
+        Args:
+            directory: Output directory
+            mode: Shard mode (see SHARD_MODES), or a label when key is given
+            count: Number of shards in hash mode
+            compression: Compression format of each shard
+            format: Output format of each shard
+            key: Custom token -> shard name function (e.g. buckets.bucket_key)
+            manifest: Extra manifest entries needed to recompute custom keys
+            token_stats: Add the tokenstats columns to CSV and JSONL shards
+        """
+        if key is None and mode not in SHARD_MODES:
+            raise ConfigError(f"Unknown shard mode: {mode} (known: {', '.join(SHARD_MODES)})")
+        if count < 1:
+            raise ConfigError("shard_count must be at least 1")
+        self.directory = Path(directory)
+        self.mode = mode
+        self.count = count
+        self.compression = compression
+        self.format = format
+        self.key = key or (lambda token: shard_key(token, mode, count))
+        self.extra_manifest = manifest or {}
+        self.token_stats = token_stats
+        self.writers: Dict[str, OutputWriter] = {}
+        self.lines_written = 0
+
+    def open(self):
+        """Create the output directory"""
+        if self.directory.exists() and not self.directory.is_dir():
+            raise StorageError(f"Sharded output needs a directory: {self.directory}")
+        self.directory.mkdir(parents=True, exist_ok=True)
+
+    def write(self, token: str, metadata: dict = None):
+        """
+        Write a token to its shard, opening the shard on first use
+
+        Args:
+            token: Token to write
+            metadata: Optional metadata
+        """
+        key = self.key(token)
+        writer = self.writers.get(key)
+        if writer is None:
+            path = self.directory / shard_file_name(key, self.format, self.compression)
+            writer = OutputWriter(path, self.compression, self.format, token_stats=self.token_stats)
+            writer.open()
+            self.writers[key] = writer
+        writer.write(token, metadata)
+        self.lines_written += 1
+
+    def write_token(self, token: str, metadata: dict = None):
+        self.write(token, metadata)
+
+    def finalize(self):
+        self.close()
+
+    @property
+    def bytes_written(self) -> int:
+        return sum(writer.bytes_written for writer in self.writers.values())
+
+    def close(self):
+        """Close every shard and write the manifest"""
+        for writer in self.writers.values():
+            writer.close()
+        manifest = {
+            "mode": self.mode,
+            "count": self.count,
+            "format": self.format,
+            "compression": self.compression,
+            **self.extra_manifest,
+            "shards": {key: writer.lines_written for key, writer in sorted(self.writers.items())},
+        }
+        (self.directory / MANIFEST_NAME).write_text(json.dumps(manifest, indent=2) + "\n")
+
+
+def load_manifest(directory: Path) -> Dict:
+    """
+    Read the manifest of a sharded output directory
+
+    Args:
+        directory: Directory written by ShardedWriter
+
+    Returns:
+        Manifest dictionary
+    """
+    path = Path(directory) / MANIFEST_NAME
+    try:
+        return json.loads(path.read_text())
+    except FileNotFoundError:
+        raise StorageError(f"Not a sharded output directory (no {MANIFEST_NAME}): {directory}")
+    except (OSError, ValueError) as e:
+        raise StorageError(f"Cannot read {path}: {e}")
+
+
+def shard_contains(directory: Path, token: str) -> bool:
+    """
+    Check whether a token was written to a sharded output
+
+    Only the token's own shard is read.
+
+    Args:
+        directory: Directory written by ShardedWriter
+        token: Token to look up
+
+    Returns:
+        True if the token is present
+    """
+    manifest = load_manifest(directory)
+    if manifest["format"] != "txt":
+        raise StorageError(f"Lookup needs txt shards, {directory} holds {manifest['format']}")
+    if manifest["mode"] in BUCKET_METRICS:
+        key = bucket_key(manifest["mode"], manifest.get("entropy_mode", "shannon"),
+                         manifest.get("thresholds"))(token)
+    else:
+        key = shard_key(token, manifest["mode"], manifest["count"])
+    path = Path(directory) / shard_file_name(key, manifest["format"], manifest["compression"])
+    if not path.exists():
+        return False
+    with open_text(path, manifest["compression"]) as f:
+        return any(line.rstrip("\r\n") == token for line in f)
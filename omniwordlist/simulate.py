@@ -0,0 +1,128 @@
+"""
+Rate-of-discovery simulation
+
+Replays a candidate stream against a reference list (e.g. leaked
+passwords) and records how much of the reference each prefix of the
+stream would have cracked: the cumulative hit curve used to compare
+generation strategies offline.
+"""
+
+import csv
+from collections import Counter
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import IO, Iterable, List, Optional, Set
+
+from .error import ConfigError
+from .storage import open_text
+
+
+@dataclass
+class HitPoint:
+    """Progress after a number of candidates"""
+    depth: int
+    # Reference entries cracked (duplicates in the reference count separately)
+    hits: int
+    # Distinct reference passwords cracked
+    unique_hits: int
+    # hits as a fraction of the reference size
+    fraction: float
+
+
+@dataclass
+class SimulationResult:
+    """Outcome of one simulation"""
+    reference_size: int
+    reference_unique: int
+    candidates: int = 0
+    hits: int = 0
+    unique_hits: int = 0
+    curve: List[HitPoint] = field(default_factory=list)
+
+    @property
+    def fraction(self) -> float:
+        """Fraction of the reference cracked by the whole stream"""
+        return self.hits / self.reference_size if self.reference_size else 0.0
+
+    def depth_for(self, fraction: float) -> Optional[int]:
+        """
+        Candidates needed to crack a fraction of the reference
+
+        Args:
+            fraction: Target fraction in [0, 1]
+
+        Returns:
+            Depth of the first curve point reaching it, or None if never reached
+        """
+        for point in self.curve:
+            if point.fraction >= fraction:
+                return point.depth
+        return None
+
+    def write_csv(self, out: IO) -> None:
+        """Write the curve as depth,hits,unique_hits,fraction rows"""
+        writer = csv.writer(out)
+        writer.writerow(["depth", "hits", "unique_hits", "fraction"])
+        for point in self.curve:
+            writer.writerow([point.depth, point.hits, point.unique_hits, f"{point.fraction:.6f}"])
+
+
+def load_reference(path: Path) -> Counter:
+    """
+    Read a reference list, counting repeated passwords
+
+    Args:
+        path: One password per line, optionally compressed
+
+    Returns:
+        Password -> occurrences
+    """
+    reference: Counter = Counter()
+    with open_text(Path(path)) as f:
+        for line in f:
+            password = line.rstrip("\r\n")
+            if password:
+                reference[password] += 1
+    if not reference:
+        raise ConfigError(f"Reference list is empty: {path}")
+    return reference
+
+
+def simulate(candidates: Iterable[str], reference: Counter, step: Optional[int] = None,
+             limit: Optional[int] = None) -> SimulationResult:
+    """
+    Replay candidates against a reference list
+
+    Args:
+        candidates: Candidate stream, in guessing order
+        reference: Result of load_reference
+        step: Record a curve point every `step` candidates (default: at every hit)
+        limit: Stop after this many candidates
+
+    Returns:
+        Totals and the cumulative hit curve (always ending at the last candidate)
+    """
+    if step is not None and step < 1:
+        raise ConfigError("step must be at least 1")
+
+    result = SimulationResult(sum(reference.values()), len(reference))
+    cracked: Set[str] = set()
+
+    def record():
+        result.curve.append(HitPoint(result.candidates, result.hits, result.unique_hits, result.fraction))
+
+    for candidate in candidates:
+        if limit is not None and result.candidates >= limit:
+            break
+        result.candidates += 1
+        hit = candidate in reference and candidate not in cracked
+        if hit:
+            cracked.add(candidate)
+            result.hits += reference[candidate]
+            result.unique_hits += 1
+        if (step is None and hit) or (step is not None and result.candidates % step == 0):
+            record()
+
+    if not result.curve or result.curve[-1].depth != result.candidates:
+        record()
+    return result
@@ -0,0 +1,200 @@
+"""
+Smart case variants
+
+Rather than every case permutation of a word (2^n of them), emit only
+the case patterns people actually use, most likely first: lowercase
+and capitalize-first dominate breach lists while all-caps is rare. The
+share of every pattern per length bucket comes from a table trained on
+breach lists; a default table ships in data/case_stats.json and
+`omni case-stats` trains one from any word list.
+"""
+
+import json
+import re
+from collections import Counter
+from functools import lru_cache
+from pathlib import Path
+from typing import Callable, Dict, Iterable, List, Optional, Tuple
+
+from .error import ConfigError
+
+
+def _capitalize(token: str) -> str:
+    lowered = token.lower()
+    for i, char in enumerate(lowered):
+        if char.isalpha():
+            return lowered[:i] + char.upper() + lowered[i + 1:]
+    return lowered
+
+
+def _last_upper(token: str) -> str:
+    lowered = token.lower()
+    for i in range(len(lowered) - 1, -1, -1):
+        if lowered[i].isalpha():
+            return lowered[:i] + lowered[i].upper() + lowered[i + 1:]
+    return lowered
+
+
+def _title(token: str) -> str:
+    return re.sub(r"[^\W\d_]+", lambda match: match.group(0).capitalize(), token)
+
+
+def _toggle(token: str) -> str:
+    chars, upper = [], False
+    for char in token:
+        if char.isalpha():
+            chars.append(char.upper() if upper else char.lower())
+            upper = not upper
+        else:
+            chars.append(char)
+    return "".join(chars)
+
+
+# Case patterns the table can refer to: name -> function producing it
+CASE_PATTERNS: Dict[str, Callable[[str], str]] = {
+    "lower": str.lower,
+    "upper": str.upper,
+    "capitalize": _capitalize,
+    "title": _title,
+    "last_upper": _last_upper,
+    "toggle": _toggle,
+}
+
+# Length buckets of trained tables
+DEFAULT_BUCKETS = ("1-4", "5-8", "9+")
+
+DEFAULT_TABLE_PATH = Path(__file__).parent / "data" / "case_stats.json"
+
+
+def classify_case(token: str) -> Optional[str]:
+    """
+    Case pattern of a token
+
+    Args:
+        token: Token as found in a breach list
+
+    Returns:
+        Name from CASE_PATTERNS, "other" for unrecognised mixes, or None
+        when the token has no cased letters
+    """
+    letters = [char for char in token if char.isalpha() and char.lower() != char.upper()]
+    if not letters:
+        return None
+    # Single-letter words are ambiguous; prefer the most common reading
+    for name in ("lower", "capitalize", "upper", "title", "last_upper", "toggle"):
+        if CASE_PATTERNS[name](token) == token:
+            return name
+    return "other"
+
+
+def _bucket_range(bucket: str) -> Tuple[int, float]:
+    try:
+        if bucket.endswith("+"):
+            return int(bucket[:-1]), float("inf")
+        low, _, high = bucket.partition("-")
+        return int(low), int(high or low)
+    except ValueError:
+        raise ConfigError(f"Invalid case statistics length bucket: {bucket!r} (expected e.g. 1-4 or 9+)")
+
+
+def length_bucket(length: int, buckets: Iterable[str]) -> Optional[str]:
+    """Bucket ("5-8", "9+", ...) covering a token length, None if none does"""
+    for bucket in buckets:
+        low, high = _bucket_range(bucket)
+        if low <= length <= high:
+            return bucket
+    return None
+
+
+def validate_case_stats(table: Dict) -> Dict:
+    """Check a case statistics table, returning it"""
+    by_length = table.get("by_length") if isinstance(table, dict) else None
+    if not isinstance(by_length, dict) or not by_length:
+        raise ConfigError("Case statistics need a non-empty by_length table")
+    for bucket, shares in by_length.items():
+        _bucket_range(bucket)
+        unknown = set(shares) - set(CASE_PATTERNS) - {"other"}
+        if unknown:
+            raise ConfigError(f"Unknown case patterns in bucket {bucket}: {', '.join(sorted(unknown))}")
+    return table
+
+
+@lru_cache(maxsize=8)
+def _load(path: str) -> Dict:
+    try:
+        return validate_case_stats(json.loads(Path(path).read_text(encoding="utf-8")))
+    except (OSError, ValueError) as e:
+        raise ConfigError(f"Cannot read case statistics {path}: {e}")
+
+
+def load_case_stats(path: Optional[str] = None) -> Dict:
+    """
+    Load a case statistics table
+
+    Args:
+        path: Table written by train_case_stats (the shipped table if omitted)
+
+    Returns:
+        Table with pattern shares per length bucket
+    """
+    return _load(str(path or DEFAULT_TABLE_PATH))
+
+
+def train_case_stats(words: Iterable[str], buckets: Iterable[str] = DEFAULT_BUCKETS,
+                     source: str = "") -> Dict:
+    """
+    Measure case pattern shares of a word list
+
+    Args:
+        words: Breach list or other sample of real passwords
+        buckets: Length buckets to keep apart
+        source: Description stored with the table
+
+    Returns:
+        Table for load_case_stats / case_variants; shares of each bucket
+        are over words with letters and include "other"
+    """
+    buckets = list(buckets)
+    counts = {bucket: Counter() for bucket in buckets}
+    for word in words:
+        pattern = classify_case(word)
+        bucket = length_bucket(len(word), buckets)
+        if pattern is not None and bucket is not None:
+            counts[bucket][pattern] += 1
+    by_length = {}
+    for bucket, counter in counts.items():
+        total = sum(counter.values())
+        by_length[bucket] = {name: round(count / total, 6) for name, count in counter.most_common()} if total else {}
+    return {"source": source, "by_length": by_length}
+
+
+def ranked_patterns(table: Dict, length: int) -> List[str]:
+    """Case patterns for a token length, most likely first"""
+    shares = table["by_length"].get(length_bucket(length, table["by_length"]), {})
+    return [name for name, _ in sorted(shares.items(), key=lambda item: -item[1]) if name in CASE_PATTERNS]
+
+
+def case_variants(token: str, count: int, table: Optional[Dict] = None) -> List[str]:
+    """
+    Most likely case variants of a token
+
+    Variants that coincide (digits, already-cased letters) count once,
+    so fewer than count may come back.
+
+    Args:
+        token: Base token
+        count: Variants wanted
+        table: Case statistics (the shipped table if omitted)
+
+    Returns:
+        Distinct variants, most likely first
+    """
+    table = table or load_case_stats()
+    variants = []
+    for name in ranked_patterns(table, len(token)):
+        variant = CASE_PATTERNS[name](token)
+        if variant not in variants:
+            variants.append(variant)
+            if len(variants) >= count:
+                break
+    return variants or [token]
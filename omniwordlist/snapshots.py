@@ -0,0 +1,134 @@
+"""
+Preset output snapshots
+
+Generates a bounded, seeded sample from presets and compares it with
+committed snapshot files, so changes to transforms, fields or filters
+that silently alter what a preset produces are caught.
+"""
+
+import copy
+import difflib
+import json
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Dict, Iterable, List, Optional
+
+from .error import PresetError
+from .generator import Generator
+from .presets import BUILTIN_PRESETS, PresetManager
+
+
+# Tokens sampled from the start of each preset
+SNAPSHOT_SIZE = 25
+
+# Seed used when a preset does not set one, so random transforms are reproducible
+SNAPSHOT_SEED = 1337
+
+# Snapshot directory of the repository's test suite
+DEFAULT_SNAPSHOT_DIR = Path("tests") / "snapshots" / "presets"
+
+
+@dataclass
+class SnapshotResult:
+    """Outcome of comparing one preset with its snapshot"""
+    preset: str
+    # "ok", "changed", "missing" or "error"
+    status: str
+    detail: str = ""
+    diff: List[str] = field(default_factory=list)
+
+    @property
+    def ok(self) -> bool:
+        return self.status == "ok"
+
+
+def take_snapshot(name: str, size: int = SNAPSHOT_SIZE,
+                  manager: Optional[PresetManager] = None) -> Dict:
+    """
+    Sample a preset
+
+    Args:
+        name: Preset name
+        size: Tokens to sample
+        manager: Preset manager (a default one if omitted)
+
+    Returns:
+        Snapshot with the preset name, its keyspace size and the tokens
+    """
+    config = copy.deepcopy((manager or PresetManager()).get_preset_config(name))
+    if config.seed is None:
+        config.seed = SNAPSHOT_SEED
+    generator = Generator(config)
+    return {
+        "preset": name,
+        "keyspace": generator.keyspace_size(),
+        "tokens": generator.generate_list(limit=size),
+    }
+
+
+def snapshot_path(directory: Path, name: str) -> Path:
+    """Snapshot file of a preset"""
+    return Path(directory) / f"{name}.json"
+
+
+def write_snapshot(directory: Path, snapshot: Dict) -> Path:
+    """Write a snapshot file, returning its path"""
+    path = snapshot_path(directory, snapshot["preset"])
+    path.parent.mkdir(parents=True, exist_ok=True)
+    path.write_text(json.dumps(snapshot, indent=2, ensure_ascii=False) + "\n", encoding="utf-8")
+    return path
+
+
+def _diff(expected: Dict, actual: Dict) -> List[str]:
+    lines = []
+    if expected.get("keyspace") != actual["keyspace"]:
+        lines.append(f"keyspace: {expected.get('keyspace')} -> {actual['keyspace']}")
+    lines.extend(difflib.unified_diff(expected.get("tokens", []), actual["tokens"],
+                                      "snapshot", "current", lineterm="", n=1))
+    return lines
+
+
+def verify_presets(directory: Path, names: Optional[Iterable[str]] = None,
+                   size: int = SNAPSHOT_SIZE, update: bool = False) -> List[SnapshotResult]:
+    """
+    Compare presets with their snapshots
+
+    Args:
+        directory: Directory holding <preset>.json snapshots
+        names: Presets to check (every builtin preset if omitted)
+        size: Tokens sampled per preset
+        update: Rewrite changed or missing snapshots instead of failing
+
+    Returns:
+        One result per preset
+    """
+    manager = PresetManager()
+    results = []
+    for name in names or BUILTIN_PRESETS:
+        try:
+            actual = take_snapshot(name, size, manager)
+        except Exception as e:
+            results.append(SnapshotResult(name, "error", f"{type(e).__name__}: {e}"))
+            continue
+
+        path = snapshot_path(directory, name)
+        if not path.exists():
+            expected = None
+        else:
+            try:
+                expected = json.loads(path.read_text(encoding="utf-8"))
+            except ValueError as e:
+                raise PresetError(f"Corrupt snapshot {path}: {e}")
+
+        if expected is not None and expected.get("keyspace") == actual["keyspace"] \
+                and expected.get("tokens") == actual["tokens"]:
+            results.append(SnapshotResult(name, "ok"))
+        elif update:
+            write_snapshot(directory, actual)
+            results.append(SnapshotResult(name, "ok", f"updated {path}"))
+        elif expected is None:
+            results.append(SnapshotResult(name, "missing", f"no snapshot at {path}"))
+        else:
+            results.append(SnapshotResult(name, "changed", f"output differs from {path}",
+                                          _diff(expected, actual)))
+    return results
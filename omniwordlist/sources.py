@@ -0,0 +1,407 @@
+"""
+External word sources
+
+Dictionaries, denylists and charset files referenced from configs.
+Files are read once per process and cached by path and mtime.
+
+Sources may also be http(s) URLs. They are downloaded into a local
+cache directory and revalidated with ETag / Last-Modified, so centrally
+maintained wordlists stay in sync without re-downloading unchanged
+files. A cached copy is used when the server cannot be reached, and
+interrupted downloads of large lists resume with ranged requests.
+Lists fetched from SecLists are referenced by name
+("seclists:Passwords/xato-net-10-million", see seclists).
+
+Word lists can also be streamed from stdin ("-") or a named pipe so
+other tools can feed values in without intermediate files. Streams
+are consumed once and kept in memory for the rest of the process.
+
+iter_wordlist is the exception for input wordlists of mutation mode,
+which can be far larger than memory: it streams lines without keeping
+or caching them.
+"""
+
+import hashlib
+import http.client
+import json
+import re
+import stat
+import sys
+import urllib.error
+import urllib.parse
+import urllib.request
+import warnings
+from pathlib import Path
+from typing import Dict, Iterator, List, Optional, Sequence, Tuple
+from .error import ConfigError, StorageError
+from .frequency import FrequencyModel, is_frequency_model
+from .dictionary import MMAP_MIN_BYTES, MmapDictionary
+from .locking import atomic_write_json
+from .opsec import require_disk
+from .seclists import is_seclists_source, resolve_list
+from .storage import open_binary
+
+
+REMOTE_SCHEMES = ("http://", "https://")
+
+# Connections tried per download, each resuming where the last one stopped
+FETCH_ATTEMPTS = 3
+
+# Source reference for standard input
+STDIN_SOURCE = "-"
+
+_cache: Dict[Tuple[str, str], Tuple[float, object]] = {}
+
+# URLs already revalidated (and SecLists names already verified) in this process,
+# mapped to their local file
+_resolved_urls: Dict[str, Path] = {}
+
+# Word lists read from stdin or named pipes (a stream can only be read once)
+_streams: Dict[str, List[str]] = {}
+
+
+def is_remote(path: str) -> bool:
+    """Check whether a source reference is a URL"""
+    return str(path).lower().startswith(REMOTE_SCHEMES)
+
+
+def is_stream(path: str) -> bool:
+    """Check whether a source reference is stdin or a named pipe"""
+    if path == STDIN_SOURCE:
+        return True
+    if is_remote(path):
+        return False
+    try:
+        return stat.S_ISFIFO(Path(path).expanduser().stat().st_mode)
+    except OSError:
+        return False
+
+
+def default_cache_dir() -> Path:
+    """Directory remote sources are cached in"""
+    return Path.home() / ".omniwordlist" / "cache" / "sources"
+
+
+def cache_key(url: str) -> str:
+    """
+    Cache file name of a URL
+    
+    The suffixes of the remote file name are kept so compression and
+    format can still be detected from the cached copy.
+    """
+    key = hashlib.sha256(url.encode()).hexdigest()[:16]
+    name = urllib.parse.urlsplit(url).path.rsplit("/", 1)[-1]
+    suffixes = "".join(re.findall(r"\.[A-Za-z0-9]{1,8}", name)[-2:])
+    return key + suffixes
+
+
+def _resumable(validator: Optional[str]) -> bool:
+    """Whether a partial download can be resumed with If-Range (needs a strong validator)"""
+    return bool(validator) and not validator.startswith("W/")
+
+
+class StaleSourceWarning(UserWarning):
+    """A remote source could not be revalidated and its cached copy is used"""
+    pass
+
+
+def _stale_copy(url: str, path: Path, error) -> Path:
+    """Warn that a source could not be revalidated and return its cached copy"""
+    warnings.warn(f"Could not revalidate {url} ({error}); using the cached copy {path}, which may be stale",
+                  StaleSourceWarning, stacklevel=3)
+    return path
+
+
+def fetch_remote(url: str, cache_dir: Optional[Path] = None, timeout: float = 60,
+                 attempts: int = FETCH_ATTEMPTS) -> Path:
+    """
+    Download a remote source into the cache, revalidating an existing copy
+    
+    Interrupted downloads are kept as a .part file and resumed with a
+    ranged request, within this call and by later calls, as long as the
+    server still reports the same ETag / Last-Modified; any other failure
+    removes the .part file. When the server cannot be reached the cached
+    copy is used with a StaleSourceWarning, but a source the server
+    reports gone (404 / 410) fails rather than serving the stale copy.
+    
+    Args:
+        url: http(s) URL
+        cache_dir: Override for the cache directory
+        timeout: Request timeout in seconds
+        attempts: Connections tried before giving up
+        
+    Returns:
+        Path of the cached file
+        
+    Raises:
+        StorageError: If the source is gone, or unreachable without a cached copy
+    """
+    cache_dir = cache_dir or default_cache_dir()
+    require_disk("cache", f"{url} into the source cache")
+    name = cache_key(url)
+    key = name[:16]
+    path = cache_dir / name
+    meta_path = cache_dir / f"{key}.json"
+    tmp_path = cache_dir / f"{key}.part"
+    tmp_meta_path = cache_dir / f"{key}.part.json"
+    
+    meta = {}
+    if path.exists() and meta_path.exists():
+        meta = json.loads(meta_path.read_text())
+    
+    cache_dir.mkdir(parents=True, exist_ok=True)
+    error = None
+    # Set when the last attempt dropped mid-transfer with a partial copy a later call can resume
+    keep_partial = False
+    try:
+        for _ in range(max(1, attempts)):
+            request = urllib.request.Request(url)
+            if meta.get("etag"):
+                request.add_header("If-None-Match", meta["etag"])
+            if meta.get("last_modified"):
+                request.add_header("If-Modified-Since", meta["last_modified"])
+            
+            offset = 0
+            if tmp_path.exists() and tmp_meta_path.exists():
+                validator = json.loads(tmp_meta_path.read_text()).get("validator")
+                offset = tmp_path.stat().st_size if _resumable(validator) else 0
+                if offset:
+                    request.add_header("Range", f"bytes={offset}-")
+                    request.add_header("If-Range", validator)
+            
+            try:
+                with urllib.request.urlopen(request, timeout=timeout) as response:
+                    headers = response.headers
+                    if response.status == 206:
+                        if not headers.get("Content-Range", "").startswith(f"bytes {offset}-"):
+                            raise StorageError(f"Unexpected range from {url}: {headers.get('Content-Range')}")
+                    else:
+                        offset = 0
+                    validator = headers.get("ETag") or headers.get("Last-Modified")
+                    atomic_write_json(tmp_meta_path, {"url": url, "validator": validator})
+                    expected = headers.get("Content-Length")
+                    received = 0
+                    with open(tmp_path, 'ab' if offset else 'wb') as out:
+                        while True:
+                            chunk = response.read(1 << 16)
+                            if not chunk:
+                                break
+                            out.write(chunk)
+                            received += len(chunk)
+                    if expected is not None and received < int(expected):
+                        raise ConnectionError(f"connection closed after {received} of {expected} bytes")
+                break
+            except urllib.error.HTTPError as e:
+                keep_partial = False
+                if e.code == 304:
+                    return path
+                if e.code == 416:
+                    # The partial copy no longer matches the remote file: start over
+                    tmp_path.unlink(missing_ok=True)
+                    error = f"HTTP {e.code}"
+                    continue
+                if e.code in (404, 410):
+                    stale = f" (not using the stale cached copy {path})" if meta else ""
+                    raise StorageError(f"Failed to fetch {url}: HTTP {e.code}{stale}")
+                if meta:
+                    return _stale_copy(url, path, f"HTTP {e.code}")
+                raise StorageError(f"Failed to fetch {url}: HTTP {e.code}")
+            except StorageError as e:
+                tmp_path.unlink(missing_ok=True)
+                error = e
+                keep_partial = False
+            except (OSError, http.client.HTTPException) as e:
+                # Dropped connection: retry from what was received so far
+                error = e
+                keep_partial = (tmp_path.exists() and tmp_meta_path.exists()
+                                and _resumable(json.loads(tmp_meta_path.read_text()).get("validator")))
+        else:
+            # Offline: fall back to the last good copy
+            if meta:
+                return _stale_copy(url, path, error)
+            raise StorageError(f"Failed to fetch {url}: {error}")
+        
+        tmp_path.replace(path)
+        keep_partial = False
+    finally:
+        if not keep_partial:
+            tmp_path.unlink(missing_ok=True)
+            tmp_meta_path.unlink(missing_ok=True)
+    
+    atomic_write_json(meta_path, {
+        "url": url,
+        "etag": headers.get("ETag"),
+        "last_modified": headers.get("Last-Modified"),
+    })
+    return path
+
+
+def resolve_source(path: str, cache_dir: Optional[Path] = None) -> Path:
+    """
+    Turn a source reference into a local file path
+    
+    URLs are fetched (or revalidated) once per process. "seclists:<name>"
+    refers to a list fetched with `omni seclists fetch` (see seclists).
+    
+    Args:
+        path: File path, http(s) URL or SecLists name
+        cache_dir: Override for the remote cache directory
+        
+    Returns:
+        Local path
+    """
+    if is_seclists_source(path):
+        if path not in _resolved_urls:
+            _resolved_urls[path] = resolve_list(path)
+        return _resolved_urls[path]
+    if not is_remote(path):
+        return Path(path).expanduser()
+    if path not in _resolved_urls:
+        _resolved_urls[path] = fetch_remote(path, cache_dir)
+    return _resolved_urls[path]
+
+
+def _cached(path: str, kind: str, loader):
+    """Load a file through the mtime-keyed cache"""
+    file_path = resolve_source(path)
+    try:
+        mtime = file_path.stat().st_mtime
+    except OSError as e:
+        raise ConfigError(f"Cannot read {kind} {path}: {e.strerror}")
+    
+    key = (str(file_path), kind)
+    cached = _cache.get(key)
+    if cached is not None and cached[0] == mtime:
+        return cached[1]
+    
+    value = loader(file_path)
+    _cache[key] = (mtime, value)
+    return value
+
+
+def read_word_file(path: str) -> List[str]:
+    """
+    Read a word list (one word per line, blank lines skipped)
+    
+    Args:
+        path: Path to the file, "-" for stdin, or a named pipe
+        
+    Returns:
+        Words in file order (frequency order for frequency models)
+    """
+    def lines(f) -> List[str]:
+        return [line.rstrip('\r\n') for line in f if line.strip()]
+    
+    def load(file_path: Path) -> List[str]:
+        if is_frequency_model(file_path):
+            with FrequencyModel(file_path) as model:
+                return model.words()
+        with open(file_path, 'r', encoding='utf-8', errors='ignore') as f:
+            return lines(f)
+    
+    if is_stream(path):
+        if path not in _streams:
+            if path == STDIN_SOURCE:
+                _streams[path] = lines(sys.stdin)
+            else:
+                with open(Path(path).expanduser(), 'r', encoding='utf-8', errors='ignore') as f:
+                    _streams[path] = lines(f)
+        return _streams[path]
+    
+    return _cached(path, "word list", load)
+
+
+def load_dictionary(path: str) -> Sequence[str]:
+    """
+    Open a word list for the field engine
+    
+    Large local files are memory-mapped (see dictionary.MmapDictionary);
+    everything else is read like read_word_file.
+    
+    Args:
+        path: Path, URL, "-" or named pipe
+        
+    Returns:
+        Sequence of words in file order
+    """
+    if is_stream(path):
+        return read_word_file(path)
+    
+    def load(file_path: Path) -> Sequence[str]:
+        if file_path.stat().st_size < MMAP_MIN_BYTES or is_frequency_model(file_path):
+            return read_word_file(str(file_path))
+        return MmapDictionary(file_path)
+    
+    return _cached(path, "dictionary", load)
+
+
+def iter_wordlist(path: str, skipped: Optional[Dict[str, int]] = None) -> Iterator[str]:
+    """
+    Stream a word list line by line without loading it into memory
+    
+    Blank lines and lines that are not valid UTF-8 are skipped and
+    counted in skipped["blank"] and skipped["invalid_utf8"].
+    
+    Args:
+        path: File (optionally compressed), URL, SecLists name, named pipe or "-" for stdin
+        skipped: Counters updated as lines are skipped
+        
+    Yields:
+        Lines without their line ending
+    """
+    skipped = skipped if skipped is not None else {}
+    f = sys.stdin.buffer if path == STDIN_SOURCE else open_binary(resolve_source(path))
+    try:
+        for line in f:
+            line = line.rstrip(b"\r\n")
+            if not line:
+                skipped["blank"] = skipped.get("blank", 0) + 1
+                continue
+            try:
+                yield line.decode("utf-8")
+            except UnicodeDecodeError:
+                skipped["invalid_utf8"] = skipped.get("invalid_utf8", 0) + 1
+    finally:
+        if f is not sys.stdin.buffer:
+            f.close()
+
+
+def count_lines(path: str) -> Optional[int]:
+    """
+    Number of lines of a word list, blank ones included
+    
+    Args:
+        path: As for iter_wordlist
+        
+    Returns:
+        Line count, or None for stdin and named pipes (they can only be read once)
+    """
+    if is_stream(path):
+        return None
+    count = 0
+    last = b"\n"
+    with open_binary(resolve_source(path)) as f:
+        for block in iter(lambda: f.read(1 << 20), b""):
+            count += block.count(b"\n")
+            last = block[-1:]
+    # A final line without a newline
+    return count + (last != b"\n")
+
+
+def read_charset_file(path: str) -> str:
+    """
+    Read a charset file (its contents without the trailing newline)
+    
+    Args:
+        path: Path to the file
+        
+    Returns:
+        Charset string
+    """
+    def load(file_path: Path) -> str:
+        charset = file_path.read_text(encoding='utf-8').rstrip('\r\n')
+        if not charset:
+            raise ConfigError(f"Charset file is empty: {file_path}")
+        return charset
+    
+    return _cached(path, "charset file", load)
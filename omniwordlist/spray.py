@@ -0,0 +1,106 @@
+"""
+Password-spray scheduling
+
+Spraying tries a few likely passwords against many accounts while
+staying under the domain lockout policy. The scheduler cuts the
+ordered candidate stream into batches of attempts per observation
+window and dates each batch so it can be run no earlier than the
+time the previous window's failed attempts have expired.
+"""
+
+from dataclasses import dataclass
+from datetime import datetime, timedelta
+from typing import Iterable, Iterator, List, Optional
+from .error import ConfigError
+
+
+@dataclass
+class SprayPolicy:
+    """
+    Lockout policy the schedule must respect
+    
+    Attributes:
+        lockout_threshold: Failed attempts that lock an account (0 = no lockout)
+        window_hours: Observation window after which failed attempts reset
+        safety_margin: Attempts kept in reserve below the threshold
+        attempts_per_window: Explicit attempts per window (overrides the threshold)
+    """
+    lockout_threshold: int = 5
+    window_hours: float = 24.0
+    safety_margin: int = 2
+    attempts_per_window: Optional[int] = None
+    
+    def validate(self):
+        """Validate the policy"""
+        if self.window_hours <= 0:
+            raise ConfigError("Spray window must be positive")
+        if self.attempts_per_window is not None and self.attempts_per_window < 1:
+            raise ConfigError("attempts_per_window must be at least 1")
+        if self.lockout_threshold < 0 or self.safety_margin < 0:
+            raise ConfigError("lockout_threshold and safety_margin must not be negative")
+        if self.attempts_per_window is None and self.lockout_threshold and \
+                self.lockout_threshold <= self.safety_margin:
+            raise ConfigError(
+                f"safety_margin ({self.safety_margin}) leaves no attempts below "
+                f"lockout_threshold ({self.lockout_threshold})"
+            )
+    
+    def attempts(self) -> int:
+        """Attempts per user allowed in each window"""
+        if self.attempts_per_window is not None:
+            return self.attempts_per_window
+        if not self.lockout_threshold:
+            # No lockout: still spray one password per window unless told otherwise
+            return 1
+        return self.lockout_threshold - self.safety_margin
+    
+    @property
+    def window(self) -> timedelta:
+        return timedelta(hours=self.window_hours)
+
+
+@dataclass
+class SprayBatch:
+    """Passwords to spray against every user in one window"""
+    index: int
+    not_before: datetime
+    passwords: List[str]
+
+
+def schedule_spray(candidates: Iterable[str], policy: SprayPolicy, start: datetime,
+                   max_batches: Optional[int] = None) -> Iterator[SprayBatch]:
+    """
+    Cut an ordered candidate stream into dated spray batches
+    
+    Candidates are taken in order, so the most likely passwords land
+    in the earliest windows. Duplicates are skipped.
+    
+    Args:
+        candidates: Candidate passwords, most likely first
+        policy: Lockout policy
+        start: Time the first batch may run
+        max_batches: Stop after this many batches
+    
+    Yields:
+        Batches, one per observation window
+    """
+    policy.validate()
+    size = policy.attempts()
+    seen = set()
+    batch: List[str] = []
+    index = 0
+    
+    for candidate in candidates:
+        if candidate in seen:
+            continue
+        seen.add(candidate)
+        batch.append(candidate)
+        if len(batch) == size:
+            yield SprayBatch(index, start + policy.window * index, batch)
+            index += 1
+            batch = []
+            if max_batches is not None and index >= max_batches:
+                return
+    
+    if batch:
+        yield SprayBatch(index, start + policy.window * index, batch)
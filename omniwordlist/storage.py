@@ -6,16 +6,226 @@ Handles checkpointing, output writing, compression, and metadata
 
 import gzip
 import bz2
+import io
+import itertools
 import json
+import os
 from pathlib import Path
-from typing import Iterator, Optional
+from typing import Callable, Dict, Iterable, Iterator, List, Optional, Tuple
 from .error import StorageError
+from .locking import locked_read_json, locked_write_json
+from .opsec import require_disk
 
 
-class OutputWriter:
+# File name suffix of each compression format
+COMPRESSION_SUFFIXES = {"gzip": ".gz", "bzip2": ".bz2", "lz4": ".lz4", "zstd": ".zst"}
+
+# Output encodings: UTF-8, UTF-16LE text, or UTF-16LE bytes as hex lines
+OUTPUT_ENCODINGS = ("utf-8", "utf-16le", "utf-16le-hex")
+
+
+def detect_compression(path: Path) -> Optional[str]:
+    """Compression format of a file, judged by its suffix"""
+    suffix = Path(path).suffix.lower()
+    for compression, known in COMPRESSION_SUFFIXES.items():
+        if suffix == known:
+            return compression
+    return None
+
+
+def open_text(path: Path, compression: Optional[str] = None):
+    """
+    Open a (possibly compressed) output file for reading as text
+    
+    Args:
+        path: File path
+        compression: Compression format (detected from the suffix if omitted)
+        
+    Returns:
+        Text file object
+    """
+    compression = compression or detect_compression(path)
+    try:
+        if compression == "gzip":
+            return gzip.open(path, 'rt', encoding='utf-8', errors='ignore')
+        if compression == "bzip2":
+            return bz2.open(path, 'rt', encoding='utf-8', errors='ignore')
+        if compression == "lz4":
+            try:
+                import lz4.frame
+            except ImportError:
+                raise StorageError("lz4 compression requires lz4 package")
+            return lz4.frame.open(path, 'rt', encoding='utf-8', errors='ignore')
+        if compression == "zstd":
+            try:
+                import zstandard as zstd
+            except ImportError:
+                raise StorageError("zstd compression requires zstandard package")
+            # Resumed runs append a frame per run
+            reader = zstd.ZstdDecompressor().stream_reader(open(path, 'rb'), read_across_frames=True)
+            return io.TextIOWrapper(reader, encoding='utf-8', errors='ignore')
+        return open(path, 'r', encoding='utf-8', errors='ignore')
+    except OSError as e:
+        raise StorageError(f"Cannot read {path}: {e.strerror}")
+
+
+def open_binary(path: Path, compression: Optional[str] = None):
+    """
+    Open a (possibly compressed) file for reading as bytes
+    
+    Args:
+        path: File path
+        compression: Compression format (detected from the suffix if omitted)
+        
+    Returns:
+        Binary file object that iterates over lines
+    """
+    compression = compression or detect_compression(path)
+    try:
+        if compression == "gzip":
+            return gzip.open(path, 'rb')
+        if compression == "bzip2":
+            return bz2.open(path, 'rb')
+        if compression == "lz4":
+            try:
+                import lz4.frame
+            except ImportError:
+                raise StorageError("lz4 compression requires lz4 package")
+            return lz4.frame.open(path, 'rb')
+        if compression == "zstd":
+            try:
+                import zstandard as zstd
+            except ImportError:
+                raise StorageError("zstd compression requires zstandard package")
+            reader = zstd.ZstdDecompressor().stream_reader(open(path, 'rb'), read_across_frames=True)
+            return io.BufferedReader(reader)
+        return open(path, 'rb')
+    except OSError as e:
+        raise StorageError(f"Cannot read {path}: {e.strerror}")
+
+
+def inspect_output(path: Path, compression: Optional[str] = None) -> Tuple[int, Optional[str], int]:
+    """
+    Count the complete lines of an output file and find the last one
+    
+    A line is complete once its newline is written, so a line cut short
+    by a crash is not counted. Compressed streams that break off (a run
+    killed mid-write) count the lines before the break.
+    
+    Args:
+        path: Output file
+        compression: Compression format (detected from the suffix if omitted)
+        
+    Returns:
+        (complete lines, last complete line or None, uncompressed bytes
+        up to the end of it)
+    """
+    lines, last, complete, offset = 0, None, 0, 0
+    pending = b""
+    with open_binary(path, compression) as f:
+        # read1 returns what is decompressed so far; read drops it when the stream breaks off
+        read = getattr(f, "read1", f.read)
+        while True:
+            try:
+                block = read(1 << 20)
+            except EOFError:
+                break
+            if not block:
+                break
+            # pending is the unfinished line before this block
+            buffer, start = pending + block, offset - len(pending)
+            offset += len(block)
+            end = buffer.rfind(b"\n")
+            if end < 0:
+                pending = buffer
+                continue
+            lines += block.count(b"\n")
+            last = buffer[buffer.rfind(b"\n", 0, end) + 1:end]
+            complete = start + end + 1
+            pending = buffer[end + 1:]
+    return lines, (last.decode("utf-8", errors="replace") if last is not None else None), complete
+
+
+def keep_lines(path: Path, lines: int, compression: Optional[str] = None):
+    """
+    Cut a compressed output file after its first lines
+    
+    Compressed streams cannot be truncated in place, and one cut off
+    mid-write cannot be appended to: the kept lines are recompressed
+    into a new file that replaces the old one.
+    
+    Args:
+        path: Output file
+        lines: Lines to keep
+        compression: Compression format (detected from the suffix if omitted)
+    """
+    path = Path(path)
+    compression = compression or detect_compression(path)
+    temporary = path.with_name(f".{path.name}.recovering")
+    with open_binary(path, compression) as f:
+        kept = (line.rstrip(b"\n").decode("utf-8", errors="replace") for line in itertools.islice(f, lines))
+        write_tokens_to_file(kept, temporary, compression)
+    os.replace(temporary, path)
+
+
+class StorageBackend:
+    """
+    Destination of generated tokens
+    
+    Subclass to send output somewhere other than a local file (object
+    storage, a database, a message queue) and register a factory with
+    register_storage_backend so configs can select it by name.
+    """
+    
+    def open(self):
+        """Prepare the destination before the first token"""
+        pass
+    
+    def write_token(self, token: str, metadata: dict = None):
+        """
+        Store one token
+        
+        Args:
+            token: Token to store
+            metadata: Optional metadata (e.g. provenance tags)
+        """
+        raise NotImplementedError
+    
+    def finalize(self):
+        """Flush and release the destination after the last token"""
+        pass
+    
+    def flush(self):
+        """Push buffered tokens to the destination (called before checkpoints)"""
+        pass
+    
+    def __enter__(self):
+        self.open()
+        return self
+    
+    def __exit__(self, exc_type, exc_val, exc_tb):
+        self.finalize()
+
+
+def encode_token(token: str, encoding: str = "utf-8") -> str:
+    """
+    Token as written under an output encoding
+    
+    utf-16le-hex turns the token into the hex of its UTF-16LE bytes (the
+    form NTLM tooling expects); the other encodings keep the token and
+    only change how the file is encoded.
+    """
+    if encoding == "utf-16le-hex":
+        return token.encode("utf-16-le").hex()
+    return token
+
+
+class OutputWriter(StorageBackend):
     """Base output writer"""
     
-    def __init__(self, path: Path, compression: Optional[str] = None, format: str = "txt"):
+    def __init__(self, path: Path, compression: Optional[str] = None, format: str = "txt",
+                 encoding: str = "utf-8", plain_path: Optional[Path] = None, append: bool = False,
+                 token_stats: bool = False):
         """
         Initialize output writer
         
@@ -23,10 +233,22 @@ class OutputWriter:
             path: Output file path
             compression: Compression format (gzip, bzip2, lz4, zstd)
             format: Output format (txt, jsonl, csv)
+            encoding: Output encoding (see OUTPUT_ENCODINGS)
+            plain_path: Also write the tokens unencoded (UTF-8) to this file
+            append: Continue an existing file (resumed runs); compressed
+                output gets a new stream appended
+            token_stats: Add the tokenstats columns to CSV and JSONL records
         """
+        if encoding not in OUTPUT_ENCODINGS:
+            raise StorageError(f"Unknown output encoding: {encoding} (known: {', '.join(OUTPUT_ENCODINGS)})")
         self.path = path
         self.compression = compression
         self.format = format
+        self.encoding = encoding
+        self.file_encoding = "utf-16-le" if encoding == "utf-16le" else "utf-8"
+        self.append = append
+        self.token_stats = token_stats
+        self.plain = OutputWriter(plain_path, compression, format, append=append) if plain_path else None
         self.file_handle = None
         self.bytes_written = 0
         self.lines_written = 0
@@ -36,30 +258,42 @@ class OutputWriter:
         # Ensure parent directory exists
         self.path.parent.mkdir(parents=True, exist_ok=True)
         
+        appending = self.append and self.path.exists()
+        mode = 'a' if appending else 'w'
+        
         # Open with appropriate compression
         if self.compression == "gzip":
-            self.file_handle = gzip.open(self.path, 'wt', encoding='utf-8')
+            self.file_handle = gzip.open(self.path, mode + 't', encoding=self.file_encoding)
         elif self.compression == "bzip2":
-            self.file_handle = bz2.open(self.path, 'wt', encoding='utf-8')
+            self.file_handle = bz2.open(self.path, mode + 't', encoding=self.file_encoding)
         elif self.compression == "lz4":
             try:
                 import lz4.frame
-                self.file_handle = lz4.frame.open(self.path, 'wt', encoding='utf-8')
+                self.file_handle = lz4.frame.open(self.path, mode + 't', encoding=self.file_encoding)
             except ImportError:
                 raise StorageError("lz4 compression requires lz4 package")
         elif self.compression == "zstd":
             try:
                 import zstandard as zstd
                 cctx = zstd.ZstdCompressor()
-                self.file_handle = cctx.stream_writer(open(self.path, 'wb'))
+                self.file_handle = cctx.stream_writer(open(self.path, mode + 'b'))
             except ImportError:
                 raise StorageError("zstd compression requires zstandard package")
         else:
-            self.file_handle = open(self.path, 'w', encoding='utf-8')
+            self.file_handle = open(self.path, mode, encoding=self.file_encoding)
+            if appending:
+                self.bytes_written = self.path.stat().st_size
         
         # Write CSV header if needed
-        if self.format == "csv":
-            self._write_line("token,entropy,length")
+        if self.format == "csv" and not appending:
+            columns = ["token", "entropy", "length"]
+            if self.token_stats:
+                from .tokenstats import STATS_COLUMNS
+                columns += STATS_COLUMNS
+            self._write_line(",".join(columns) + "\n")
+        
+        if self.plain is not None:
+            self.plain.open()
     
     def write(self, token: str, metadata: dict = None):
         """
@@ -72,6 +306,15 @@ class OutputWriter:
         if not self.file_handle:
             raise StorageError("Output file not opened")
         
+        if self.plain is not None:
+            self.plain.write(token, metadata)
+        stats = None
+        if self.token_stats and self.format in ("csv", "jsonl"):
+            from .tokenstats import token_stats
+            # Statistics describe the token, not its pre-encoded form
+            stats = token_stats(token)
+        token = encode_token(token, self.encoding)
+        
         if self.format == "txt":
             line = token + "\n"
         elif self.format == "jsonl":
@@ -81,27 +324,46 @@ class OutputWriter:
                 "entropy": calculate_entropy(token),
                 "length": len(token)
             }
+            if stats:
+                data.update(stats)
             if metadata:
                 data.update(metadata)
             line = json.dumps(data) + "\n"
         elif self.format == "csv":
             from .filters import calculate_entropy
             entropy = calculate_entropy(token)
-            line = f'"{token}",{entropy},{len(token)}\n'
+            quoted = token.replace('"', '""')
+            line = f'"{quoted}",{entropy},{len(token)}'
+            if stats:
+                line += "".join(f',"{value}"' if isinstance(value, str) else f",{value}"
+                                for value in stats.values())
+            line += "\n"
         else:
             line = token + "\n"
         
         self._write_line(line)
     
+    def write_token(self, token: str, metadata: dict = None):
+        self.write(token, metadata)
+    
+    def finalize(self):
+        self.close()
+    
+    def flush(self):
+        if self.file_handle:
+            self.file_handle.flush()
+        if self.plain is not None:
+            self.plain.flush()
+    
     def _write_line(self, line: str):
         """Internal method to write line"""
         if self.compression == "zstd":
             # zstd needs bytes
-            self.file_handle.write(line.encode('utf-8'))
+            self.file_handle.write(line.encode(self.file_encoding))
         else:
             self.file_handle.write(line)
         
-        self.bytes_written += len(line.encode('utf-8'))
+        self.bytes_written += len(line.encode(self.file_encoding))
         self.lines_written += 1
     
     def close(self):
@@ -115,15 +377,144 @@ class OutputWriter:
                     pass
             self.file_handle.close()
             self.file_handle = None
+        if self.plain is not None:
+            self.plain.close()
+
+
+def split_part_path(path: Path, part: int) -> Path:
+    """Path of one part of split output: list.txt.gz -> list.part0001.txt.gz"""
+    stem, dot, suffixes = path.name.partition(".")
+    return path.with_name(f"{stem}.part{part:04d}{dot}{suffixes}")
+
+
+class SplitWriter(StorageBackend):
+    """Writes output into numbered part files of bounded size (crunch -b / -c)"""
     
-    def __enter__(self):
-        """Context manager entry"""
-        self.open()
-        return self
+    def __init__(self, path: Path, compression: Optional[str] = None, format: str = "txt",
+                 split_bytes: Optional[int] = None, split_lines: Optional[int] = None, **options):
+        """
+        Initialize a split writer
+        
+        Args:
+            path: Output file path; parts are named after it (see split_part_path)
+            compression: Compression format of each part
+            format: Output format of each part
+            split_bytes: Start a new part once a part has this many uncompressed
+                bytes, so a part exceeds it by less than one line
+            split_lines: Start a new part after this many lines
+            **options: OutputWriter options of each part (encoding, token_stats)
+        """
+        if not split_bytes and not split_lines:
+            raise StorageError("Split output needs split_bytes or split_lines")
+        if options.get("plain_path"):
+            raise StorageError("plain_output cannot be split")
+        self.path = Path(path)
+        self.compression = compression
+        self.format = format
+        self.split_bytes = split_bytes
+        self.split_lines = split_lines
+        self.options = options
+        self.parts: List[OutputWriter] = []
+        self.current: Optional[OutputWriter] = None
+        # Tokens in the current part (a CSV header is not one)
+        self.part_lines = 0
+        self.lines_written = 0
     
-    def __exit__(self, exc_type, exc_val, exc_tb):
-        """Context manager exit"""
+    @property
+    def bytes_written(self) -> int:
+        """Uncompressed bytes written across all parts"""
+        return sum(part.bytes_written for part in self.parts)
+    
+    def open(self):
+        """Open the first part"""
+        self._next_part()
+    
+    def _next_part(self):
+        if self.current is not None:
+            self.current.close()
+        self.current = OutputWriter(split_part_path(self.path, len(self.parts) + 1),
+                                    self.compression, self.format, **self.options)
+        self.current.open()
+        self.parts.append(self.current)
+        self.part_lines = 0
+    
+    def _full(self) -> bool:
+        part = self.current
+        return bool((self.split_bytes and part.bytes_written >= self.split_bytes)
+                    or (self.split_lines and self.part_lines >= self.split_lines))
+    
+    def write(self, token: str, metadata: dict = None):
+        """
+        Write a token, starting a new part when the current one is full
+        
+        Args:
+            token: Token to write
+            metadata: Optional metadata
+        """
+        if self._full():
+            self._next_part()
+        self.current.write(token, metadata)
+        self.part_lines += 1
+        self.lines_written += 1
+    
+    def write_token(self, token: str, metadata: dict = None):
+        self.write(token, metadata)
+    
+    def flush(self):
+        if self.current is not None:
+            self.current.flush()
+    
+    def finalize(self):
         self.close()
+    
+    def close(self):
+        """Close the current part"""
+        if self.current is not None:
+            self.current.close()
+            self.current = None
+
+
+# Storage backends selectable with Config.storage_backend:
+# name -> factory(path, compression, format, **options)
+STORAGE_BACKENDS: Dict[str, Callable[..., StorageBackend]] = {"file": OutputWriter}
+
+
+def register_storage_backend(name: str, factory: Callable[..., StorageBackend]):
+    """
+    Make a storage backend selectable by name
+    
+    Args:
+        name: Value of Config.storage_backend selecting it
+        factory: Called as factory(path, compression, format, **options) to create
+            the backend; options are only passed when the config sets them
+    """
+    STORAGE_BACKENDS[name] = factory
+
+
+def create_storage_backend(name: str, path: Optional[Path], compression: Optional[str] = None,
+                           format: str = "txt", **options) -> StorageBackend:
+    """
+    Create a registered storage backend
+    
+    Args:
+        name: Registered backend name
+        path: Output location (meaning is up to the backend)
+        compression: Compression format
+        format: Output format
+        **options: Backend options such as encoding and plain_path of OutputWriter
+        
+    Returns:
+        Unopened backend
+    """
+    factory = STORAGE_BACKENDS.get(name)
+    if factory is None:
+        raise StorageError(f"Unknown storage backend: {name} (known: {', '.join(sorted(STORAGE_BACKENDS))})")
+    return factory(path, compression, format, **options)
+
+
+# Checkpoint status: written periodically while running, or paused by cancellation
+CHECKPOINT_RUNNING = "running"
+CHECKPOINT_PAUSED = "paused"
 
 
 class CheckpointManager:
@@ -136,6 +527,7 @@ class CheckpointManager:
         Args:
             checkpoint_dir: Directory for checkpoint files
         """
+        require_disk("checkpoints", f"checkpoints to {checkpoint_dir}")
         self.checkpoint_dir = checkpoint_dir
         self.checkpoint_dir.mkdir(parents=True, exist_ok=True)
     
@@ -148,8 +540,7 @@ class CheckpointManager:
             state: State dictionary to save
         """
         checkpoint_path = self.checkpoint_dir / f"{job_id}.checkpoint.json"
-        with open(checkpoint_path, 'w') as f:
-            json.dump(state, f, indent=2)
+        locked_write_json(checkpoint_path, state)
     
     def load_checkpoint(self, job_id: str) -> Optional[dict]:
         """
@@ -165,8 +556,7 @@ class CheckpointManager:
         if not checkpoint_path.exists():
             return None
         
-        with open(checkpoint_path, 'r') as f:
-            return json.load(f)
+        return locked_read_json(checkpoint_path)
     
     def delete_checkpoint(self, job_id: str):
         """
@@ -201,3 +591,44 @@ def write_tokens_to_file(tokens: Iterator[str], output_path: Path,
             writer.write(token)
             count += 1
     return count
+
+
+def write_combos(pairs: Iterable[Tuple[str, str]], output_path: Path,
+                 separator: str = ":", compression: Optional[str] = None) -> int:
+    """
+    Write username/password pairs as a combo list (user:pass per line)
+    
+    Args:
+        pairs: (username, password) pairs
+        output_path: Output file path
+        separator: Separator between username and password
+        compression: Optional compression format
+        
+    Returns:
+        Number of pairs written
+    """
+    return write_tokens_to_file((f"{user}{separator}{password}" for user, password in pairs),
+                                output_path, compression)
+
+
+def write_split_credentials(pairs: Iterable[Tuple[str, str]], users_path: Path,
+                            passwords_path: Path, compression: Optional[str] = None) -> Tuple[int, int]:
+    """
+    Write the unique usernames and passwords of pairs to two files
+    
+    Args:
+        pairs: (username, password) pairs
+        users_path: Output file for usernames
+        passwords_path: Output file for passwords
+        compression: Optional compression format
+        
+    Returns:
+        (usernames written, passwords written)
+    """
+    users = {}
+    passwords = {}
+    for user, password in pairs:
+        users.setdefault(user, None)
+        passwords.setdefault(password, None)
+    return (write_tokens_to_file(iter(users), users_path, compression),
+            write_tokens_to_file(iter(passwords), passwords_path, compression))
@@ -0,0 +1,114 @@
+"""
+Structured identifier templates
+
+Vehicle plates and employee IDs follow rigid layouts, which makes
+them natural Crunch-style patterns. The builders here turn a few
+parameters into patterns that the normal pattern generator enumerates.
+"""
+
+from typing import Callable, Dict, List, Sequence
+from .charset import PATTERN_MARKERS
+from .error import ConfigError
+
+
+# Indian state / union territory codes used on registration plates
+INDIAN_STATE_CODES = [
+    "AP", "AR", "AS", "BR", "CG", "DL", "GA", "GJ", "HP", "HR", "JH", "JK",
+    "KA", "KL", "MH", "MP", "OD", "PB", "RJ", "TN", "TS", "UK", "UP", "WB",
+]
+
+
+def _literal(text: str, what: str) -> str:
+    """Ensure literal template text does not contain pattern markers"""
+    markers = [c for c in text if c in PATTERN_MARKERS]
+    if markers:
+        raise ConfigError(f"{what} {text!r} contains pattern markers: {''.join(markers)}")
+    return text
+
+
+def indian_plate_patterns(states: Sequence[str] = ("MH",), series_letters: int = 2) -> List[str]:
+    """
+    Patterns for Indian vehicle plates such as MH12AB1234
+    
+    Args:
+        states: State codes to generate plates for
+        series_letters: Number of series letters (1 or 2)
+        
+    Returns:
+        One pattern per state
+    """
+    if series_letters not in (1, 2):
+        raise ConfigError("series_letters must be 1 or 2")
+    
+    patterns = []
+    for state in states:
+        code = state.strip().upper()
+        if code not in INDIAN_STATE_CODES:
+            raise ConfigError(f"Unknown Indian state code: {state}")
+        patterns.append(code + "%%" + "," * series_letters + "%%%%")
+    return patterns
+
+
+def employee_id_patterns(prefix: str = "EMP", digits: int = 4, separator: str = "") -> List[str]:
+    """
+    Patterns for prefixed employee IDs such as EMP0042 or EMP-0042
+    
+    Args:
+        prefix: Fixed ID prefix
+        digits: Number of trailing digits
+        separator: Text between prefix and digits
+        
+    Returns:
+        Single-element pattern list
+    """
+    if digits < 1:
+        raise ConfigError("digits must be at least 1")
+    return [_literal(prefix, "Prefix") + _literal(separator, "Separator") + "%" * digits]
+
+
+def initials_id_patterns(letters: int = 2, digits: int = 5, separator: str = "-",
+                         uppercase: bool = False) -> List[str]:
+    """
+    Patterns for initials-based IDs such as ab-12345
+    
+    Args:
+        letters: Number of leading letters
+        digits: Number of trailing digits
+        separator: Text between letters and digits
+        uppercase: Use uppercase letters instead of lowercase
+        
+    Returns:
+        Single-element pattern list
+    """
+    if letters < 1 or digits < 1:
+        raise ConfigError("letters and digits must be at least 1")
+    marker = "," if uppercase else "@"
+    return [marker * letters + _literal(separator, "Separator") + "%" * digits]
+
+
+# Template builders addressable by name
+STRUCTURED_ID_TEMPLATES: Dict[str, Callable[..., List[str]]] = {
+    "plate-in": indian_plate_patterns,
+    "employee": employee_id_patterns,
+    "initials": initials_id_patterns,
+}
+
+
+def build_patterns(kind: str, **params) -> List[str]:
+    """
+    Build the patterns of a named structured-ID template
+    
+    Args:
+        kind: Template name from STRUCTURED_ID_TEMPLATES
+        **params: Builder parameters
+        
+    Returns:
+        List of patterns
+    """
+    if kind not in STRUCTURED_ID_TEMPLATES:
+        known = ", ".join(sorted(STRUCTURED_ID_TEMPLATES))
+        raise ConfigError(f"Unknown structured ID template: {kind} (known: {known})")
+    try:
+        return STRUCTURED_ID_TEMPLATES[kind](**params)
+    except TypeError as e:
+        raise ConfigError(f"Invalid parameters for {kind}: {e}")
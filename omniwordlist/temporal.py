@@ -0,0 +1,302 @@
+"""
+Temporal wordlist generation
+
+Seasons, months, holidays and quarters dominate real corporate
+passwords (Summer2025!, Diwali@2024, Q3-2025). This module knows those
+words in several locales and renders them for every period in a
+date range.
+
+Field values, words, prefix and suffix may also hold relative tokens
+that resolve against the run date, so presets stay current instead of
+hardcoding a year:
+
+    {year}  {year-1}  {year-3..+1}   full years, offsets or a range
+    {yy}    {yy+1}                   two-digit years
+    {season}  {season-1}             English season name (3-month steps)
+    {grad:30}  {grad:25..30}         years people of that age left school
+                                     or university (ages 18 and 22)
+"""
+
+import itertools
+import re
+from datetime import date, timedelta
+from typing import Dict, Iterable, Iterator, List, Optional, Sequence, Tuple
+from .error import ConfigError
+
+
+# Season names per locale, keyed by northern-hemisphere season
+SEASONS: Dict[str, Dict[str, List[str]]] = {
+    "en": {"winter": ["Winter"], "spring": ["Spring"], "summer": ["Summer"],
+           "autumn": ["Autumn", "Fall"]},
+    "de": {"winter": ["Winter"], "spring": ["Fruehling", "Frühling"], "summer": ["Sommer"],
+           "autumn": ["Herbst"]},
+    "fr": {"winter": ["Hiver"], "spring": ["Printemps"], "summer": ["Ete", "Été"],
+           "autumn": ["Automne"]},
+    "es": {"winter": ["Invierno"], "spring": ["Primavera"], "summer": ["Verano"],
+           "autumn": ["Otono", "Otoño"]},
+    "hi": {"winter": ["Sardi"], "spring": ["Basant"], "summer": ["Garmi"],
+           "autumn": ["Patjhad"]},
+}
+
+# Month names per locale, January first
+MONTHS: Dict[str, List[str]] = {
+    "en": ["January", "February", "March", "April", "May", "June", "July",
+           "August", "September", "October", "November", "December"],
+    "de": ["Januar", "Februar", "Maerz", "April", "Mai", "Juni", "Juli",
+           "August", "September", "Oktober", "November", "Dezember"],
+    "fr": ["Janvier", "Fevrier", "Mars", "Avril", "Mai", "Juin", "Juillet",
+           "Aout", "Septembre", "Octobre", "Novembre", "Decembre"],
+    "es": ["Enero", "Febrero", "Marzo", "Abril", "Mayo", "Junio", "Julio",
+           "Agosto", "Septiembre", "Octubre", "Noviembre", "Diciembre"],
+    "hi": ["Janvari", "Farvari", "March", "April", "Mai", "Joon", "Julai",
+           "Agast", "Sitambar", "Aktubar", "Navambar", "Disambar"],
+}
+
+# Holidays per locale with the month(s) they usually fall in
+HOLIDAYS: Dict[str, List[Tuple[str, Sequence[int]]]] = {
+    "en": [("NewYear", (1,)), ("Valentine", (2,)), ("Easter", (3, 4)),
+           ("Halloween", (10,)), ("Thanksgiving", (11,)), ("Christmas", (12,)),
+           ("Xmas", (12,))],
+    "de": [("Neujahr", (1,)), ("Fasching", (2, 3)), ("Ostern", (3, 4)),
+           ("Oktoberfest", (9, 10)), ("Weihnachten", (12,)), ("Silvester", (12,))],
+    "fr": [("NouvelAn", (1,)), ("Paques", (3, 4)), ("Toussaint", (11,)),
+           ("Noel", (12,))],
+    "es": [("AnoNuevo", (1,)), ("Reyes", (1,)), ("SemanaSanta", (3, 4)),
+           ("Navidad", (12,))],
+    "hi": [("Pongal", (1,)), ("Holi", (3,)), ("Eid", (3, 4, 5, 6)),
+           ("Navratri", (9, 10)), ("Diwali", (10, 11)), ("Dussehra", (10,))],
+}
+
+# Season of each month (northern hemisphere)
+MONTH_SEASONS = {
+    12: "winter", 1: "winter", 2: "winter",
+    3: "spring", 4: "spring", 5: "spring",
+    6: "summer", 7: "summer", 8: "summer",
+    9: "autumn", 10: "autumn", 11: "autumn",
+}
+
+# How a period word is combined with its year
+DEFAULT_TEMPLATES = [
+    "{word}{year}",
+    "{word}{year}!",
+    "{word}@{year}",
+    "{word}{yy}",
+    "{word}{yy}!",
+]
+
+# How quarters are rendered
+QUARTER_TEMPLATES = [
+    "Q{quarter}-{year}",
+    "Q{quarter}{year}",
+    "Q{quarter}_{year}",
+    "{year}Q{quarter}",
+]
+
+KINDS = ("seasons", "months", "holidays", "quarters")
+
+
+def parse_date(value: str) -> date:
+    """
+    Parse an ISO date (YYYY-MM-DD)
+
+    Args:
+        value: Date string
+
+    Returns:
+        Parsed date
+    """
+    try:
+        return date.fromisoformat(value)
+    except ValueError:
+        raise ConfigError(f"Invalid date (expected YYYY-MM-DD): {value}")
+
+
+def default_range(today: Optional[date] = None) -> Tuple[date, date]:
+    """The twelve months leading up to today"""
+    today = today or date.today()
+    return today - timedelta(days=365), today
+
+
+def months_in_range(start: date, end: date) -> List[Tuple[int, int]]:
+    """
+    List the (year, month) periods touched by a date range
+
+    Args:
+        start: First day of the range
+        end: Last day of the range
+
+    Returns:
+        Chronological list of (year, month) tuples
+    """
+    if end < start:
+        raise ConfigError("Temporal range end must not be before its start")
+
+    periods = []
+    year, month = start.year, start.month
+    while (year, month) <= (end.year, end.month):
+        periods.append((year, month))
+        month += 1
+        if month > 12:
+            year, month = year + 1, 1
+    return periods
+
+
+def period_words(start: date, end: date, locales: Sequence[str],
+                 kinds: Sequence[str] = KINDS) -> Iterator[Tuple[str, int]]:
+    """
+    Yield (word, year) pairs for every period in the range
+
+    Quarters are yielded with a "Q<n>" word and rendered separately.
+
+    Args:
+        start: First day of the range
+        end: Last day of the range
+        locales: Locale codes (en, de, fr, es, hi)
+        kinds: Which period kinds to include
+    """
+    for locale in locales:
+        if locale not in MONTHS:
+            raise ConfigError(f"Unsupported locale: {locale} (known: {', '.join(sorted(MONTHS))})")
+    for kind in kinds:
+        if kind not in KINDS:
+            raise ConfigError(f"Unsupported temporal kind: {kind}")
+
+    for year, month in months_in_range(start, end):
+        for locale in locales:
+            if "seasons" in kinds:
+                for word in SEASONS[locale][MONTH_SEASONS[month]]:
+                    yield word, year
+            if "months" in kinds:
+                yield MONTHS[locale][month - 1], year
+            if "holidays" in kinds:
+                for word, holiday_months in HOLIDAYS[locale]:
+                    if month in holiday_months:
+                        yield word, year
+        if "quarters" in kinds:
+            yield f"Q{(month - 1) // 3 + 1}", year
+
+
+def generate_temporal(start: date, end: date, locales: Sequence[str] = ("en",),
+                      kinds: Sequence[str] = KINDS,
+                      templates: Optional[Sequence[str]] = None,
+                      case_variants: bool = True) -> Iterator[str]:
+    """
+    Generate period-targeted candidates for a date range
+
+    Args:
+        start: First day of the range
+        end: Last day of the range
+        locales: Locale codes
+        kinds: Which period kinds to include (seasons, months, holidays, quarters)
+        templates: Word templates using {word}, {year} and {yy}
+        case_variants: Also emit lowercase and uppercase words
+
+    Yields:
+        Unique candidates, most recent period last
+    """
+    templates = templates or DEFAULT_TEMPLATES
+    seen = set()
+
+    for word, year in period_words(start, end, locales, kinds):
+        if word.startswith("Q") and word[1:].isdigit():
+            rendered = [t.format(quarter=word[1:], year=year) for t in QUARTER_TEMPLATES]
+        else:
+            variants = [word, word.lower(), word.upper()] if case_variants else [word]
+            rendered = [
+                template.format(word=variant, year=year, yy=f"{year % 100:02d}")
+                for variant in variants
+                for template in templates
+            ]
+
+        for candidate in rendered:
+            if candidate not in seen:
+                seen.add(candidate)
+                yield candidate
+
+
+# Relative token: {<name><argument>} with a name known to RELATIVE_TOKENS
+RELATIVE_TOKEN = re.compile(r"\{(year|yy|season|grad)([^{}]*)\}")
+
+# Ages at which people typically graduate (school, university)
+GRADUATION_AGES = (18, 22)
+
+_OFFSETS = re.compile(r"([+-]\d+)?(?:\.\.([+-]?\d+))?")
+_AGES = re.compile(r":(\d+)(?:\.\.(\d+))?")
+
+_SEASON_ORDER = ("winter", "spring", "summer", "autumn")
+
+
+def _offsets(token: str, argument: str) -> range:
+    match = _OFFSETS.fullmatch(argument)
+    if not match:
+        raise ConfigError(f"Invalid relative token {token} (expected e.g. {{year}}, {{year-1}} or {{year-3..+1}})")
+    low = int(match.group(1) or 0)
+    high = int(match.group(2)) if match.group(2) is not None else low
+    if high < low:
+        raise ConfigError(f"Invalid relative token {token}: range end before its start")
+    return range(low, high + 1)
+
+
+def _relative_values(name: str, argument: str, today: date) -> List[str]:
+    token = f"{{{name}{argument}}}"
+    if name == "grad":
+        match = _AGES.fullmatch(argument)
+        if not match:
+            raise ConfigError(f"Invalid relative token {token} (expected e.g. {{grad:30}} or {{grad:25..30}})")
+        low = int(match.group(1))
+        high = int(match.group(2)) if match.group(2) is not None else low
+        years = sorted({today.year - age + offset for age in range(low, high + 1)
+                        for offset in GRADUATION_AGES if offset <= age})
+        return [str(year) for year in years]
+    offsets = _offsets(token, argument)
+    if name == "year":
+        return [str(today.year + offset) for offset in offsets]
+    if name == "yy":
+        return [f"{(today.year + offset) % 100:02d}" for offset in offsets]
+    current = _SEASON_ORDER.index(MONTH_SEASONS[today.month])
+    return [SEASONS["en"][_SEASON_ORDER[(current + offset) % 4]][0] for offset in offsets]
+
+
+def resolve_relative(value: str, today: Optional[date] = None) -> List[str]:
+    """
+    Resolve the relative temporal tokens of a value
+
+    Ranges multiply out, so "Summer{yy-1..0}" yields two values. Braces
+    that are not a relative token stay as they are.
+
+    Args:
+        value: Value possibly holding tokens such as {year} or {grad:30}
+        today: Run date (today if omitted)
+
+    Returns:
+        Resolved values (just the value itself if it holds no token)
+    """
+    parts = RELATIVE_TOKEN.split(value)
+    if len(parts) == 1:
+        return [value]
+    today = today or date.today()
+    # split() alternates literal text with (name, argument) pairs
+    choices = []
+    for i in range(0, len(parts) - 1, 3):
+        choices.append([parts[i]])
+        choices.append(_relative_values(parts[i + 1], parts[i + 2], today))
+    choices.append([parts[-1]])
+    return ["".join(combo) for combo in itertools.product(*choices)]
+
+
+def resolve_relative_values(values: Iterable[str], today: Optional[date] = None) -> List[str]:
+    """
+    Resolve relative tokens across a value list
+
+    Args:
+        values: Field values, words or similar
+        today: Run date (today if omitted)
+
+    Returns:
+        Resolved values in order, without duplicates
+    """
+    resolved = {}
+    for value in values:
+        for item in resolve_relative(value, today):
+            resolved[item] = None
+    return list(resolved)
@@ -0,0 +1,108 @@
+"""
+Per-token statistics columns
+
+Adds analysis columns to CSV, JSONL and SQLite output so a list can be
+loaded into a notebook without post-processing: quality score, the
+language of the token's letters, its hashcat mask and how many
+characters it has of each class.
+
+Passwords rarely hold enough text for word-level language
+identification, so the language is told by the Unicode script of the
+letters (latin, cyrillic, greek, arabic, cjk, hangul, ...): "mixed"
+when letters come from several scripts, empty without letters.
+"""
+
+import unicodedata
+from typing import Dict, Union
+
+from .filters import calculate_quality_score, char_class
+
+
+# Classes of filters.char_class, one count column each
+CLASS_COLUMNS = ("lower", "upper", "digit", "symbol", "other")
+
+# Column order in CSV headers and SQLite tables
+STATS_COLUMNS = ("quality_score", "language", "mask") + CLASS_COLUMNS
+
+# SQLite column type of each column
+STATS_SQL_TYPES = {"quality_score": "REAL", "language": "TEXT", "mask": "TEXT",
+                   **dict.fromkeys(CLASS_COLUMNS, "INTEGER")}
+
+# hashcat mask placeholder per character class; other characters take ?b per UTF-8 byte
+_MASK_PLACEHOLDERS = {"lower": "?l", "upper": "?u", "digit": "?d", "symbol": "?s"}
+
+
+def detect_language(token: str) -> str:
+    """
+    Script of the letters in a token
+
+    Args:
+        token: Token to inspect
+
+    Returns:
+        Lowercase script name, "mixed" or "" when the token has no letters
+    """
+    scripts = set()
+    for char in token:
+        if not char.isalpha():
+            continue
+        if char.isascii():
+            scripts.add("latin")
+            continue
+        # Character names start with the script: "CYRILLIC SMALL LETTER A", "CJK UNIFIED IDEOGRAPH-4E2D"
+        name = unicodedata.name(char, "")
+        scripts.add(name.split(" ", 1)[0].lower() if name else "other")
+    if len(scripts) > 1:
+        return "mixed"
+    return scripts.pop() if scripts else ""
+
+
+def token_mask(token: str) -> str:
+    """
+    hashcat mask matching a token, e.g. ?u?l?l?l?d?d for Pass12
+
+    Args:
+        token: Token to describe
+
+    Returns:
+        Mask string
+    """
+    parts = []
+    for char in token:
+        placeholder = _MASK_PLACEHOLDERS.get(char_class(char))
+        parts.append(placeholder or "?b" * len(char.encode("utf-8")))
+    return "".join(parts)
+
+
+def class_counts(token: str) -> Dict[str, int]:
+    """
+    Characters of each class (see filters.char_class)
+
+    Args:
+        token: Token to count
+
+    Returns:
+        Class -> count, every class of CLASS_COLUMNS present
+    """
+    counts = dict.fromkeys(CLASS_COLUMNS, 0)
+    for char in token:
+        counts[char_class(char)] += 1
+    return counts
+
+
+def token_stats(token: str) -> Dict[str, Union[float, int, str]]:
+    """
+    Statistics columns of a token
+
+    Args:
+        token: Token to describe
+
+    Returns:
+        Column -> value, in STATS_COLUMNS order
+    """
+    return {
+        "quality_score": round(calculate_quality_score(token), 4),
+        "language": detect_language(token),
+        "mask": token_mask(token),
+        **class_counts(token),
+    }
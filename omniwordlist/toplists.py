@@ -0,0 +1,102 @@
+"""
+Built-in top-N common password sources
+
+The 100 most common passwords ship with the package. Larger lists
+(1k/10k/100k) are fetched once from SecLists (through the SecLists
+integration, so they share its data directory and checksums) and
+reused from there.
+"""
+
+from pathlib import Path
+from typing import Dict, List, Optional
+from .error import ConfigError
+from . import seclists
+
+
+# Top 100 of the SecLists "10 million password list", most common first
+BUILTIN_TOP_PASSWORDS = (
+    "123456", "password", "12345678", "qwerty", "123456789", "12345", "1234",
+    "111111", "1234567", "dragon", "123123", "baseball", "abc123", "football",
+    "monkey", "letmein", "696969", "shadow", "master", "666666", "qwertyuiop",
+    "123321", "mustang", "1234567890", "michael", "654321", "pussy", "superman",
+    "1qaz2wsx", "7777777", "fuckyou", "121212", "000000", "qazwsx", "123qwe",
+    "killer", "trustno1", "jordan", "jennifer", "zxcvbnm", "asdfgh", "hunter",
+    "buster", "soccer", "harley", "batman", "andrew", "tigger", "sunshine",
+    "iloveyou", "fuckme", "2000", "charlie", "robert", "thomas", "hockey",
+    "ranger", "daniel", "starwars", "klaster", "112233", "george", "asshole",
+    "computer", "michelle", "jessica", "pepper", "1111", "zxcvbn", "555555",
+    "11111111", "131313", "freedom", "777777", "pass", "fuck", "maggie",
+    "159753", "aaaaaa", "ginger", "princess", "joshua", "cheese", "amanda",
+    "summer", "love", "ashley", "6969", "nicole", "chelsea", "biteme",
+    "matthew", "access", "yankees", "987654321", "dallas", "austin", "thunder",
+    "taylor", "matrix",
+)
+
+# Downloadable list sizes and their SecLists catalog names
+TOPLIST_NAMES: Dict[int, str] = {
+    1000: "Passwords/top-1000",
+    10000: "Passwords/top-10000",
+    100000: "Passwords/top-100000",
+}
+
+# Where a top-N list is placed relative to the generated stream
+TOP_PLACEMENTS = ("first", "last")
+
+
+def toplist_path(size: int, data_dir: Optional[Path] = None) -> Path:
+    """Local path of a downloaded top-N list"""
+    return (data_dir or seclists.default_data_dir()) / seclists.repository_path(TOPLIST_NAMES[size])
+
+
+def fetch_top_passwords(size: int, data_dir: Optional[Path] = None) -> Path:
+    """
+    Download a top-N list into the data directory
+    
+    Args:
+        size: List size (one of TOPLIST_NAMES)
+        data_dir: Override for the SecLists data directory
+        
+    Returns:
+        Path of the downloaded list
+    """
+    if size not in TOPLIST_NAMES:
+        sizes = ", ".join(str(s) for s in sorted(TOPLIST_NAMES))
+        raise ConfigError(f"No downloadable top list of size {size} (available: {sizes})")
+    
+    path, _ = seclists.fetch_list(TOPLIST_NAMES[size], data_dir)
+    return path
+
+
+def load_top_passwords(count: int, data_dir: Optional[Path] = None) -> List[str]:
+    """
+    Load the `count` most common passwords, most common first
+    
+    Args:
+        count: Number of passwords wanted
+        data_dir: Override for the SecLists data directory
+        
+    Returns:
+        List of passwords
+    """
+    if count <= len(BUILTIN_TOP_PASSWORDS):
+        return list(BUILTIN_TOP_PASSWORDS[:count])
+    
+    for size in sorted(TOPLIST_NAMES):
+        path = toplist_path(size, data_dir)
+        if size >= count and path.exists():
+            passwords = []
+            with open(path, 'r', encoding='utf-8', errors='ignore') as f:
+                for line in f:
+                    word = line.rstrip("\r\n")
+                    if word:
+                        passwords.append(word)
+                    if len(passwords) >= count:
+                        break
+            return passwords
+    
+    needed = min((s for s in TOPLIST_NAMES if s >= count), default=None)
+    if needed is None:
+        raise ConfigError(f"Top lists are available up to {max(TOPLIST_NAMES)} entries")
+    raise ConfigError(
+        f"Top-{count} list not downloaded; run `omni toplist fetch {needed}` first"
+    )
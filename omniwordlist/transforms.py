@@ -6,7 +6,9 @@ keyboard shifts, and many other transformations.
 """
 
 import random
-from typing import List, Callable
+import re
+import unicodedata
+from typing import List, Callable, Tuple
 from .error import TransformError
 
 
@@ -47,14 +49,76 @@ KEYBOARD_SHIFT_MAP = {
     't': ['r', 'y', 'f', 'g'],
 }
 
+# Locales whose case mapping pairs dotted i with İ and dotless ı with I
+# (Turkish, Azeri), selected with a suffix: uppercase:tr, toggle_case:az, ...
+TURKIC_LOCALES = ('tr', 'az')
+TURKIC_UPPER = str.maketrans({'i': 'İ', 'ı': 'I'})
+TURKIC_LOWER = str.maketrans({'I': 'ı', 'İ': 'i'})
+
 # Common emojis for injection
 EMOJIS = ['😀', '😃', '😄', '😁', '😆', '😅', '🤣', '😂', '🙂', '🙃', 
           '😉', '😊', '😇', '❤️', '💕', '💖', '💗', '💙', '💚', '💛',
           '🔥', '✨', '⭐', '🌟', '💫', '🎉', '🎊', '🎈', '🎁', '🏆']
 
 
+# Code points that never start a grapheme cluster of their own: zero width
+# joiner, variation selectors, emoji skin tone modifiers and tag characters
+# (combining marks are recognised by category)
+ZWJ = '\u200d'
+GRAPHEME_EXTENDERS = ((0x200D, 0x200D), (0xFE00, 0xFE0F), (0x1F3FB, 0x1F3FF), (0xE0020, 0xE007F))
+REGIONAL_INDICATORS = (0x1F1E6, 0x1F1FF)
+
+
+def _extends_grapheme(char: str) -> bool:
+    code = ord(char)
+    if any(low <= code <= high for low, high in GRAPHEME_EXTENDERS):
+        return True
+    return unicodedata.category(char) in ('Mn', 'Mc', 'Me')
+
+
+def _is_regional_indicator(char: str) -> bool:
+    return REGIONAL_INDICATORS[0] <= ord(char) <= REGIONAL_INDICATORS[1]
+
+
+def grapheme_clusters(text: str) -> List[str]:
+    """
+    Split text into user-perceived characters
+    
+    A simplified form of Unicode extended grapheme clusters: combining
+    marks, variation selectors, skin tone modifiers and tags stay with
+    the character before them, a zero width joiner joins the characters
+    on both sides (emoji ZWJ sequences), regional indicators pair up
+    into flags and CR LF stays together.
+    
+    Args:
+        text: Text to split
+        
+    Returns:
+        Clusters, joining back to the text
+    """
+    clusters: List[str] = []
+    joined = False
+    for char in text:
+        if clusters and (joined or _extends_grapheme(char)
+                         or (char == '\n' and clusters[-1] == '\r')
+                         or (_is_regional_indicator(char) and len(clusters[-1]) == 1
+                             and _is_regional_indicator(clusters[-1]))):
+            clusters[-1] += char
+        else:
+            clusters.append(char)
+        joined = char == ZWJ
+    return clusters
+
+
 class Transform:
-    """Base transform class"""
+    """
+    Base transform class
+    
+    Randomized transforms set `randomized` and take the random source
+    as a second argument of apply.
+    """
+    
+    randomized = False
     
     @staticmethod
     def apply(token: str) -> str:
@@ -102,12 +166,64 @@ class ToggleCaseTransform(Transform):
         return ''.join(c.lower() if c.isupper() else c.upper() for c in token)
 
 
+def turkic_upper(text: str) -> str:
+    """Uppercase with Turkish / Azeri i mappings (i -> İ, ı -> I)"""
+    return text.translate(TURKIC_UPPER).upper()
+
+
+def turkic_lower(text: str) -> str:
+    """Lowercase with Turkish / Azeri i mappings (I -> ı, İ -> i)"""
+    return text.translate(TURKIC_LOWER).lower()
+
+
+class TurkicUppercaseTransform(Transform):
+    """Convert to uppercase, Turkish / Azeri rules"""
+    
+    @staticmethod
+    def apply(token: str) -> str:
+        return turkic_upper(token)
+
+
+class TurkicLowercaseTransform(Transform):
+    """Convert to lowercase, Turkish / Azeri rules"""
+    
+    @staticmethod
+    def apply(token: str) -> str:
+        return turkic_lower(token)
+
+
+class TurkicCapitalizeTransform(Transform):
+    """Capitalize first letter, Turkish / Azeri rules"""
+    
+    @staticmethod
+    def apply(token: str) -> str:
+        return turkic_upper(token[:1]) + turkic_lower(token[1:])
+
+
+class TurkicTitleCaseTransform(Transform):
+    """Convert to title case, Turkish / Azeri rules"""
+    
+    @staticmethod
+    def apply(token: str) -> str:
+        return re.sub(r"[^\W\d_]+", lambda match: TurkicCapitalizeTransform.apply(match.group(0)), token)
+
+
+class TurkicToggleCaseTransform(Transform):
+    """Toggle case of each character, Turkish / Azeri rules"""
+    
+    @staticmethod
+    def apply(token: str) -> str:
+        return ''.join(turkic_lower(c) if c.isupper() else turkic_upper(c) for c in token)
+
+
 class ReverseTransform(Transform):
-    """Reverse the token"""
+    """Reverse the token, keeping grapheme clusters (emoji, combining marks) intact"""
     
     @staticmethod
     def apply(token: str) -> str:
-        return token[::-1]
+        if token.isascii():
+            return token[::-1]
+        return ''.join(reversed(grapheme_clusters(token)))
 
 
 class LeetBasicTransform(Transform):
@@ -127,12 +243,14 @@ class LeetBasicTransform(Transform):
 class LeetFullTransform(Transform):
     """Apply full leet speak transformation with random choices"""
     
+    randomized = True
+    
     @staticmethod
-    def apply(token: str) -> str:
+    def apply(token: str, rng: random.Random = random) -> str:
         result = []
         for char in token.lower():
             if char in LEET_MAP:
-                result.append(random.choice(LEET_MAP[char]))
+                result.append(rng.choice(LEET_MAP[char]))
             else:
                 result.append(char)
         return ''.join(result)
@@ -154,24 +272,28 @@ class HomoglyphSingleTransform(Transform):
 class HomoglyphRandomTransform(Transform):
     """Replace random characters with homoglyphs"""
     
+    randomized = True
+    
     @staticmethod
-    def apply(token: str) -> str:
+    def apply(token: str, rng: random.Random = random) -> str:
         result = list(token.lower())
         for i, char in enumerate(result):
-            if char in HOMOGLYPH_MAP and random.random() < 0.3:
-                result[i] = random.choice(HOMOGLYPH_MAP[char])
+            if char in HOMOGLYPH_MAP and rng.random() < 0.3:
+                result[i] = rng.choice(HOMOGLYPH_MAP[char])
         return ''.join(result)
 
 
 class KeyboardShiftTransform(Transform):
     """Replace characters with adjacent keyboard keys"""
     
+    randomized = True
+    
     @staticmethod
-    def apply(token: str) -> str:
+    def apply(token: str, rng: random.Random = random) -> str:
         result = []
         for char in token.lower():
-            if char in KEYBOARD_SHIFT_MAP and random.random() < 0.2:
-                result.append(random.choice(KEYBOARD_SHIFT_MAP[char]))
+            if char in KEYBOARD_SHIFT_MAP and rng.random() < 0.2:
+                result.append(rng.choice(KEYBOARD_SHIFT_MAP[char]))
             else:
                 result.append(char)
         return ''.join(result)
@@ -180,37 +302,46 @@ class KeyboardShiftTransform(Transform):
 class AppendNumbers4Transform(Transform):
     """Append 4-digit number"""
     
+    randomized = True
+    
     @staticmethod
-    def apply(token: str) -> str:
-        return f"{token}{random.randint(0, 9999):04d}"
+    def apply(token: str, rng: random.Random = random) -> str:
+        return f"{token}{rng.randint(0, 9999):04d}"
 
 
 class AppendNumbers2Transform(Transform):
     """Append 2-digit number"""
     
+    randomized = True
+    
     @staticmethod
-    def apply(token: str) -> str:
-        return f"{token}{random.randint(0, 99):02d}"
+    def apply(token: str, rng: random.Random = random) -> str:
+        return f"{token}{rng.randint(0, 99):02d}"
 
 
 class AppendYearTransform(Transform):
     """Append year (1900-2099)"""
     
+    randomized = True
+    
     @staticmethod
-    def apply(token: str) -> str:
-        return f"{token}{random.randint(1900, 2099)}"
+    def apply(token: str, rng: random.Random = random) -> str:
+        return f"{token}{rng.randint(1900, 2099)}"
 
 
 class EmojiInsertionTransform(Transform):
-    """Insert random emoji at random position"""
+    """Insert random emoji at a random grapheme cluster boundary"""
+    
+    randomized = True
     
     @staticmethod
-    def apply(token: str) -> str:
+    def apply(token: str, rng: random.Random = random) -> str:
         if not token:
             return token
-        pos = random.randint(0, len(token))
-        emoji = random.choice(EMOJIS)
-        return token[:pos] + emoji + token[pos:]
+        clusters = grapheme_clusters(token)
+        pos = rng.randint(0, len(clusters))
+        emoji = rng.choice(EMOJIS)
+        return ''.join(clusters[:pos]) + emoji + ''.join(clusters[pos:])
 
 
 class PluralizationTransform(Transform):
@@ -257,7 +388,6 @@ class DiacriticsStripTransform(Transform):
             return unidecode(token)
         except ImportError:
             # Fallback if unidecode not available
-            import unicodedata
             return ''.join(c for c in unicodedata.normalize('NFD', token)
                           if unicodedata.category(c) != 'Mn')
 
@@ -283,6 +413,15 @@ TRANSFORM_REGISTRY = {
     'diacritics_strip': DiacriticsStripTransform,
 }
 
+for _locale in TURKIC_LOCALES:
+    TRANSFORM_REGISTRY.update({
+        f'uppercase:{_locale}': TurkicUppercaseTransform,
+        f'lowercase:{_locale}': TurkicLowercaseTransform,
+        f'capitalize:{_locale}': TurkicCapitalizeTransform,
+        f'title_case:{_locale}': TurkicTitleCaseTransform,
+        f'toggle_case:{_locale}': TurkicToggleCaseTransform,
+    })
+
 
 def get_transform(name: str) -> Transform:
     """Get transform by name"""
@@ -291,15 +430,126 @@ def get_transform(name: str) -> Transform:
     return TRANSFORM_REGISTRY[name]
 
 
-def apply_transforms(token: str, transform_names: List[str]) -> str:
-    """Apply a pipeline of transforms to a token"""
+def token_rng(seed: int, token: str) -> random.Random:
+    """
+    Random source for the randomized transforms of one token
+    
+    Derived from the seed and the token itself, so a token transforms
+    the same way no matter where in a run (or in which chunk) it is
+    generated.
+    """
+    return random.Random(f"{seed}:{token}")
+
+
+def apply_transforms(token: str, transform_names: List[str], rng: random.Random = None) -> str:
+    """
+    Apply a pipeline of transforms to a token
+    
+    Args:
+        token: Token to transform
+        transform_names: Transform chain in application order
+        rng: Random source of randomized transforms (the unseeded
+            global one if omitted)
+        
+    Returns:
+        Transformed token
+    """
     result = token
     for name in transform_names:
         transform = get_transform(name)
-        result = transform.apply(result)
+        if transform.randomized:
+            result = transform.apply(result, rng or random)
+        else:
+            result = transform.apply(result)
     return result
 
 
+# Transforms that only change letter case
+CASE_TRANSFORMS = {'uppercase', 'lowercase', 'capitalize', 'title_case', 'toggle_case'}
+
+# Case transforms whose result does not depend on the input casing
+ABSOLUTE_CASE_TRANSFORMS = {'uppercase', 'lowercase', 'capitalize', 'title_case'}
+
+# Transforms that keep ASCII input ASCII
+ASCII_PRESERVING_TRANSFORMS = CASE_TRANSFORMS | {
+    'reverse', 'leet_basic', 'keyboard_shift', 'append_numbers_4', 'append_numbers_2',
+    'append_year', 'pluralization',
+}
+
+# Transforms whose output depends on the random state
+RANDOM_TRANSFORMS = {name for name, transform in TRANSFORM_REGISTRY.items() if transform.randomized}
+
+# Deterministic transforms where applying twice equals applying once
+IDEMPOTENT_TRANSFORMS = {'uppercase', 'lowercase', 'leet_basic',
+                         'diacritics_strip'} | {
+    f'{name}:{locale}' for name in ('uppercase', 'lowercase') for locale in TURKIC_LOCALES
+}
+
+# (first, second) pairs where the second transform has nothing left to do
+NOOP_AFTER = {
+    ('leet_basic', 'leet_full'),
+    ('leet_basic', 'lowercase'),
+    ('leet_full', 'lowercase'),
+    ('homoglyph_single', 'lowercase'),
+    ('homoglyph_random', 'lowercase'),
+}
+
+
+def optimize_transforms(transform_names: List[str]) -> Tuple[List[str], List[str]]:
+    """
+    Canonicalize a transform chain for ASCII tokens, dropping redundant steps
+    
+    Removes steps whose effect is overwritten (capitalize then lowercase),
+    repeated idempotent steps, self-cancelling pairs (reverse, reverse;
+    toggle_case, toggle_case) and steps that cannot change anything after
+    their predecessor. Random transforms are never merged, since
+    repeating them changes the output.
+    
+    Case rewrites are only exact while the token is ASCII (Unicode case
+    mappings such as ß -> SS are not reversible), so they are applied only
+    where every earlier step keeps ASCII input ASCII. Callers must run the
+    optimized chain on ASCII tokens only and the original chain otherwise.
+    
+    Args:
+        transform_names: Transform chain in application order
+        
+    Returns:
+        (optimized chain, notes describing each removal)
+    """
+    for name in transform_names:
+        get_transform(name)
+    
+    chain = list(transform_names)
+    notes = []
+    changed = True
+    while changed:
+        changed = False
+        for i in range(len(chain) - 1):
+            first, second = chain[i], chain[i + 1]
+            ascii_so_far = all(name in ASCII_PRESERVING_TRANSFORMS for name in chain[:i])
+            if first == second and first in IDEMPOTENT_TRANSFORMS:
+                notes.append(f"dropped repeated {second}")
+                del chain[i + 1]
+            elif first == second == 'reverse':
+                notes.append("dropped reverse, reverse: cancel out")
+                del chain[i:i + 2]
+            elif (first, second) in NOOP_AFTER:
+                notes.append(f"dropped {second}: no effect after {first}")
+                del chain[i + 1]
+            elif ascii_so_far and first == second == 'toggle_case':
+                notes.append("dropped toggle_case, toggle_case: cancel out")
+                del chain[i:i + 2]
+            elif ascii_so_far and first in CASE_TRANSFORMS and second in ABSOLUTE_CASE_TRANSFORMS:
+                notes.append(f"dropped {first}: overridden by {second}")
+                del chain[i]
+            else:
+                continue
+            changed = True
+            break
+    
+    return chain, notes
+
+
 def list_transforms() -> List[str]:
     """List all available transforms"""
     return sorted(TRANSFORM_REGISTRY.keys())
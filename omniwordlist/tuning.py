@@ -0,0 +1,140 @@
+"""
+Two-pass filter tuning
+
+A pre-pass samples the keyspace, measures the entropy / quality
+distribution of candidates that survive the current filters and picks
+the threshold that should leave roughly the requested number of
+tokens, so "give me the best 500M candidates" needs no manual tuning.
+"""
+
+import random
+import re
+from dataclasses import dataclass, field
+from typing import Callable, Dict, List, Optional
+from .config import Config
+from .error import ConfigError
+from .filters import calculate_quality_score, estimate_entropy
+
+
+# Metrics that can be tuned and the FilterConfig field each one sets
+TUNABLE_METRICS: Dict[str, Callable[[str, Config], float]] = {
+    "quality": lambda token, config: calculate_quality_score(token),
+    "entropy": lambda token, config: estimate_entropy(token, config.filters.entropy_mode),
+}
+
+METRIC_FILTER_FIELDS = {
+    "quality": "min_quality",
+    "entropy": "min_entropy",
+}
+
+_COUNT_UNITS = {"": 1, "k": 10 ** 3, "m": 10 ** 6, "g": 10 ** 9, "b": 10 ** 9, "t": 10 ** 12}
+_COUNT_RE = re.compile(r"^\s*([0-9]*\.?[0-9]+)\s*([kmgbt]?)\s*$", re.IGNORECASE)
+
+
+def parse_count(value: str) -> int:
+    """
+    Parse a human count such as "500M" or "10k"
+    
+    Args:
+        value: Count string
+        
+    Returns:
+        Integer count
+    """
+    match = _COUNT_RE.match(str(value))
+    if not match:
+        raise ConfigError(f"Invalid count: {value}")
+    return int(float(match.group(1)) * _COUNT_UNITS[match.group(2).lower()])
+
+
+@dataclass
+class TuningReport:
+    """Result of a tuning pre-pass"""
+    metric: str
+    sample_size: int
+    keyspace: int
+    pass_rate: float
+    target_size: int
+    threshold: float
+    expected_output: int
+    percentiles: Dict[int, float] = field(default_factory=dict)
+
+
+def _quantile(sorted_values: List[float], q: float) -> float:
+    """Quantile of pre-sorted values (nearest-rank)"""
+    if not sorted_values:
+        return 0.0
+    index = min(len(sorted_values) - 1, max(0, int(q * len(sorted_values))))
+    return sorted_values[index]
+
+
+def tune_filters(config: Config, target_size: int, metric: str = "quality",
+                 sample_size: int = 10000, rng: Optional[random.Random] = None) -> TuningReport:
+    """
+    Sample the keyspace and suggest a threshold hitting a target output size
+    
+    Args:
+        config: Base configuration (current filters are applied to the sample)
+        target_size: Desired number of output tokens
+        metric: Metric to threshold on (quality or entropy)
+        sample_size: Number of candidates to sample
+        rng: Random source
+        
+    Returns:
+        TuningReport with the suggested threshold
+    """
+    from .generator import Generator
+    
+    if metric not in TUNABLE_METRICS:
+        raise ConfigError(f"Unknown tuning metric: {metric} (known: {', '.join(TUNABLE_METRICS)})")
+    if target_size < 1:
+        raise ConfigError("target_size must be at least 1")
+    
+    generator = Generator(config)
+    keyspace = generator.keyspace_size()
+    score = TUNABLE_METRICS[metric]
+    
+    values = []
+    for candidate in generator.sample_candidates(sample_size, rng):
+        token = generator.prepare_token(candidate)
+        if generator.filter_pipeline.rejection_reason(token) is None:
+            values.append(score(token, config))
+    values.sort()
+    
+    pass_rate = len(values) / sample_size if sample_size else 0.0
+    passing = keyspace * pass_rate
+    
+    # Keep the top `target / passing` share of the surviving candidates
+    if passing <= target_size or not values:
+        threshold = getattr(config.filters, METRIC_FILTER_FIELDS[metric])
+        kept_share = 1.0
+    else:
+        kept_share = target_size / passing
+        threshold = _quantile(values, 1.0 - kept_share)
+        kept_share = sum(1 for v in values if v >= threshold) / len(values)
+    
+    return TuningReport(
+        metric=metric,
+        sample_size=sample_size,
+        keyspace=keyspace,
+        pass_rate=pass_rate,
+        target_size=target_size,
+        threshold=threshold,
+        expected_output=int(passing * kept_share),
+        percentiles={p: _quantile(values, p / 100) for p in (10, 25, 50, 75, 90, 99)},
+    )
+
+
+def apply_tuning(config: Config, report: TuningReport) -> Config:
+    """
+    Set the tuned threshold on a configuration
+    
+    Args:
+        config: Configuration to update in place
+        report: Report produced by tune_filters
+        
+    Returns:
+        The updated configuration
+    """
+    setattr(config.filters, METRIC_FILTER_FIELDS[report.metric], report.threshold)
+    return config
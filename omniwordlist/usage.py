@@ -0,0 +1,113 @@
+"""
+Local usage ledger
+
+With the usage_stats setting on (`omni stats --enable`), every finished
+`omni run` adds to a small JSON file: runs, tokens written, the largest
+keyspace and runs per preset, for reporting tool usage in engagement
+writeups. The ledger is only ever read by `omni stats`; nothing is sent
+anywhere. A run paused with Ctrl-C is counted once, when `omni resume`
+finishes it.
+"""
+
+import json
+from dataclasses import asdict, dataclass, field
+from datetime import datetime
+from pathlib import Path
+from typing import Dict, Optional
+
+from .error import StorageError
+from .locking import atomic_write_json, file_lock
+from .opsec import disk_allowed
+from .settings import UserSettings
+
+
+def default_usage_path() -> Path:
+    """File the usage ledger is kept in"""
+    return Path.home() / ".omniwordlist" / "usage.json"
+
+
+@dataclass
+class UsageLedger:
+    """Totals over all recorded runs"""
+    runs: int = 0
+    # Output lines written
+    tokens: int = 0
+    largest_keyspace: int = 0
+    # Preset name -> runs
+    presets: Dict[str, int] = field(default_factory=dict)
+    # ISO timestamps of the first and latest recorded run
+    first_run: Optional[str] = None
+    last_run: Optional[str] = None
+
+    @classmethod
+    def load(cls, path: Optional[Path] = None) -> 'UsageLedger':
+        """
+        Read the ledger, returning an empty one when the file does not exist
+
+        Args:
+            path: Ledger file (default_usage_path() if omitted)
+
+        Returns:
+            UsageLedger instance
+        """
+        path = Path(path or default_usage_path())
+        if not path.exists():
+            return cls()
+        try:
+            return cls(**json.loads(path.read_text()))
+        except (OSError, ValueError, TypeError) as e:
+            raise StorageError(f"Cannot read usage ledger {path}: {e}")
+
+    def save(self, path: Optional[Path] = None) -> Path:
+        """Write the ledger"""
+        path = Path(path or default_usage_path())
+        atomic_write_json(path, asdict(self))
+        return path
+
+    def add_run(self, tokens: int, keyspace: int, preset: Optional[str] = None,
+                when: Optional[datetime] = None):
+        """
+        Count a finished run
+
+        Args:
+            tokens: Lines the run wrote
+            keyspace: Raw candidate keyspace of its config
+            preset: Preset the run used, if any
+            when: Time of the run (now if omitted)
+        """
+        stamp = (when or datetime.now()).isoformat(timespec="seconds")
+        self.runs += 1
+        self.tokens += tokens
+        self.largest_keyspace = max(self.largest_keyspace, keyspace)
+        if preset:
+            self.presets[preset] = self.presets.get(preset, 0) + 1
+        self.first_run = self.first_run or stamp
+        self.last_run = stamp
+
+
+def record_run(tokens: int, keyspace: int, preset: Optional[str] = None,
+               path: Optional[Path] = None, settings: Optional[UserSettings] = None) -> bool:
+    """
+    Add a finished run to the ledger if usage stats are enabled
+
+    Args:
+        tokens: Lines the run wrote
+        keyspace: Raw candidate keyspace of its config
+        preset: Preset the run used, if any
+        path: Ledger file (default_usage_path() if omitted)
+        settings: User settings (loaded if omitted)
+
+    Returns:
+        Whether the run was recorded
+    """
+    settings = settings or UserSettings.load()
+    if not settings.usage_stats or not disk_allowed("usage"):
+        return False
+    path = Path(path or default_usage_path())
+    path.parent.mkdir(parents=True, exist_ok=True)
+    # Concurrent runs must not lose each other's updates
+    with file_lock(path):
+        ledger = UsageLedger.load(path)
+        ledger.add_run(tokens, keyspace, preset)
+        ledger.save(path)
+    return True
@@ -14,6 +14,7 @@ setup(
     author="Aaryan Bansal",
     license="MIT",
     packages=find_packages(),
+    package_data={"omniwordlist": ["data/*.json"]},
     scripts=scripts,
     install_requires=[
         line.strip()
@@ -0,0 +1,19 @@
+"""
+Shared test helpers
+"""
+
+from omniwordlist import GeneratorObserver
+
+
+class CancelAfter(GeneratorObserver):
+    """Cancel a run once it has produced `limit` tokens"""
+
+    def __init__(self, token, limit):
+        self.token = token
+        self.limit = limit
+        self.seen = 0
+
+    def on_token(self, token):
+        self.seen += 1
+        if self.seen >= self.limit:
+            self.token.cancel()
@@ -0,0 +1,40 @@
+"""
+Tests for simulation-driven strategy tuning
+"""
+
+from collections import Counter
+
+import pytest
+
+from omniwordlist import Config
+from omniwordlist.autotune import autotune, discovery_area
+from omniwordlist.error import ConfigError
+from omniwordlist.simulate import simulate
+
+
+def test_discovery_area_rewards_early_hits():
+    """The same hits found earlier score a larger area"""
+    reference = Counter({'a': 1, 'b': 1})
+    early = simulate(['a', 'b', 'x', 'y'], reference)
+    late = simulate(['x', 'y', 'a', 'b'], reference)
+    assert early.fraction == late.fraction == 1.0
+    assert discovery_area(early, 4) > discovery_area(late, 4)
+
+
+def test_autotune_picks_best_variation():
+    """Each dimension keeps the variation that cracks the most"""
+    base = Config(words=['acme'], enabled_fields=['year'], field_sources={'year': ['2024']},
+                  max_length=20)
+    reference = Counter({'Acme2024': 1, '2024acme': 1})
+    report = autotune(base, reference, budget=50,
+                      transform_sets=[[], ['capitalize'], ['uppercase']])
+    
+    best = report.best.config
+    assert best.transforms == ['capitalize']
+    assert best.field_order in ('swap_pairs', 'permute')
+    assert report.best.result.fraction == 1.0
+    assert base.transforms == [] and base.field_order == 'fixed'
+    assert report.trials[0].description == 'base config'
+    
+    with pytest.raises(ConfigError, match='Unknown tuning dimension'):
+        autotune(base, reference, 10, dimensions=['nope'])
@@ -2,6 +2,7 @@
 Basic tests for OmniWordlist Pro
 """
 
+import itertools
 import pytest
 from pathlib import Path
 import tempfile
@@ -213,5 +214,29 @@ def test_output_writer():
         assert 'test3' in content
 
 
+def test_generation_streams_lazily():
+    """Huge keyspaces are streamed, never materialized up front"""
+    config = Config(min_length=8, max_length=8,
+                    charset='abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789')
+    generator = Generator(config)
+    assert generator.keyspace_size() == 62 ** 8
+    assert list(itertools.islice(generator.generate(), 3)) == ['aaaaaaaa', 'aaaaaaab', 'aaaaaaac']
+    assert generator.candidates_seen == 3
+    
+    pattern = Generator(Config(pattern='@@@@@@@@%%%%', max_length=12))
+    assert next(pattern.generate()) == 'aaaaaaaa0000'
+
+
+def test_dedupe_runs_after_transforms():
+    """Transforms that collapse mixed-case inputs leave no duplicates when dedupe is on"""
+    words = ['Acme', 'ACME', 'acme', 'Corp', 'CORP']
+    collapsed = Generator(Config(words=words, transforms=['lowercase'])).generate_list()
+    assert len(collapsed) > len(set(collapsed))
+    
+    tokens = Generator(Config(words=words, transforms=['lowercase'], dedupe=True)).generate_list()
+    assert len(tokens) == len(set(tokens))
+    assert set(tokens) == set(collapsed)
+
+
 if __name__ == '__main__':
     pytest.main([__file__, '-v'])
@@ -0,0 +1,48 @@
+"""
+Tests for entropy / quality bucketed output
+"""
+
+import pytest
+
+from omniwordlist import Config, Generator
+from omniwordlist.buckets import DEFAULT_THRESHOLDS, bucket_key, bucket_thresholds
+from omniwordlist.error import ConfigError
+from omniwordlist.shard import ShardedWriter, load_manifest, shard_contains
+
+
+def test_bucket_thresholds():
+    """Defaults follow the metric / entropy mode; explicit cut points must ascend"""
+    assert bucket_thresholds('quality') == DEFAULT_THRESHOLDS['quality']
+    assert bucket_thresholds('entropy', 'nist') == DEFAULT_THRESHOLDS['nist']
+    assert bucket_thresholds('entropy', 'shannon', [1, 2.5]) == (1.0, 2.5)
+    with pytest.raises(ConfigError):
+        bucket_thresholds('entropy', 'shannon', [3, 2])
+    with pytest.raises(ConfigError):
+        bucket_thresholds('length')
+    with pytest.raises(ConfigError):
+        Config(bucket_by='entropy', shard_by='hash').validate()
+
+
+def test_bucket_key():
+    """Tokens are routed by their entropy estimate"""
+    key = bucket_key('entropy', 'shannon', [1.0, 2.5])
+    assert key('aaaa') == 'low'
+    assert key('abcd') == 'medium'
+    assert key('aB3$xY9!') == 'high'
+
+
+def test_bucketed_output_lookup(tmp_path):
+    """Every token lands in one bucket and the manifest recomputes it"""
+    tokens = list(Generator(Config(charset='aB1!', min_length=1, max_length=3)).generate())
+    key = bucket_key('entropy', 'shannon', [1.0, 1.5])
+    with ShardedWriter(tmp_path, 'entropy', key=key,
+                       manifest={'entropy_mode': 'shannon', 'thresholds': [1.0, 1.5]}) as writer:
+        for token in tokens:
+            writer.write(token)
+    
+    manifest = load_manifest(tmp_path)
+    assert set(manifest['shards']) == {'low', 'medium', 'high'}
+    assert sum(manifest['shards'].values()) == len(tokens)
+    assert (tmp_path / 'low.txt').read_text().splitlines()[0] == 'a'
+    assert all(shard_contains(tmp_path, token) for token in tokens)
+    assert not shard_contains(tmp_path, 'zzz')
@@ -0,0 +1,146 @@
+"""
+Tests for job bundles
+"""
+
+import json
+import zipfile
+
+import pytest
+
+from omniwordlist import Config, Generator
+from omniwordlist.bundle import create_bundle, load_bundle
+from omniwordlist.error import ConfigError
+from omniwordlist.smartcase import train_case_stats
+
+
+def test_bundle_round_trip(tmp_path):
+    """Referenced files travel inside the bundle"""
+    src = tmp_path / 'src'
+    src.mkdir()
+    (src / 'words.txt').write_text('acme\nwidget\n')
+    (src / 'digits.txt').write_text('1\n2\n')
+    (src / 'deny.txt').write_text('acme1\n')
+    config = Config(enabled_fields=['company', 'digit'],
+                    field_sources={'company': 'words.txt', 'digit': str(src / 'digits.txt')},
+                    denylist='deny.txt')
+
+    bundle_path = tmp_path / 'job.omni'
+    files = create_bundle(config, bundle_path, base_dir=src)
+    assert len(files) == 3
+
+    # Loading must not depend on the original files
+    for path in src.iterdir():
+        path.unlink()
+    loaded = load_bundle(bundle_path, extract_dir=tmp_path / 'unpacked')
+
+    assert list(Generator(loaded).generate()) == ['acme2', 'widget1', 'widget2']
+
+
+def test_bundle_missing_file(tmp_path):
+    """Missing referenced files fail at bundle time"""
+    config = Config(denylist='nope.txt')
+    with pytest.raises(ConfigError, match='nope.txt'):
+        create_bundle(config, tmp_path / 'job.omni', base_dir=tmp_path)
+
+
+def test_bundle_rejects_unsafe_entries(tmp_path):
+    """Entries outside the files directory are refused"""
+    bundle_path = tmp_path / 'evil.omni'
+    with zipfile.ZipFile(bundle_path, 'w') as zf:
+        zf.writestr('config.json', '{}')
+        zf.writestr('manifest.json', '{"format_version": 1, "files": {"../../x": "x"}}')
+    with pytest.raises(ConfigError, match='Invalid bundle entry'):
+        load_bundle(bundle_path, extract_dir=tmp_path / 'unpacked')
+
+
+def test_bundle_combinator_lists(tmp_path):
+    """Combinator word lists travel inside the bundle"""
+    (tmp_path / 'left.txt').write_text('blue\nred\n')
+    (tmp_path / 'right.txt').write_text('fish\n')
+    config = Config(combinator_left='left.txt', combinator_right='right.txt')
+    bundle_path = tmp_path / 'job.omni'
+    create_bundle(config, bundle_path, base_dir=tmp_path)
+    (tmp_path / 'left.txt').unlink()
+    (tmp_path / 'right.txt').unlink()
+    loaded = load_bundle(bundle_path, extract_dir=tmp_path / 'unpacked')
+    assert list(Generator(loaded).generate()) == ['bluefish', 'redfish']
+
+
+def test_bundle_input_wordlist(tmp_path):
+    """The mutation-mode input word list travels inside the bundle"""
+    (tmp_path / 'base.txt').write_text('summer\nwinter\n')
+    config = Config(input_wordlist='base.txt', transforms=['uppercase'])
+    bundle_path = tmp_path / 'job.omni'
+    create_bundle(config, bundle_path, base_dir=tmp_path)
+    (tmp_path / 'base.txt').unlink()
+    loaded = load_bundle(bundle_path, extract_dir=tmp_path / 'unpacked')
+    assert list(Generator(loaded).generate()) == ['SUMMER', 'WINTER']
+
+
+def test_bundle_markov_files(tmp_path):
+    """Markov training lists and models travel inside the bundle; a model to save does not"""
+    (tmp_path / 'train.txt').write_text('abc\nabc\nabd\n')
+    train = Config(markov_train=str(tmp_path / 'train.txt'), markov_model=str(tmp_path / 'model.json'), markov_order=2,
+                   min_length=3, max_length=3)
+    expected = list(Generator(train).generate())
+    create_bundle(train, tmp_path / 'train.omni', base_dir=tmp_path)
+    reuse = Config(markov_model='model.json', min_length=3, max_length=3)
+    create_bundle(reuse, tmp_path / 'model.omni', base_dir=tmp_path)
+    (tmp_path / 'train.txt').unlink()
+    (tmp_path / 'model.json').unlink()
+
+    for name in ('train.omni', 'model.omni'):
+        loaded = load_bundle(tmp_path / name, extract_dir=tmp_path / 'unpacked')
+        assert loaded.markov_model is None or loaded.markov_model.startswith(str(tmp_path / 'unpacked'))
+        assert list(Generator(loaded).generate()) == expected
+
+
+def test_bundle_chain_elements(tmp_path):
+    """The chain mode element file travels inside the bundle"""
+    (tmp_path / 'elements.txt').write_text('ab\nc\n')
+    config = Config(chain_elements='elements.txt', chain_max_elements=2, min_length=3, max_length=3)
+    bundle_path = tmp_path / 'job.omni'
+    create_bundle(config, bundle_path, base_dir=tmp_path)
+    (tmp_path / 'elements.txt').unlink()
+    loaded = load_bundle(bundle_path, extract_dir=tmp_path / 'unpacked')
+    assert sorted(Generator(loaded).generate()) == ['abc', 'cab']
+
+
+def test_bundle_case_stats(tmp_path):
+    """The smart-case statistics file travels inside the bundle"""
+    (tmp_path / 'case.json').write_text(json.dumps(train_case_stats(['KEY', 'KEY', 'key'])))
+    config = Config(words=['key'], smart_case=1, case_stats_file='case.json')
+    bundle_path = tmp_path / 'job.omni'
+    create_bundle(config, bundle_path, base_dir=tmp_path)
+    (tmp_path / 'case.json').unlink()
+    loaded = load_bundle(bundle_path, extract_dir=tmp_path / 'unpacked')
+    assert list(Generator(loaded).generate()) == ['KEY']
+
+
+def test_bundle_length_distribution(tmp_path):
+    """A length distribution file travels inside the bundle; an inline one stays inline"""
+    (tmp_path / 'lengths.json').write_text(json.dumps({'2': 1}))
+    config = Config(min_length=1, max_length=2, charset='ab', length_distribution='lengths.json')
+    bundle_path = tmp_path / 'job.omni'
+    create_bundle(config, bundle_path, base_dir=tmp_path)
+    (tmp_path / 'lengths.json').unlink()
+    loaded = load_bundle(bundle_path, extract_dir=tmp_path / 'unpacked')
+    assert list(Generator(loaded).generate()) == ['aa', 'ab', 'ba', 'bb', 'a', 'b']
+
+    config.length_distribution = {'2': 1}
+    assert create_bundle(config, bundle_path, base_dir=tmp_path) == {}
+
+
+def test_bundle_combo_users(tmp_path):
+    """A local combo user list travels inside the bundle; a URL stays a URL"""
+    (tmp_path / 'users.txt').write_text('alice\nbob\n')
+    config = Config(min_length=1, max_length=1, charset='x', combo_users='users.txt')
+    bundle_path = tmp_path / 'job.omni'
+    create_bundle(config, bundle_path, base_dir=tmp_path)
+    (tmp_path / 'users.txt').unlink()
+    loaded = load_bundle(bundle_path, extract_dir=tmp_path / 'unpacked')
+    assert list(Generator(loaded).output()) == ['alice:x', 'bob:x']
+
+    config.combo_users = 'https://example.com/users.txt'
+    create_bundle(config, bundle_path, base_dir=tmp_path)
+    assert load_bundle(bundle_path, extract_dir=tmp_path / 'unpacked').combo_users == config.combo_users
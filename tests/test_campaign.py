@@ -0,0 +1,71 @@
+"""
+Tests for cross-run campaign deduplication
+"""
+
+from omniwordlist import Config, Generator, CancellationToken
+from omniwordlist.campaign import CampaignStore, forget_campaign, list_campaigns
+from omniwordlist.storage import CheckpointManager
+
+from tests.helpers import CancelAfter
+
+
+def test_later_runs_skip_tokens_of_earlier_runs(tmp_path):
+    """A second strategy of the same campaign only writes new tokens"""
+    db = tmp_path / 'campaigns.db'
+    first = Generator(Config(words=['alpha', 'bravo'], campaign='acme', campaign_db=db,
+                             output_file=tmp_path / 'first.txt'))
+    first.write()
+    # The run closes its database once generation ends
+    assert first.campaign.connection is None
+
+    second = Generator(Config(words=['bravo', 'charlie', 'alpha', 'delta'], campaign='acme', campaign_db=db,
+                              output_file=tmp_path / 'second.txt'))
+    second.write()
+    assert (tmp_path / 'second.txt').read_text().split() == ['charlie', 'delta']
+    assert second.rejections == {'campaign': 2}
+
+    # Other campaigns and runs without a campaign are unaffected
+    other = Generator(Config(words=['alpha'], campaign='globex', campaign_db=db))
+    assert other.generate_list() == ['alpha']
+    assert Generator(Config(words=['alpha'])).generate_list() == ['alpha']
+
+    campaigns = {entry['campaign']: entry for entry in list_campaigns(db)}
+    assert campaigns['acme']['runs'] == 2 and campaigns['acme']['tokens'] == 4
+    assert forget_campaign(db, 'acme') == 4
+    assert Generator(Config(words=['alpha'], campaign='acme', campaign_db=db)).generate_list() == ['alpha']
+
+
+def test_previews_do_not_record(tmp_path):
+    """Only written output is recorded; an unrecorded run can repeat"""
+    config = Config(words=['alpha', 'bravo'], campaign='acme', campaign_db=tmp_path / 'c.db')
+    assert Generator(config).preview() == ['alpha', 'bravo']
+    assert Generator(config).generate_list() == ['alpha', 'bravo']
+
+
+def test_store_ignores_its_own_run(tmp_path):
+    """Tokens count as seen only when another run recorded them"""
+    store = CampaignStore(tmp_path / 'c.db', 'acme', run_id='one')
+    store.record('alpha')
+    store.flush()
+    assert not store.seen('alpha')
+    assert CampaignStore(tmp_path / 'c.db', 'acme', run_id='two').seen('alpha')
+    assert not CampaignStore(tmp_path / 'c.db', 'other').seen('alpha')
+
+
+def test_resumed_run_keeps_its_campaign_run(tmp_path):
+    """A cancelled campaign run resumed from its checkpoint matches a full run"""
+    full = Generator(Config(min_length=1, max_length=2, charset='abc')).generate_list()
+
+    output = tmp_path / 'out.txt'
+    config = Config(min_length=1, max_length=2, charset='abc', output_file=output,
+                    campaign='acme', campaign_db=tmp_path / 'c.db',
+                    checkpoint_dir=tmp_path / 'ck', checkpoint_interval=2)
+    token = CancellationToken()
+    generator = Generator(config, cancel_token=token)
+    generator.add_observer(CancelAfter(token, 5))
+    generator.write()
+
+    resumed = Generator.resume(generator.job_id, CheckpointManager(tmp_path / 'ck'))
+    assert resumed.campaign.run_id == generator.campaign.run_id
+    resumed.write()
+    assert output.read_text().split() == full
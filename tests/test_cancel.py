@@ -0,0 +1,206 @@
+"""
+Tests for cooperative cancellation and checkpoint resume
+"""
+
+import gzip
+import os
+import signal
+import tempfile
+from pathlib import Path
+
+import pytest
+
+from omniwordlist import Config, Generator, CancellationToken
+from omniwordlist import generator as generator_module
+from omniwordlist.cancel import interrupt_cancels
+from omniwordlist.error import ConfigError, GeneratorError
+from omniwordlist.storage import CheckpointManager
+
+from tests.helpers import CancelAfter
+
+
+def test_cancel_stops_generation_with_partial_stats():
+    """A cancelled run stops early and reports how far it got"""
+    token = CancellationToken()
+    generator = Generator(Config(min_length=2, max_length=2, charset='abc'), cancel_token=token)
+    generator.add_observer(CancelAfter(token, 4))
+
+    tokens = list(generator.generate())
+
+    assert tokens == ['aa', 'ab', 'ac', 'ba']
+    stats = generator.get_stats()
+    assert stats['cancelled']
+    assert stats['last_token'] == 'ba'
+    assert stats['tokens_generated'] == 4
+
+
+def test_cancel_writes_resumable_checkpoint():
+    """Resuming from the cancel checkpoint yields exactly the remaining tokens"""
+    with tempfile.TemporaryDirectory() as tmpdir:
+        config = Config(min_length=2, max_length=2, charset='abc', checkpoint_dir=Path(tmpdir))
+        token = CancellationToken()
+        generator = Generator(config, cancel_token=token)
+        generator.add_observer(CancelAfter(token, 4))
+        head = list(generator.generate())
+
+        state = CheckpointManager(Path(tmpdir)).load_checkpoint(generator.job_id)
+        assert state is not None
+        resumed = Generator.from_checkpoint(state)
+        tail = list(resumed.generate())
+
+        full = list(Generator(Config(min_length=2, max_length=2, charset='abc')).generate())
+        assert head + tail == full
+        assert resumed.tokens_generated == len(full)
+
+
+def test_resume_output_file_matches_uninterrupted_run(tmp_path):
+    """A cancelled write resumed into its file ends up identical to a full run"""
+    full = tmp_path / 'full.txt'
+    Generator(Config(min_length=1, max_length=3, charset='abcd', output_file=full)).write()
+    
+    output = tmp_path / 'out.txt'
+    config = Config(min_length=1, max_length=3, charset='abcd', output_file=output,
+                    checkpoint_dir=tmp_path / 'ck', checkpoint_interval=10)
+    token = CancellationToken()
+    generator = Generator(config, cancel_token=token)
+    generator.add_observer(CancelAfter(token, 25))
+    generator.write()
+    
+    manager = CheckpointManager(tmp_path / 'ck')
+    state = manager.load_checkpoint(generator.job_id)
+    assert state['tokens_written'] == 25 and state['current_length'] == 3
+    assert state['status'] == 'paused'
+    
+    # Lines written after the checkpoint (a hard kill) are cut on resume
+    with open(output, 'a') as f:
+        f.write('zz\n')
+    resumed = Generator.resume(generator.job_id, manager)
+    resumed.write()
+    assert output.read_text() == full.read_text()
+    assert manager.load_checkpoint(generator.job_id) is None
+    
+    # Dedupe is refilled from written tokens, which only plain UTF-8 lines give back
+    for fmt, encoding in (('jsonl', 'utf-8'), ('txt', 'utf-16le')):
+        with pytest.raises(ConfigError):
+            Config(dedupe=True, format=fmt, output_encoding=encoding, checkpoint_dir=tmp_path).validate()
+
+
+def test_resume_refuses_a_reordered_charset_file(tmp_path):
+    """A checkpoint records the alphabet ordering; resuming under another one fails"""
+    charset_file = tmp_path / 'charset.txt'
+    charset_file.write_text('abc')
+    config = Config(min_length=2, max_length=2, charset_file=str(charset_file), checkpoint_dir=tmp_path)
+    token = CancellationToken()
+    generator = Generator(config, cancel_token=token)
+    generator.add_observer(CancelAfter(token, 4))
+    list(generator.generate())
+    state = CheckpointManager(tmp_path).load_checkpoint(generator.job_id)
+    assert state['alphabets'] == ['abc']
+    
+    charset_file.write_text('cba')
+    with pytest.raises(GeneratorError, match='Alphabet ordering'):
+        Generator.from_checkpoint(state)
+
+
+def test_resume_from_output_without_checkpoint(tmp_path):
+    """The last complete line is found by rank (plain) or by generation (transforms, gzip)"""
+    config = Config(min_length=1, max_length=3, charset='abc', suffix='!')
+    full = Generator(config).generate_list()
+    output = tmp_path / 'out.txt'
+    output.write_text('\n'.join(full[:20]) + '\nab')
+    resumed = Generator.resume_from_output(Config(min_length=1, max_length=3, charset='abc', suffix='!'), output)
+    assert resumed.tokens_written == 20
+    resumed.write()
+    assert output.read_text().splitlines() == full
+    
+    config = Config(min_length=1, max_length=6, charset='abc', transforms=['uppercase'], dedupe=True)
+    full = Generator(config).generate_list()
+    output = tmp_path / 'out.txt.gz'
+    data = gzip.compress(('\n'.join(full) + '\n').encode())
+    # A run killed mid-write leaves a stream that breaks off
+    output.write_bytes(data[:len(data) // 2])
+    resumed = Generator.resume_from_output(config, output)
+    assert 0 < resumed.tokens_written < len(full)
+    resumed.write()
+    with gzip.open(output, 'rt') as f:
+        assert f.read().splitlines() == full
+    
+    output = tmp_path / 'other.txt'
+    output.write_text('a\nzz\n')
+    with pytest.raises(GeneratorError, match='does not match'):
+        Generator.resume_from_output(Config(min_length=1, max_length=3, charset='abc', transforms=['uppercase']),
+                                     output)
+
+
+def test_periodic_checkpoints_and_dedupe_resume(tmp_path):
+    """Checkpoints follow the interval; a resumed dedupe run skips earlier tokens"""
+    words = ['a', 'b', 'a', 'c', 'b', 'd']
+    full = Generator(Config(words=words, dedupe=True)).generate_list()
+    
+    output = tmp_path / 'out.txt'
+    config = Config(words=words, dedupe=True, output_file=output,
+                    checkpoint_dir=tmp_path, checkpoint_interval=2)
+    generator = Generator(config)
+    backend = generator.open_backend()
+    with backend:
+        lines = generator.write_lines(backend)
+        next(lines), next(lines), next(lines)
+    state = CheckpointManager(tmp_path).load_checkpoint(generator.job_id)
+    assert state['tokens_written'] == 2 and state['last_token'] == 'b'
+    assert state['status'] == 'running'
+    
+    resumed = Generator.resume(generator.job_id, CheckpointManager(tmp_path))
+    resumed.write()
+    assert output.read_text().splitlines() == full
+
+
+def test_cancelled_write_flushes_before_the_backend_closes(tmp_path):
+    """The paused checkpoint describes output already on disk"""
+    output = tmp_path / 'out.txt'
+    token = CancellationToken()
+    generator = Generator(Config(min_length=1, max_length=3, charset='abc', output_file=output,
+                                 checkpoint_dir=tmp_path / 'ck'), cancel_token=token)
+    generator.add_observer(CancelAfter(token, 7))
+    backend = generator.open_backend()
+    with backend:
+        assert len(list(generator.write_lines(backend))) == 7
+        state = CheckpointManager(tmp_path / 'ck').load_checkpoint(generator.job_id)
+        assert output.stat().st_size == state['output_bytes']
+
+
+def test_first_interrupt_cancels_and_second_force_quits():
+    """Ctrl-C sets the cancellation flag once, then raises KeyboardInterrupt"""
+    token = CancellationToken()
+    notified = []
+    previous = signal.getsignal(signal.SIGINT)
+    try:
+        with interrupt_cancels(token, lambda: notified.append(True)):
+            os.kill(os.getpid(), signal.SIGINT)
+            assert token.is_cancelled() and notified == [True]
+            os.kill(os.getpid(), signal.SIGINT)
+        assert False, "second Ctrl-C did not force-quit"
+    except KeyboardInterrupt:
+        pass
+    assert signal.getsignal(signal.SIGINT) is previous
+
+
+def test_resume_mid_expansion_skips_finished_words(tmp_path, monkeypatch):
+    """Checkpoints record the variant within a word; resuming does not re-expand earlier words"""
+    config = Config(words=['alpha', 'bravo', 'charlie'], smart_case=3, checkpoint_dir=tmp_path)
+    full = Generator(config).generate_list()
+    
+    token = CancellationToken()
+    generator = Generator(config, cancel_token=token)
+    generator.add_observer(CancelAfter(token, 5))
+    head = generator.generate_list()
+    state = CheckpointManager(tmp_path).load_checkpoint(generator.job_id)
+    assert (state['base_index'], state['variant_index']) == (1, 2)
+    
+    expanded = []
+    real_case_variants = generator_module.case_variants
+    monkeypatch.setattr(generator_module, 'case_variants',
+                        lambda word, *args: expanded.append(word) or real_case_variants(word, *args))
+    resumed = Generator.from_checkpoint(state)
+    assert head + resumed.generate_list() == full
+    assert expanded == ['bravo', 'charlie']
+    assert resumed.candidates_seen == generator.candidates_seen + len(full) - len(head)
@@ -0,0 +1,104 @@
+"""
+Tests for cluster coordination
+"""
+
+import threading
+import urllib.error
+import urllib.request
+
+import pytest
+
+from omniwordlist import Config, Generator
+from omniwordlist.cluster import Coordinator, chunk_manifest, load_chunk_manifest, make_server, run_worker
+from omniwordlist.error import ConfigError, GeneratorError, StorageError
+
+
+class FakeClock:
+    def __init__(self):
+        self.now = 0.0
+    
+    def __call__(self):
+        return self.now
+
+
+def test_timed_out_chunks_are_reissued():
+    """A chunk whose worker goes quiet is handed to the next claimant"""
+    clock = FakeClock()
+    coordinator = Coordinator(Config(), keyspace_size=25, chunk_size=10, timeout=60, clock=clock)
+    
+    assert coordinator.claim('a')['chunk'] == 0
+    clock.now = 30
+    assert coordinator.claim('b') == {'chunk': 1, 'start': 10, 'end': 20}
+    clock.now = 61
+    assert coordinator.claim('c')['chunk'] == 0
+    assert coordinator.claim('c') == {'chunk': 2, 'start': 20, 'end': 25}
+    assert coordinator.claim('c') is None
+    
+    assert coordinator.complete('c', 0, 5)
+    assert not coordinator.complete('a', 0, 5)
+    coordinator.complete('b', 1, 10)
+    coordinator.complete('c', 2, 5)
+    status = coordinator.status()
+    assert status['done'] and status['reissued'] == 1
+    assert status['workers']['c'] == {'claimed': 2, 'completed': 2, 'tokens': 10}
+
+
+def test_workers_reproduce_single_run(tmp_path):
+    """Chunk files from workers concatenate to the single-run output"""
+    config = Config(min_length=1, max_length=2, charset='abcde')
+    expected = Generator(config).generate_list()
+    coordinator = Coordinator(config, Generator(config).keyspace_size(), chunk_size=4)
+    server = make_server(coordinator, 's3cret', '127.0.0.1', 0)
+    threading.Thread(target=server.serve_forever, daemon=True).start()
+    try:
+        url = f"http://127.0.0.1:{server.server_address[1]}"
+        assert run_worker(url, 's3cret', tmp_path, 'w1', max_chunks=3) == 3
+        run_worker(url, 's3cret', tmp_path, 'w2')
+    finally:
+        server.shutdown()
+    
+    assert coordinator.done
+    tokens = [line for path in sorted(tmp_path.glob('chunk-*.txt'))
+              for line in path.read_text().splitlines()]
+    assert tokens == expected
+
+
+def test_coordinator_requires_the_token(tmp_path):
+    """Requests without the shared token are refused on every endpoint"""
+    config = Config(min_length=1, max_length=1, charset='ab')
+    coordinator = Coordinator(config, Generator(config).keyspace_size(), chunk_size=1)
+    with pytest.raises(ConfigError, match='shared token'):
+        make_server(coordinator, '')
+    server = make_server(coordinator, 's3cret', '127.0.0.1', 0)
+    threading.Thread(target=server.serve_forever, daemon=True).start()
+    try:
+        url = f"http://127.0.0.1:{server.server_address[1]}"
+        for path, data in (('/job', None), ('/status', None), ('/claim', b'{}'), ('/complete', b'{"chunk": 0}')):
+            request = urllib.request.Request(url + path, data=data, headers={'X-Omni-Token': 'wrong'})
+            with pytest.raises(urllib.error.HTTPError) as e:
+                urllib.request.urlopen(request)
+            assert e.value.code == 401
+        with pytest.raises(StorageError, match='401'):
+            run_worker(url, 'wrong', tmp_path, 'w1')
+    finally:
+        server.shutdown()
+    assert coordinator.status()['workers'] == {}
+
+
+def test_chunk_manifest_pins_the_alphabet_ordering(tmp_path):
+    """Chunks from a manifest are refused once the charset resolves to another ordering"""
+    charset_file = tmp_path / 'charset.txt'
+    charset_file.write_text('abc')
+    config = Config(min_length=1, max_length=2, charset_file=str(charset_file))
+    manifest = chunk_manifest(config, 5)
+    assert (manifest['alphabets'], manifest['keyspace'], manifest['total_chunks']) == (['abc'], 12, 3)
+    
+    generator, chunk_size = load_chunk_manifest(manifest)
+    assert chunk_size == 5 and list(generator.generate_range(0, 5)) == ['a', 'b', 'c', 'aa', 'ab']
+    
+    charset_file.write_text('bac')
+    with pytest.raises(GeneratorError, match='Alphabet ordering'):
+        load_chunk_manifest(manifest)
+    
+    pattern = Config(pattern='1%1', custom_charsets=['yx'])
+    assert Generator(pattern).alphabets() == ['yx', '0123456789']
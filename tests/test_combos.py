@@ -0,0 +1,43 @@
+"""
+Tests for combo-list output
+"""
+
+import pytest
+
+from omniwordlist import Config, Generator
+from omniwordlist.error import ConfigError
+
+
+def combo_config(**kwargs):
+    return Config(min_length=1, max_length=1, charset='abc', combo_users=['alice', 'bob'], **kwargs)
+
+
+def test_product_is_password_major():
+    """Each password is paired with every user before the next password"""
+    generator = Generator(combo_config())
+    assert list(generator.output())[:4] == ['alice:a', 'bob:a', 'alice:b', 'bob:b']
+    assert generator.estimate_count() == 6
+
+
+def test_zip_and_per_user():
+    """zip pairs users and passwords one to one; per_user caps passwords"""
+    generator = Generator(combo_config(combo_mode='zip', combo_separator=';'))
+    assert list(generator.output()) == ['alice;a', 'bob;b']
+    assert generator.estimate_count() == 2
+
+    generator = Generator(combo_config(combo_mode='per_user', combo_per_user=1))
+    assert list(generator.output()) == ['alice:a', 'bob:a']
+    assert generator.estimate_count() == 2
+
+
+def test_per_user_needs_count():
+    """per_user mode requires combo_per_user"""
+    with pytest.raises(ConfigError, match='combo_per_user'):
+        combo_config(combo_mode='per_user').validate()
+
+
+def test_max_lines_caps_combo_lines():
+    """max_lines counts user:pass lines, not passwords"""
+    generator = Generator(combo_config(max_lines=3))
+    assert list(generator.output()) == ['alice:a', 'bob:a', 'alice:b']
+    assert generator.estimate_count() == 3
@@ -0,0 +1,34 @@
+"""
+Tests for config loading: unknown and deprecated keys
+"""
+
+import pytest
+
+from omniwordlist import Config
+from omniwordlist import config as config_module
+from omniwordlist.config import ConfigWarning
+from omniwordlist.error import ConfigError
+
+
+def test_unknown_key_suggests_fix():
+    """Typos are rejected with the closest valid key"""
+    with pytest.raises(ConfigError, match="max_lenght \\(did you mean max_length\\?\\)"):
+        Config.from_dict({'min_length': 1, 'max_lenght': 8})
+    with pytest.raises(ConfigError, match="filters.min_entropi \\(did you mean filters.min_entropy\\?\\)"):
+        Config.from_dict({'filters': {'min_entropi': 2.0}})
+    with pytest.raises(ConfigError, match="Unknown config key: zzz$"):
+        Config.from_dict({'zzz': 1})
+
+
+def test_deprecated_keys_are_renamed(monkeypatch):
+    """Deprecated keys still load, with a warning"""
+    monkeypatch.setattr(config_module, 'DEPRECATED_KEYS', {'max_len': 'max_length', 'legacy': None})
+    with pytest.warns(ConfigWarning, match='max_len is deprecated, use max_length'):
+        config = Config.from_dict({'max_len': 6, 'legacy': True})
+    assert config.max_length == 6
+
+
+def test_round_trip_has_no_unknown_keys():
+    """Serialized configs load back unchanged"""
+    config = Config(words=['a'], max_keyspace=10)
+    assert Config.from_dict(config.to_dict()).to_dict() == config.to_dict()
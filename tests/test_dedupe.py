@@ -0,0 +1,54 @@
+"""
+Tests for exact and Bloom-filter deduplication
+"""
+
+import pytest
+
+from omniwordlist import Config, Generator
+from omniwordlist.dedupe import BloomDeduper, ExactDeduper, bloom_bits, bloom_capacity, MIN_BLOOM_CAPACITY
+from omniwordlist.error import ConfigError
+
+
+def test_exact_mode_keeps_128_bit_hashes():
+    """Exact dedupe stores one 16-byte hash per unique token"""
+    deduper = ExactDeduper()
+    assert deduper.add('alpha') and deduper.add('Alpha')
+    assert not deduper.add('alpha')
+    assert len(deduper) == 2
+    assert all(len(digest) == 16 for digest in deduper.hashes)
+    assert deduper.memory_bytes() > 0
+
+
+def test_bloom_filter_drops_every_duplicate_and_few_new_tokens():
+    """A Bloom filter never lets a duplicate through; false positives stay near the configured rate"""
+    deduper = BloomDeduper(10000, 0.01)
+    assert deduper.memory_bytes() == (bloom_bits(10000, 0.01) + 7) // 8
+    assert deduper.hash_count == 7
+
+    dropped = sum(1 for i in range(10000) if not deduper.add(f'token{i}'))
+    assert dropped < 10000 * 0.01 * 2
+    assert not any(deduper.add(f'token{i}') for i in range(0, 10000, 7))
+
+
+def test_generator_bloom_mode_sizes_from_the_estimate():
+    """Bloom mode is sized from the keyspace estimate and reports its memory"""
+    config = Config(min_length=1, max_length=2, charset='abc', transforms=['uppercase'],
+                    dedupe=True, dedupe_mode='bloom')
+    generator = Generator(config)
+    tokens = generator.generate_list()
+    assert len(tokens) == len(set(tokens)) == 12
+    assert generator.deduper.capacity == bloom_capacity(12) == MIN_BLOOM_CAPACITY
+    assert generator.progress_stats().dedupe_memory == generator.deduper.memory_bytes()
+    assert generator.get_stats()['dedup_cache_size'] == 12
+
+    config.dedupe_capacity = 50000
+    assert Generator(config).dedupe_filter().capacity == 50000
+
+
+def test_dedupe_mode_validation():
+    with pytest.raises(ConfigError):
+        Config(dedupe_mode='fuzzy').validate()
+    with pytest.raises(ConfigError):
+        Config(dedupe_mode='bloom', bloom_fp_rate=0).validate()
+    with pytest.raises(ConfigError):
+        Config(dedupe_capacity=0).validate()
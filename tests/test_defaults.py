@@ -0,0 +1,38 @@
+"""
+Tests for default device credentials
+"""
+
+import pytest
+
+from omniwordlist.defaults import get_default_credentials, list_vendors
+from omniwordlist.error import ConfigError
+from omniwordlist.presets import PresetManager
+from omniwordlist.storage import write_combos, write_split_credentials
+
+
+def test_vendor_credentials_and_aliases():
+    """Vendors resolve through aliases and pairs are deduplicated"""
+    pairs = get_default_credentials(['D-Link', 'tplink'])
+    assert ('admin', '') in pairs
+    assert pairs.count(('admin', 'admin')) == 1
+    assert 'hikvision' in list_vendors('camera')
+
+    with pytest.raises(ConfigError, match='acme'):
+        get_default_credentials(['acme'])
+
+
+def test_combo_and_split_output(tmp_path):
+    """Pairs are written as combos or as separate user and password files"""
+    pairs = [('admin', 'admin'), ('admin', '1234'), ('root', '1234')]
+    assert write_combos(pairs, tmp_path / 'combo.txt') == 3
+    assert (tmp_path / 'combo.txt').read_text().splitlines() == ['admin:admin', 'admin:1234', 'root:1234']
+
+    users, passwords = write_split_credentials(pairs, tmp_path / 'u.txt', tmp_path / 'p.txt')
+    assert (users, passwords) == (2, 2)
+    assert (tmp_path / 'p.txt').read_text().splitlines() == ['admin', '1234']
+
+
+def test_device_presets():
+    """Device presets emit vendor default passwords"""
+    config = PresetManager().get_preset_config('router_defaults')
+    assert 'password' in config.words and '' not in config.words
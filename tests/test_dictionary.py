@@ -0,0 +1,42 @@
+"""
+Tests for memory-mapped dictionaries
+"""
+
+import itertools
+
+from omniwordlist import Config, Generator
+from omniwordlist import sources
+from omniwordlist.dictionary import MmapDictionary, sequence_product
+
+
+def test_mmap_dictionary_indexes_lines(tmp_path):
+    """Blank lines are skipped and CRLF endings stripped"""
+    path = tmp_path / 'words.txt'
+    path.write_bytes(b'alpha\r\n\nbeta\n  \ngamma')
+    words = MmapDictionary(path)
+    
+    assert len(words) == 3
+    assert list(words) == ['alpha', 'beta', 'gamma']
+    assert words[-1] == 'gamma'
+    assert words[0:2] == ['alpha', 'beta']
+    words.close()
+
+
+def test_sequence_product_matches_itertools():
+    """Index-based product keeps itertools.product order"""
+    inputs = [['a', 'b'], ['1', '2', '3'], ['x']]
+    assert list(sequence_product(inputs)) == list(itertools.product(*inputs))
+    assert list(sequence_product([['a'], []])) == []
+
+
+def test_large_field_sources_are_mapped(tmp_path, monkeypatch):
+    """Field sources above the threshold are served from the mapping"""
+    monkeypatch.setattr(sources, 'MMAP_MIN_BYTES', 1)
+    path = tmp_path / 'names.txt'
+    path.write_text('ann\nbob\n')
+    config = Config(enabled_fields=['names', 'year'],
+                    field_sources={'names': str(path), 'year': ['1', '2']})
+    generator = Generator(config)
+    
+    assert isinstance(generator._field_values()[0], MmapDictionary)
+    assert generator.generate_list() == ['ann1', 'ann2', 'bob1', 'bob2']
@@ -0,0 +1,60 @@
+"""
+Tests for directory export ingestion
+"""
+
+import pytest
+
+from omniwordlist import Generator
+from omniwordlist.directory import employee_config, parse_directory_export, username_candidates
+from omniwordlist.error import ConfigError
+
+
+EXPORT = (
+    '\ufeff"displayName";"sAMAccountName";"department";"l";"whenCreated"\n'
+    '"Doe, Jane";"jdoe";"Finance";"Pune";"2020-01-01"\n'
+    '"Raj Kumar";"";"Sales";"";""\n'
+)
+
+
+def test_columns_map_to_attributes(tmp_path):
+    """Semicolon exports with a BOM are sniffed and names are split"""
+    path = tmp_path / 'users.csv'
+    path.write_text(EXPORT, encoding='utf-8')
+    parsed = parse_directory_export(path)
+    
+    assert parsed.columns == {'displayName': 'display_name', 'sAMAccountName': 'username',
+                              'department': 'department', 'l': 'city'}
+    jane, raj = parsed.employees
+    assert (jane.first_name, jane.last_name, jane.city) == ('Jane', 'Doe', 'Pune')
+    assert jane.extra == {'whenCreated': '2020-01-01'}
+    assert (raj.first_name, raj.last_name, raj.key) == ('Raj', 'Kumar', 'raj.kumar')
+
+
+def test_username_candidates(tmp_path):
+    """The exported account name comes first, then the formats"""
+    path = tmp_path / 'users.csv'
+    path.write_text(EXPORT, encoding='utf-8')
+    jane = parse_directory_export(path).employees[0]
+    
+    assert username_candidates(jane, ['first.last', 'flast']) == ['jdoe', 'jane.doe']
+    with pytest.raises(ConfigError, match='Unknown username format'):
+        username_candidates(jane, ['bogus'])
+
+
+def test_employee_config_combines_attributes(tmp_path):
+    """Attributes and years are combined one or two at a time"""
+    path = tmp_path / 'users.csv'
+    path.write_text(EXPORT, encoding='utf-8')
+    raj = parse_directory_export(path).employees[1]
+    tokens = set(Generator(employee_config(raj, years=[2024])).generate())
+    
+    assert {'Raj', 'kumar', 'Sales2024', 'raj2024', 'KumarRaj'} <= tokens
+    assert 'RajKumarSales' not in tokens
+
+
+def test_unrecognized_export(tmp_path):
+    """Files without directory columns are rejected"""
+    path = tmp_path / 'other.csv'
+    path.write_text('a,b\n1,2\n')
+    with pytest.raises(ConfigError, match='No recognized directory columns'):
+        parse_directory_export(path)
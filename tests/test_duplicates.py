@@ -0,0 +1,96 @@
+"""
+Tests for class-aware duplicate limits in pattern mode
+"""
+
+import itertools
+
+import pytest
+
+from omniwordlist import Config, Generator
+from omniwordlist.charset import check_duplicate_limit, parse_duplicate_limit
+from omniwordlist.error import ConfigError
+
+
+def test_parse_duplicate_limit():
+    """Items pair a count with a marker; a bare count covers every class"""
+    assert parse_duplicate_limit('2@') == {'@': 2}
+    assert parse_duplicate_limit('2@,3%') == {'@': 2, '%': 3}
+    assert parse_duplicate_limit('2@3%') == {'@': 2, '%': 3}
+    assert parse_duplicate_limit('2,') == {',': 2}
+    assert parse_duplicate_limit('1') == {'': 1}
+    for spec in ('0@', 'x2', ''):
+        with pytest.raises(ConfigError):
+            parse_duplicate_limit(spec)
+
+
+def test_limits_apply_per_class():
+    """Only runs within one marker class count; other classes stay unlimited"""
+    config = Config(pattern='@@%%', duplicate_limit='1%', max_length=10)
+    tokens = list(Generator(config).generate())
+    assert len(tokens) == 26 * 26 * 90
+    assert 'aa12' in tokens and 'aa11' not in tokens
+    
+    # Same order as the unlimited product, minus the pruned combinations
+    unlimited = Generator(Config(pattern='@@%%', max_length=10)).generate()
+    assert tokens == [t for t in unlimited if t[2] != t[3]]
+
+
+def test_literals_exempt():
+    """Literal characters neither count towards nor continue a run"""
+    config = Config(pattern='@aa@', duplicate_limit='1@', max_length=10)
+    tokens = list(Generator(config).generate())
+    assert 'aaaa' in tokens
+    assert len(tokens) == 26 * 26
+    
+    config = Config(pattern='%%-%%', duplicate_limit='2', max_length=10)
+    tokens = set(Generator(config).generate())
+    assert '11-11' in tokens
+
+
+def test_marker_runs_not_adjacent_in_class():
+    """A run ends where the pattern switches to another marker"""
+    config = Config(pattern='%@%', duplicate_limit='1%', max_length=10)
+    expected = [''.join(c) for c in itertools.product('0123456789', 'abcdefghijklmnopqrstuvwxyz', '0123456789')]
+    assert list(Generator(config).generate()) == expected
+
+
+def test_parse_marker_without_count():
+    """A marker without a count forbids adjacent repeats in its class"""
+    assert parse_duplicate_limit('@') == {'@': 1}
+    assert parse_duplicate_limit('@,%') == {'@': 1, '%': 1}
+    assert parse_duplicate_limit('2@%') == {'@': 2, '%': 1}
+
+
+def test_check_duplicate_limit_by_class():
+    """Without a pattern, runs are limited by the class of the repeated character"""
+    limits = parse_duplicate_limit('2@')
+    assert check_duplicate_limit('aab1', limits)
+    assert not check_duplicate_limit('aaab1', limits)
+    assert check_duplicate_limit('a111', limits)
+    assert check_duplicate_limit('AAAA', limits)
+    
+    # Mixed specs limit each class on its own
+    limits = parse_duplicate_limit('2@,3%')
+    assert check_duplicate_limit('aa111', limits)
+    assert not check_duplicate_limit('aa1111', limits)
+    assert not check_duplicate_limit('aaa111', limits)
+    assert check_duplicate_limit('aa!!!!', limits)
+    
+    # No count: no adjacent repeats
+    limits = parse_duplicate_limit('%')
+    assert check_duplicate_limit('aaa1212', limits)
+    assert not check_duplicate_limit('a11', limits)
+    
+    # A bare count limits every character
+    assert not check_duplicate_limit('!!!', parse_duplicate_limit('2'))
+
+
+def test_charset_mode_duplicate_limit():
+    """Charset mode drops tokens whose runs exceed the limit of their class"""
+    config = Config(charset='a1', min_length=3, max_length=3, duplicate_limit='2@')
+    generator = Generator(config)
+    tokens = list(generator.generate())
+    assert 'aaa' not in tokens
+    assert '111' in tokens and 'aa1' in tokens
+    assert len(tokens) == 7
+    assert generator.rejections == {'duplicate_limit': 1}
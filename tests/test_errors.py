@@ -0,0 +1,37 @@
+"""
+Tests for error codes and exit statuses
+"""
+
+import pytest
+
+from omniwordlist import Config, Generator
+from omniwordlist.error import (ConfigError, GenerationInterrupted, KeyspaceTooLargeError, StorageError,
+                                EXIT_CONFIG, EXIT_ERROR, EXIT_INTERRUPTED, EXIT_IO,
+                                EXIT_KEYSPACE_TOO_LARGE, error_payload, exit_code_for)
+
+
+def test_exit_codes_by_error_type():
+    """Each failure class maps to its own exit status"""
+    assert exit_code_for(ConfigError('bad')) == EXIT_CONFIG
+    assert exit_code_for(StorageError('disk')) == EXIT_IO
+    assert exit_code_for(FileNotFoundError('missing')) == EXIT_IO
+    assert exit_code_for(GenerationInterrupted('stop', 'job1')) == EXIT_INTERRUPTED
+    assert exit_code_for(RuntimeError('boom')) == EXIT_ERROR
+    # click exits with 2 on usage errors
+    assert 2 not in {EXIT_CONFIG, EXIT_ERROR, EXIT_INTERRUPTED, EXIT_IO, EXIT_KEYSPACE_TOO_LARGE}
+
+
+def test_error_payload():
+    """Payloads carry a stable code, the message and the exit status"""
+    assert error_payload(GenerationInterrupted('stopped', 'abc123')) == {
+        'error': 'interrupted', 'type': 'GenerationInterrupted', 'message': 'stopped',
+        'exit_code': EXIT_INTERRUPTED, 'job_id': 'abc123'}
+    assert error_payload(PermissionError('denied'))['error'] == 'io_error'
+
+
+def test_max_keyspace_refuses_large_jobs():
+    """Generators refuse keyspaces above max_keyspace"""
+    with pytest.raises(KeyspaceTooLargeError) as info:
+        Generator(Config(min_length=1, max_length=3, charset='abc', max_keyspace=38))
+    assert info.value.exit_code == EXIT_KEYSPACE_TOO_LARGE
+    assert Generator(Config(min_length=1, max_length=3, charset='abc', max_keyspace=39))
@@ -0,0 +1,29 @@
+"""
+Tests for attack-time estimation
+"""
+
+import pytest
+
+from omniwordlist.estimate import parse_hash_rate, attack_seconds, format_duration, rate_for_mode
+from omniwordlist.error import ConfigError
+
+
+def test_parse_hash_rate():
+    """Hash rates accept SI suffixes with or without H/s"""
+    assert parse_hash_rate("50GH/s") == 50e9
+    assert parse_hash_rate("1.5 MH/s") == 1.5e6
+    assert parse_hash_rate("200k") == 200e3
+    assert parse_hash_rate("1000") == 1000
+    with pytest.raises(ConfigError):
+        parse_hash_rate("fast")
+
+
+def test_attack_duration():
+    """Durations scale with device count and format two units"""
+    assert attack_seconds(10 ** 12, 1e9) == 1000
+    assert attack_seconds(10 ** 12, 1e9, devices=4) == 250
+    assert format_duration(90061) == "1 day 1 hour"
+    assert format_duration(365 * 86400 + 5 * 3600) == "1 year"
+    assert format_duration(2 * 86400 + 59) == "2 days"
+    assert format_duration(0.5) == "under a second"
+    assert rate_for_mode("NTLM") > rate_for_mode("bcrypt")
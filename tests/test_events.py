@@ -0,0 +1,47 @@
+"""
+Tests for generator event hooks
+"""
+
+from omniwordlist import Config, Generator, GeneratorObserver
+
+
+class RecordingObserver(GeneratorObserver):
+    def __init__(self):
+        self.tokens = []
+        self.progress = []
+        self.stages = []
+
+    def on_token(self, token):
+        self.tokens.append(token)
+
+    def on_progress(self, tokens_generated):
+        self.progress.append(tokens_generated)
+
+    def on_stage_change(self, stage):
+        self.stages.append(stage)
+
+
+def test_observer_receives_events():
+    """Observers see every token, periodic progress and stage changes"""
+    config = Config(min_length=1, max_length=2, charset='ab', progress_interval=2)
+    generator = Generator(config)
+    observer = RecordingObserver()
+    generator.add_observer(observer)
+
+    tokens = list(generator.generate())
+
+    assert observer.tokens == tokens
+    assert observer.progress == [2, 4, 6]
+    assert observer.stages == ['charset', 'length:1', 'length:2', 'finished']
+
+
+def test_observer_removed():
+    """Removed observers stop receiving events"""
+    generator = Generator(Config(min_length=1, max_length=1, charset='ab'))
+    observer = RecordingObserver()
+    generator.add_observer(observer)
+    generator.remove_observer(observer)
+
+    list(generator.generate())
+
+    assert observer.tokens == []
@@ -0,0 +1,41 @@
+"""
+Tests for single-token explanations
+"""
+
+from omniwordlist import Config
+from omniwordlist.config import FilterConfig
+from omniwordlist.explain import explain_token
+
+
+def test_produced_token_rank():
+    """Produced tokens report their position among emitted tokens"""
+    config = Config(charset='ab', min_length=1, max_length=2)
+    explanation = explain_token(config, 'ba')
+    assert explanation.produced is True
+    assert explanation.rank == 5
+    assert explanation.passes_filters
+
+
+def test_filtered_token():
+    """Failing filters are reported and the candidate's rejecting stage recorded"""
+    config = Config(charset='ab', min_length=1, max_length=3,
+                    filters=FilterConfig(min_len=1, max_len=3, max_repeats=1))
+    explanation = explain_token(config, 'aab')
+    assert explanation.produced is False
+    assert explanation.rejected_by == 'repeats'
+    assert [(c.name, c.passed) for c in explanation.filters] == [('length', True), ('repeats', False)]
+
+
+def test_scan_limit_and_derivations():
+    """Unfinished scans stay undecided; field values explain near misses"""
+    config = Config(words=['acme', 'summer'], transforms=['capitalize'])
+    explanation = explain_token(config, 'ACME')
+    assert explanation.produced is False
+    assert {(d.value, d.transforms[0]) for d in explanation.derivations} == {('acme', 'uppercase'), ('acme', 'toggle_case')}
+    
+    explanation = explain_token(config, 'summer2024')
+    assert {d.transforms[0] for d in explanation.derivations} == {'append_numbers_4', 'append_year'}
+    
+    explanation = explain_token(Config(charset='ab', min_length=1, max_length=4), 'bbbb', scan_limit=3)
+    assert explanation.produced is None
+    assert explanation.candidates_scanned == 3
@@ -0,0 +1,52 @@
+"""
+Tests for range extraction from existing wordlists
+"""
+
+import gzip
+
+import pytest
+
+from omniwordlist.error import ConfigError
+from omniwordlist.extract import slice_lines, slice_tokens
+from omniwordlist.index import build_index
+
+
+TOKENS = sorted(f'{a}{b}' for a in 'abcd' for b in 'abcd')
+
+
+def write_list(path, tokens=TOKENS):
+    path.write_text(''.join(token + '\n' for token in tokens))
+    return path
+
+
+def test_slice_lines_with_and_without_index(tmp_path):
+    """Line ranges are the same whether or not an index is used"""
+    path = write_list(tmp_path / 'list.txt')
+    assert list(slice_lines(path, 5, 7)) == TOKENS[4:7]
+    build_index(path, interval=3)
+    assert list(slice_lines(path, 5, 7)) == TOKENS[4:7]
+    assert list(slice_lines(path, 15)) == TOKENS[14:]
+    with pytest.raises(ConfigError):
+        list(slice_lines(path, 4, 2))
+
+
+def test_slice_tokens(tmp_path):
+    """Sorted lists select by comparison, unsorted ones by boundary tokens"""
+    path = write_list(tmp_path / 'list.txt')
+    build_index(path, interval=4)
+    assert list(slice_tokens(path, 'bb', 'cb')) == ['bb', 'bc', 'bd', 'ca', 'cb']
+    assert list(slice_tokens(path, 'bbb', 'cab')) == ['bc', 'bd', 'ca']
+    
+    shuffled = write_list(tmp_path / 'shuffled.txt', ['dd', 'ab', 'ca', 'ba', 'cc'])
+    assert list(slice_tokens(shuffled, 'ab', 'ba')) == ['ab', 'ca', 'ba']
+    with pytest.raises(ConfigError, match='not found'):
+        list(slice_tokens(shuffled, 'zz'))
+
+
+def test_slice_compressed(tmp_path):
+    """Compressed lists are read transparently"""
+    path = tmp_path / 'list.txt.gz'
+    with gzip.open(path, 'wt') as f:
+        f.write(''.join(token + '\n' for token in TOKENS))
+    assert list(slice_lines(path, 2, 3)) == TOKENS[1:3]
+    assert list(slice_tokens(path, 'da', sorted_list=True)) == TOKENS[12:]
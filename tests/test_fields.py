@@ -0,0 +1,200 @@
+"""
+Tests for field packs and field-based generation
+"""
+
+import random
+
+import pytest
+
+from omniwordlist import Config, Generator
+from omniwordlist.error import ConfigError
+from omniwordlist.fields import FieldManager, FieldProvider, FieldRegistry, default_registry
+
+
+def test_region_packs():
+    """Region codes and country aliases resolve to themed packs"""
+    assert {'EU', 'IN', 'US'} <= set(FieldManager.list_regions())
+    ids = {f['id'] for f in FieldManager.get_fields_by_region('in')}
+    assert 'cricket_team_in' in ids
+    assert FieldManager.get_fields_by_region('UK') == FieldManager.get_fields_by_region('EU')
+
+    pack = FieldManager.get_field('region:IN')
+    assert 'Dhoni' in pack['examples'] and 'CSK' in pack['examples']
+
+
+def test_region_adds_pack_component():
+    """--region appends the pack as an extra field component"""
+    config = Config(enabled_fields=['birth_year'], region='US', max_length=30)
+    tokens = list(Generator(config).generate())
+    assert '1990Lakers' in tokens
+    assert len(tokens) == 5 * len(FieldManager.get_field('region:US')['examples'])
+
+
+def test_generated_numeric_fields():
+    """Generated fields enumerate their full numeric range"""
+    values = FieldManager.get_field_values('phone_last_4')
+    assert len(values) == 10000
+    assert values[0] == '0000' and values[-1] == '9999'
+
+    pins = FieldManager.get_field_values('pin_code_in')
+    assert pins[0] == '110001'
+    assert all(len(p) == 6 for p in pins[:100])
+
+
+def test_numeric_fields_combine_with_names():
+    """Numeric formats combine with name fields like any other field"""
+    config = Config(enabled_fields=['pet_name', 'mobile_prefix_in'], max_length=20)
+    generator = Generator(config)
+    tokens = list(generator.generate())
+    assert 'Max98' in tokens
+    assert generator.keyspace_size() == 5 * 29
+
+
+def test_format_phone():
+    """Phone numbers render in local layouts"""
+    from omniwordlist.formats import format_phone
+    assert format_phone('5551234567', 'US') == '555-123-4567'
+    assert format_phone('5551234567', 'us', layout=1) == '(555) 123-4567'
+
+
+def test_phone_number_fields_use_local_layouts():
+    """Phone number fields render mobile numbers in the local layout without materializing them"""
+    numbers = FieldManager.get_field_values('phone_number_fr')
+    assert len(numbers) == 2 * 10 ** 8
+    assert (numbers[0], numbers[-1]) == ('06 00 00 00 00', '07 99 99 99 99')
+
+    config = Config(words=['max'], enabled_fields=['phone_number_us'], separators=['@'])
+    generator = Generator(config)
+    assert generator.keyspace_size() == 16 * 10 ** 7
+    assert next(iter(generator.generate())) == 'max@201-000-0000'
+
+    postal = FieldManager.get_field_values('code_postal_fr')
+    assert (postal[0], postal[-1], len(postal)) == ('01000', '98890', 97891)
+
+
+def test_separator_list_multiplies_combinations():
+    """Each separator yields its own variant and is counted in the keyspace"""
+    config = Config(words=['aaryan'], enabled_fields=['year'],
+                    field_sources={'year': ['1990', '90']}, separators=['', '.', '_'])
+    generator = Generator(config)
+    tokens = list(generator.generate())
+    assert tokens == ['aaryan1990', 'aaryan.1990', 'aaryan_1990',
+                      'aaryan90', 'aaryan.90', 'aaryan_90']
+    assert generator.keyspace_size() == 6
+
+
+def test_swap_pairs_and_permutations():
+    """Component orders can be swapped pairwise or fully permuted"""
+    sources = {'name': ['bob'], 'year': ['90'], 'sym': ['!']}
+    config = Config(enabled_fields=['name', 'year'], field_sources=sources, field_order='swap_pairs')
+    assert list(Generator(config).generate()) == ['bob90', '90bob']
+
+    config = Config(enabled_fields=['name', 'year', 'sym'], field_sources=sources,
+                    field_order='permute', min_components=2, max_components=2)
+    generator = Generator(config)
+    tokens = list(generator.generate())
+    assert tokens == ['bob90', 'bob!', '90bob', '90!', '!bob', '!90']
+    assert generator.keyspace_size() == 6
+
+
+def test_derived_fields():
+    """Derived fields are computed from the other components of each combination"""
+    config = Config(enabled_fields=['name', 'year', 'derive:md5:name:6', 'derive:base64:name+year'],
+                    field_sources={'name': ['admin', 'root'], 'year': ['2024']}, separators=['-'])
+    generator = Generator(config)
+    tokens = list(generator.generate())
+    assert tokens == ['admin-2024-21232f-YWRtaW4yMDI0', 'root-2024-63a9f0-cm9vdDIwMjQ=']
+    assert generator.keyspace_size() == 2
+
+
+def test_derived_field_needs_enabled_source():
+    """Derived fields must reference enabled fields"""
+    config = Config(enabled_fields=['derive:crc32:name'])
+    with pytest.raises(ConfigError, match='source field name'):
+        Generator(config)
+
+
+class TeamProvider(FieldProvider):
+    """Provider standing in for a database-backed field source"""
+
+    def __init__(self, teams):
+        self.teams = teams
+        self.queries = 0
+
+    def fields(self):
+        return [{"id": "team", "category": "custom", "group": "crm", "type": "string",
+                 "examples": self.teams[:2], "cardinality": len(self.teams)}]
+
+    def values(self, field_id):
+        if field_id != "team":
+            return None
+        self.queries += 1
+        return iter(self.teams)
+
+
+def test_custom_field_provider():
+    """Registered providers serve values to generation and show up in listings"""
+    provider = TeamProvider(['red', 'blue', 'green'])
+    registry = FieldRegistry()
+    registry.register(provider)
+    config = Config(enabled_fields=['team', 'birth_year'], max_length=30)
+    tokens = list(Generator(config, field_registry=registry).generate())
+    assert tokens[:2] == ['red1990', 'red1985']
+    assert len(tokens) == 3 * 5
+    assert provider.queries == 1
+    assert registry.get_field('team')['category'] == 'custom'
+    assert registry.get_field('region:IN') is not None
+
+    default_registry.register(provider)
+    try:
+        assert 'team' in FieldManager.list_fields()
+        assert 'custom' in FieldManager.list_categories()
+        assert FieldManager.get_field_values('team') == ['red', 'blue', 'green']
+    finally:
+        default_registry.unregister(provider)
+    assert FieldManager.get_field('team') is None
+
+
+def test_provider_can_override_catalog():
+    """A provider registered first wins over the built-in catalog"""
+    provider = TeamProvider(['x'])
+    provider.fields = lambda: [{"id": "birth_year", "category": "personal", "group": "dates"}]
+    provider.values = lambda field_id: ['2001'] if field_id == 'birth_year' else None
+    registry = FieldRegistry()
+    registry.register(provider, first=True)
+    assert registry.values('birth_year') == ['2001']
+    assert registry.values('last_name_0')[0] == 'Smith'
+    assert [f['id'] for f in registry.fields()].count('birth_year') == 1
+
+
+def test_length_window_applies_to_field_combinations():
+    """With field_length_window, combinations outside min_length..max_length (in characters) are never built"""
+    config = Config(enabled_fields=['name', 'mood'], separators=['', '_'], min_length=5, max_length=6,
+                    field_length_window=True,
+                    field_sources={'name': ['al', 'bob', 'christine'], 'mood': ['😀😀', 'yay', 'x']})
+    generator = Generator(config)
+    tokens = list(generator.generate())
+    assert tokens == ['al_😀😀', 'alyay', 'al_yay', 'bob😀😀', 'bob_😀😀', 'bobyay', 'bob_x']
+    assert generator.keyspace_size() == len(tokens)
+
+
+def test_single_and_empty_separator():
+    """A single separator joins components, an empty one concatenates; prefix and suffix wrap the result"""
+    config = Config(words=['Aaryan'], enabled_fields=['year'], field_sources={'year': ['1990']},
+                    separator='.', prefix='<', suffix='>', max_length=12)
+    assert list(Generator(config).generate()) == ['<Aaryan.1990>']
+    config.separator = ''
+    assert list(Generator(config).generate()) == ['<Aaryan1990>']
+
+
+def test_permute_with_only_max_components():
+    """min_components defaults to 1 and may not exceed max_components"""
+    config = Config(enabled_fields=['name', 'year', 'sym'], field_order='permute', max_components=2,
+                    field_sources={'name': ['bob'], 'year': ['90'], 'sym': ['!']})
+    generator = Generator(config)
+    tokens = list(generator.generate())
+    assert tokens[:3] == ['bob', '90', '!'] and len(tokens) == 9
+    assert generator.keyspace_size() == 9
+    assert len(generator.sample_candidates(5, random.Random(1))) == 5
+    with pytest.raises(ConfigError, match='min_components cannot exceed'):
+        Config(enabled_fields=['name'], field_order='permute', min_components=3, max_components=2).validate()
@@ -0,0 +1,142 @@
+"""
+Tests for filters and rejection statistics
+"""
+
+import math
+
+import pytest
+
+from omniwordlist import Config, FilterConfig, Generator
+from omniwordlist.config import ConfigWarning
+from omniwordlist.error import ConfigError
+from omniwordlist.filters import CharsetFilter, create_filter_pipeline, estimate_entropy
+
+
+def test_rejection_report_counts_per_filter():
+    """Each rejecting stage is reported with its share of candidates"""
+    config = Config(min_length=1, max_length=2, charset='ab',
+                    filters=FilterConfig(min_len=2))
+    generator = Generator(config)
+    tokens = list(generator.generate())
+
+    assert tokens == ['aa', 'ab', 'ba', 'bb']
+    report = {entry['stage']: entry for entry in generator.rejection_report()}
+    assert report['length']['rejected'] == 2
+    assert report['length']['rate'] == 2 / 6
+
+
+def test_rejects_output_sample(tmp_path):
+    """Rejected tokens are sampled to a file with their reason"""
+    rejects = tmp_path / 'rejects.txt'
+    config = Config(min_length=1, max_length=2, charset='abc', dedupe=True,
+                    transforms=['lowercase'], filters=FilterConfig(min_len=2),
+                    rejects_output=rejects, rejects_sample_size=2)
+    generator = Generator(config)
+    list(generator.generate())
+
+    lines = rejects.read_text().splitlines()
+    assert len(lines) == 2
+    assert all(line.split('\t')[1] == 'length' for line in lines)
+    assert generator.rejected_total == 3
+
+
+def test_entropy_modes():
+    """Each estimator measures entropy in its own terms"""
+    assert estimate_entropy('aaaa', 'shannon') == 0.0
+    assert estimate_entropy('ab12', 'classes') == 4 * math.log2(36)
+    assert estimate_entropy('Password1', 'nist') == 4 + 7 * 2 + 1.5 + 6
+    # Common words and sequences need far fewer guesses than random strings
+    assert estimate_entropy('password123', 'guesses') < estimate_entropy('x7Qp2mZk9w', 'guesses')
+
+
+def test_entropy_mode_filter():
+    """min_entropy is interpreted with the configured estimator"""
+    config = Config(min_length=2, max_length=2, charset='a1',
+                    filters=FilterConfig(entropy_mode='classes', min_entropy=10))
+    assert list(Generator(config).generate()) == ['a1', '1a']
+
+
+def test_entropy_has_no_upper_bound_by_default():
+    """Strong tokens pass in every mode unless max_entropy is set"""
+    config = Config(words=['Ab1!Ab1!Ab1!Ab1!'], max_length=16,
+                    filters=FilterConfig(entropy_mode='classes', min_entropy=10))
+    assert list(Generator(config).generate()) == ['Ab1!Ab1!Ab1!Ab1!']
+    config.filters.max_entropy = 100.0
+    assert list(Generator(config).generate()) == []
+
+
+def test_charset_filter_ascii_and_unicode():
+    """The byte-level and unicode charset paths agree"""
+    ascii_filter = CharsetFilter(FilterConfig(charset_filter='abc123'))
+    assert ascii_filter.should_include('cab321')
+    assert not ascii_filter.should_include('abcd')
+    assert not ascii_filter.should_include('abé')
+
+    unicode_filter = CharsetFilter(FilterConfig(charset_filter='aé'))
+    assert unicode_filter.should_include('éaé')
+    assert not unicode_filter.should_include('ab')
+
+
+def test_max_repeats():
+    """Runs longer than max_repeats are rejected"""
+    config = Config(min_length=3, max_length=3, charset='ab',
+                    filters=FilterConfig(max_repeats=2))
+    tokens = list(Generator(config).generate())
+    assert 'aaa' not in tokens and 'bbb' not in tokens
+    assert len(tokens) == 6
+
+
+def test_charset_classes_allow_and_deny():
+    """Named classes work in both allow and deny form"""
+    allow = CharsetFilter(FilterConfig(charset_classes=['ascii_printable']))
+    assert allow.should_include('P@ss word!')
+    assert not allow.should_include('tab\there')
+
+    config = Config(min_length=1, max_length=1, charset='aбz',
+                    filters=FilterConfig(charset_deny='z', charset_deny_classes=['script:cyrillic']))
+    assert list(Generator(config).generate()) == ['a']
+
+
+def test_unknown_charset_class_rejected():
+    """Unknown class names fail validation"""
+    config = Config(filters=FilterConfig(charset_classes=['klingon']))
+    with pytest.raises(ConfigError, match='klingon'):
+        config.validate()
+
+
+def test_char_and_byte_length():
+    """Lengths count characters by default; byte limits are separate"""
+    config = Config(min_length=2, max_length=2, charset='aé',
+                    filters=FilterConfig(min_len=2, max_len=2))
+    assert len(list(Generator(config).generate())) == 4
+
+    config.filters.max_bytes = 3
+    assert list(Generator(config).generate()) == ['aa', 'aé', 'éa']
+
+
+def test_byte_length_counts_utf8():
+    """min_bytes/max_bytes measure the UTF-8 encoding of multibyte tokens"""
+    tokens = ['ab', 'é', 'éé', '日本', '🔑', 'a🔑']
+    config = FilterConfig(min_len=1, min_bytes=3, max_bytes=4)
+    assert [t for t in tokens if create_filter_pipeline(config).check(t) is None] == ['éé', '🔑']
+    config = FilterConfig(min_len=1, min_bytes=5)
+    assert [t for t in tokens if create_filter_pipeline(config).check(t) is None] == ['日本', 'a🔑']
+
+    # Configs written with the old key names keep loading
+    with pytest.warns(ConfigWarning, match='filters.max_bytes'):
+        config = Config.from_dict({'filters': {'max_byte_len': 4}})
+    assert config.filters.max_bytes == 4
+
+
+def test_max_lines_counts_filtered_output():
+    """max_lines caps emitted tokens, not raw candidates, and stops generation there"""
+    # The filter rejects the six candidates shorter than three characters
+    config = Config(min_length=1, max_length=3, charset='ab', max_lines=5,
+                    filters=FilterConfig(min_len=3))
+    generator = Generator(config)
+    tokens = list(generator.generate())
+
+    assert tokens == ['aaa', 'aab', 'aba', 'abb', 'baa']
+    assert generator.tokens_generated == 5
+    assert generator.candidates_seen == 11
+    assert generator.get_stats()['tokens_generated'] == 5
@@ -0,0 +1,43 @@
+"""
+Tests for frequency models
+"""
+
+from omniwordlist import Config, Generator
+from omniwordlist.frequency import FrequencyModel, import_frequency_corpus, parse_frequency_line
+
+
+def test_parse_formats():
+    """TSV in either column order and uniq -c lines are accepted"""
+    assert parse_frequency_line('password\t120\n') == ('password', 120)
+    assert parse_frequency_line('42\tletmein') == ('letmein', 42)
+    assert parse_frequency_line('    7 hello world') == ('hello world', 7)
+    assert parse_frequency_line('no count here') is None
+
+
+def test_model_ranks_and_lookups(tmp_path):
+    """Duplicates are summed; ranks follow counts and lookups use the word index"""
+    corpus = tmp_path / 'corpus.tsv'
+    corpus.write_text('dragon\t5\nsummer\t9\n123456\t40\ndragon\t6\nzzz\t1\n')
+    model_path = tmp_path / 'model.omf'
+    assert import_frequency_corpus(corpus, model_path, min_count=2) == 3
+    
+    with FrequencyModel(model_path) as model:
+        assert list(model) == [('123456', 40), ('dragon', 11), ('summer', 9)]
+        assert model.total == 60
+        assert model.count('dragon') == 11
+        assert model.count('zzz') == 0
+        assert model.order(['x', 'summer', 'y', '123456']) == ['123456', 'summer', 'x', 'y']
+
+
+def test_model_orders_fields_and_acts_as_source(tmp_path):
+    """Field values follow the model, and the model itself is a word source"""
+    corpus = tmp_path / 'corpus.tsv'
+    corpus.write_text('blue\t3\nred\t10\n')
+    model_path = tmp_path / 'colors.omf'
+    import_frequency_corpus(corpus, model_path)
+    
+    config = Config(words=['green', 'blue', 'red'], frequency_model=str(model_path))
+    assert Generator(config).generate_list() == ['red', 'blue', 'green']
+    
+    config = Config(enabled_fields=['color'], field_sources={'color': str(model_path)})
+    assert Generator(config).generate_list() == ['red', 'blue']
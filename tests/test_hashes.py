@@ -0,0 +1,53 @@
+"""
+Tests for hashed (lookup table) output
+"""
+
+import hashlib
+
+import pytest
+
+from omniwordlist import Config, Generator
+from omniwordlist.error import ConfigError
+from omniwordlist.hashes import _md4, hash_lines, hash_token
+
+
+def test_known_digests():
+    """MD5, SHA1 and NTLM match published values"""
+    assert hash_token('password', 'md5') == '5f4dcc3b5aa765d61d8327deb882cf99'
+    assert hash_token('password', 'sha1') == '5baa61e4c9b93f3f0682250b6cf8331b7ee68fd8'
+    assert hash_token('password', 'ntlm') == '8846f7eaee8fb117ad06bdd830b7586c'
+    # RFC 1320 test vectors of the pure-Python fallback
+    assert _md4(b'').hex() == '31d6cfe0d16ae931b73c59d7e0c089c0'
+    assert _md4(b'abc').hex() == 'a448017aaf21d8525fc10ae87aa6729d'
+    assert _md4(b'1234567890' * 8).hex() == 'e33b4ddc9c38f2199c3e7b164fcc0536'
+
+
+def test_parallel_hashing_keeps_order():
+    """Several processes and small batches give the same lines as one process"""
+    tokens = [f'word{i}' for i in range(1000)]
+    single = list(hash_lines(tokens, 'md5'))
+    assert list(hash_lines(iter(tokens), 'md5', processes=4, batch_size=7)) == single
+    assert single[0] == f"{hashlib.md5(b'word0').hexdigest()}:word0"
+
+
+def test_generator_csv_output(tmp_path):
+    """hash_format csv writes a header and quotes candidates that need it"""
+    output = tmp_path / 'table.csv'
+    config = Config(charset='a,', min_length=1, max_length=1, hash_algorithm='sha1', hash_format='csv',
+                    output_file=output)
+    config.validate()
+    generator = Generator(config)
+    # The header is not an output line
+    assert generator.write() == generator.tokens_written == 2
+    assert output.read_text().splitlines() == [
+        'candidate,hash',
+        f"a,{hash_token('a', 'sha1')}",
+        f'",",{hash_token(",", "sha1")}',
+    ]
+
+
+def test_invalid_hash_config():
+    with pytest.raises(ConfigError, match='Unknown hash algorithm'):
+        Config(hash_algorithm='bcrypt').validate()
+    with pytest.raises(ConfigError, match='txt output'):
+        Config(hash_algorithm='md5', format='jsonl').validate()
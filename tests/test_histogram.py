@@ -0,0 +1,24 @@
+"""
+Tests for the length histogram and coverage report
+"""
+
+import itertools
+
+from omniwordlist import Config, Generator
+from omniwordlist.histogram import LengthHistogram, length_coverage
+
+
+def test_histogram_and_partial_coverage():
+    """Coverage fills length bands in generation order"""
+    generator = Generator(Config(min_length=1, max_length=3, charset='ab'))
+    histogram = LengthHistogram()
+    generator.add_observer(histogram)
+    
+    list(itertools.islice(generator.generate(), 4))
+    assert histogram.items() == [(1, 2), (2, 2)]
+    assert length_coverage(generator) == {1: 1.0, 2: 0.5, 3: 0.0}
+
+
+def test_coverage_needs_addressable_mode():
+    """Field mode has no per-length coverage"""
+    assert length_coverage(Generator(Config(words=['a']))) is None
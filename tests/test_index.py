@@ -0,0 +1,49 @@
+"""
+Tests for the searchable index sidecar
+"""
+
+import pytest
+
+from omniwordlist.error import StorageError
+from omniwordlist.index import build_index, index_path_for, load_index, lookup
+
+
+def write_list(path, tokens):
+    path.write_text(''.join(token + '\n' for token in tokens))
+    return path
+
+
+def test_sorted_lookup(tmp_path):
+    """Sorted lists are searched through the index"""
+    tokens = sorted(f'{a}{b}' for a in 'abcdef' for b in 'xyz')
+    path = write_list(tmp_path / 'list.txt', tokens)
+    index = build_index(path, interval=4)
+    assert index.sorted and index.lines == len(tokens)
+    assert len(index.entries) == 5
+    assert index_path_for(path).exists()
+    
+    for line, token in enumerate(tokens):
+        assert lookup(path, token) == line
+    assert lookup(path, 'aa') is None
+    assert lookup(path, 'zz') is None
+    
+    # Line seeks land on the indexed line at or before the target
+    offset, start = index.offset_of_line(9)
+    assert start == 8
+    with open(path, 'rb') as f:
+        f.seek(offset)
+        assert f.readline().decode().strip() == tokens[8]
+
+
+def test_unsorted_and_stale_index(tmp_path):
+    """Unsorted lists fall back to scanning; stale indexes are ignored"""
+    path = write_list(tmp_path / 'list.txt', ['b', 'a', 'c'])
+    assert not build_index(path, interval=2).sorted
+    assert lookup(path, 'a') == 1
+    
+    write_list(path, ['b', 'a', 'c', 'd'])
+    assert load_index(path) is None
+    assert lookup(path, 'd') == 3
+    
+    with pytest.raises(StorageError):
+        build_index(write_list(tmp_path / 'list.txt.gz', ['a']))
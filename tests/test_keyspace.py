@@ -0,0 +1,209 @@
+"""
+Tests for rank addressing and chunking
+"""
+
+import pytest
+
+from omniwordlist import Config, Generator
+from omniwordlist.error import ConfigError, GeneratorError
+from omniwordlist.keyspace import Keyspace, chunk_bounds, chunk_count
+
+
+def test_token_at_matches_generation_order():
+    """Every rank decodes to the candidate generated at that position"""
+    config = Config(min_length=1, max_length=3, charset='abc')
+    keyspace = Generator(config).keyspace()
+    generated = Generator(config).generate_list()
+    
+    assert keyspace.size == len(generated)
+    assert [keyspace.token_at(rank) for rank in range(keyspace.size)] == generated
+    assert list(keyspace.iter_range(2, 9)) == generated[2:9]
+
+
+def test_pattern_keyspace():
+    """Pattern literals stay fixed while placeholders count"""
+    keyspace = Keyspace.for_pattern('x%%')
+    assert keyspace.size == 100
+    assert keyspace.token_at(42) == 'x42'
+    with pytest.raises(GeneratorError):
+        keyspace.token_at(100)
+
+
+def test_chunks_cover_keyspace_once():
+    """Generating every chunk reproduces the full run"""
+    config = Config(min_length=1, max_length=3, charset='abcd')
+    expected = Generator(config).generate_list()
+    size = len(expected)
+    
+    chunks = [chunk_bounds(i, 10, size) for i in range(chunk_count(size, 10))]
+    tokens = [t for c in chunks for t in Generator(config).generate_range(c.start, c.end)]
+    assert tokens == expected
+    assert chunks[-1].size == size % 10
+
+
+def test_unaddressable_mode_enumerates():
+    """Field mode has no keyspace but ranges still work"""
+    config = Config(words=['a', 'b', 'c'])
+    generator = Generator(config)
+    assert generator.keyspace() is None
+    assert list(generator.generate_range(1, 3)) == Generator(config).generate_list()[1:3]
+
+
+def test_constrained_keyspaces():
+    """Permutations and duplicate limits share the odometer; pruned ranks stay gaps"""
+    import itertools
+    
+    config = Config(min_length=1, max_length=3, charset='abcd', permutations_only=True)
+    generator = Generator(config)
+    keyspace = generator.keyspace()
+    expected = [''.join(p) for r in range(1, 4) for p in itertools.permutations('abcd', r)]
+    assert generator.generate_list() == expected
+    assert generator.rank_count() == keyspace.size == 4 + 16 + 64
+    assert generator.keyspace_size() == len(expected)
+    
+    ranked = [keyspace.token_at(rank) for rank in range(keyspace.size) if keyspace.accepts(rank)]
+    assert ranked == expected
+    assert keyspace.token_at(4) == 'aa' and not keyspace.accepts(4)
+    
+    # Chunks over the rank space cover every candidate exactly once
+    size = generator.rank_count()
+    chunks = [chunk_bounds(i, 7, size) for i in range(chunk_count(size, 7))]
+    tokens = [t for c in chunks for t in Generator(config).generate_range(c.start, c.end)]
+    assert tokens == expected
+    
+    config = Config(pattern='%%%', duplicate_limit='2%', max_length=10)
+    keyspace = Generator(config).keyspace()
+    assert list(keyspace.iter_range(0, 1000)) == [f'{i:03d}' for i in range(1000) if i % 111]
+    assert list(keyspace.iter_range(110, 113)) == ['110', '112']
+
+
+def test_start_and_end_strings_seek():
+    """start_string / end_string select an inclusive slice of the generation order"""
+    config = Config(min_length=1, max_length=3, charset='zab')
+    full = Generator(config).generate_list()
+    keyspace = Generator(config).keyspace()
+    assert [keyspace.rank_of(token) for token in full] == list(range(len(full)))
+    
+    config.start_string, config.end_string = 'bz', 'zaz'
+    assert Generator(config).generate_list() == full[full.index('bz'):full.index('zaz') + 1]
+    
+    # Seeking deep into a huge keyspace does not enumerate what comes before
+    config = Config(min_length=1, max_length=12, charset='abcdefghijklmnopqrstuvwxyz',
+                    start_string='zzzzzzzzzzzx')
+    assert Generator(config).generate_list() == ['zzzzzzzzzzzx', 'zzzzzzzzzzzy', 'zzzzzzzzzzzz']
+
+
+def test_skip_take_partitions_concatenate():
+    """skip=0/take=K followed by skip=K/take=K is the first 2K tokens of the whole run"""
+    k = 7
+    for kwargs in [dict(min_length=1, max_length=3, charset='abc'), dict(pattern='@,%', max_length=10),
+                   dict(words=['bob', 'eve'], enabled_fields=['year'], separators=['', '.', '_'],
+                        field_sources={'year': ['1990', '90', '2024']})]:
+        full = Generator(Config(**kwargs)).generate_list()
+        first = Generator(Config(skip=0, take=k, **kwargs)).generate_list()
+        second = Generator(Config(skip=k, take=k, **kwargs)).generate_list()
+        assert first + second == full[:2 * k]
+    
+    # Skipping deep into a huge keyspace seeks instead of enumerating
+    generator = Generator(Config(min_length=12, max_length=12, charset='abcdefghijklmnopqrstuvwxyz',
+                                 skip=26 ** 12 - 2, take=5, start_string='aaaaaaaaaaaa'))
+    assert generator.generate_list() == ['zzzzzzzzzzzy', 'zzzzzzzzzzzz']
+    assert generator.index_range() == (26 ** 12 - 2, 26 ** 12)
+
+
+def test_skip_take_count_from_start_string():
+    """skip and take narrow the start / end string range and report absolute indices"""
+    config = Config(min_length=2, max_length=2, charset='abc', start_string='ba', skip=1, take=2)
+    generator = Generator(config)
+    assert generator.generate_list() == ['bb', 'bc']
+    assert generator.index_range() == (4, 6)
+    assert generator.estimate_count() == 2
+    
+    config.skip = 10
+    assert Generator(config).generate_list() == []
+    with pytest.raises(ConfigError):
+        Config(take=0).validate()
+
+
+def test_bounds_outside_keyspace_fail():
+    """Characters outside the charset, bad lengths and reversed bounds are config errors"""
+    for start, end in [('ax', None), ('abcd', None), (None, 'x'), ('ba', 'ab')]:
+        with pytest.raises(ConfigError):
+            Generator(Config(min_length=1, max_length=3, charset='abc',
+                             start_string=start, end_string=end))
+
+
+def test_invert_changes_leftmost_fastest():
+    """crunch -i order: the first character changes most frequently"""
+    config = Config(min_length=2, max_length=2, charset='ab', invert=True)
+    assert Generator(config).generate_list() == ['aa', 'ba', 'ab', 'bb']
+    
+    config = Config(min_length=3, max_length=3, charset='ab', invert=True)
+    assert Generator(config).generate_list() == ['aaa', 'baa', 'aba', 'bba', 'aab', 'bab', 'abb', 'bbb']
+
+
+def test_invert_composes_with_seeking():
+    """Ranks, start/end strings and max_lines follow the inverted order"""
+    config = Config(min_length=1, max_length=3, charset='abc', invert=True)
+    generated = Generator(config).generate_list()
+    keyspace = Generator(config).keyspace()
+    assert [keyspace.token_at(rank) for rank in range(keyspace.size)] == generated
+    assert [keyspace.rank_of(token) for token in generated] == list(range(keyspace.size))
+    
+    config = Config(min_length=2, max_length=2, charset='abc', invert=True,
+                    start_string='ca', end_string='cb')
+    assert Generator(config).generate_list() == ['ca', 'ab', 'bb', 'cb']
+    
+    config = Config(min_length=2, max_length=2, charset='abc', invert=True, max_lines=4)
+    assert Generator(config).generate_list() == ['aa', 'ba', 'ca', 'ab']
+    
+    # Constraints see the same candidates, only in another order
+    config = Config(pattern='@@%', duplicate_limit='1@', max_length=10)
+    expected = set(Generator(config).generate())
+    config.invert = True
+    tokens = list(Generator(config).generate())
+    assert set(tokens) == expected and len(tokens) == len(expected)
+    assert tokens[:2] == ['ba0', 'ca0']
+
+
+def test_invert_needs_addressable_mode():
+    """Field and neighborhood modes have no positions to invert"""
+    with pytest.raises(ConfigError):
+        Generator(Config(seed_words=['abc'], invert=True))
+
+
+def test_generator_token_at_and_index_of():
+    """Index lookups sum the keyspaces of each length and reject what is never generated"""
+    generator = Generator(Config(min_length=2, max_length=4, charset='abc', start_string='ba'))
+    assert generator.token_at(0) == 'aa'
+    assert generator.token_at(9) == 'aaa'
+    assert generator.index_of('cccc') == 9 + 27 + 80
+    assert all(generator.index_of(generator.token_at(i)) == i for i in range(0, 117, 7))
+    
+    for lookup, value in [(generator.token_at, 117), (generator.token_at, -1),
+                          (generator.index_of, 'abd'), (generator.index_of, 'a')]:
+        with pytest.raises(GeneratorError):
+            lookup(value)
+    
+    permutations = Generator(Config(min_length=2, max_length=2, charset='abc', permutations_only=True))
+    assert permutations.index_of('ab') == 1
+    with pytest.raises(GeneratorError):
+        permutations.token_at(0)
+    with pytest.raises(GeneratorError):
+        Generator(Config(words=['alpha'], enabled_fields=['words'])).token_at(0)
+
+
+def test_fast_charsets_match_the_odometer():
+    """Digit and lowercase keyspaces take the fast path for ranges inside a length"""
+    for charset, max_length in [('0123456789', 5), ('abcdefghijklmnopqrstuvwxyz', 4)]:
+        fast = Keyspace.for_charset(charset, 1, max_length)
+        odometer = Keyspace.for_charset(charset, 1, max_length)
+        odometer.fast = None
+        assert fast.fast is not None
+        for start, end in [(3, 200), (11, fast.size - 7), (fast.size - 30000, fast.size - 1)]:
+            assert list(fast.iter_range(start, end)) == list(odometer.iter_range(start, end))
+    
+    assert Keyspace.for_charset('0123456789', 2, 2, inverted=True).fast is None
+    assert Keyspace.for_charset('9876543210', 2, 2).fast is None
+    config = Config(min_length=6, max_length=6, charset='0123456789', start_string='004321', max_lines=2)
+    assert Generator(config).generate_list() == ['004321', '004322']
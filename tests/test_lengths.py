@@ -0,0 +1,47 @@
+"""
+Tests for target length distributions
+"""
+
+from collections import Counter
+
+import pytest
+
+from omniwordlist import Config, Generator
+from omniwordlist.error import ConfigError
+from omniwordlist.histogram import length_coverage
+from omniwordlist.lengths import load_length_distribution, measure_lengths
+
+
+def test_head_of_the_list_follows_the_shares():
+    """Every prefix holds each length in proportion; exhausted lengths leave the rest to the others"""
+    config = Config(min_length=1, max_length=3, charset='abcdefghij',
+                    length_distribution={'2': 1, '3': 3})
+    generator = Generator(config)
+    tokens = generator.generate_list()
+    assert sorted(tokens) == sorted(Generator(Config(min_length=1, max_length=3, charset='abcdefghij')).generate_list())
+
+    head = Counter(len(token) for token in tokens[:400])
+    assert (head[2], head[3], head[1]) == (100, 300, 0)
+    # Length 2 runs out after 100 tokens of its own; length 3 finishes, then the unweighted length 1
+    assert [len(token) for token in tokens[-10:]] == [1] * 10
+    assert length_coverage(generator) == {1: 1.0, 2: 1.0, 3: 1.0}
+
+
+def test_measured_table_round_trip(tmp_path):
+    """Tables from length-stats load as normalised shares"""
+    table = measure_lengths(['password', 'letmein', 'dragon', 'monkey', ''], source='sample')
+    assert table == {'source': 'sample', 'lengths': {'6': 0.5, '7': 0.25, '8': 0.25}}
+    path = tmp_path / 'lengths.json'
+    path.write_text(str(table).replace("'", '"'))
+    assert load_length_distribution(str(path)) == {6: 0.5, 7: 0.25, 8: 0.25}
+    assert load_length_distribution({8: 2, 9: 2, 10: 0}) == {8: 0.5, 9: 0.5}
+
+
+def test_invalid_distributions():
+    for value in ({}, {'0': 1}, {'8': -1}, {'eight': 1}, {'8': 0}):
+        with pytest.raises(ConfigError):
+            load_length_distribution(value)
+    with pytest.raises(ConfigError):
+        Generator(Config(pattern='@@', length_distribution={'2': 1}))
+    with pytest.raises(ConfigError):
+        Generator(Config(charset='ab', length_distribution={'2': 1}, skip=3))
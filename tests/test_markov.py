@@ -0,0 +1,61 @@
+"""
+Tests for Markov-chain generation
+"""
+
+import math
+
+import pytest
+
+from omniwordlist import Config, Generator
+from omniwordlist.error import ConfigError
+from omniwordlist.frequency import build_frequency_model
+from omniwordlist.markov import MarkovModel
+
+
+WORDS = ['abc', 'abc', 'abd', 'abcd', 'bcd']
+
+
+def test_enumeration_is_best_first_and_complete():
+    """Tokens come in descending probability and cover everything the model can form"""
+    model = MarkovModel.train(WORDS, order=2)
+    tokens = list(model.enumerate(1, 4))
+    probabilities = [probability for _, probability in tokens]
+    assert probabilities == sorted(probabilities, reverse=True)
+    assert tokens[0][0] == 'abc'
+    # 'bc' was never seen, but every transition of it was
+    assert {token for token, _ in tokens} == {'abc', 'abd', 'abcd', 'bc', 'bcd'}
+    for token, probability in tokens:
+        assert math.isclose(model.probability(token), probability)
+    assert math.isclose(sum(probabilities), 1.0)
+    assert [token for token, _ in model.enumerate(4, 4)] == ['abcd']
+
+
+def test_markov_mode_trains_saves_and_reuses(tmp_path):
+    """--train with --model saves the model; --model alone reproduces the output"""
+    train = tmp_path / 'train.txt'
+    train.write_text('\n'.join(WORDS * 3) + '\n')
+    model = tmp_path / 'model.json'
+    config = Config(markov_train=str(train), markov_model=str(model), markov_order=2,
+                    min_length=3, max_length=3, max_lines=2, transforms=['uppercase'])
+    first = list(Generator(config).generate())
+    assert first == ['ABC', 'ABD'] and model.exists()
+
+    reused = Config(markov_model=str(model), min_length=3, max_length=3, max_lines=2, transforms=['uppercase'])
+    assert list(Generator(reused).generate()) == first
+
+    with pytest.raises(ConfigError):
+        Config(markov_train=str(train), markov_order=4).validate()
+    with pytest.raises(ConfigError):
+        Config(markov_train=str(train), pattern='@@').validate()
+
+
+def test_frequency_model_counts_weight_training(tmp_path):
+    """A frequency model trains with each word weighted by its count"""
+    train = tmp_path / 'train.txt'
+    train.write_text('abc\nabd\n')
+    model = tmp_path / 'corpus.omf'
+    build_frequency_model({'abc': 1, 'abd': 50}, model)
+    plain = Config(markov_train=str(train), markov_order=2, min_length=3, max_length=3)
+    weighted = Config(markov_train=str(model), markov_order=2, min_length=3, max_length=3)
+    assert list(Generator(plain).generate()) == ['abc', 'abd']
+    assert list(Generator(weighted).generate()) == ['abd', 'abc']
@@ -0,0 +1,44 @@
+"""
+Tests for tagged multi-source merges
+"""
+
+import json
+import sqlite3
+
+import pytest
+
+from omniwordlist.error import ConfigError
+from omniwordlist.merge import merge_sources, parse_source, tag_counts, write_merged
+
+
+def test_merge_unites_tags(tmp_path):
+    """Duplicates keep the union of their sources' tags"""
+    (tmp_path / 'rules.txt').write_text('summer\nwinter\n')
+    (tmp_path / 'brute.jsonl').write_text(
+        json.dumps({'token': 'winter', 'tags': ['brute']}) + '\n' +
+        json.dumps({'token': 'autumn', 'tags': ['brute', 'seasons']}) + '\n')
+    
+    merged = merge_sources([parse_source(f'rules={tmp_path / "rules.txt"}'),
+                            parse_source(str(tmp_path / 'brute.jsonl'))])
+    assert merged == {'summer': ['rules'], 'winter': ['rules', 'brute'],
+                      'autumn': ['brute', 'seasons']}
+    assert tag_counts(merged)['brute'] == (2, 0)
+    assert tag_counts(merged)['rules'] == (2, 1)
+    
+    with pytest.raises(ConfigError):
+        parse_source('=path')
+
+
+def test_merge_outputs(tmp_path):
+    """Tags survive into JSONL and SQLite output"""
+    merged = {'a': ['x'], 'b': ['x', 'y']}
+    
+    write_merged(merged, tmp_path / 'out.jsonl', 'jsonl')
+    records = [json.loads(line) for line in (tmp_path / 'out.jsonl').read_text().splitlines()]
+    assert records == [{'token': 'a', 'tags': ['x']}, {'token': 'b', 'tags': ['x', 'y']}]
+    
+    assert write_merged(merged, tmp_path / 'out.db', 'sqlite') == 2
+    connection = sqlite3.connect(tmp_path / 'out.db')
+    assert connection.execute("SELECT token FROM token_tags WHERE tag = 'y'").fetchall() == [('b',)]
+    assert connection.execute("SELECT tags FROM tokens WHERE token = 'b'").fetchone() == ('x,y',)
+    connection.close()
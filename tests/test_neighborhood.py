@@ -0,0 +1,51 @@
+"""
+Tests for edit-distance neighborhood generation
+"""
+
+import pytest
+
+from omniwordlist import Config, Generator
+from omniwordlist.error import ConfigError
+from omniwordlist.neighborhood import edit_neighborhood
+
+
+def levenshtein(a, b):
+    previous = list(range(len(b) + 1))
+    for i, ca in enumerate(a, 1):
+        current = [i]
+        for j, cb in enumerate(b, 1):
+            current.append(min(previous[j] + 1, current[j - 1] + 1, previous[j - 1] + (ca != cb)))
+        previous = current
+    return previous[-1]
+
+
+def test_neighborhood_is_exact_and_unique():
+    """Every string within distance K appears exactly once"""
+    charset = 'ab'
+    found = list(edit_neighborhood(['ab'], 2, charset))
+    assert len(found) == len(set(found))
+    assert found[0] == 'ab'
+
+    # Brute force every string over the charset up to length 4
+    expected = set()
+    for length in range(0, 5):
+        for n in range(2 ** length):
+            word = ''.join(charset[(n >> i) & 1] for i in range(length))
+            if levenshtein('ab', word) <= 2:
+                expected.add(word)
+    assert set(found) == expected
+
+
+def test_neighborhood_mode_in_generator():
+    """seed_words switches the generator to neighborhood mode"""
+    config = Config(seed_words=['cat'], edit_distance=1, charset='cs', dedupe=True)
+    tokens = list(Generator(config).generate())
+    assert tokens[0] == 'cat'
+    assert {'at', 'cst', 'cats', 'scat', 'sat'} <= set(tokens)
+    assert Generator(config).keyspace_size() >= len(tokens)
+
+
+def test_edit_distance_bounded():
+    """Large distances are refused"""
+    with pytest.raises(ConfigError, match='edit_distance'):
+        Config(seed_words=['x'], edit_distance=9).validate()
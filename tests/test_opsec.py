@@ -0,0 +1,73 @@
+"""
+Tests for OPSEC mode and shredding
+"""
+
+import os
+
+import pytest
+
+from omniwordlist import Config, opsec
+from omniwordlist.campaign import CampaignStore
+from omniwordlist.error import ConfigError, OpsecError
+from omniwordlist.opsec import disk_allowed, require_disk, shred_path
+from omniwordlist.presets import PresetManager
+from omniwordlist.settings import UserSettings
+from omniwordlist.storage import CheckpointManager
+from omniwordlist.usage import record_run
+
+
+@pytest.fixture
+def opsec_state(monkeypatch):
+    """Restore the process-wide OPSEC switch after a test"""
+    monkeypatch.setattr(opsec, '_enabled', None)
+    monkeypatch.setattr(opsec, '_allowed', set())
+    monkeypatch.delenv(opsec.ENV_OPSEC, raising=False)
+    monkeypatch.delenv(opsec.ENV_ALLOW, raising=False)
+    return monkeypatch
+
+
+def test_opsec_mode_refuses_side_files(tmp_path, opsec_state):
+    opsec.enable(['checkpoints'])
+    with pytest.raises(OpsecError):
+        UserSettings().save(tmp_path / 'settings.json')
+    with pytest.raises(OpsecError):
+        CampaignStore(tmp_path / 'c.db', 'acme')
+    with pytest.raises(OpsecError):
+        PresetManager(tmp_path / 'presets').save_preset('mine', '', Config())
+    assert not (tmp_path / 'presets').exists()
+    assert not record_run(1, 1, path=tmp_path / 'usage.json', settings=UserSettings(usage_stats=True))
+    # Allowed kinds still work
+    CheckpointManager(tmp_path / 'ck').save_checkpoint('job', {})
+    assert list(tmp_path.iterdir()) == [tmp_path / 'ck']
+
+    opsec.disable()
+    UserSettings().save(tmp_path / 'settings.json')
+
+
+def test_opsec_mode_from_the_environment(opsec_state):
+    assert disk_allowed('presets')
+    opsec_state.setenv(opsec.ENV_OPSEC, '1')
+    opsec_state.setenv(opsec.ENV_ALLOW, 'presets,cache')
+    assert disk_allowed('presets') and disk_allowed('cache')
+    assert not disk_allowed('checkpoints')
+    with pytest.raises(OpsecError, match='--allow-disk checkpoints'):
+        require_disk('checkpoints', 'checkpoints')
+    with pytest.raises(ConfigError):
+        opsec.enable(['tmp'])
+
+
+def test_shred_overwrites_before_deleting(tmp_path):
+    target = tmp_path / 'out.txt'
+    target.write_text('secret\n' * 1000)
+    # A second link to the inode shows what happened to the data
+    os.link(target, tmp_path / 'link')
+    assert shred_path(target, passes=2) == 1
+    assert not target.exists()
+    assert (tmp_path / 'link').read_bytes() == b''
+
+    directory = tmp_path / 'shards'
+    (directory / 'a').mkdir(parents=True)
+    (directory / 'a' / 'x.txt').write_text('x')
+    (directory / 'y.txt').write_text('y')
+    assert shred_path(directory) == 2
+    assert not directory.exists()
@@ -0,0 +1,69 @@
+"""
+Tests for Crunch-style pattern generation
+"""
+
+import pytest
+
+from omniwordlist import Config, Generator
+from omniwordlist.error import ConfigError
+from omniwordlist.charset import expand_pattern_positions
+
+
+def crunch(pattern, literal_mask=None):
+    return Generator(Config(pattern=pattern, literal_mask=literal_mask)).generate_list()
+
+
+def test_literal_prefix_and_positions():
+    """pass@@%% keeps its prefix and draws each placeholder from its own charset"""
+    tokens = crunch('pass@@%%')
+    assert len(tokens) == 26 * 26 * 10 * 10
+    assert all(len(token) == 8 and token.startswith('pass') for token in tokens)
+    assert tokens[:3] == ['passaa00', 'passaa01', 'passaa02']
+    assert tokens[-1] == 'passzz99'
+
+
+def test_matches_crunch_output():
+    """Same order as `crunch 3 3 -t ,@%` and `crunch 4 4 -t pa%%`"""
+    tokens = crunch(',@%')
+    assert len(tokens) == 6760
+    assert tokens[:11] == ['Aa0', 'Aa1', 'Aa2', 'Aa3', 'Aa4', 'Aa5', 'Aa6', 'Aa7', 'Aa8', 'Aa9', 'Ab0']
+    assert tokens[-2:] == ['Zz8', 'Zz9']
+    
+    assert crunch('pa%%') == [f'pa{n:02d}' for n in range(100)]
+
+
+def test_literal_mask():
+    """A -l mask as long as the pattern protects placeholders at its marked positions"""
+    # crunch 5 5 -t p@ss% -l a@aaa
+    assert crunch('p@ss%', 'a@aaa') == [f'p@ss{n}' for n in range(10)]
+    # Only the first @ is literal; as literal_chars the same string keeps both
+    assert expand_pattern_positions('@@', literal_mask='@a') == ['@', 'abcdefghijklmnopqrstuvwxyz']
+    assert expand_pattern_positions('@@', literal_chars='@a') == ['@', '@']
+    # literal_chars lists characters literal everywhere, whatever its length
+    assert expand_pattern_positions('@%@', '@') == ['@', '0123456789', '@']
+    
+    with pytest.raises(ConfigError, match='as long as the pattern'):
+        Config(pattern='p@ss%', literal_mask='a@').validate()
+
+
+def test_custom_charsets():
+    """Markers 1-4 draw from custom charsets once any is defined"""
+    config = Config(pattern='11%', custom_charsets=['0123456789ABCDEF'])
+    config.validate()
+    tokens = Generator(config).generate_list()
+    assert len(tokens) == 16 * 16 * 10
+    assert tokens[:2] == ['000', '001'] and tokens[-1] == 'FF9'
+    
+    # Two uppercase hex characters followed by three digits from 1-5
+    config = Config(pattern='11222', custom_charsets=['0123456789ABCDEF', '12345'])
+    assert Generator(config).keyspace_size() == 16 * 16 * 5 ** 3
+    
+    # Without custom charsets digits stay literal
+    assert crunch('pass2024') == ['pass2024']
+
+
+def test_undefined_custom_charset_rejected():
+    with pytest.raises(ConfigError, match='undefined custom charset 2'):
+        Config(pattern='12', custom_charsets=['ab']).validate()
+    # A -l mask keeps a marker digit literal
+    assert Generator(Config(pattern='12', literal_mask='a2', custom_charsets=['ab'])).generate_list() == ['a2', 'b2']
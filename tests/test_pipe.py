@@ -0,0 +1,46 @@
+"""
+Tests for piping tokens through an external command
+"""
+
+import shlex
+import sys
+from pathlib import Path
+
+import pytest
+
+from omniwordlist import Config, Generator
+from omniwordlist.error import ConfigError, GeneratorError
+from omniwordlist.pipe import pipe_through
+
+
+def python_command(code: str) -> str:
+    return f"{shlex.quote(sys.executable)} -c {shlex.quote(code)}"
+
+
+FILTER = python_command(
+    "import sys\n"
+    "for line in sys.stdin:\n"
+    "    if 'b' not in line:\n"
+    "        print(line.strip().upper())\n"
+)
+
+
+def test_command_output_replaces_tokens(tmp_path):
+    """The command's lines become the output, in small batches"""
+    config = Config(min_length=2, max_length=2, charset='abc', pipe_through=FILTER, pipe_batch_size=2,
+                    output_file=tmp_path / 'out.txt')
+    Generator(config).write()
+    assert (tmp_path / 'out.txt').read_text().split() == ['AA', 'AC', 'CA', 'CC']
+
+
+def test_early_stop_and_failing_commands():
+    """Closing the stream stops the command; a failing command is an error"""
+    lines = pipe_through((str(i) for i in range(10 ** 9)), python_command(
+        "import sys\nfor line in sys.stdin:\n    print(line, end='', flush=True)"), batch_size=10)
+    assert [next(lines) for _ in range(3)] == ['0', '1', '2']
+    lines.close()
+
+    with pytest.raises(GeneratorError, match='exited with status 3'):
+        list(pipe_through(['a'], python_command("import sys; sys.exit(3)")))
+    with pytest.raises(ConfigError):
+        Config(pipe_through='./filter', checkpoint_dir=Path('checkpoints')).validate()
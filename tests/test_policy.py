@@ -0,0 +1,45 @@
+"""
+Tests for password policy inference
+"""
+
+import pytest
+
+from omniwordlist import Config, Generator
+from omniwordlist.config import FilterConfig
+from omniwordlist.error import ConfigError
+from omniwordlist.policy import BAN_CATALOG, MIN_BAN_SAMPLES, infer_policy, policy_config
+
+
+SAMPLES = ['Summer24!', 'Falcon#91', 'Rover$730', 'Winter57!'] * (MIN_BAN_SAMPLES // 4)
+
+
+def test_infers_lengths_classes_and_bans():
+    """Lengths, classes in every sample and unbroken catalogue rules are inferred"""
+    policy = infer_policy(SAMPLES + [''])
+    assert policy.samples == MIN_BAN_SAMPLES
+    assert (policy.min_length, policy.max_length) == (9, 9)
+    assert policy.required_classes == ['lower', 'upper', 'digit', 'symbol']
+    assert policy.min_classes == 4 and policy.max_repeats == 2
+    assert policy.banned_patterns == BAN_CATALOG
+
+    # 'asdf' breaks the keyboard rule, and too few samples infer no bans at all
+    few = infer_policy(['asdf1234', 'Passw0rd'])
+    assert few.required_classes == ['lower', 'digit'] and few.min_classes == 2
+    assert not few.banned_patterns and few.max_repeats is None
+
+    with pytest.raises(ConfigError):
+        infer_policy(['', ''])
+
+
+def test_policy_config_keeps_only_compliant_candidates():
+    """The emitted config filters out candidates the policy rejects"""
+    config = policy_config(infer_policy(SAMPLES), Config(pattern='12%%%3', custom_charsets=['A', 'b', '!']))
+    config.min_length = config.max_length = config.filters.min_len = 6
+    tokens = list(Generator(Config.from_dict(config.to_dict())).generate())
+    assert 'Ab135!' in tokens
+    assert not {'Ab123!', 'Ab987!', 'Ab111!'} & set(tokens)
+
+    with pytest.raises(ConfigError):
+        Config(filters=FilterConfig(required_classes=['vowel'])).validate()
+    with pytest.raises(ConfigError):
+        Config(filters=FilterConfig(deny_patterns=['('])).validate()
@@ -0,0 +1,99 @@
+"""
+Tests for preset persistence
+"""
+
+import json
+import os
+import tempfile
+import threading
+from pathlib import Path
+
+import pytest
+
+from omniwordlist import Config
+from omniwordlist.error import PresetError
+from omniwordlist.presets import PresetManager, diff_configs
+
+
+def test_concurrent_preset_saves_stay_valid():
+    """Concurrent writers never leave a corrupt preset file behind"""
+    with tempfile.TemporaryDirectory() as tmpdir:
+        manager = PresetManager(Path(tmpdir))
+
+        def save(i):
+            manager.save_preset("shared", f"writer {i}", Config(min_length=1, max_length=i + 1))
+
+        threads = [threading.Thread(target=save, args=(i,)) for i in range(8)]
+        for t in threads:
+            t.start()
+        for t in threads:
+            t.join()
+
+        data = json.loads((Path(tmpdir) / "shared.json").read_text())
+        assert data["description"].startswith("writer ")
+        assert [p.name for p in Path(tmpdir).glob("*.tmp")] == []
+
+
+def test_preset_reloads_external_changes():
+    """Edits made by another process are visible without a new manager"""
+    with tempfile.TemporaryDirectory() as tmpdir:
+        manager = PresetManager(Path(tmpdir))
+        manager.save_preset("mine", "first", Config())
+        assert manager.get_preset("mine")["description"] == "first"
+
+        path = Path(tmpdir) / "mine.json"
+        data = json.loads(path.read_text())
+        data["description"] = "edited elsewhere"
+        path.write_text(json.dumps(data))
+        stat = path.stat()
+        os.utime(path, ns=(stat.st_atime_ns, stat.st_mtime_ns + 1_000_000))
+
+        assert manager.get_preset("mine")["description"] == "edited elsewhere"
+
+
+def test_preset_versions_and_rollback():
+    """Overwriting a preset keeps prior versions that can be restored"""
+    with tempfile.TemporaryDirectory() as tmpdir:
+        manager = PresetManager(Path(tmpdir))
+        assert manager.save_preset("tuned", "v1", Config(max_length=5)) == "1.0"
+        assert manager.save_preset("tuned", "v2", Config(max_length=8), message="longer") == "1.1"
+
+        history = manager.preset_history("tuned")
+        assert [e["version"] for e in history] == ["1.0", "1.1"]
+        assert history[1]["message"] == "longer"
+        assert manager.get_preset("tuned@1.0")["config"]["max_length"] == 5
+
+        assert manager.rollback_preset("tuned", "1.0") == "1.2"
+        assert manager.get_preset_config("tuned").max_length == 5
+        assert manager.get_preset("tuned@1.1")["config"]["max_length"] == 8
+
+
+def test_deleted_preset_history_is_purged():
+    """Re-creating a deleted preset starts a fresh history; versions must be major.minor"""
+    with tempfile.TemporaryDirectory() as tmpdir:
+        manager = PresetManager(Path(tmpdir))
+        manager.save_preset("gone", "old", Config(max_length=5))
+        manager.save_preset("gone", "old", Config(max_length=6))
+        manager.delete_preset("gone")
+
+        assert manager.save_preset("gone", "new", Config(max_length=9)) == "1.0"
+        assert [e["description"] for e in manager.preset_history("gone")] == ["new"]
+        with pytest.raises(PresetError, match="Preset version not found"):
+            manager.get_preset("gone@1.1")
+        with pytest.raises(PresetError, match="Invalid preset version"):
+            manager.get_preset("gone@../../gone")
+
+
+def test_preset_changes_before_overwrite():
+    """Overwrites can be previewed as a per-setting diff"""
+    assert diff_configs({"max_length": 5, "filters": {"min_len": 2, "max_len": 9}},
+                        {"max_length": 8, "filters": {"min_len": 2}}) == [
+        ("filters.max_len", 9, None), ("max_length", 5, 8)]
+
+    with tempfile.TemporaryDirectory() as tmpdir:
+        manager = PresetManager(Path(tmpdir))
+        assert manager.preset_changes("tuned", "v1", Config(max_length=5)) is None
+        manager.save_preset("tuned", "v1", Config(max_length=5))
+        assert manager.preset_changes("tuned", "v1", Config(max_length=5)) == []
+        assert manager.preset_changes("tuned", "v2", Config(max_length=8)) == [
+            ("description", "v1", "v2"), ("max_length", 5, 8)]
@@ -0,0 +1,54 @@
+"""
+Tests for live preview sampling
+"""
+
+from omniwordlist import Config, FilterConfig
+from omniwordlist.error import ConfigError
+from omniwordlist.preview import PreviewSampler, annotate_token, sample_tokens
+
+
+def test_sample_tokens_respects_limit():
+    """Samples come from the real generator and stop at the limit"""
+    result = sample_tokens(Config(min_length=1, max_length=2, charset='ab'), limit=3)
+    assert result.tokens == ['a', 'b', 'aa']
+    assert result.error is None
+
+
+def test_sample_tokens_reports_config_errors():
+    """Invalid configs produce an error instead of raising"""
+    result = sample_tokens(Config(min_length=3, max_length=1))
+    assert result.tokens == []
+    assert 'max_length' in result.error
+    assert isinstance(result.exception, ConfigError)
+
+
+def test_sampler_debounces_updates():
+    """Rapid updates collapse into one sample of the latest config"""
+    results = []
+    sampler = PreviewSampler(limit=2, debounce=0.05, on_result=results.append)
+    for charset in ('ab', 'cd', 'xy'):
+        sampler.update(Config(min_length=1, max_length=1, charset=charset))
+    sampler.wait(timeout=5)
+
+    assert sampler.runs == 1
+    assert [r.tokens for r in results] == [['x', 'y']]
+    assert sampler.result.revision == 3
+
+
+def test_sample_keeps_rejected_entries():
+    """Rejected candidates are reported with the failing filter"""
+    config = Config(min_length=1, max_length=2, charset='ab',
+                    filters=FilterConfig(min_len=2))
+    result = sample_tokens(config, limit=3)
+    assert result.entries == [('a', 'length'), ('b', 'length'), ('aa', None), ('ab', None), ('ba', None)]
+    assert result.tokens == ['aa', 'ab', 'ba']
+
+
+def test_annotate_token_styles_classes():
+    """Character classes get distinct styles and rejects show the reason"""
+    text = annotate_token('aB1!é')
+    styles = [str(span.style) for span in text.spans]
+    assert styles == ['bold', 'cyan', 'magenta', 'bold yellow']
+
+    rejected = annotate_token('ab', 'length')
+    assert rejected.plain == 'ab  ✗ length'
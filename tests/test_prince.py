@@ -0,0 +1,52 @@
+"""
+Tests for PRINCE-style chain generation
+"""
+
+import pytest
+
+from omniwordlist import Config, Generator
+from omniwordlist.error import ConfigError
+from omniwordlist.prince import ChainIndex
+
+
+def test_chains_are_ordered_pruned_and_counted():
+    """Chains come shortest first, stay within the window and match the count"""
+    index = ChainIndex(['ab', 'c', 'ab', 'de', ''])
+    chains = list(index.chains(2, 4, 3))
+    assert chains[:6] == ['ab', 'de', 'cc', 'cab', 'cde', 'abc']
+    assert all(2 <= len(chain) <= 4 for chain in chains)
+    assert len(chains) == len(set(chains)) == index.count(2, 4, 3)
+    assert list(index.chains(2, 4, 3)) == chains
+
+    joined = list(index.chains(3, 5, 2, separator='-'))
+    assert joined == ['c-c', 'c-ab', 'c-de', 'ab-c', 'de-c',
+                      'ab-ab', 'ab-de', 'de-ab', 'de-de']
+    assert index.count(3, 5, 2, separator='-') == len(joined)
+
+
+def test_chain_mode_over_fields_and_files(tmp_path):
+    """Field values are the default elements; a file replaces them"""
+    config = Config(words=['ab', 'cd'], enabled_fields=['words'], chain_max_elements=2,
+                    min_length=4, max_length=4)
+    generator = Generator(config)
+    assert list(generator.generate()) == ['abab', 'abcd', 'cdab', 'cdcd']
+    assert generator.keyspace_size() == 4
+
+    elements = tmp_path / 'elements.txt'
+    elements.write_text('x\nyy\n')
+    config = Config(chain_elements=str(elements), chain_max_elements=3, min_length=3, max_length=3,
+                    transforms=['uppercase'])
+    assert list(Generator(config).generate()) == ['XYY', 'YYX', 'XXX']
+
+    # A one-entry separators list joins chains like separator does
+    config = Config(chain_elements=str(elements), chain_max_elements=2, separators=['-'], min_length=4, max_length=4)
+    generator = Generator(config)
+    assert list(generator.generate()) == ['x-yy', 'yy-x']
+    assert generator.keyspace_size() == 2
+
+    with pytest.raises(ConfigError):
+        Config(chain_max_elements=0).validate()
+    with pytest.raises(ConfigError):
+        Config(chain_elements=str(elements)).validate()
+    with pytest.raises(ConfigError):
+        Config(chain_max_elements=2, pattern='@@').validate()
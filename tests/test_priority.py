@@ -0,0 +1,92 @@
+"""
+Tests for the priority merge of candidate streams
+"""
+
+import json
+
+from omniwordlist import Config, Generator
+from omniwordlist.error import ConfigError
+from omniwordlist.priority import (MergePlan, Stream, StreamSpec, best_first, make_scorer, priority_merge,
+                                   quota_allotments, quota_horizon, quota_shares)
+
+
+def test_rank_scorer_interleaves_streams():
+    """Equal ranks alternate between streams instead of concatenating them"""
+    streams = [Stream('top', ['password', '123456', 'qwerty']),
+               Stream('acme', Generator(Config(words=['acme', 'corp'])).generate())]
+    merged = list(priority_merge(streams, make_scorer('rank')))
+    assert [entry.token for entry in merged] == ['password', 'acme', '123456', 'corp', 'qwerty']
+    assert [entry.tag for entry in merged[:2]] == ['top', 'acme']
+
+
+def test_duplicates_keep_their_best_position():
+    """A token from several streams is emitted once, from the stream scoring it highest"""
+    streams = [Stream('a', ['x', 'y', 'shared']), Stream('b', ['shared', 'z'], weight=2.0)]
+    merged = list(priority_merge(streams, make_scorer('rank')))
+    assert [entry.token for entry in merged] == ['shared', 'x', 'z', 'y']
+    assert merged[0].tag == 'b' and merged[0].score == 2.0
+
+
+def test_window_resorts_unordered_streams():
+    """Quality scores re-sort each stream within the lookahead window"""
+    score = make_scorer('quality')
+    tokens = ['aa', 'Tr0ub4dor&3', 'abc', 'Correct-Horse9']
+    assert [token for _, token in best_first(Stream('s', tokens), score, window=1)] == tokens
+    resorted = [value for value, _ in best_first(Stream('s', tokens), score, window=len(tokens))]
+    assert resorted == sorted(resorted, reverse=True)
+
+
+def test_limit_counts_unique_tokens():
+    """limit stops after that many distinct tokens"""
+    streams = [Stream('a', ['x', 'y']), Stream('b', ['x', 'z'])]
+    assert [entry.token for entry in priority_merge(streams, make_scorer('rank'), limit=2)] == ['x', 'y']
+
+
+def test_quotas_hold_per_block():
+    """Every block keeps each stream's share even when one stream scores higher"""
+    streams = [Stream('a', [f'a{i}' for i in range(20)], weight=10.0, quota=3),
+               Stream('b', [f'b{i}' for i in range(20)], quota=1)]
+    merged = [entry.tag for entry in priority_merge(streams, make_scorer('rank'), block=4, limit=12)]
+    for start in range(0, 12, 4):
+        assert sorted(merged[start:start + 4]) == ['a', 'a', 'a', 'b']
+
+    # Without quotas equal ranks simply alternate
+    unlimited = [entry.tag for entry in priority_merge([Stream('a', ['a0', 'a1']), Stream('b', ['b0'])],
+                                                       make_scorer('rank'))]
+    assert unlimited == ['a', 'b', 'a']
+
+
+def test_exhausted_stream_share_goes_to_the_others():
+    """A stream that runs out no longer holds back the rest"""
+    streams = [Stream('a', ['a0', 'a1', 'a2', 'a3'], quota=1), Stream('b', ['b0'], quota=1)]
+    assert [entry.token for entry in priority_merge(streams, make_scorer('rank'), block=2)] == \
+        ['a0', 'b0', 'a1', 'a2', 'a3']
+
+
+def test_quota_arithmetic():
+    """Shares are normalized, blocks filled exactly, and the horizon found"""
+    streams = [Stream('a', [], quota=50), Stream('b', [], quota=30), Stream('c', [], quota=20)]
+    shares = quota_shares(streams)
+    assert shares == [0.5, 0.3, 0.2]
+    assert quota_allotments([1 / 3] * 3, 10) == [4, 3, 3]
+    assert quota_horizon([1000, 600, 100], shares) == (500, 2)
+    try:
+        quota_shares([Stream('a', [], quota=1), Stream('b', [])])
+        assert False, "partial quotas accepted"
+    except ConfigError:
+        pass
+
+
+def test_merge_plan_round_trip(tmp_path):
+    """A plan file opens the same streams with their weights and quotas"""
+    words = tmp_path / 'words.txt'
+    words.write_text('alpha\nbravo\n')
+    plan = MergePlan(streams=[StreamSpec(str(words), quota=2), StreamSpec(str(words), tag='again', quota=1)], block=3)
+    path = tmp_path / 'plan.json'
+    path.write_text(json.dumps(plan.to_dict()))
+    loaded = MergePlan.from_json(path)
+    assert loaded == plan
+    assert [spec.name for spec in loaded.streams] == ['words', 'again']
+    assert [spec.size() for spec in loaded.streams] == [2, 2]
+    stream = loaded.streams[0].open()
+    assert (stream.tag, list(stream.tokens), stream.quota) == ('words', ['alpha', 'bravo'], 2)
@@ -0,0 +1,52 @@
+"""
+Tests for pipeline counters and progress callbacks
+"""
+
+from omniwordlist import Config, FilterConfig, Generator
+from omniwordlist.progress import RateWindow, throttled
+
+
+def test_counters_follow_each_pipeline_stage(tmp_path):
+    """Filtered and duplicate candidates drop out at their own stage"""
+    config = Config(words=['aaa', 'AAA', 'cc', 'bbbb', 'dddd'], transforms=['lowercase'], dedupe=True, max_lines=2,
+                    filters=FilterConfig(min_len=3), output_file=tmp_path / 'out.txt')
+    generator = Generator(config)
+    generator.write()
+    stats = generator.progress_stats()
+    assert (stats.generated, stats.after_filters, stats.after_dedupe, stats.emitted, stats.written) == (4, 3, 2, 2, 2)
+    assert generator.get_stats()['after_dedupe'] == 2
+
+
+def test_progress_callback_every_interval():
+    """The callback fires every progress_interval tokens and once at the end"""
+    generator = Generator(Config(min_length=1, max_length=2, charset='abc', progress_interval=4))
+    reports = []
+    generator.set_progress_callback(lambda stats: reports.append(stats.emitted))
+    generator.generate_list()
+    assert reports == [4, 8, 12]
+
+    generator.set_progress_callback(None)
+    assert not generator.observers
+
+
+def test_rate_window_forgets_old_samples():
+    """The rate covers only the last window of samples"""
+    now = [0.0]
+    window = RateWindow(seconds=10, clock=lambda: now[0])
+    window.sample(0)
+    now[0] = 10.0
+    assert window.sample(1000) == 100.0
+    # A stall shows up once the fast period leaves the window
+    now[0] = 30.0
+    assert window.sample(1100) == 5.0
+
+
+def test_throttled_callback():
+    """A throttled callback skips calls inside its interval"""
+    now = [0.0]
+    calls = []
+    callback = throttled(calls.append, 5, clock=lambda: now[0])
+    for t in (1, 6, 7, 12):
+        now[0] = t
+        callback(t)
+    assert calls == [6, 12]
@@ -0,0 +1,50 @@
+"""
+Tests for validate --fix config repairs
+"""
+
+from omniwordlist import Config
+from omniwordlist.repair import find_fixes
+
+
+def repair(data):
+    """Apply fixes until none are left, as with --fix --yes"""
+    fixes = find_fixes(data)
+    while fixes:
+        fixes[0].apply(data)
+        fixes = find_fixes(data)
+    return data
+
+
+def test_broken_config_is_repaired():
+    """Common mistakes are repaired into a valid config"""
+    data = repair({
+        'min_length': 8, 'max_lenght': 4, 'compression': 'GZ',
+        'transforms': ['uppercase', 'lowercaes'],
+        'enabled_fields': ['derive:md5:pet_name:6'],
+        'filters': {'min_len': 9, 'max_len': 2},
+    })
+    assert (data['min_length'], data['max_length']) == (4, 8)
+    assert (data['filters']['min_len'], data['filters']['max_len']) == (2, 9)
+    assert data['compression'] == 'gzip'
+    assert data['transforms'] == ['uppercase']
+    assert data['enabled_fields'] == ['pet_name', 'derive:md5:pet_name:6']
+    Config.from_dict(data).validate()
+
+
+def test_fix_descriptions():
+    """Removed transforms carry a suggestion; unknown keys are dropped"""
+    descriptions = [fix.description for fix in find_fixes(
+        {'transforms': ['lowercaes'], 'zzz': 1, 'compression': 'rar'})]
+    assert "Remove unknown key zzz" in descriptions
+    assert "Remove unknown transform 'lowercaes' (did you mean lowercase?)" in descriptions
+    assert "Remove unsupported compression 'rar' (write uncompressed)" in descriptions
+
+
+def test_valid_config_needs_no_fixes():
+    """Nothing is offered for a config that already loads"""
+    assert find_fixes({'min_length': 1, 'max_length': 8, 'compression': 'zstd',
+                       'enabled_fields': ['first_name'],
+                       'field_sources': {'first_name': ['a']}}) == []
+    assert [fix.description for fix in find_fixes(
+        {'enabled_fields': [], 'field_sources': {'city_name': ['x']}})] == [
+        "Enable field 'city_name' (it has a field source but is not enabled)"]
@@ -0,0 +1,25 @@
+"""
+Tests for password structure replication
+"""
+
+from omniwordlist import Generator
+from omniwordlist.replicate import describe_structure, infer_structure, replicate_config
+
+
+def test_infer_structure():
+    """Words, years, digits and symbols are told apart"""
+    segments = infer_structure('Summer2019!')
+    assert [(s.kind, s.text) for s in segments] == [('word', 'Summer'), ('year', '2019'), ('symbols', '!')]
+    assert describe_structure(segments) == 'Capitalized word + year + symbol'
+    assert describe_structure(infer_structure('acme42##')) == 'Lower word + 2 digits + 2 symbols'
+
+
+def test_replicated_candidates_keep_structure():
+    """Candidates reuse the structure with other words, years and symbols"""
+    config = replicate_config('Summer2019!', words=['winter', 'acme'], years=[2023, 2024], symbols='!@')
+    tokens = list(Generator(config).generate())
+
+    assert 'Summer2019!' in tokens
+    assert 'Winter2024@' in tokens
+    assert 'Acme2023!' in tokens
+    assert len(tokens) == 3 * 3 * 2
@@ -0,0 +1,76 @@
+"""
+Tests for the SecLists integration
+"""
+
+import threading
+from functools import partial
+from http.server import HTTPServer, SimpleHTTPRequestHandler
+
+import pytest
+
+from omniwordlist import Config, Generator
+from omniwordlist import seclists, sources
+from omniwordlist.error import ConfigError, StorageError
+from omniwordlist.seclists import fetch_list, load_manifest, repository_path, resolve_list
+
+
+class QuietHandler(SimpleHTTPRequestHandler):
+    def log_message(self, *args):
+        pass
+
+
+@pytest.fixture
+def mirror(tmp_path, monkeypatch):
+    """A local SecLists mirror with one password list; fetched lists go to tmp_path/data"""
+    root = tmp_path / 'mirror'
+    list_path = root / 'Passwords' / 'Common-Credentials' / '10-million-password-list-top-1000.txt'
+    list_path.parent.mkdir(parents=True)
+    list_path.write_text('123456\npassword\nqwerty\n')
+
+    httpd = HTTPServer(('127.0.0.1', 0), partial(QuietHandler, directory=str(root)))
+    thread = threading.Thread(target=httpd.serve_forever, daemon=True)
+    thread.start()
+    monkeypatch.setattr(seclists, 'SECLISTS_RAW_BASE', f'http://127.0.0.1:{httpd.server_address[1]}/')
+    monkeypatch.setattr(seclists, 'default_data_dir', lambda: tmp_path / 'data')
+    monkeypatch.setattr(sources, '_resolved_urls', {})
+    yield tmp_path / 'data'
+    httpd.shutdown()
+    httpd.server_close()
+
+
+def test_repository_path():
+    """Catalog names map to repository paths; unknown names must be .txt repository paths"""
+    assert repository_path('seclists:Usernames/names') == 'Usernames/Names/names.txt'
+    assert repository_path('Passwords/Leaked-Databases/foo.txt') == 'Passwords/Leaked-Databases/foo.txt'
+    with pytest.raises(ConfigError, match='Unknown SecLists list'):
+        repository_path('Passwords/nothing')
+    with pytest.raises(ConfigError):
+        repository_path('../etc/passwd.txt')
+
+
+def test_fetch_records_checksum(mirror):
+    """Fetched lists mirror the repository layout and are recorded in the manifest"""
+    path, checksum = fetch_list('Passwords/top-1000')
+    assert path == mirror / 'Passwords' / 'Common-Credentials' / '10-million-password-list-top-1000.txt'
+    assert path.read_text() == '123456\npassword\nqwerty\n'
+    assert load_manifest(mirror)[repository_path('Passwords/top-1000')]['sha256'] == checksum
+    assert not (mirror / '.downloads').exists()
+
+
+def test_named_source_in_config(mirror):
+    """seclists:<name> works as a field source once fetched"""
+    config = Config(enabled_fields=['company'],
+                    field_sources={'company': 'seclists:Passwords/top-1000'})
+    with pytest.raises(ConfigError, match='omni seclists fetch Passwords/top-1000'):
+        Generator(config).generate_list()
+
+    fetch_list('Passwords/top-1000')
+    assert Generator(config).generate_list() == ['123456', 'password', 'qwerty']
+
+
+def test_modified_list_fails_checksum(mirror):
+    """A fetched list changed on disk is refused"""
+    path, _ = fetch_list('Passwords/top-1000')
+    path.write_text('tampered\n')
+    with pytest.raises(StorageError, match='Checksum mismatch'):
+        resolve_list('seclists:Passwords/top-1000')
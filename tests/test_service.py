@@ -0,0 +1,63 @@
+"""
+Tests for service packaging of coordinators and workers
+"""
+
+import pytest
+
+from omniwordlist.error import ConfigError
+from omniwordlist.service import (ServiceSpec, install_service, systemd_unit, windows_script,
+                                  windows_script_path, windows_task_command)
+
+
+OMNI = ['/opt/omni/bin/omni']
+
+
+def test_systemd_unit_carries_limits_and_data_dir(tmp_path):
+    spec = ServiceSpec('worker', ['--url', 'http://coord:8765', '--name', 'rack 1 at 50%'],
+                       data_dir=tmp_path / 'data', user='omni', memory_max='8G', cpu_quota=200,
+                       nice=10, executable=OMNI)
+    unit = systemd_unit(spec).splitlines()
+    assert 'ExecStart=/opt/omni/bin/omni worker --url http://coord:8765 --name "rack 1 at 50%%"' in unit
+    assert f'WorkingDirectory={tmp_path / "data"}' in unit
+    assert f'Environment=HOME={tmp_path / "data"}' in unit
+    for line in ('User=omni', 'MemoryMax=8G', 'CPUQuota=200%', 'Nice=10', 'WantedBy=multi-user.target'):
+        assert line in unit
+
+    user_unit = systemd_unit(spec, user_service=True).splitlines()
+    assert 'User=omni' not in user_unit and 'WantedBy=default.target' in user_unit
+
+
+def test_install_writes_unit_and_enables_it(tmp_path):
+    commands = []
+    spec = ServiceSpec('coordinate', ['--preset', 'basic', '--size', '10M'], data_dir=tmp_path / 'data',
+                       executable=OMNI)
+    path = install_service(spec, 'systemd', user_service=True, unit_dir=tmp_path / 'units',
+                           runner=commands.append)
+    assert path == tmp_path / 'units' / 'omniwordlist-coordinate.service'
+    assert path.read_text() == systemd_unit(spec, user_service=True)
+    assert (tmp_path / 'data').is_dir()
+    assert commands == [['systemctl', '--user', 'daemon-reload'],
+                        ['systemctl', '--user', 'enable', '--now', 'omniwordlist-coordinate']]
+
+
+def test_windows_startup_task_runs_a_script(tmp_path):
+    commands = []
+    spec = ServiceSpec('worker', ['--url', 'http://coord:8765'], name='omni-worker',
+                       data_dir=tmp_path, executable=OMNI)
+    path = install_service(spec, 'windows', start=False, runner=commands.append)
+    assert path == windows_script_path(spec) == tmp_path / 'omni-worker.cmd'
+    script = path.read_bytes().decode().split('\r\n')
+    assert f'cd /d "{tmp_path}"' in script and f'set "USERPROFILE={tmp_path}"' in script
+    assert '/opt/omni/bin/omni worker --url http://coord:8765' in script
+    assert commands == [windows_task_command(spec)]
+    with open(path, newline='') as f:
+        assert windows_script(spec) == f.read()
+
+
+def test_spec_validation():
+    with pytest.raises(ConfigError):
+        ServiceSpec('preview')
+    with pytest.raises(ConfigError):
+        ServiceSpec('worker', cpu_quota=0)
+    with pytest.raises(ConfigError):
+        ServiceSpec('worker', nice=40)
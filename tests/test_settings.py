@@ -0,0 +1,40 @@
+"""
+Tests for user settings and default-enabled fields
+"""
+
+import pytest
+
+from omniwordlist import Config, Generator
+from omniwordlist.error import ConfigError
+from omniwordlist.settings import UserSettings
+
+
+def test_category_toggles_persist(tmp_path):
+    """Toggles round-trip through the settings file"""
+    path = tmp_path / 'settings.json'
+    assert UserSettings.load(path).default_fields() == []
+    
+    settings = UserSettings()
+    settings.set_category_defaults('personal', True)
+    settings.save(path)
+    assert UserSettings.load(path).default_fields() == [
+        'first_name_male_0', 'first_name_female_0', 'last_name_0']
+    
+    with pytest.raises(ConfigError, match='Unknown field category'):
+        settings.set_category_defaults('nope', True)
+
+
+def test_default_fields_join_words_configs():
+    """Default fields are added to words-only configs unless ignored"""
+    settings = UserSettings()
+    settings.set_category_defaults('personal', True)
+    
+    tokens = list(Generator(settings.apply_default_fields(Config(words=['acme'], max_length=30))).generate())
+    assert 'acmeJohnMarySmith' in tokens
+    config = settings.apply_default_fields(Config(words=['acme'], ignore_default_fields=True))
+    assert list(Generator(config).generate()) == ['acme']
+    # An explicit selection replaces the defaults
+    config = settings.apply_default_fields(Config(words=['acme'], enabled_fields=['birth_month_name'], max_length=30))
+    assert list(Generator(config).generate())[0] == 'acmeJanuary'
+    # The generator itself does not read the settings
+    assert list(Generator(Config(words=['acme'])).generate()) == ['acme']
@@ -0,0 +1,37 @@
+"""
+Tests for sharded output
+"""
+
+import pytest
+
+from omniwordlist import Config, Generator
+from omniwordlist.error import ConfigError
+from omniwordlist.shard import ShardedWriter, load_manifest, shard_contains, shard_key
+
+
+def test_shard_keys():
+    """First-char shards fold case; hash shards are stable and in range"""
+    assert shard_key('Apple', 'first_char') == 'a'
+    assert shard_key('7up', 'first_char') == '7'
+    assert shard_key('!x', 'first_char') == shard_key('', 'first_char') == 'other'
+    keys = {shard_key(f'token{i}', 'hash', 16) for i in range(200)}
+    assert keys == {f'{i:x}' for i in range(16)}
+    assert shard_key('token1', 'hash', 256) == shard_key('token1', 'hash', 256)
+    assert len(shard_key('token1', 'hash', 256)) == 2
+    with pytest.raises(ConfigError):
+        shard_key('x', 'nope')
+
+
+def test_sharded_output_lookup(tmp_path):
+    """Every token lands in exactly one shard and can be looked up"""
+    tokens = list(Generator(Config(charset='aB1!', min_length=1, max_length=2)).generate())
+    for mode, compression in (('first_char', None), ('hash', 'gzip')):
+        directory = tmp_path / mode
+        with ShardedWriter(directory, mode, 4, compression) as writer:
+            for token in tokens:
+                writer.write(token)
+        
+        manifest = load_manifest(directory)
+        assert sum(manifest['shards'].values()) == len(tokens)
+        assert all(shard_contains(directory, token) for token in tokens)
+        assert not shard_contains(directory, 'zz')
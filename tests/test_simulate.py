@@ -0,0 +1,37 @@
+"""
+Tests for rate-of-discovery simulation
+"""
+
+import io
+
+from omniwordlist import Config, Generator
+from omniwordlist.simulate import load_reference, simulate
+
+
+def test_hit_curve(tmp_path):
+    """Repeated reference passwords count once per occurrence, candidates once"""
+    reference_path = tmp_path / 'leaked.txt'
+    reference_path.write_text('ab\nab\nba\nzz\n\n')
+    reference = load_reference(reference_path)
+    assert sum(reference.values()) == 4
+    
+    result = simulate(['xx', 'ab', 'ab', 'ba', 'yy'], reference)
+    assert (result.candidates, result.hits, result.unique_hits) == (5, 3, 2)
+    assert [(p.depth, p.hits) for p in result.curve] == [(2, 2), (4, 3), (5, 3)]
+    assert result.fraction == 0.75
+    assert result.depth_for(0.5) == 2
+    assert result.depth_for(0.9) is None
+    
+    out = io.StringIO()
+    result.write_csv(out)
+    assert out.getvalue().splitlines()[:2] == ['depth,hits,unique_hits,fraction', '2,2,1,0.500000']
+
+
+def test_generated_stream_with_step_and_limit(tmp_path):
+    """Generated streams are replayed; step and limit shape the curve"""
+    reference_path = tmp_path / 'leaked.txt'
+    reference_path.write_text('aa\ndd\n')
+    tokens = Generator(Config(charset='abcd', min_length=2, max_length=2)).generate()
+    result = simulate(tokens, load_reference(reference_path), step=5, limit=12)
+    assert [p.depth for p in result.curve] == [5, 10, 12]
+    assert result.hits == 1
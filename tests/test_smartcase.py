@@ -0,0 +1,45 @@
+"""
+Tests for smart case variants
+"""
+
+import pytest
+
+from omniwordlist import Config, Generator
+from omniwordlist.error import ConfigError
+from omniwordlist.smartcase import (case_variants, classify_case, load_case_stats,
+                                    train_case_stats, validate_case_stats)
+
+
+def test_classify_case():
+    assert classify_case('password1') == 'lower'
+    assert classify_case('Password1') == 'capitalize'
+    assert classify_case('PASSWORD') == 'upper'
+    assert classify_case('Summer2024Fun') == 'title'
+    assert classify_case('passworD') == 'last_upper'
+    assert classify_case('pAsSwOrD') == 'toggle'
+    assert classify_case('pAssword') == 'other'
+    assert classify_case('123456') is None
+
+
+def test_shipped_table_ranks_common_patterns_first():
+    """Lowercase, then capitalize-first; never all 2^n permutations"""
+    assert case_variants('monkey', 2) == ['monkey', 'Monkey']
+    assert case_variants('monkey', 3, load_case_stats()) == ['monkey', 'Monkey', 'MONKEY']
+    # Variants without cased letters collapse into one
+    assert case_variants('1234', 3) == ['1234']
+
+
+def test_trained_table():
+    table = train_case_stats(['ABC', 'XYZ', 'abc', 'Hello', 'world', 'world'])
+    assert table['by_length']['1-4'] == {'upper': 0.666667, 'lower': 0.333333}
+    assert case_variants('key', 1, table) == ['KEY']
+    with pytest.raises(ConfigError, match='Unknown case patterns'):
+        validate_case_stats({'by_length': {'1-4': {'camel': 1.0}}})
+
+
+def test_generator_smart_case():
+    config = Config(pattern='word%', smart_case=2)
+    tokens = Generator(config).generate_list()
+    assert tokens[:4] == ['word0', 'Word0', 'word1', 'Word1']
+    assert len(tokens) == 20
+    assert Generator(config).estimate_count() == 20
@@ -0,0 +1,37 @@
+"""
+Snapshot tests for builtin preset output
+
+After an intended change to preset output, refresh the snapshots with
+`omni verify-presets --update` and review the diff.
+"""
+
+import json
+from pathlib import Path
+
+from omniwordlist.snapshots import take_snapshot, verify_presets
+
+
+SNAPSHOT_DIR = Path(__file__).parent / 'snapshots' / 'presets'
+
+
+def test_builtin_presets_match_snapshots():
+    """Every builtin preset still produces its committed sample"""
+    results = verify_presets(SNAPSHOT_DIR)
+    failures = [f"{r.preset}: {r.detail}\n" + "\n".join(r.diff) for r in results if not r.ok]
+    assert not failures, "\n".join(failures)
+
+
+def test_changed_and_missing_snapshots(tmp_path):
+    """Differences are reported with a diff; --update rewrites them"""
+    snapshot = take_snapshot('pattern_basic', size=5)
+    assert snapshot['tokens'] == ['pass00', 'pass01', 'pass02', 'pass03', 'pass04']
+    snapshot['tokens'][2] = 'pass99'
+    (tmp_path / 'pattern_basic.json').write_text(json.dumps(snapshot))
+    
+    results = verify_presets(tmp_path, ['pattern_basic', 'employee_id_emp'], size=5)
+    assert [r.status for r in results] == ['changed', 'missing']
+    assert '-pass99' in results[0].diff and '+pass02' in results[0].diff
+    
+    results = verify_presets(tmp_path, ['pattern_basic', 'employee_id_emp'], size=5, update=True)
+    assert all(r.ok for r in results)
+    assert verify_presets(tmp_path, ['pattern_basic', 'employee_id_emp'], size=5)[1].ok
@@ -0,0 +1,227 @@
+"""
+Tests for external and remote word sources
+"""
+
+import io
+import os
+import sys
+import threading
+from http.server import BaseHTTPRequestHandler, HTTPServer
+
+import pytest
+
+from omniwordlist import Config, Generator
+from omniwordlist.error import StorageError
+from omniwordlist import sources
+from omniwordlist.sources import StaleSourceWarning, fetch_remote
+
+
+class WordsHandler(BaseHTTPRequestHandler):
+    """Serves a word list with an ETag and answers revalidation with 304"""
+    body = b'alpha\nbeta\n'
+    etag = '"v1"'
+    requests = []
+
+    def do_GET(self):
+        WordsHandler.requests.append(self.headers.get('If-None-Match'))
+        if self.headers.get('If-None-Match') == self.etag:
+            self.send_response(304)
+            self.end_headers()
+            return
+        self.send_response(200)
+        self.send_header('ETag', self.etag)
+        self.send_header('Content-Length', str(len(self.body)))
+        self.end_headers()
+        self.wfile.write(self.body)
+
+    def log_message(self, *args):
+        pass
+
+
+@pytest.fixture
+def server():
+    WordsHandler.requests = []
+    httpd = HTTPServer(('127.0.0.1', 0), WordsHandler)
+    thread = threading.Thread(target=httpd.serve_forever, daemon=True)
+    thread.start()
+    yield f'http://127.0.0.1:{httpd.server_address[1]}/words.txt'
+    httpd.shutdown()
+    httpd.server_close()
+
+
+def test_fetch_remote_revalidates_with_etag(server, tmp_path):
+    """A second fetch sends the ETag and keeps the cached copy on 304"""
+    path = fetch_remote(server, cache_dir=tmp_path)
+    assert path.read_text() == 'alpha\nbeta\n'
+
+    again = fetch_remote(server, cache_dir=tmp_path)
+    assert again == path
+    assert again.read_text() == 'alpha\nbeta\n'
+    assert WordsHandler.requests == [None, '"v1"']
+
+
+class FlakyHandler(BaseHTTPRequestHandler):
+    """Drops the first download halfway and honours Range / If-Range afterwards"""
+    body = b''.join(b'word%04d\n' % i for i in range(2000))
+    etag = '"big"'
+    requests = []
+
+    def do_GET(self):
+        FlakyHandler.requests.append(self.headers.get('Range'))
+        start = 0
+        if self.headers.get('Range') and self.headers.get('If-Range') == self.etag:
+            start = int(self.headers['Range'].split('=')[1].rstrip('-'))
+            self.send_response(206)
+            self.send_header('Content-Range', f'bytes {start}-{len(self.body) - 1}/{len(self.body)}')
+        else:
+            self.send_response(200)
+        self.send_header('ETag', self.etag)
+        self.send_header('Content-Length', str(len(self.body) - start))
+        self.end_headers()
+        if len(FlakyHandler.requests) == 1:
+            self.wfile.write(self.body[:len(self.body) // 2])
+            self.wfile.flush()
+            self.close_connection = True
+            return
+        self.wfile.write(self.body[start:])
+
+    def log_message(self, *args):
+        pass
+
+
+def test_fetch_remote_resumes_interrupted_download(tmp_path):
+    """A dropped download continues with a ranged request instead of starting over"""
+    FlakyHandler.requests = []
+    httpd = HTTPServer(('127.0.0.1', 0), FlakyHandler)
+    thread = threading.Thread(target=httpd.serve_forever, daemon=True)
+    thread.start()
+    try:
+        url = f'http://127.0.0.1:{httpd.server_address[1]}/list.txt'
+        path = fetch_remote(url, cache_dir=tmp_path)
+    finally:
+        httpd.shutdown()
+        httpd.server_close()
+
+    assert path.read_bytes() == FlakyHandler.body
+    assert path.name.endswith('.txt')
+    assert FlakyHandler.requests == [None, f'bytes={len(FlakyHandler.body) // 2}-']
+    assert not list(tmp_path.glob('*.part'))
+
+
+def test_fetch_remote_offline_without_cache(tmp_path):
+    """Unreachable sources without a cached copy fail clearly"""
+    with pytest.raises(StorageError, match='Failed to fetch'):
+        fetch_remote('http://127.0.0.1:9/words.txt', cache_dir=tmp_path, timeout=2)
+
+
+class StatusHandler(BaseHTTPRequestHandler):
+    """Serves a word list until told to answer with an error status"""
+    status = 200
+
+    def do_GET(self):
+        if StatusHandler.status != 200:
+            self.send_error(StatusHandler.status)
+            return
+        self.send_response(200)
+        self.send_header('ETag', '"v1"')
+        self.send_header('Content-Length', '6')
+        self.end_headers()
+        self.wfile.write(b'alpha\n')
+
+    def log_message(self, *args):
+        pass
+
+
+def test_fetch_remote_stale_cache(tmp_path):
+    """A cached copy is used with a warning when the server fails, but not once the source is gone"""
+    StatusHandler.status = 200
+    httpd = HTTPServer(('127.0.0.1', 0), StatusHandler)
+    thread = threading.Thread(target=httpd.serve_forever, daemon=True)
+    thread.start()
+    try:
+        url = f'http://127.0.0.1:{httpd.server_address[1]}/words.txt'
+        path = fetch_remote(url, cache_dir=tmp_path)
+
+        StatusHandler.status = 503
+        with pytest.warns(StaleSourceWarning, match='may be stale'):
+            assert fetch_remote(url, cache_dir=tmp_path) == path
+
+        StatusHandler.status = 404
+        with pytest.raises(StorageError, match='HTTP 404 .not using the stale cached copy'):
+            fetch_remote(url, cache_dir=tmp_path)
+    finally:
+        httpd.shutdown()
+        httpd.server_close()
+
+
+class TruncatingHandler(BaseHTTPRequestHandler):
+    """Always drops the connection halfway, without a validator to resume from"""
+
+    def do_GET(self):
+        self.send_response(200)
+        self.send_header('Content-Length', '100')
+        self.end_headers()
+        self.wfile.write(b'x' * 50)
+        self.close_connection = True
+
+    def log_message(self, *args):
+        pass
+
+
+def test_failed_download_removes_part_file(tmp_path):
+    httpd = HTTPServer(('127.0.0.1', 0), TruncatingHandler)
+    thread = threading.Thread(target=httpd.serve_forever, daemon=True)
+    thread.start()
+    try:
+        url = f'http://127.0.0.1:{httpd.server_address[1]}/words.txt'
+        with pytest.raises(StorageError, match='connection closed after 50 of 100 bytes'):
+            fetch_remote(url, cache_dir=tmp_path, attempts=2)
+    finally:
+        httpd.shutdown()
+        httpd.server_close()
+    assert not list(tmp_path.glob('*.part*'))
+
+
+def test_field_source_from_file(tmp_path):
+    """Field sources read one word per line"""
+    words = tmp_path / 'words.txt'
+    words.write_text('acme\n\nwidget\n')
+    config = Config(enabled_fields=['company'], field_sources={'company': str(words)})
+    assert list(Generator(config).generate()) == ['acme', 'widget']
+
+
+def test_field_source_from_stdin(monkeypatch):
+    """"-" streams values from stdin, read once however often they are used"""
+    monkeypatch.setattr(sources, '_streams', {})
+    monkeypatch.setattr(sys, 'stdin', io.StringIO('alice\nbob\n'))
+    config = Config(enabled_fields=['names'], field_sources={'names': '-'})
+    generator = Generator(config)
+    assert generator.keyspace_size() == 2
+    assert list(generator.generate()) == ['alice', 'bob']
+
+
+@pytest.mark.skipif(not hasattr(os, 'mkfifo'), reason='named pipes need POSIX')
+def test_field_source_from_named_pipe(tmp_path, monkeypatch):
+    """Named pipes are read like stdin"""
+    monkeypatch.setattr(sources, '_streams', {})
+    pipe = tmp_path / 'names'
+    os.mkfifo(pipe)
+    writer = threading.Thread(target=pipe.write_text, args=('carol\ndave\n',))
+    writer.start()
+    config = Config(enabled_fields=['names'], field_sources={'names': str(pipe)})
+    assert list(Generator(config).generate()) == ['carol', 'dave']
+    writer.join()
+
+
+def test_inline_words_field():
+    """Inline words act as a field without any dictionary file"""
+    config = Config(words=['acme', 'phoenix'], enabled_fields=['year'],
+                    field_sources={'year': ['2024', '2025']})
+    assert list(Generator(config).generate()) == [
+        'acme2024', 'acme2025', 'phoenix2024', 'phoenix2025']
+
+
+def test_inline_words_alone():
+    """A words list is enough for a targeted job"""
+    config = Config(words=['acme', 'widgets'], transforms=['uppercase'])
+    assert list(Generator(config).generate()) == ['ACME', 'WIDGETS']
@@ -0,0 +1,37 @@
+"""
+Tests for password-spray scheduling
+"""
+
+from datetime import datetime
+
+import pytest
+
+from omniwordlist.error import ConfigError
+from omniwordlist.spray import SprayPolicy, schedule_spray
+
+
+START = datetime(2026, 1, 5, 9, 0)
+
+
+def test_batches_respect_policy():
+    """Batches hold threshold - margin attempts and are one window apart"""
+    policy = SprayPolicy(lockout_threshold=5, window_hours=24, safety_margin=2)
+    batches = list(schedule_spray(['a', 'b', 'a', 'c', 'd', 'e', 'f', 'g'], policy, START))
+    
+    assert [batch.passwords for batch in batches] == [['a', 'b', 'c'], ['d', 'e', 'f'], ['g']]
+    assert [batch.not_before.day for batch in batches] == [5, 6, 7]
+
+
+def test_explicit_attempts_and_batch_limit():
+    """per-window attempts override the threshold and max_batches stops early"""
+    policy = SprayPolicy(attempts_per_window=1, window_hours=0.5)
+    batches = list(schedule_spray(iter('wxyz'), policy, START, max_batches=2))
+    
+    assert [batch.passwords for batch in batches] == [['w'], ['x']]
+    assert batches[1].not_before == datetime(2026, 1, 5, 9, 30)
+
+
+def test_margin_must_leave_attempts():
+    """A margin at or above the threshold is rejected"""
+    with pytest.raises(ConfigError, match='safety_margin'):
+        list(schedule_spray(['a'], SprayPolicy(lockout_threshold=2, safety_margin=2), START))
@@ -0,0 +1,172 @@
+"""
+Tests for storage backends
+"""
+
+import pytest
+
+from omniwordlist import CancellationToken, Config, Generator
+from omniwordlist.error import ConfigError, StorageError
+from omniwordlist.shard import ShardedWriter
+from omniwordlist.storage import (STORAGE_BACKENDS, CheckpointManager, StorageBackend,
+                                  create_storage_backend, open_text, register_storage_backend)
+
+from tests.helpers import CancelAfter
+
+
+class ListBackend(StorageBackend):
+    """Backend collecting tokens in memory, standing in for a queue producer"""
+
+    def __init__(self, path=None, compression=None, format="txt"):
+        self.tokens = []
+        self.events = []
+
+    def open(self):
+        self.events.append("open")
+
+    def write_token(self, token, metadata=None):
+        self.tokens.append((token, metadata))
+
+    def finalize(self):
+        self.events.append("finalize")
+
+
+class ListBackendWithBytes(ListBackend):
+    """ListBackend that counts the bytes of the lines it stores"""
+
+    def __init__(self):
+        super().__init__()
+        self.bytes_written = 0
+
+    def write_token(self, token, metadata=None):
+        super().write_token(token, metadata)
+        self.bytes_written += len(token) + 1
+
+
+def test_file_backend(tmp_path):
+    """The default backend writes config.output_file"""
+    output = tmp_path / 'out.txt'
+    config = Config(charset='ab', min_length=1, max_length=2, output_file=output)
+    assert Generator(config).write() == 6
+    assert output.read_text().splitlines() == ['a', 'b', 'aa', 'ab', 'ba', 'bb']
+    with pytest.raises(StorageError):
+        create_storage_backend('nope', output)
+
+
+def test_registered_backend(tmp_path):
+    """Configs select registered backends by name; checkpoints record what they stored"""
+    backends = []
+    
+    def factory(path, compression, format):
+        backends.append(ListBackend(path, compression, format))
+        return backends[-1]
+    
+    register_storage_backend('memory', factory)
+    try:
+        config = Config(charset='ab', min_length=1, max_length=1, storage_backend='memory', tags=['t1'])
+        assert Generator(config).write() == 2
+        assert backends[0].tokens == [('a', {'tags': ['t1']}), ('b', {'tags': ['t1']})]
+        assert backends[0].events == ['open', 'finalize']
+        
+        with pytest.raises(ConfigError):
+            Config(storage_backend='memory', shard_by='hash').validate()
+        
+        cancel = CancellationToken()
+        config = Config(charset='abc', min_length=2, max_length=2, checkpoint_dir=tmp_path)
+        generator = Generator(config, cancel_token=cancel)
+        generator.add_observer(CancelAfter(cancel, 4))
+        backend = ListBackend()
+        generator.write(backend)
+        state = CheckpointManager(tmp_path).load_checkpoint(generator.job_id)
+        assert state['tokens_written'] == len(backend.tokens) == 4
+    finally:
+        del STORAGE_BACKENDS['memory']
+    with pytest.raises(ConfigError):
+        Config(storage_backend='memory').validate()
+
+
+def test_utf16le_output_with_plain_copy(tmp_path):
+    """UTF-16LE output is pre-encoded while plain_output keeps the UTF-8 tokens"""
+    config = Config(charset='aé', min_length=1, max_length=1, output_file=tmp_path / 'out.txt',
+                    output_encoding='utf-16le', plain_output=tmp_path / 'plain.txt')
+    config.validate()
+    assert Generator(config).write() == 2
+    assert (tmp_path / 'out.txt').read_bytes() == 'a\né\n'.encode('utf-16-le')
+    assert (tmp_path / 'plain.txt').read_text(encoding='utf-8') == 'a\né\n'
+
+
+def test_utf16le_hex_output(tmp_path):
+    path = tmp_path / 'out.txt'
+    with create_storage_backend('file', path, encoding='utf-16le-hex') as backend:
+        backend.write_token('pass')
+    assert path.read_text() == '7000610073007300\n'
+    with pytest.raises(ConfigError, match='txt output'):
+        Config(output_encoding='utf-16le-hex', format='jsonl').validate()
+
+
+def test_max_bytes_stops_within_one_line(tmp_path):
+    """Output stops once it reaches max_bytes, overshooting by less than one line"""
+    full = Generator(Config(min_length=1, max_length=3, charset='abcd')).generate_list()
+
+    for compression in (None, 'gzip'):
+        output = tmp_path / f'out-{compression}.txt'
+        generator = Generator(Config(min_length=1, max_length=3, charset='abcd', max_bytes=100,
+                                     output_file=output, compression=compression))
+        generator.write()
+        with open_text(output, compression) as f:
+            data = f.read()
+        assert 100 <= len(data.encode()) < 100 + len('abc\n')
+        lines = data.split()
+        assert lines == full[:len(lines)]
+        assert generator.limit_reached == 'max_bytes'
+        assert generator.last_token == lines[-1]
+
+    # The --start hint continues from the raw candidate behind the last token
+    assert Generator(Config(charset='ab', prefix='x-')).raw_candidate('x-ab') == 'ab'
+    assert Generator(Config(charset='ab', transforms=['uppercase'])).raw_candidate('AB') is None
+
+    # A limit the output never reaches changes nothing
+    generator = Generator(Config(min_length=1, max_length=2, charset='ab', max_bytes=10 ** 6))
+    assert generator.write(ListBackendWithBytes()) == 6
+    assert generator.limit_reached is None
+
+
+def test_max_bytes_caps_the_total_of_sharded_output(tmp_path):
+    config = Config(min_length=1, max_length=3, charset='abcd', max_bytes=200, shard_by='first_char')
+    generator = Generator(config)
+    writer = ShardedWriter(tmp_path / 'shards', 'first_char', 4)
+    generator.write(writer)
+    sizes = sum(path.stat().st_size for path in (tmp_path / 'shards').glob('*.txt'))
+    assert 200 <= writer.bytes_written == sizes < 200 + len('abc\n')
+
+
+def test_split_output_parts_and_max_bytes(tmp_path):
+    """Parts rotate at split_by_bytes and max_bytes caps their total"""
+    full = Generator(Config(min_length=1, max_length=3, charset='abcd')).generate_list()
+    output = tmp_path / 'list.txt'
+    generator = Generator(Config(min_length=1, max_length=3, charset='abcd', max_bytes=200,
+                                 split_by_bytes=50, output_file=output))
+    generator.write()
+
+    parts = sorted(tmp_path.glob('list.part*.txt'))
+    assert parts[0].name == 'list.part0001.txt'
+    assert not output.exists()
+    sizes = [path.stat().st_size for path in parts]
+    assert all(50 <= size < 50 + len('abc\n') for size in sizes[:-1])
+    assert 200 <= sum(sizes) < 200 + len('abc\n')
+    lines = ''.join(path.read_text() for path in parts).split()
+    assert lines == full[:len(lines)]
+    assert generator.limit_reached == 'max_bytes'
+
+
+def test_split_output_by_lines(tmp_path):
+    output = tmp_path / 'list.txt.gz'
+    generator = Generator(Config(min_length=1, max_length=2, charset='abc', split_by_lines=5,
+                                 output_file=output, compression='gzip'))
+    assert generator.write() == 12
+    parts = sorted(tmp_path.glob('list.part*.txt.gz'))
+    assert [len(open_text(path, 'gzip').read().split()) for path in parts] == [5, 5, 2]
+
+    with pytest.raises(ConfigError, match='cannot be sharded'):
+        Config(split_by_lines=5, shard_by='first_char').validate()
+    with pytest.raises(ConfigError, match='split_by_bytes'):
+        Config(split_by_bytes=0).validate()
@@ -0,0 +1,37 @@
+"""
+Tests for positional patterns and structured-ID templates
+"""
+
+import pytest
+
+from omniwordlist import Config, Generator
+from omniwordlist.error import ConfigError
+from omniwordlist.presets import PresetManager
+from omniwordlist.structured_ids import build_patterns
+
+
+def test_pattern_keeps_literal_prefix():
+    """Literal pattern characters stay fixed at their position"""
+    tokens = list(Generator(Config(min_length=6, max_length=6, pattern='pass%%')).generate())
+    assert len(tokens) == 100
+    assert tokens[0] == 'pass00' and tokens[-1] == 'pass99'
+
+
+def test_structured_id_builders():
+    """Builders produce patterns for the requested layouts"""
+    assert build_patterns('plate-in', states=['mh', 'KA']) == ['MH%%,,%%%%', 'KA%%,,%%%%']
+    assert build_patterns('employee', prefix='ACME', digits=3, separator='-') == ['ACME-%%%']
+    assert build_patterns('initials') == ['@@-%%%%%']
+    with pytest.raises(ConfigError):
+        build_patterns('employee', prefix='E%P')
+    with pytest.raises(ConfigError):
+        build_patterns('plate-in', states=['XX'])
+
+
+def test_employee_id_preset():
+    """The employee ID preset enumerates EMP0000-EMP9999"""
+    config = PresetManager().get_preset_config('employee_id_emp')
+    generator = Generator(config)
+    tokens = generator.generate_list(limit=3)
+    assert tokens == ['EMP0000', 'EMP0001', 'EMP0002']
+    assert generator.keyspace_size() == 10000
@@ -0,0 +1,68 @@
+"""
+Tests for temporal candidate generation
+"""
+
+from datetime import date
+
+import pytest
+
+from omniwordlist import Config, Generator
+from omniwordlist.error import ConfigError
+from omniwordlist.temporal import generate_temporal, months_in_range, resolve_relative
+
+
+def test_months_in_range_crosses_years():
+    """Ranges spanning a year boundary list every month once"""
+    periods = months_in_range(date(2024, 11, 15), date(2025, 2, 1))
+    assert periods == [(2024, 11), (2024, 12), (2025, 1), (2025, 2)]
+
+
+def test_generate_temporal_candidates():
+    """Seasons, holidays and quarters are rendered with their year"""
+    tokens = list(generate_temporal(date(2025, 7, 1), date(2025, 7, 31), locales=["en"]))
+    assert "Summer2025!" in tokens
+    assert "summer@2025" in tokens
+    assert "July25" in tokens
+    assert "Q3-2025" in tokens
+    assert len(tokens) == len(set(tokens))
+
+
+def test_generate_temporal_locales():
+    """Locale-specific holidays appear only in their months"""
+    tokens = list(generate_temporal(date(2024, 10, 1), date(2024, 11, 30),
+                                    locales=["hi"], kinds=["holidays"]))
+    assert "Diwali@2024" in tokens
+    assert not any(t.startswith("Holi2") for t in tokens)
+    with pytest.raises(ConfigError):
+        list(generate_temporal(date(2024, 1, 1), date(2024, 1, 2), locales=["xx"]))
+
+
+def test_resolve_relative_tokens():
+    """Relative tokens follow the run date; ranges multiply out"""
+    today = date(2026, 10, 16)
+    assert resolve_relative("Summer{year}!", today) == ["Summer2026!"]
+    assert resolve_relative("{season}{yy-1..0}", today) == ["Autumn25", "Autumn26"]
+    assert resolve_relative("{season-1}", today) == ["Summer"]
+    assert resolve_relative("{season+1}", today) == ["Winter"]
+    # Someone aged 30 left school in 2014 and university in 2018
+    assert resolve_relative("{grad:30}", today) == ["2014", "2018"]
+    assert resolve_relative("{name}", today) == ["{name}"]
+    for value in ("{year+}", "{year1..0}", "{grad}", "{yy+2..-2}"):
+        with pytest.raises(ConfigError):
+            resolve_relative(value, today)
+
+
+def test_relative_tokens_in_config():
+    """Inline values, catalog values and suffix resolve at generation time"""
+    config = Config(words=['acme'], enabled_fields=['year'], field_sources={'year': ['{year-1..0}']},
+                    suffix='{season}', reference_date='2025-07-01')
+    assert Generator(config).generate_list() == ['acme2024Summer', 'acme2025Summer']
+    
+    config = Config(enabled_fields=['common_suffix_0'], reference_date='2030-01-01')
+    tokens = Generator(config).generate_list()
+    assert '2030' in tokens and '2029' in tokens
+    
+    with pytest.raises(ConfigError):
+        Config(suffix='{year-1..0}').validate()
+    with pytest.raises(ConfigError):
+        Config(reference_date='yesterday').validate()
@@ -0,0 +1,57 @@
+"""
+Tests for per-token statistics columns
+"""
+
+import csv
+import json
+import sqlite3
+
+import pytest
+
+from omniwordlist import Config, Generator
+from omniwordlist.error import ConfigError
+from omniwordlist.merge import write_merged
+from omniwordlist.tokenstats import STATS_COLUMNS, detect_language, token_mask, token_stats
+
+
+def test_stats_of_a_token():
+    """Mask, class counts and script-based language of a token"""
+    stats = token_stats('Pass12!')
+    assert list(stats) == list(STATS_COLUMNS)
+    assert stats['mask'] == '?u?l?l?l?d?d?s'
+    assert (stats['lower'], stats['upper'], stats['digit'], stats['symbol'], stats['other']) == (3, 1, 2, 1, 0)
+    assert 0 < stats['quality_score'] <= 1
+
+    assert token_mask('é1') == '?b?b?d'
+    assert [detect_language(token) for token in ('password', 'пароль1', '密码', 'pass密码', '1234')] == \
+        ['latin', 'cyrillic', 'cjk', 'mixed', '']
+
+
+def test_csv_and_jsonl_output_carry_stats(tmp_path):
+    """Stats columns follow token, entropy and length and load with a CSV reader"""
+    output = tmp_path / 'out.csv'
+    config = Config(words=['Tr"ee', 'Шар7'], enabled_fields=['words'], format='csv', token_stats=True,
+                    output_file=output)
+    Generator(config).write()
+    with open(output, newline='', encoding='utf-8') as f:
+        rows = list(csv.DictReader(f))
+    assert list(rows[0]) == ['token', 'entropy', 'length', *STATS_COLUMNS]
+    assert [row['token'] for row in rows] == ['Tr"ee', 'Шар7']
+    assert (rows[1]['language'], rows[1]['mask'], rows[1]['other']) == ('cyrillic', '?b?b?b?b?b?b?d', '3')
+
+    config.format, config.output_file = 'jsonl', tmp_path / 'out.jsonl'
+    Generator(config).write()
+    record = json.loads((tmp_path / 'out.jsonl').read_text().splitlines()[0])
+    assert record['mask'] == '?u?l?s?l?l'
+
+    with pytest.raises(ConfigError):
+        Config(token_stats=True).validate()
+
+
+def test_sqlite_merge_carries_stats(tmp_path):
+    """Merged SQLite output gets one typed column per statistic"""
+    assert write_merged({'abc1': ['x']}, tmp_path / 'out.db', 'sqlite', token_stats=True) == 1
+    connection = sqlite3.connect(tmp_path / 'out.db')
+    row = connection.execute("SELECT mask, language, digit, typeof(quality_score) FROM tokens").fetchone()
+    connection.close()
+    assert row == ('?l?l?l?d', 'latin', 1, 'real')
@@ -0,0 +1,85 @@
+"""
+Tests for top-N common password sources
+"""
+
+import threading
+from functools import partial
+from http.server import HTTPServer, SimpleHTTPRequestHandler
+
+import pytest
+
+from omniwordlist import Config, Generator, seclists
+from omniwordlist.error import ConfigError, StorageError
+from omniwordlist.toplists import fetch_top_passwords, load_top_passwords, toplist_path
+
+
+def test_include_top_emitted_first():
+    """Top passwords lead the stream when placed first"""
+    config = Config(pattern='@', min_length=1, max_length=8, charset='ab', include_top=3)
+    tokens = list(Generator(config).generate())
+    assert tokens[:4] == ['123456', 'password', '12345678', 'a']
+
+
+def test_include_top_emitted_last():
+    """Top passwords trail the stream when placed last"""
+    config = Config(pattern='@', max_length=8, include_top=2, top_placement='last')
+    assert list(Generator(config).generate())[-3:] == ['z', '123456', 'password']
+
+
+def test_include_top_merged_and_length_window():
+    """Merged top passwords alternate with generated ones; those outside min..max length are dropped"""
+    config = Config(pattern='@', max_length=8, include_top=3, top_placement='merged')
+    generator = Generator(config)
+    assert list(generator.generate())[:7] == ['123456', 'a', 'password', 'b', '12345678', 'c', 'd']
+    assert generator.keyspace_size() == 26 + 3
+
+    config = Config(pattern='@', max_length=6, include_top=3)
+    generator = Generator(config)
+    assert list(generator.generate())[:2] == ['123456', 'a']
+    assert generator.keyspace_size() == 26 + 1
+
+    with pytest.raises(ConfigError, match='Unsupported top_placement'):
+        Config(include_top=3, top_placement='middle').validate()
+
+
+def test_exclude_top():
+    """Excluded common passwords never reach the output"""
+    config = Config(min_length=4, max_length=4, charset='1234', exclude_top=100)
+    tokens = list(Generator(config).generate())
+    assert '1234' not in tokens
+    assert '1111' not in tokens
+    assert '1112' in tokens
+
+
+def test_large_top_list_requires_download(tmp_path):
+    """Lists beyond the built-in 100 must be fetched first"""
+    with pytest.raises(ConfigError, match="toplist fetch 1000"):
+        load_top_passwords(500, data_dir=tmp_path)
+
+    # Fetched lists live where the SecLists integration keeps them
+    path = toplist_path(1000, tmp_path)
+    assert path == tmp_path / 'Passwords/Common-Credentials/10-million-password-list-top-1000.txt'
+    path.parent.mkdir(parents=True)
+    path.write_text(''.join(f'pw{i}\n' for i in range(1000)))
+    assert load_top_passwords(500, data_dir=tmp_path)[-1] == 'pw499'
+
+
+class QuietHandler(SimpleHTTPRequestHandler):
+    def log_message(self, *args):
+        pass
+
+
+def test_failed_fetch_leaves_nothing_behind(tmp_path, monkeypatch):
+    """A list missing upstream fails without leaving partial downloads in the data directory"""
+    (tmp_path / 'mirror').mkdir()
+    httpd = HTTPServer(('127.0.0.1', 0), partial(QuietHandler, directory=str(tmp_path / 'mirror')))
+    thread = threading.Thread(target=httpd.serve_forever, daemon=True)
+    thread.start()
+    monkeypatch.setattr(seclists, 'SECLISTS_RAW_BASE', f'http://127.0.0.1:{httpd.server_address[1]}/')
+    try:
+        with pytest.raises(StorageError, match='HTTP 404'):
+            fetch_top_passwords(1000, data_dir=tmp_path / 'data')
+    finally:
+        httpd.shutdown()
+        httpd.server_close()
+    assert not (tmp_path / 'data' / '.downloads').exists()
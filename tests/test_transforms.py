@@ -0,0 +1,75 @@
+"""
+Tests for the transform chain optimizer
+"""
+
+import random
+
+from omniwordlist import Config, Generator
+from omniwordlist.transforms import apply_transforms, optimize_transforms
+
+
+def test_optimizer_drops_redundant_steps():
+    """Overridden, repeated and cancelling steps are removed"""
+    chain, notes = optimize_transforms(['uppercase', 'lowercase', 'reverse', 'reverse',
+                                        'leet_basic', 'leet_basic', 'capitalize'])
+    assert chain == ['lowercase', 'leet_basic', 'capitalize']
+    assert len(notes) == 3
+
+
+def test_optimizer_keeps_random_repeats():
+    """Repeating a random transform changes the output and is kept"""
+    chain, notes = optimize_transforms(['append_numbers_2', 'append_numbers_2'])
+    assert chain == ['append_numbers_2', 'append_numbers_2']
+    assert notes == []
+
+
+def test_optimized_chain_only_used_for_ascii():
+    """Non-ASCII tokens go through the original chain"""
+    config = Config(words=['straße', 'strasse'], transforms=['uppercase', 'lowercase'])
+    generator = Generator(config)
+    assert generator.ascii_transforms == ['lowercase']
+    assert list(generator.generate()) == ['strasse', 'strasse']
+
+
+def test_seed_makes_random_transforms_reproducible():
+    """The same seed gives the same output, another seed a different one"""
+    def run(seed):
+        config = Config(words=[f'word{i}' for i in range(50)], seed=seed,
+                        transforms=['leet_full', 'homoglyph_random', 'keyboard_shift',
+                                    'append_numbers_4', 'emoji_insertion'])
+        return Generator(config).generate_list()
+    
+    assert run(7) == run(7)
+    assert run(7) != run(8)
+    
+    # Each token draws from its own source, so a range matches the full run
+    config = Config(words=[f'word{i}' for i in range(50)], seed=7, transforms=['append_numbers_4'])
+    full = Generator(config).generate_list()
+    assert list(Generator(config).generate_range(20, 30)) == full[20:30]
+
+
+def test_turkic_case_transforms():
+    """Turkish / Azeri case transforms pair i with İ and ı with I"""
+    assert apply_transforms('istanbul', ['uppercase:tr']) == 'İSTANBUL'
+    assert apply_transforms('ISPARTA', ['lowercase:az']) == 'ısparta'
+    assert apply_transforms('İzmir ılık', ['title_case:tr']) == 'İzmir Ilık'
+    assert apply_transforms('iIıİ', ['toggle_case:tr']) == 'İıIi'
+    assert apply_transforms('ıspanak', ['capitalize:tr']) == 'Ispanak'
+    
+    # The chain for ASCII tokens keeps a locale step that leaves ASCII
+    config = Config(words=['kilim'], transforms=['uppercase:tr', 'lowercase:tr', 'lowercase:tr'])
+    generator = Generator(config)
+    assert generator.ascii_transforms == ['uppercase:tr', 'lowercase:tr']
+    assert list(generator.generate()) == ['kilim']
+
+
+def test_reverse_and_emoji_insertion_keep_graphemes():
+    """Emoji sequences, flags and combining marks survive reverse and emoji insertion"""
+    assert apply_transforms('a👍🏽b👨‍👩‍👧', ['reverse']) == '👨‍👩‍👧b👍🏽a'
+    assert apply_transforms('🇩🇪🇫🇷x', ['reverse']) == 'x🇫🇷🇩🇪'
+    assert apply_transforms('cafe\u0301', ['reverse']) == 'e\u0301fac'
+    
+    for seed in range(20):
+        token = apply_transforms('e\u0301👍🏽', ['emoji_insertion'], random.Random(seed))
+        assert token.startswith('e\u0301') or token.endswith('e\u0301👍🏽')
+        assert '👍🏽' in token
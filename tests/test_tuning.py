@@ -0,0 +1,35 @@
+"""
+Tests for two-pass filter tuning
+"""
+
+import random
+
+from omniwordlist import Config, Generator
+from omniwordlist.tuning import parse_count, tune_filters, apply_tuning
+
+
+def test_parse_count():
+    """Counts accept k/M/B suffixes"""
+    assert parse_count("500M") == 500_000_000
+    assert parse_count("10k") == 10_000
+    assert parse_count("42") == 42
+
+
+def test_tuned_threshold_approximates_target():
+    """Applying the tuned threshold keeps roughly the target number of tokens"""
+    config = Config(min_length=3, max_length=3, charset='abcdef0123')
+    report = tune_filters(config, target_size=300, metric='entropy',
+                          sample_size=5000, rng=random.Random(1))
+    assert report.keyspace == 1000
+    assert report.threshold > 0
+
+    tokens = list(Generator(apply_tuning(config, report)).generate())
+    assert abs(len(tokens) - report.expected_output) < 150
+
+
+def test_target_above_keyspace_keeps_filters():
+    """A target larger than the keyspace leaves the threshold unchanged"""
+    config = Config(min_length=2, max_length=2, charset='ab')
+    report = tune_filters(config, target_size=1000, sample_size=200, rng=random.Random(0))
+    assert report.threshold == config.filters.min_quality
+    assert report.expected_output == 4
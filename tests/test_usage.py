@@ -0,0 +1,38 @@
+"""
+Tests for the local usage ledger
+"""
+
+from datetime import datetime
+
+from omniwordlist.settings import UserSettings
+from omniwordlist.usage import UsageLedger, record_run
+
+
+def test_runs_are_only_recorded_when_enabled(tmp_path):
+    path = tmp_path / 'usage.json'
+    assert not record_run(100, 1000, 'pentest_default', path, UserSettings())
+    assert not path.exists()
+
+    enabled = UserSettings(usage_stats=True)
+    assert record_run(100, 1000, 'pentest_default', path, enabled)
+    assert record_run(50, 5000, None, path, enabled)
+    assert record_run(10, 10, 'pentest_default', path, enabled)
+
+    ledger = UsageLedger.load(path)
+    assert (ledger.runs, ledger.tokens, ledger.largest_keyspace) == (3, 160, 5000)
+    assert ledger.presets == {'pentest_default': 2}
+
+
+def test_ledger_round_trip_and_settings_toggle(tmp_path):
+    ledger = UsageLedger()
+    ledger.add_run(5, 7, 'router_defaults', when=datetime(2024, 1, 2, 3, 4, 5))
+    ledger.add_run(1, 2, when=datetime(2024, 2, 1))
+    ledger.save(tmp_path / 'usage.json')
+    loaded = UsageLedger.load(tmp_path / 'usage.json')
+    assert loaded == ledger
+    assert (loaded.first_run, loaded.last_run) == ('2024-01-02T03:04:05', '2024-02-01T00:00:00')
+    assert UsageLedger.load(tmp_path / 'missing.json') == UsageLedger()
+
+    settings = UserSettings(usage_stats=True)
+    settings.save(tmp_path / 'settings.json')
+    assert UserSettings.load(tmp_path / 'settings.json').usage_stats
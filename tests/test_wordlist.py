@@ -0,0 +1,88 @@
+"""
+Tests for mutation and combinator modes (streaming input word lists)
+"""
+
+import gzip
+import io
+import sys
+
+import pytest
+
+from omniwordlist import Config, FilterConfig, Generator
+from omniwordlist.error import ConfigError
+
+
+LINES = b'alpha\n\nbeta\r\n\xff\xfeoops\nx\ngamma'
+
+
+def test_wordlist_lines_run_through_the_pipeline(tmp_path):
+    """Lines get transforms, filters and dedupe; blank and invalid UTF-8 lines are counted"""
+    for name, data in (('words.txt', LINES), ('words.txt.gz', gzip.compress(LINES))):
+        path = tmp_path / name
+        path.write_bytes(data)
+        config = Config(input_wordlist=str(path), transforms=['uppercase'], suffix='!',
+                        filters=FilterConfig(min_len=3))
+        generator = Generator(config)
+        assert generator.keyspace_size() == 6
+        assert list(generator.generate()) == ['ALPHA!', 'BETA!', 'GAMMA!']
+        assert generator.get_stats()['skipped_lines'] == {'blank': 1, 'invalid_utf8': 1}
+
+
+def test_wordlist_from_stdin_and_resume(tmp_path, monkeypatch):
+    """stdin is streamed once; resuming a file skips the candidates already seen"""
+    monkeypatch.setattr(sys, 'stdin', io.TextIOWrapper(io.BytesIO(b'one\ntwo\nthree\n')))
+    generator = Generator(Config(input_wordlist='-'))
+    assert generator.keyspace_size() == 0
+    assert list(generator.generate()) == ['one', 'two', 'three']
+
+    path = tmp_path / 'words.txt'
+    path.write_bytes(b'one\ntwo\nthree\n')
+    assert list(Generator(Config(input_wordlist=str(path), skip=1, take=1)).generate()) == ['two']
+
+
+def test_wordlist_replaces_generation():
+    """Mutation mode cannot be mixed with the generation modes"""
+    with pytest.raises(ConfigError):
+        Config(input_wordlist='words.txt', pattern='@@').validate()
+    with pytest.raises(ConfigError):
+        Generator(Config(input_wordlist='words.txt', enabled_fields=['year']))
+
+
+def test_hybrid_patterns_attach_to_each_word(tmp_path):
+    """Each word gets the whole mask keyspace; length filters see the combined token"""
+    path = tmp_path / 'names.txt'
+    path.write_text('al\nbob\n')
+    config = Config(input_wordlist=str(path), append_pattern='%1', custom_charsets=['!?'],
+                    filters=FilterConfig(max_len=4))
+    generator = Generator(config)
+    assert generator.keyspace_size() == 2 * 20
+    tokens = list(generator.generate())
+    assert tokens[:3] == ['al0!', 'al0?', 'al1!'] and len(tokens) == 20
+
+    config = Config(input_wordlist=str(path), prepend_pattern='@')
+    assert list(Generator(config).generate())[-2:] == ['ybob', 'zbob']
+
+    with pytest.raises(ConfigError):
+        Config(append_pattern='%%').validate()
+    with pytest.raises(ConfigError):
+        Config(input_wordlist=str(path), append_pattern='%', prepend_pattern='%').validate()
+
+
+def test_combinator_joins_left_and_right(tmp_path):
+    """Every left word joins every right word; separators, swap, prefix and filters apply"""
+    left, right = tmp_path / 'left.txt', tmp_path / 'right.txt'
+    left.write_text('red\nblue\n')
+    right.write_text('cat\n\ndog\n')
+    generator = Generator(Config(combinator_left=str(left), combinator_right=str(right)))
+    assert generator.keyspace_size() == 6
+    assert list(generator.generate()) == ['redcat', 'reddog', 'bluecat', 'bluedog']
+    assert generator.skipped_lines == {'blank': 1}
+
+    config = Config(combinator_left=str(left), combinator_right=str(right), combinator_swap=True,
+                    separator='-', prefix='!', transforms=['uppercase'], filters=FilterConfig(max_len=8))
+    assert list(Generator(config).generate()) == ['!RED-CAT', '!CAT-RED', '!RED-DOG', '!DOG-RED']
+
+    with pytest.raises(ConfigError):
+        Config(combinator_left=str(left)).validate()
+    with pytest.raises(ConfigError):
+        Config(combinator_left='-', combinator_right='-').validate()